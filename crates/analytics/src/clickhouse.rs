@@ -14,6 +14,7 @@ use super::{
     payment_intents::{filters::PaymentIntentFilterRow, metrics::PaymentIntentMetricRow},
     payments::{
         distribution::PaymentDistributionRow, filters::PaymentFilterRow, metrics::PaymentMetricRow,
+        routing_inputs::PaymentRoutingInputRow,
     },
     query::{Aggregate, ToSql, Window},
     refunds::{
@@ -170,6 +171,7 @@ where
 impl super::payments::filters::PaymentFilterAnalytics for ClickhouseClient {}
 impl super::payments::metrics::PaymentMetricAnalytics for ClickhouseClient {}
 impl super::payments::distribution::PaymentDistributionAnalytics for ClickhouseClient {}
+impl super::payments::routing_inputs::PaymentRoutingInputAnalytics for ClickhouseClient {}
 impl super::payment_intents::filters::PaymentIntentFilterAnalytics for ClickhouseClient {}
 impl super::payment_intents::metrics::PaymentIntentMetricAnalytics for ClickhouseClient {}
 impl super::refunds::metrics::RefundMetricAnalytics for ClickhouseClient {}
@@ -266,6 +268,16 @@ impl TryInto<PaymentFilterRow> for serde_json::Value {
     }
 }
 
+impl TryInto<PaymentRoutingInputRow> for serde_json::Value {
+    type Error = Report<ParsingError>;
+
+    fn try_into(self) -> Result<PaymentRoutingInputRow, Self::Error> {
+        serde_json::from_value(self).change_context(ParsingError::StructParseFailure(
+            "Failed to parse PaymentRoutingInputRow in clickhouse results",
+        ))
+    }
+}
+
 impl TryInto<PaymentIntentMetricRow> for serde_json::Value {
     type Error = Report<ParsingError>;
 
@@ -30,7 +30,7 @@ use hyperswitch_interfaces::secrets_interface::{
     SecretManagementInterface, SecretsManagementError,
 };
 use refunds::distribution::{RefundDistribution, RefundDistributionRow};
-pub use types::AnalyticsDomain;
+pub use types::{AnalyticsDomain, DBEnumWrapper};
 pub mod lambda_utils;
 pub mod utils;
 
@@ -3,6 +3,7 @@ mod core;
 pub mod distribution;
 pub mod filters;
 pub mod metrics;
+pub mod routing_inputs;
 pub mod types;
 pub use accumulator::{
     PaymentDistributionAccumulator, PaymentMetricAccumulator, PaymentMetricsAccumulator,
@@ -13,4 +14,4 @@ pub trait PaymentAnalytics:
 {
 }
 
-pub use self::core::{get_filters, get_metrics};
+pub use self::core::{get_filters, get_metrics, get_routing_inputs};
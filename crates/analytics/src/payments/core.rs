@@ -7,7 +7,7 @@ use api_models::analytics::{
         PaymentMetricsBucketIdentifier,
     },
     FilterValue, GetPaymentFiltersRequest, GetPaymentMetricRequest, PaymentFiltersResponse,
-    PaymentsAnalyticsMetadata, PaymentsMetricsResponse,
+    PaymentsAnalyticsMetadata, PaymentsMetricsResponse, TimeRange,
 };
 use bigdecimal::ToPrimitive;
 use common_enums::Currency;
@@ -23,6 +23,7 @@ use super::{
     distribution::PaymentDistributionRow,
     filters::{get_payment_filter_for_dimension, PaymentFilterRow},
     metrics::PaymentMetricRow,
+    routing_inputs::{get_payment_routing_inputs, PaymentRoutingInputRow},
     PaymentMetricsAccumulator,
 };
 use crate::{
@@ -419,3 +420,28 @@ pub async fn get_filters(
     }
     Ok(res)
 }
+
+/// Loads the routing inputs of the payment attempts made within the time range, for replaying
+/// them through a routing algorithm
+pub async fn get_routing_inputs(
+    pool: &AnalyticsProvider,
+    auth: &AuthInfo,
+    time_range: &TimeRange,
+    limit: u64,
+) -> AnalyticsResult<Vec<PaymentRoutingInputRow>> {
+    match pool {
+        AnalyticsProvider::Sqlx(pool) => {
+            get_payment_routing_inputs(auth, time_range, limit, pool).await
+        }
+        AnalyticsProvider::Clickhouse(pool) => {
+            get_payment_routing_inputs(auth, time_range, limit, pool).await
+        }
+        AnalyticsProvider::CombinedCkh(_, ckh_pool) => {
+            get_payment_routing_inputs(auth, time_range, limit, ckh_pool).await
+        }
+        AnalyticsProvider::CombinedSqlx(sqlx_pool, _) => {
+            get_payment_routing_inputs(auth, time_range, limit, sqlx_pool).await
+        }
+    }
+    .change_context(AnalyticsError::UnknownError)
+}
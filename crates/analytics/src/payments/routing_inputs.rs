@@ -0,0 +1,115 @@
+use api_models::analytics::{Granularity, TimeRange};
+use common_utils::errors::ReportSwitchExt;
+use diesel_models::enums::{AttemptStatus, AuthenticationType, CaptureMethod, Currency};
+use error_stack::ResultExt;
+use time::PrimitiveDateTime;
+
+use crate::{
+    enums::AuthInfo,
+    query::{Aggregate, GroupByClause, Order, QueryBuilder, QueryFilter, ToSql, Window},
+    types::{
+        AnalyticsCollection, AnalyticsDataSource, DBEnumWrapper, FiltersError, FiltersResult,
+        LoadRow,
+    },
+};
+
+pub trait PaymentRoutingInputAnalytics: LoadRow<PaymentRoutingInputRow> {}
+
+/// The payment attempt fields used as inputs while routing a payment
+const ROUTING_INPUT_COLUMNS: [&str; 9] = [
+    "currency",
+    "amount",
+    "payment_method",
+    "payment_method_type",
+    "authentication_type",
+    "capture_method",
+    "card_network",
+    "connector",
+    "status",
+];
+
+/// Loads the payment attempts made within the time range, grouped by the fields used while
+/// routing them along with the connector they were routed to and their outcome. Attempts are
+/// additionally grouped by the hour they were made in, with the time of the earliest attempt of
+/// each group returned as its `start_bucket`. At most `limit` groups are loaded, the groups with
+/// the most attempts first.
+pub async fn get_payment_routing_inputs<T>(
+    auth: &AuthInfo,
+    time_range: &TimeRange,
+    limit: u64,
+    pool: &T,
+) -> FiltersResult<Vec<PaymentRoutingInputRow>>
+where
+    T: AnalyticsDataSource + PaymentRoutingInputAnalytics,
+    PrimitiveDateTime: ToSql<T>,
+    AnalyticsCollection: ToSql<T>,
+    Granularity: GroupByClause<T>,
+    Aggregate<&'static str>: ToSql<T>,
+    Window<&'static str>: ToSql<T>,
+{
+    let mut query_builder: QueryBuilder<T> = QueryBuilder::new(AnalyticsCollection::Payment);
+
+    for column in ROUTING_INPUT_COLUMNS {
+        query_builder.add_select_column(column).switch()?;
+    }
+
+    query_builder
+        .add_select_column(Aggregate::Count {
+            field: None,
+            alias: Some("count"),
+        })
+        .switch()?;
+    query_builder
+        .add_select_column(Aggregate::Min {
+            field: "created_at",
+            alias: Some("start_bucket"),
+        })
+        .switch()?;
+
+    time_range
+        .set_filter_clause(&mut query_builder)
+        .attach_printable("Error filtering time range")
+        .switch()?;
+
+    auth.set_filter_clause(&mut query_builder).switch()?;
+
+    for column in ROUTING_INPUT_COLUMNS {
+        query_builder
+            .add_group_by_clause(column)
+            .attach_printable("Error grouping by routing inputs")
+            .switch()?;
+    }
+
+    Granularity::OneHour
+        .set_group_by_clause(&mut query_builder)
+        .attach_printable("Error grouping by hour")
+        .switch()?;
+
+    query_builder
+        .add_order_by_clause("count", Order::Descending)
+        .attach_printable("Error adding order by clause")
+        .switch()?;
+    query_builder.set_limit(limit);
+
+    query_builder
+        .execute_query::<PaymentRoutingInputRow, _>(pool)
+        .await
+        .change_context(FiltersError::QueryBuildingError)?
+        .change_context(FiltersError::QueryExecutionFailure)
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PaymentRoutingInputRow {
+    pub currency: Option<DBEnumWrapper<Currency>>,
+    pub amount: Option<i64>,
+    pub payment_method: Option<String>,
+    pub payment_method_type: Option<String>,
+    pub authentication_type: Option<DBEnumWrapper<AuthenticationType>>,
+    pub capture_method: Option<DBEnumWrapper<CaptureMethod>>,
+    pub card_network: Option<String>,
+    pub connector: Option<String>,
+    pub status: Option<DBEnumWrapper<AttemptStatus>>,
+    pub count: Option<i64>,
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub start_bucket: Option<PrimitiveDateTime>,
+}
@@ -412,6 +412,7 @@ where
     order_by: Vec<String>,
     having: Option<Vec<(String, FilterTypes, String)>>,
     limit_by: Option<LimitByClause>,
+    limit: Option<u64>,
     outer_select: Vec<String>,
     top_n: Option<TopN>,
     table: AnalyticsCollection,
@@ -573,6 +574,7 @@ where
             order_by: Default::default(),
             having: Default::default(),
             limit_by: Default::default(),
+            limit: Default::default(),
             outer_select: Default::default(),
             top_n: Default::default(),
             table,
@@ -752,6 +754,10 @@ where
         Ok(())
     }
 
+    pub fn set_limit(&mut self, limit: u64) {
+        self.limit = Some(limit);
+    }
+
     pub fn add_granularity_in_mins(&mut self, granularity: Granularity) -> QueryResult<()> {
         let interval = match granularity {
             Granularity::OneMin => "1",
@@ -894,6 +900,10 @@ where
             query.push_str(&format!(" {}", limit_by));
         }
 
+        if let Some(limit) = self.limit {
+            query.push_str(&format!(" LIMIT {limit}"));
+        }
+
         if !self.outer_select.is_empty() {
             query.insert_str(
                 0,
@@ -20,7 +20,9 @@ use super::payments::AddressDetails;
 #[cfg(feature = "v1")]
 use crate::routing;
 use crate::{
-    consts::{MAX_ORDER_FULFILLMENT_EXPIRY, MIN_ORDER_FULFILLMENT_EXPIRY},
+    consts::{
+        MAX_ORDER_FULFILLMENT_EXPIRY, MAX_WEBHOOK_ENDPOINT_ID_LENGTH, MIN_ORDER_FULFILLMENT_EXPIRY,
+    },
     enums as api_enums, payment_methods,
 };
 
@@ -261,6 +263,72 @@ pub struct CardTestingGuardConfig {
     pub customer_id_blocking_threshold: i32,
     /// Determines Redis Expiry for Card Testing Guard for profile
     pub card_testing_guard_expiry: i32,
+    /// Velocity rules which block a card BIN, IP address, customer, email or device once too many
+    /// payments made using it fail within a sliding window
+    #[serde(default)]
+    pub velocity_rules: Vec<CardTestingVelocityRule>,
+    /// Values which are exempt from the velocity rules, payments made using any of these values
+    /// are never blocked by the velocity rules
+    #[serde(default)]
+    pub allowlist: Vec<CardTestingGuardAllowlistEntry>,
+}
+
+impl CardTestingGuardConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        for rule in &self.velocity_rules {
+            if rule.failure_threshold == 0 || rule.window_in_minutes == 0 {
+                return Err(format!(
+                    "`failure_threshold` and `window_in_minutes` of the {} velocity rule must be greater than 0",
+                    rule.dimension
+                ));
+            }
+
+            if rule.block_durations_in_minutes.is_empty()
+                || rule.block_durations_in_minutes.contains(&0)
+            {
+                return Err(format!(
+                    "`block_durations_in_minutes` of the {} velocity rule must contain durations greater than 0",
+                    rule.dimension
+                ));
+            }
+        }
+
+        if let Some(entry) = self
+            .allowlist
+            .iter()
+            .find(|entry| entry.value.trim().is_empty())
+        {
+            return Err(format!(
+                "Empty value received in the {} allowlist entry",
+                entry.dimension
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CardTestingVelocityRule {
+    /// The attribute of the payments whose failures are counted
+    pub dimension: common_enums::CardTestingGuardDimension,
+    /// Number of failed payments within the window after which the value is blocked
+    pub failure_threshold: u32,
+    /// Duration of the sliding window in which failed payments are counted, in minutes
+    pub window_in_minutes: u32,
+    /// Durations for which the value is blocked, in minutes. Repeated blocks escalate through
+    /// the durations, the last duration is used once all of them have been used.
+    pub block_durations_in_minutes: Vec<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CardTestingGuardAllowlistEntry {
+    pub dimension: common_enums::CardTestingGuardDimension,
+    /// The value exempt from the velocity rules, the device is identified by the value shown in
+    /// the blocks of the profile
+    pub value: String,
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize, ToSchema)]
@@ -688,6 +756,71 @@ pub struct WebhookDetails {
     /// If this property is true, a webhook message is posted whenever a payment fails
     #[schema(example = true)]
     pub payment_failed_enabled: Option<bool>,
+
+    /// Additional webhook endpoints, each receiving only the events it is subscribed to
+    pub webhook_endpoints: Option<Vec<WebhookEndpoint>>,
+
+    /// The format of the outgoing webhooks, Stripe compatible events are sent when set to `stripe`
+    #[schema(example = "stripe")]
+    pub webhook_format: Option<common_enums::OutgoingWebhookFormat>,
+}
+
+impl WebhookDetails {
+    pub fn validate(&self) -> Result<(), String> {
+        let mut endpoint_ids = HashSet::new();
+        for endpoint in self.webhook_endpoints.iter().flatten() {
+            if endpoint.endpoint_id.is_empty()
+                || endpoint.endpoint_id.len() > MAX_WEBHOOK_ENDPOINT_ID_LENGTH
+            {
+                return Err(format!(
+                    "`endpoint_id` must be between 1 and {} characters long",
+                    MAX_WEBHOOK_ENDPOINT_ID_LENGTH
+                ));
+            }
+
+            if !endpoint_ids.insert(endpoint.endpoint_id.as_str()) {
+                return Err(format!(
+                    "Duplicate webhook endpoint `{}` received in webhook_details",
+                    endpoint.endpoint_id
+                ));
+            }
+
+            if url::Url::parse(endpoint.url.peek()).is_err() {
+                return Err(format!(
+                    "Invalid url received for webhook endpoint `{}`",
+                    endpoint.endpoint_id
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookEndpoint {
+    /// Identifier of the endpoint, unique within the business profile
+    #[schema(max_length = 32, example = "ledger")]
+    pub endpoint_id: String,
+
+    /// The url for the webhook endpoint
+    #[schema(value_type = String, example = "https://ledger.ekart.com/webhooks")]
+    pub url: Secret<String>,
+
+    /// The event types delivered to this endpoint. All events are delivered if this is empty
+    #[serde(default)]
+    #[schema(example = json!(["payment_succeeded", "refund_succeeded"]))]
+    pub enabled_events: Vec<common_enums::EventType>,
+
+    /// The key used to sign webhooks sent to this endpoint. The `payment_response_hash_key` of
+    /// the business profile is used if not provided
+    #[schema(value_type = Option<String>)]
+    pub signing_secret: Option<Secret<String>>,
+
+    /// Webhooks are not delivered to the endpoint if this property is false. Defaults to true
+    #[schema(example = true)]
+    pub is_enabled: Option<bool>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -3042,6 +3175,99 @@ pub struct ConnectorAgnosticMitChoice {
 
 impl common_utils::events::ApiEventMetric for ConnectorAgnosticMitChoice {}
 
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookSigningSecretRotateRequest {
+    /// The secret to stage, a random secret is generated if not provided
+    pub signing_secret: Option<Secret<String>>,
+    /// Duration for which webhooks are signed with both the current and the staged secret
+    pub grace_period_in_seconds: Option<u32>,
+}
+
+impl common_utils::events::ApiEventMetric for WebhookSigningSecretRotateRequest {}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WebhookSigningSecretRotationResponse {
+    pub profile_id: id_type::ProfileId,
+    /// Key ID of the secret outgoing webhooks are currently signed with
+    pub current_key_id: Option<String>,
+    /// Key ID of the staged secret, if a rotation is in progress
+    pub staged_key_id: Option<String>,
+    /// The staged secret, only returned when it is staged
+    pub staged_signing_secret: Option<Secret<String>>,
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub grace_period_ends_at: Option<time::PrimitiveDateTime>,
+}
+
+impl common_utils::events::ApiEventMetric for WebhookSigningSecretRotationResponse {}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CardTestingGuardBlock {
+    /// Identifier of the block, which is used to clear it
+    pub block_id: String,
+    pub dimension: common_enums::CardTestingGuardDimension,
+    /// The blocked value, emails are masked and devices are identified by a fingerprint
+    pub value: String,
+    /// Number of times the value has been blocked recently, which determines the block duration
+    pub strike: u32,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub blocked_at: time::PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub expires_at: time::PrimitiveDateTime,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CardTestingGuardBlocksResponse {
+    pub profile_id: id_type::ProfileId,
+    pub blocks: Vec<CardTestingGuardBlock>,
+}
+
+impl common_utils::events::ApiEventMetric for CardTestingGuardBlocksResponse {}
+
+impl common_utils::events::ApiEventMetric for WebhookEndpoint {}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookEndpointUpdateRequest {
+    /// Identifier of the endpoint to be updated
+    #[serde(skip_deserializing)]
+    pub endpoint_id: String,
+    /// The url for the webhook endpoint
+    pub url: Option<Secret<String>>,
+    /// The event types delivered to this endpoint, all events are delivered if this is empty
+    pub enabled_events: Option<Vec<common_enums::EventType>>,
+    /// Webhooks are not delivered to the endpoint if this property is false
+    pub is_enabled: Option<bool>,
+}
+
+impl common_utils::events::ApiEventMetric for WebhookEndpointUpdateRequest {}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WebhookEndpointResponse {
+    pub profile_id: id_type::ProfileId,
+    #[serde(flatten)]
+    pub webhook_endpoint: WebhookEndpoint,
+}
+
+impl common_utils::events::ApiEventMetric for WebhookEndpointResponse {}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WebhookEndpointListResponse {
+    pub profile_id: id_type::ProfileId,
+    pub webhook_endpoints: Vec<WebhookEndpoint>,
+}
+
+impl common_utils::events::ApiEventMetric for WebhookEndpointListResponse {}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WebhookEndpointDeleteResponse {
+    pub profile_id: id_type::ProfileId,
+    pub endpoint_id: String,
+    pub deleted: bool,
+}
+
+impl common_utils::events::ApiEventMetric for WebhookEndpointDeleteResponse {}
+
 impl common_utils::events::ApiEventMetric for payment_methods::PaymentMethodMigrate {}
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
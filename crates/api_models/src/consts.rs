@@ -3,3 +3,6 @@ pub const MAX_ORDER_FULFILLMENT_EXPIRY: i64 = 1800;
 
 /// Min payment intent fulfillment expiry
 pub const MIN_ORDER_FULFILLMENT_EXPIRY: i64 = 60;
+
+/// Max length of the identifier of a business profile webhook endpoint
+pub const MAX_WEBHOOK_ENDPOINT_ID_LENGTH: usize = 32;
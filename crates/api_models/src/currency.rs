@@ -15,6 +15,22 @@ pub struct CurrencyConversionParams {
 pub struct CurrencyConversionResponse {
     pub converted_amount: String,
     pub currency: String,
+    /// Effective rate applied to the amount
+    pub rate: String,
+    /// Rates used for the conversion, a cross rate through an intermediate currency has two
+    pub rate_sources: Vec<CurrencyConversionRateSource>,
+}
+
+/// A rate applied during a currency conversion and the provider it came from
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CurrencyConversionRateSource {
+    pub from_currency: String,
+    pub to_currency: String,
+    pub rate: String,
+    pub source: String,
+    /// Unix timestamp at which the rate was fetched
+    pub fetched_at: i64,
 }
 
 impl ApiEventMetric for CurrencyConversionResponse {}
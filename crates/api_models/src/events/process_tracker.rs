@@ -0,0 +1,68 @@
+use common_utils::events::{ApiEventMetric, ApiEventsType};
+
+use crate::process_tracker::{
+    ProcessTrackerPausedRunnersResponse, ProcessTrackerRunnerPauseRequest,
+    ProcessTrackerRunnerResumeRequest, ProcessTrackerTaskId, ProcessTrackerTaskListConstraints,
+    ProcessTrackerTaskListResponse, ProcessTrackerTaskResponse, ProcessTrackerTaskRetryRequest,
+    ProcessTrackerTasksCancelRequest, ProcessTrackerTasksCancelResponse,
+};
+
+impl ApiEventMetric for ProcessTrackerTaskListConstraints {
+    fn get_api_event_type(&self) -> Option<ApiEventsType> {
+        Some(ApiEventsType::ProcessTracker)
+    }
+}
+
+impl ApiEventMetric for ProcessTrackerTaskListResponse {
+    fn get_api_event_type(&self) -> Option<ApiEventsType> {
+        Some(ApiEventsType::ProcessTracker)
+    }
+}
+
+impl ApiEventMetric for ProcessTrackerTaskId {
+    fn get_api_event_type(&self) -> Option<ApiEventsType> {
+        Some(ApiEventsType::ProcessTracker)
+    }
+}
+
+impl ApiEventMetric for ProcessTrackerTaskRetryRequest {
+    fn get_api_event_type(&self) -> Option<ApiEventsType> {
+        Some(ApiEventsType::ProcessTracker)
+    }
+}
+
+impl ApiEventMetric for ProcessTrackerTaskResponse {
+    fn get_api_event_type(&self) -> Option<ApiEventsType> {
+        Some(ApiEventsType::ProcessTracker)
+    }
+}
+
+impl ApiEventMetric for ProcessTrackerTasksCancelRequest {
+    fn get_api_event_type(&self) -> Option<ApiEventsType> {
+        Some(ApiEventsType::ProcessTracker)
+    }
+}
+
+impl ApiEventMetric for ProcessTrackerTasksCancelResponse {
+    fn get_api_event_type(&self) -> Option<ApiEventsType> {
+        Some(ApiEventsType::ProcessTracker)
+    }
+}
+
+impl ApiEventMetric for ProcessTrackerRunnerPauseRequest {
+    fn get_api_event_type(&self) -> Option<ApiEventsType> {
+        Some(ApiEventsType::ProcessTracker)
+    }
+}
+
+impl ApiEventMetric for ProcessTrackerRunnerResumeRequest {
+    fn get_api_event_type(&self) -> Option<ApiEventsType> {
+        Some(ApiEventsType::ProcessTracker)
+    }
+}
+
+impl ApiEventMetric for ProcessTrackerPausedRunnersResponse {
+    fn get_api_event_type(&self) -> Option<ApiEventsType> {
+        Some(ApiEventsType::ProcessTracker)
+    }
+}
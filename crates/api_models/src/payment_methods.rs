@@ -1652,6 +1652,10 @@ pub struct PaymentMethodListRequest {
     /// Indicates the limit of last used payment methods
     #[schema(example = 1)]
     pub limit: Option<i64>,
+
+    /// Include the reasons why payment methods were filtered out by the connector eligibility checks. Ignored unless the request is authenticated with the merchant's API key
+    #[schema(example = false)]
+    pub debug: Option<bool>,
 }
 
 #[cfg(all(
@@ -1727,6 +1731,9 @@ impl<'de> serde::Deserialize<'de> for PaymentMethodListRequest {
                         "limit" => {
                             set_or_reject_duplicate(&mut output.limit, "limit", map.next_value()?)?;
                         }
+                        "debug" => {
+                            set_or_reject_duplicate(&mut output.debug, "debug", map.next_value()?)?;
+                        }
                         _ => {}
                     }
                 }
@@ -1902,6 +1909,39 @@ pub struct PaymentMethodListResponse {
 
     /// flag that indicates whether to calculate tax on the order amount
     pub is_tax_calculation_enabled: bool,
+
+    /// The payment methods that were filtered out by the connector eligibility checks, along with the reason. Only populated when `debug` is set in the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eligibility_failures: Option<Vec<PaymentMethodEligibilityFailure>>,
+}
+
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "payment_methods_v2")
+))]
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+pub struct PaymentMethodEligibilityFailure {
+    /// The payment method that was filtered out
+    #[schema(value_type = PaymentMethod)]
+    pub payment_method: api_enums::PaymentMethod,
+
+    /// The payment method type that was filtered out
+    #[schema(value_type = PaymentMethodType)]
+    pub payment_method_type: api_enums::PaymentMethodType,
+
+    /// The connector for which the payment method is unavailable
+    #[schema(example = "stripe")]
+    pub connector: String,
+
+    /// The merchant connector account for which the payment method is unavailable
+    #[schema(value_type = String)]
+    pub merchant_connector_id: id_type::MerchantConnectorAccountId,
+
+    /// Human-readable explanation of why the payment method is unavailable
+    #[schema(
+        example = "payment method Credit is unavailable because connector stripe does not support currency EUR in billing country DE"
+    )]
+    pub reason: String,
 }
 
 #[cfg(all(
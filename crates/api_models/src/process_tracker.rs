@@ -1,2 +1,132 @@
 #[cfg(feature = "v2")]
 pub mod revenue_recovery;
+
+use common_utils::id_type;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::enums;
+
+/// Filters for listing process tracker tasks, the most recently created tasks are listed first
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessTrackerTaskListConstraints {
+    pub runner: Option<enums::ProcessTrackerRunner>,
+    pub status: Option<enums::ProcessTrackerStatus>,
+    /// Lists the tasks whose tracking data belongs to the merchant
+    pub merchant_id: Option<id_type::MerchantId>,
+    /// The maximum number of tasks to list, defaults to 10
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessTrackerTaskListResponse {
+    pub count: usize,
+    pub data: Vec<ProcessTrackerTaskResponse>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessTrackerTaskResponse {
+    pub id: String,
+    pub name: Option<String>,
+    pub tag: Vec<String>,
+    pub runner: Option<String>,
+    pub retry_count: i32,
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub schedule_time: Option<PrimitiveDateTime>,
+    pub tracking_data: serde_json::Value,
+    pub business_status: String,
+    pub status: enums::ProcessTrackerStatus,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub updated_at: PrimitiveDateTime,
+    /// The retries of the task, oldest first
+    pub retry_history: Vec<ProcessTrackerRetryResponse>,
+    /// The tasks which the task is still waiting for
+    pub parent_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessTrackerRetryResponse {
+    pub retry_count: i32,
+    /// Business status of the task when it was retried
+    pub business_status: String,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub retried_at: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub schedule_time: PrimitiveDateTime,
+    /// Whether the retry was forced through the process tracker API
+    pub is_forced: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessTrackerTaskId {
+    pub task_id: String,
+}
+
+/// The body of the requests which only identify the operator performing them
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessTrackerOperatorBody {
+    /// The operator performing the request, recorded in the audit event of the request
+    pub operator: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessTrackerTaskRetryRequest {
+    pub task_id: String,
+    pub operator: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessTrackerTasksCancelRequest {
+    /// The tasks to cancel, tasks which have been picked up by the scheduler already are not
+    /// cancelled
+    pub task_ids: Vec<String>,
+    pub reason: Option<String>,
+    /// The operator cancelling the tasks, recorded in the audit event of the request
+    pub operator: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessTrackerTasksCancelResponse {
+    pub cancelled_task_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessTrackerRunnerPauseBody {
+    pub reason: Option<String>,
+    /// The operator pausing the runner, recorded in the audit event of the request
+    pub operator: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessTrackerRunnerPauseRequest {
+    pub runner: enums::ProcessTrackerRunner,
+    pub reason: Option<String>,
+    pub operator: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessTrackerRunnerResumeRequest {
+    pub runner: enums::ProcessTrackerRunner,
+    pub operator: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessTrackerPausedRunner {
+    pub runner: enums::ProcessTrackerRunner,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub paused_at: PrimitiveDateTime,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessTrackerPausedRunnersResponse {
+    pub count: usize,
+    pub data: Vec<ProcessTrackerPausedRunner>,
+}
@@ -227,6 +227,8 @@ pub struct RefundResponse {
     /// The payment id against which refund is initiated
     #[schema(value_type = String)]
     pub payment_id: common_utils::id_type::PaymentId,
+    /// The id of the payment attempt against which refund is initiated
+    pub attempt_id: Option<String>,
     /// The refund amount, which should be less than or equal to the total payment amount. Amount for the payment in lowest denomination of the currency. (i.e) in cents for USD denomination, in paisa for INR denomination etc
     #[schema(value_type = i64 , minimum = 100, example = 6540)]
     pub amount: MinorUnit,
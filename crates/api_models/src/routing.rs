@@ -2,9 +2,13 @@ use std::fmt::Debug;
 
 use common_utils::{errors::ParsingError, ext_traits::ValueExt, pii};
 pub use euclid::{
-    dssa::types::EuclidAnalysable,
+    backend::{EvaluationSummary, EvaluationTrace},
+    dssa::types::{AnalysisWarning, EuclidAnalysable},
     frontend::{
-        ast,
+        ast::{
+            self,
+            formatter::{self as dsl_formatter, EuclidFormattable},
+        },
         dir::{DirKeyKind, EuclidDirFilter},
     },
 };
@@ -13,7 +17,7 @@ use utoipa::ToSchema;
 
 use crate::enums::{RoutableConnectors, TransactionType};
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum ConnectorSelection {
     Priority(Vec<RoutableConnectorChoice>),
@@ -131,9 +135,33 @@ impl EuclidDirFilter for ConnectorSelection {
         DirKeyKind::CardRedirectType,
         DirKeyKind::BankTransferType,
         DirKeyKind::RealTimePaymentType,
+        DirKeyKind::TimeOfDay,
+        DirKeyKind::DayOfWeek,
+        DirKeyKind::Date,
     ];
 }
 
+impl EuclidFormattable for ConnectorSelection {
+    fn format_output(&self) -> Result<String, dsl_formatter::FormatError> {
+        match self {
+            Self::Priority(choices) => dsl_formatter::format_priority(
+                choices
+                    .iter()
+                    .map(|choice| (&choice.connector, choice.merchant_connector_id.as_ref())),
+            ),
+            Self::VolumeSplit(splits) => {
+                dsl_formatter::format_volume_split(splits.iter().map(|split| {
+                    (
+                        &split.connector.connector,
+                        split.connector.merchant_connector_id.as_ref(),
+                        split.split,
+                    )
+                }))
+            }
+        }
+    }
+}
+
 impl EuclidAnalysable for ConnectorSelection {
     fn get_dir_value_for_analysis(
         &self,
@@ -487,6 +515,86 @@ pub struct RoutingDictionaryRecord {
     pub modified_at: i64,
     pub algorithm_for: Option<TransactionType>,
     pub decision_engine_routing_id: Option<String>,
+    /// Warnings from the static analysis of an advanced routing algorithm, returned when the
+    /// algorithm is created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Vec<Object>>)]
+    pub analysis_warnings: Option<Vec<AnalysisWarning>>,
+}
+
+/// Replays the payment attempts of a profile through a routing algorithm, to compare it with the
+/// active algorithm before activating it
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct RoutingSimulationRequest {
+    /// The routing algorithm to simulate
+    pub algorithm: RoutingAlgorithm,
+    /// The profile whose payment attempts are replayed
+    #[schema(value_type = String)]
+    pub profile_id: common_utils::id_type::ProfileId,
+    /// The number of days of payment attempts to replay, counting back from now. Defaults to 7
+    /// days, with a maximum of 90 days.
+    #[schema(example = 7)]
+    pub lookback_days: Option<u16>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+pub struct RoutingSimulationResponse {
+    #[schema(value_type = String)]
+    pub profile_id: common_utils::id_type::ProfileId,
+    /// The number of payment attempts replayed through the algorithms
+    pub simulated_attempts: u64,
+    /// The number of payment attempts which could not be replayed, as their amount or currency is
+    /// not known
+    pub skipped_attempts: u64,
+    /// Whether only the most common payment attempts were replayed, as the profile made more
+    /// distinct payment attempts within the lookback period than can be replayed at once
+    pub truncated: bool,
+    /// How the payment attempts were actually routed
+    pub historical: RoutingSimulationOutcome,
+    /// The routing algorithm active for the profile, if any
+    #[schema(value_type = Option<String>)]
+    pub active_algorithm_id: Option<common_utils::id_type::RoutingId>,
+    /// How the active routing algorithm routes the payment attempts, if there is one
+    pub active: Option<RoutingSimulationOutcome>,
+    /// How the simulated routing algorithm routes the payment attempts
+    pub candidate: RoutingSimulationOutcome,
+}
+
+/// A suite of regression fixtures to run against a routing algorithm before activating it
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct RoutingFixturesRequest {
+    /// The fixtures, each pairing a routing input with the rule and the connector selection it
+    /// is expected to produce
+    #[schema(value_type = Vec<Object>)]
+    pub fixtures: Vec<euclid::backend::fixtures::RoutingFixture<ConnectorSelection>>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+pub struct RoutingFixturesResponse {
+    #[schema(value_type = String)]
+    pub algorithm_id: common_utils::id_type::RoutingId,
+    /// The result of every fixture, as evaluated by both the interpreter backends
+    #[schema(value_type = Object)]
+    pub report: euclid::backend::fixtures::FixtureSuiteReport<ConnectorSelection>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, ToSchema)]
+pub struct RoutingSimulationOutcome {
+    /// The distribution of payment attempts across connectors, in descending order of attempts
+    pub connectors: Vec<ConnectorSimulationVolume>,
+    /// The success rate expected based on the historical success rate of each connector for each
+    /// payment method, over the attempts routed to connectors having any history
+    pub expected_success_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+pub struct ConnectorSimulationVolume {
+    pub connector: String,
+    /// The number of payment attempts routed to the connector. Attempts routed through a volume
+    /// split are distributed according to the split, so this may be fractional.
+    pub attempts: f64,
+    /// The percentage of all replayed payment attempts routed to the connector
+    pub share: f64,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
@@ -1118,3 +1226,53 @@ impl RoutableConnectorChoiceWithBucketName {
         }
     }
 }
+
+#[cfg(test)]
+mod connector_selection_tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_format_output_matches_dsl_connector_selection() {
+        for selection in [
+            serde_json::json!({
+                "type": "priority",
+                "data": ["stripe", { "connector": "adyen", "merchant_connector_id": "mca_adyen" }]
+            }),
+            serde_json::json!({
+                "type": "volume_split",
+                "data": [
+                    { "connector": "stripe", "split": 70 },
+                    {
+                        "connector": { "connector": "checkout", "merchant_connector_id": "mca_checkout" },
+                        "split": 30
+                    }
+                ]
+            }),
+        ] {
+            let connector_selection: ConnectorSelection =
+                serde_json::from_value(selection.clone()).unwrap();
+            let dsl_connector_selection: ast::ConnectorSelection =
+                serde_json::from_value(selection).unwrap();
+
+            assert_eq!(
+                connector_selection.format_output().unwrap(),
+                dsl_connector_selection.format_output().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_volume_split() {
+        let connector_selection: ConnectorSelection = serde_json::from_value(serde_json::json!({
+            "type": "volume_split",
+            "data": [{ "connector": "stripe", "split": 70 }, { "connector": "adyen", "split": 30 }]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            connector_selection.format_output().unwrap(),
+            "{ stripe: 70%, adyen: 30% }"
+        );
+    }
+}
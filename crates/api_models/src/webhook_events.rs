@@ -38,8 +38,13 @@ pub struct EventListConstraints {
     pub event_types: Option<HashSet<EventType>>,
     /// Filter all events by `is_overall_delivery_successful` field of the event.
     pub is_delivered: Option<bool>,
+
+    /// Filter events delivered to the specified business profile webhook endpoint.
+    pub webhook_endpoint_id: Option<String>,
 }
 
+impl common_utils::events::ApiEventMetric for EventListConstraints {}
+
 #[derive(Debug)]
 pub enum EventListConstraintsInternal {
     GenericFilter {
@@ -50,6 +55,7 @@ pub enum EventListConstraintsInternal {
         event_classes: Option<HashSet<EventClass>>,
         event_types: Option<HashSet<EventType>>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
     },
     ObjectIdFilter {
         object_id: String,
@@ -89,6 +95,18 @@ pub struct EventListItemResponse {
     #[schema(max_length = 64, example = "evt_018e31720d1b7a2b82677d3032cab959")]
     pub initial_attempt_id: String,
 
+    /// The identifier of the business profile webhook endpoint the event was sent to. This is
+    /// not populated for events sent to the primary webhook URL of the business profile.
+    #[schema(max_length = 32, example = "ledger")]
+    pub webhook_endpoint_id: Option<String>,
+
+    /// Time at which the event was moved to the dead-letter queue, after its automatic delivery
+    /// retries were exhausted. This is not populated for events that are not in the dead-letter
+    /// queue.
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub dead_lettered_at: Option<PrimitiveDateTime>,
+
     /// Time at which the event was created.
     #[schema(example = "2022-09-10T10:11:12Z")]
     #[serde(with = "common_utils::custom_serde::iso8601")]
@@ -121,6 +139,101 @@ impl common_utils::events::ApiEventMetric for TotalEventsResponse {
     }
 }
 
+/// The constraints to apply when listing the events in the dead-letter queue.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeadLetterEventListConstraints {
+    /// List the events of the specified business profile.
+    #[schema(value_type = String)]
+    pub profile_id: common_utils::id_type::ProfileId,
+
+    /// Filter events created after the specified time.
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub created_after: Option<PrimitiveDateTime>,
+
+    /// Filter events created before the specified time.
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub created_before: Option<PrimitiveDateTime>,
+
+    /// Filter events by their type.
+    pub event_types: Option<HashSet<EventType>>,
+
+    /// Include at most the specified number of events.
+    pub limit: Option<u16>,
+}
+
+/// The request body for redelivering the events in the dead-letter queue.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeadLetterRedeliveryRequest {
+    /// The constraints the events to redeliver must satisfy.
+    #[serde(flatten)]
+    pub constraints: DeadLetterEventListConstraints,
+
+    /// Deliver the events to the specified URL instead of the webhook URL configured in the
+    /// business profile.
+    #[schema(value_type = Option<String>)]
+    pub webhook_url: Option<Secret<String>>,
+
+    /// The maximum number of events to redeliver per minute, so that the recovering webhook
+    /// endpoint is not flooded with requests.
+    #[schema(example = 60)]
+    pub deliveries_per_minute: Option<u16>,
+}
+
+/// The response body for redelivering the events in the dead-letter queue.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeadLetterRedeliveryResponse {
+    /// The identifiers of the initial delivery attempts of the events scheduled for redelivery.
+    pub event_ids: Vec<String>,
+
+    /// The number of events redelivered per minute.
+    pub deliveries_per_minute: u16,
+
+    /// Time by which all the events are expected to have been redelivered.
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub estimated_completion_at: PrimitiveDateTime,
+}
+
+/// The state of the circuit breaker guarding the deliveries to a webhook endpoint.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookCircuitBreakerState {
+    /// Events are delivered to the webhook endpoint as usual.
+    #[default]
+    Closed,
+    /// Deliveries to the webhook endpoint are short-circuited and queued for retry.
+    Open,
+    /// A single probe delivery is allowed to check whether the webhook endpoint has recovered.
+    HalfOpen,
+}
+
+/// The circuit breaker guarding the deliveries to a webhook endpoint of a business profile.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookCircuitBreakerResponse {
+    /// The identifier of the webhook endpoint, or `null` for the primary webhook URL of the
+    /// business profile.
+    pub webhook_endpoint_id: Option<String>,
+
+    /// The current state of the circuit breaker.
+    pub state: WebhookCircuitBreakerState,
+
+    /// The number of consecutive failed deliveries to the webhook endpoint.
+    pub consecutive_failures: u32,
+
+    /// Time at which the circuit breaker was last opened.
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub opened_at: Option<PrimitiveDateTime>,
+
+    /// Time after which a probe delivery is allowed, if the circuit breaker is open.
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub next_probe_at: Option<PrimitiveDateTime>,
+
+    /// The number of short-circuited deliveries queued for retry once a probe delivery succeeds.
+    pub queued_deliveries: usize,
+}
+
 /// The response body for retrieving an event.
 #[derive(Debug, Serialize, ToSchema)]
 pub struct EventRetrieveResponse {
@@ -145,6 +258,46 @@ impl common_utils::events::ApiEventMetric for EventRetrieveResponse {
     }
 }
 
+/// An event along with the request sent in the webhook, available irrespective of whether the
+/// webhook was delivered.
+#[derive(Debug, Serialize)]
+pub struct EventContentResponse {
+    #[serde(flatten)]
+    pub event_information: EventListItemResponse,
+
+    /// The request information (headers and body) sent in the webhook.
+    pub request: OutgoingWebhookRequestContent,
+}
+
+impl common_utils::events::ApiEventMetric for EventContentResponse {
+    fn get_api_event_type(&self) -> Option<common_utils::events::ApiEventsType> {
+        Some(common_utils::events::ApiEventsType::Events {
+            merchant_id: self.event_information.merchant_id.clone(),
+        })
+    }
+}
+
+/// The response body of list initial delivery attempts along with the request sent in each
+/// webhook.
+#[derive(Debug, Serialize)]
+pub struct TotalEventContentsResponse {
+    /// The list of events
+    pub events: Vec<EventContentResponse>,
+    /// Count of total events
+    pub total_count: i64,
+}
+
+impl common_utils::events::ApiEventMetric for TotalEventContentsResponse {
+    fn get_api_event_type(&self) -> Option<common_utils::events::ApiEventsType> {
+        Some(common_utils::events::ApiEventsType::Events {
+            merchant_id: self
+                .events
+                .first()
+                .map(|event| event.event_information.merchant_id.clone())?,
+        })
+    }
+}
+
 /// The request information (headers and body) sent in the webhook.
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct OutgoingWebhookRequestContent {
@@ -226,3 +379,47 @@ impl common_utils::events::ApiEventMetric for WebhookDeliveryRetryRequestInterna
         })
     }
 }
+
+#[derive(Debug, serde::Serialize)]
+pub struct DeadLetterEventListRequestInternal {
+    pub merchant_id: common_utils::id_type::MerchantId,
+    pub constraints: DeadLetterEventListConstraints,
+}
+
+impl common_utils::events::ApiEventMetric for DeadLetterEventListRequestInternal {
+    fn get_api_event_type(&self) -> Option<common_utils::events::ApiEventsType> {
+        Some(common_utils::events::ApiEventsType::Events {
+            merchant_id: self.merchant_id.clone(),
+        })
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct DeadLetterRedeliveryRequestInternal {
+    pub merchant_id: common_utils::id_type::MerchantId,
+    pub request: DeadLetterRedeliveryRequest,
+}
+
+impl common_utils::events::ApiEventMetric for DeadLetterRedeliveryRequestInternal {
+    fn get_api_event_type(&self) -> Option<common_utils::events::ApiEventsType> {
+        Some(common_utils::events::ApiEventsType::Events {
+            merchant_id: self.merchant_id.clone(),
+        })
+    }
+}
+
+impl common_utils::events::ApiEventMetric for DeadLetterRedeliveryResponse {}
+
+#[derive(Debug, serde::Serialize)]
+pub struct WebhookCircuitBreakerListRequestInternal {
+    pub merchant_id: common_utils::id_type::MerchantId,
+    pub profile_id: common_utils::id_type::ProfileId,
+}
+
+impl common_utils::events::ApiEventMetric for WebhookCircuitBreakerListRequestInternal {
+    fn get_api_event_type(&self) -> Option<common_utils::events::ApiEventsType> {
+        Some(common_utils::events::ApiEventsType::Events {
+            merchant_id: self.merchant_id.clone(),
+        })
+    }
+}
@@ -1524,6 +1524,62 @@ pub enum WebhookDeliveryAttempt {
     ManualRetry,
 }
 
+/// The format of the outgoing webhooks sent for a business profile
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum OutgoingWebhookFormat {
+    /// Webhooks carrying the hyperswitch API resources, signed using the
+    /// `X-Webhook-Signature-512` header
+    #[default]
+    Hyperswitch,
+    /// Stripe `event` objects carrying the Stripe compatible API resources, signed using a
+    /// timestamped `Stripe-Signature` header
+    Stripe,
+}
+
+/// The attribute of a payment, the failures of which are counted by card testing guard velocity
+/// rules
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CardTestingGuardDimension {
+    /// The BIN of the card used for the payment
+    CardBin,
+    /// The IP address of the customer, from the browser information of the payment
+    IpAddress,
+    /// The customer of the payment
+    CustomerId,
+    /// The email of the customer
+    Email,
+    /// The device of the customer, identified using the browser information of the payment
+    Device,
+}
+
 // TODO: This decision about using KV mode or not,
 // should be taken at a top level rather than pushing it down to individual functions via an enum.
 #[derive(
@@ -8139,6 +8195,8 @@ pub enum ProcessTrackerStatus {
     Finish,
     // Review the task
     Review,
+    // Waiting for its parent tasks to succeed
+    Waiting,
 }
 
 #[derive(
@@ -8163,6 +8221,9 @@ pub enum ProcessTrackerRunner {
     AttachPayoutAccountWorkflow,
     PaymentMethodStatusUpdateWorkflow,
     PassiveRecoveryWorkflow,
+    WebhookSigningSecretPromotionWorkflow,
+    DeadLetterRedeliveryWorkflow,
+    ForexRatesRefreshWorkflow,
 }
 
 #[derive(Debug)]
@@ -122,6 +122,11 @@ impl MerchantId {
         format!("guard_blocklist_for_{}", self.get_string_repr())
     }
 
+    /// get_routing_evaluation_trace_key
+    pub fn get_routing_evaluation_trace_key(&self) -> String {
+        format!("routing_evaluation_trace_{}", self.get_string_repr())
+    }
+
     /// get_merchant_fingerprint_secret_key
     pub fn get_merchant_fingerprint_secret_key(&self) -> String {
         format!("fingerprint_secret_{}", self.get_string_repr())
@@ -4,7 +4,7 @@ use rusty_money::Money;
 
 use crate::{
     error::CurrencyConversionError,
-    types::{currency_match, ExchangeRates},
+    types::{currency_match, ConversionResult, ExchangeRates, RateLeg, RateSource, RoundingConfig},
 };
 
 pub fn convert(
@@ -26,12 +26,112 @@ pub fn convert(
     }
 }
 
+/// Converts `amount`, in minor units of `from_currency`, using the highest priority source that has
+/// rates for both currencies. When no single source does, a cross rate is computed through the base
+/// currency of one of the sources, possibly combining rates from two different sources.
+pub fn convert_with_sources(
+    sources: &[RateSource],
+    rounding: &RoundingConfig,
+    from_currency: Currency,
+    to_currency: Currency,
+    amount: i64,
+) -> Result<ConversionResult, CurrencyConversionError> {
+    let mut sources = sources.iter().collect::<Vec<_>>();
+    sources.sort_by_key(|source| source.priority);
+
+    let legs = sources
+        .iter()
+        .find_map(|source| rate_leg(source, from_currency, to_currency))
+        .map(|leg| vec![leg])
+        .or_else(|| cross_rate_legs(&sources, from_currency, to_currency))
+        .ok_or_else(|| {
+            CurrencyConversionError::ConversionNotSupported(format!(
+                "{from_currency} to {to_currency}"
+            ))
+        })?;
+
+    let rate = legs
+        .iter()
+        .try_fold(Decimal::ONE, |rate, leg| rate.checked_mul(leg.rate))
+        .ok_or(CurrencyConversionError::DecimalMultiplicationFailed)?;
+
+    let money_minor = Money::from_minor(amount, currency_match(from_currency));
+    let converted = money_minor
+        .amount()
+        .checked_mul(rate)
+        .ok_or(CurrencyConversionError::DecimalMultiplicationFailed)?;
+
+    let exponent = currency_match(to_currency).exponent;
+    let rounding_mode = rounding.mode_for(exponent);
+    let mut rounded = converted.round_dp_with_strategy(exponent, rounding_mode.into());
+    rounded.rescale(exponent);
+    let minor_amount =
+        i64::try_from(rounded.mantissa()).map_err(|_| CurrencyConversionError::AmountOutOfRange)?;
+
+    Ok(ConversionResult {
+        from_currency,
+        to_currency,
+        amount: rounded,
+        minor_amount,
+        rate,
+        rounding_mode,
+        legs,
+    })
+}
+
+fn rate_leg(
+    source: &RateSource,
+    from_currency: Currency,
+    to_currency: Currency,
+) -> Option<RateLeg> {
+    source
+        .rates
+        .get_rate(from_currency, to_currency)
+        .map(|rate| RateLeg {
+            from_currency,
+            to_currency,
+            rate,
+            source: source.name.clone(),
+            fetched_at: source.fetched_at,
+        })
+}
+
+fn cross_rate_legs(
+    sources: &[&RateSource],
+    from_currency: Currency,
+    to_currency: Currency,
+) -> Option<Vec<RateLeg>> {
+    let mut intermediates = Vec::new();
+    for source in sources {
+        let base_currency = source.rates.base_currency;
+        if base_currency != from_currency
+            && base_currency != to_currency
+            && !intermediates.contains(&base_currency)
+        {
+            intermediates.push(base_currency);
+        }
+    }
+
+    intermediates.into_iter().find_map(|intermediate| {
+        let first = sources
+            .iter()
+            .find_map(|source| rate_leg(source, from_currency, intermediate))?;
+        let second = sources
+            .iter()
+            .find_map(|source| rate_leg(source, intermediate, to_currency))?;
+        Some(vec![first, second])
+    })
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used, clippy::print_stdout)]
     use std::collections::HashMap;
 
-    use crate::types::CurrencyFactors;
+    use common_enums::Currency;
+    use rust_decimal::Decimal;
+
+    use crate::types::{CurrencyFactors, ExchangeRates, RateSource};
     #[test]
     fn currency_to_currency_conversion() {
         use super::*;
@@ -98,4 +198,153 @@ mod tests {
             amount, convert_from, convert_to, res
         );
     }
+
+    fn rate_source(
+        name: &str,
+        priority: u8,
+        base_currency: Currency,
+        rates: &[(Currency, Decimal)],
+    ) -> RateSource {
+        let conversion = rates
+            .iter()
+            .map(|&(currency, rate)| {
+                let from_factor = Decimal::ONE.checked_div(rate).expect("non-zero rate");
+                (currency, CurrencyFactors::new(rate, from_factor))
+            })
+            .collect();
+        RateSource::new(
+            name,
+            priority,
+            1_700_000_000,
+            ExchangeRates::new(base_currency, conversion),
+        )
+    }
+
+    #[test]
+    fn prefers_higher_priority_source() {
+        use super::*;
+        let sources = [
+            rate_source(
+                "fallback",
+                1,
+                Currency::USD,
+                &[(Currency::INR, Decimal::new(80, 0))],
+            ),
+            rate_source(
+                "primary",
+                0,
+                Currency::USD,
+                &[(Currency::INR, Decimal::new(83, 0))],
+            ),
+        ];
+
+        let res = convert_with_sources(
+            &sources,
+            &RoundingConfig::default(),
+            Currency::USD,
+            Currency::INR,
+            1000,
+        )
+        .expect("converted_currency");
+
+        assert_eq!(res.minor_amount, 830000);
+        assert_eq!(res.rate, Decimal::new(83, 0));
+        assert_eq!(
+            res.legs
+                .iter()
+                .map(|leg| leg.source.as_str())
+                .collect::<Vec<_>>(),
+            vec!["primary"]
+        );
+    }
+
+    #[test]
+    fn cross_rate_through_intermediate_currency() {
+        use super::*;
+        let sources = [
+            rate_source(
+                "usd_rates",
+                0,
+                Currency::USD,
+                &[(Currency::INR, Decimal::new(80, 0))],
+            ),
+            rate_source(
+                "eur_rates",
+                1,
+                Currency::EUR,
+                &[(Currency::USD, Decimal::new(2, 0))],
+            ),
+        ];
+
+        // EUR -> USD from `eur_rates`, then USD -> INR from `usd_rates`
+        let res = convert_with_sources(
+            &sources,
+            &RoundingConfig::default(),
+            Currency::EUR,
+            Currency::INR,
+            100,
+        )
+        .expect("converted_currency");
+
+        assert_eq!(res.rate, Decimal::new(160, 0));
+        assert_eq!(res.minor_amount, 16000);
+        assert_eq!(
+            res.legs
+                .iter()
+                .map(|leg| (leg.source.as_str(), leg.to_currency))
+                .collect::<Vec<_>>(),
+            vec![("eur_rates", Currency::USD), ("usd_rates", Currency::INR)]
+        );
+    }
+
+    #[test]
+    fn rounding_mode_by_exponent() {
+        use super::*;
+        use crate::types::{ExponentRounding, RoundingMode};
+        let sources = [rate_source(
+            "primary",
+            0,
+            Currency::USD,
+            &[(Currency::JPY, Decimal::new(1505, 1))],
+        )];
+        let rounding = RoundingConfig {
+            default_mode: RoundingMode::HalfEven,
+            overrides: vec![ExponentRounding {
+                exponent: 0,
+                mode: RoundingMode::Down,
+            }],
+        };
+
+        // 0.99 USD * 150.5 = 148.995 JPY
+        let res = convert_with_sources(&sources, &rounding, Currency::USD, Currency::JPY, 99)
+            .expect("converted_currency");
+
+        assert_eq!(res.rounding_mode, RoundingMode::Down);
+        assert_eq!(res.minor_amount, 148);
+        assert_eq!(res.amount, Decimal::new(148, 0));
+    }
+
+    #[test]
+    fn unsupported_pair() {
+        use super::*;
+        let sources = [rate_source(
+            "primary",
+            0,
+            Currency::USD,
+            &[(Currency::INR, Decimal::new(83, 0))],
+        )];
+
+        let res = convert_with_sources(
+            &sources,
+            &RoundingConfig::default(),
+            Currency::EUR,
+            Currency::INR,
+            100,
+        );
+
+        assert!(matches!(
+            res,
+            Err(CurrencyConversionError::ConversionNotSupported(_))
+        ));
+    }
 }
@@ -5,4 +5,6 @@ pub enum CurrencyConversionError {
     DecimalMultiplicationFailed,
     #[error("Currency not supported: '{0}'")]
     ConversionNotSupported(String),
+    #[error("Converted amount doesn't fit in minor units")]
+    AmountOutOfRange,
 }
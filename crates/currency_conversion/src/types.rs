@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use common_enums::Currency;
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
 use rusty_money::iso;
 
 use crate::error::CurrencyConversionError;
@@ -75,6 +75,132 @@ impl ExchangeRates {
     }
 }
 
+impl ExchangeRates {
+    /// Rate for converting `from_currency` into `to_currency` through the base currency, if the
+    /// factors for both currencies are known
+    pub fn get_rate(&self, from_currency: Currency, to_currency: Currency) -> Option<Decimal> {
+        if from_currency == to_currency {
+            return Some(Decimal::ONE);
+        }
+
+        let from_factor = if from_currency == self.base_currency {
+            Decimal::ONE
+        } else {
+            self.conversion.get(&from_currency)?.from_factor
+        };
+        let to_factor = if to_currency == self.base_currency {
+            Decimal::ONE
+        } else {
+            self.conversion.get(&to_currency)?.to_factor
+        };
+
+        from_factor.checked_mul(to_factor)
+    }
+}
+
+/// Exchange rates published by a single provider
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RateSource {
+    /// Name of the provider, recorded in the conversion result
+    pub name: String,
+    /// Sources with a lower value are preferred
+    pub priority: u8,
+    /// Unix timestamp at which the rates were fetched
+    pub fetched_at: i64,
+    pub rates: ExchangeRates,
+}
+
+impl RateSource {
+    pub fn new(
+        name: impl Into<String>,
+        priority: u8,
+        fetched_at: i64,
+        rates: ExchangeRates,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            priority,
+            fetched_at,
+            rates,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Round to the nearest value, ties go to the even neighbour
+    #[default]
+    HalfEven,
+    /// Round to the nearest value, ties go away from zero
+    HalfUp,
+    /// Round towards zero
+    Down,
+    /// Round away from zero
+    Up,
+}
+
+impl From<RoundingMode> for RoundingStrategy {
+    fn from(mode: RoundingMode) -> Self {
+        match mode {
+            RoundingMode::HalfEven => Self::MidpointNearestEven,
+            RoundingMode::HalfUp => Self::MidpointAwayFromZero,
+            RoundingMode::Down => Self::ToZero,
+            RoundingMode::Up => Self::AwayFromZero,
+        }
+    }
+}
+
+/// Rounding applied to converted amounts, picked by the exponent of the target currency
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct RoundingConfig {
+    pub default_mode: RoundingMode,
+    pub overrides: Vec<ExponentRounding>,
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct ExponentRounding {
+    /// Number of minor unit digits of the currency, e.g. `0` for JPY or `3` for KWD
+    pub exponent: u32,
+    pub mode: RoundingMode,
+}
+
+impl RoundingConfig {
+    pub fn mode_for(&self, exponent: u32) -> RoundingMode {
+        self.overrides
+            .iter()
+            .find(|rounding| rounding.exponent == exponent)
+            .map_or(self.default_mode, |rounding| rounding.mode)
+    }
+}
+
+/// A single rate applied during a conversion, along with where it came from
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RateLeg {
+    pub from_currency: Currency,
+    pub to_currency: Currency,
+    pub rate: Decimal,
+    pub source: String,
+    pub fetched_at: i64,
+}
+
+/// A converted amount along with the rates that produced it
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ConversionResult {
+    pub from_currency: Currency,
+    pub to_currency: Currency,
+    /// Converted amount in the major unit of `to_currency`, rounded to its exponent
+    pub amount: Decimal,
+    /// Converted amount in the minor unit of `to_currency`
+    pub minor_amount: i64,
+    /// Effective rate between the two currencies
+    pub rate: Decimal,
+    pub rounding_mode: RoundingMode,
+    /// Rates in the order they were applied, a cross rate has one leg per hop
+    pub legs: Vec<RateLeg>,
+}
+
 pub fn currency_match(currency: Currency) -> &'static iso::Currency {
     match currency {
         Currency::AED => iso::AED,
@@ -669,10 +669,40 @@ pub struct CardTestingGuardConfig {
     pub is_customer_id_blocking_enabled: bool,
     pub customer_id_blocking_threshold: i32,
     pub card_testing_guard_expiry: i32,
+    #[serde(default)]
+    pub velocity_rules: Vec<CardTestingVelocityRule>,
+    #[serde(default)]
+    pub allowlist: Vec<CardTestingGuardAllowlistEntry>,
 }
 
 common_utils::impl_to_sql_from_sql_json!(CardTestingGuardConfig);
 
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct CardTestingVelocityRule {
+    pub dimension: common_enums::CardTestingGuardDimension,
+    pub failure_threshold: u32,
+    pub window_in_minutes: u32,
+    pub block_durations_in_minutes: Vec<u32>,
+}
+
+impl CardTestingVelocityRule {
+    /// Identifies the sliding window of the rule, the window is retained across changes to the
+    /// order of the rules and to the block durations, and is reset when the rule counts failures
+    /// differently
+    pub fn get_rule_id(&self) -> String {
+        format!(
+            "{}_{}_{}",
+            self.dimension, self.failure_threshold, self.window_in_minutes
+        )
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct CardTestingGuardAllowlistEntry {
+    pub dimension: common_enums::CardTestingGuardDimension,
+    pub value: String,
+}
+
 impl Default for CardTestingGuardConfig {
     fn default() -> Self {
         Self {
@@ -688,6 +718,8 @@ impl Default for CardTestingGuardConfig {
                 common_utils::consts::DEFAULT_CUSTOMER_ID_BLOCKING_THRESHOLD,
             card_testing_guard_expiry:
                 common_utils::consts::DEFAULT_CARD_TESTING_GUARD_EXPIRY_IN_SECS,
+            velocity_rules: Vec::new(),
+            allowlist: Vec::new(),
         }
     }
 }
@@ -702,10 +734,48 @@ pub struct WebhookDetails {
     pub payment_created_enabled: Option<bool>,
     pub payment_succeeded_enabled: Option<bool>,
     pub payment_failed_enabled: Option<bool>,
+    pub webhook_endpoints: Option<Vec<WebhookEndpoint>>,
+    pub signing_secret_rotation: Option<WebhookSigningSecretRotation>,
+    pub webhook_format: Option<common_enums::OutgoingWebhookFormat>,
 }
 
 common_utils::impl_to_sql_from_sql_json!(WebhookDetails);
 
+/// A signing secret staged to replace the profile's `payment_response_hash_key`
+///
+/// Until `grace_period_ends_at`, outgoing webhooks are signed with both the current and the staged
+/// secret, and only with the staged secret afterwards.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct WebhookSigningSecretRotation {
+    pub staged_secret: Secret<String>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub staged_at: time::PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub grace_period_ends_at: time::PrimitiveDateTime,
+}
+
+impl WebhookSigningSecretRotation {
+    pub fn is_in_grace_period(&self, now: time::PrimitiveDateTime) -> bool {
+        now < self.grace_period_ends_at
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct WebhookEndpoint {
+    pub endpoint_id: String,
+    pub url: Secret<String>,
+    pub enabled_events: Vec<common_enums::EventType>,
+    pub signing_secret: Option<Secret<String>>,
+    pub is_enabled: Option<bool>,
+}
+
+impl WebhookEndpoint {
+    pub fn is_subscribed_to(&self, event_type: common_enums::EventType) -> bool {
+        self.is_enabled.unwrap_or(true)
+            && (self.enabled_events.is_empty() || self.enabled_events.contains(&event_type))
+    }
+}
+
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize, diesel::AsExpression)]
 #[diesel(sql_type = diesel::sql_types::Jsonb)]
 pub struct BusinessPaymentLinkConfig {
@@ -0,0 +1,14 @@
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
+
+use crate::schema::drainer_applied_entry;
+
+/// A stream entry applied to the database by the drainer, recorded in the same transaction as the
+/// entry itself so that entries read again from the stream are not applied twice
+#[derive(Clone, Debug, Eq, PartialEq, Identifiable, Queryable, Selectable, Insertable)]
+#[diesel(table_name = drainer_applied_entry, primary_key(stream_name, entry_id), check_for_backend(diesel::pg::Pg))]
+pub struct DrainerAppliedEntry {
+    pub stream_name: String,
+    pub entry_id: String,
+    pub table_name: String,
+    pub applied_at: time::PrimitiveDateTime,
+}
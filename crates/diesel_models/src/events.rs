@@ -27,6 +27,8 @@ pub struct EventNew {
     pub delivery_attempt: Option<storage_enums::WebhookDeliveryAttempt>,
     pub metadata: Option<EventMetadata>,
     pub is_overall_delivery_successful: Option<bool>,
+    pub webhook_endpoint_id: Option<String>,
+    pub dead_lettered_at: Option<PrimitiveDateTime>,
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -35,6 +37,7 @@ pub struct EventUpdateInternal {
     pub is_webhook_notified: Option<bool>,
     pub response: Option<Encryption>,
     pub is_overall_delivery_successful: Option<bool>,
+    pub dead_lettered_at: Option<Option<PrimitiveDateTime>>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable, Selectable)]
@@ -60,6 +63,8 @@ pub struct Event {
     pub delivery_attempt: Option<storage_enums::WebhookDeliveryAttempt>,
     pub metadata: Option<EventMetadata>,
     pub is_overall_delivery_successful: Option<bool>,
+    pub webhook_endpoint_id: Option<String>,
+    pub dead_lettered_at: Option<PrimitiveDateTime>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, AsExpression, diesel::FromSqlRow)]
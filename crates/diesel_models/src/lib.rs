@@ -13,6 +13,7 @@ pub mod blocklist_fingerprint;
 pub mod callback_mapper;
 pub mod customers;
 pub mod dispute;
+pub mod drainer_applied_entry;
 pub mod dynamic_routing_stats;
 pub mod enums;
 pub mod ephemeral_key;
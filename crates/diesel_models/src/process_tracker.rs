@@ -40,6 +40,12 @@ pub struct ProcessTracker {
     #[serde(with = "common_utils::custom_serde::iso8601")]
     pub updated_at: PrimitiveDateTime,
     pub version: ApiVersion,
+    pub retry_history: Option<RetryHistory>,
+    /// Recurring schedule of the task, recurring tasks are rescheduled every time they finish
+    pub schedule: Option<ProcessTrackerSchedule>,
+    /// Parent tasks of a waiting task which have not succeeded yet, the task is executed once all
+    /// of them succeed
+    pub parent_ids: Option<Vec<String>>,
 }
 
 impl ProcessTracker {
@@ -66,6 +72,12 @@ pub struct ProcessTrackerNew {
     pub created_at: PrimitiveDateTime,
     pub updated_at: PrimitiveDateTime,
     pub version: ApiVersion,
+    pub retry_history: Option<RetryHistory>,
+    /// Recurring schedule of the task, recurring tasks are rescheduled every time they finish
+    pub schedule: Option<ProcessTrackerSchedule>,
+    /// Parent tasks of a waiting task which have not succeeded yet, the task is executed once all
+    /// of them succeed
+    pub parent_ids: Option<Vec<String>>,
 }
 
 impl ProcessTrackerNew {
@@ -102,8 +114,113 @@ impl ProcessTrackerNew {
             created_at: current_time,
             updated_at: current_time,
             version: api_version,
+            retry_history: None,
+            schedule: None,
+            parent_ids: None,
         })
     }
+
+    /// Makes the task wait for the parent tasks, the task is executed once all of them succeed
+    pub fn with_parent_ids(mut self, parent_ids: Vec<String>) -> Self {
+        if !parent_ids.is_empty() {
+            self.status = storage_enums::ProcessTrackerStatus::Waiting;
+            self.parent_ids = Some(parent_ids);
+        }
+        self
+    }
+}
+
+/// Recurring schedule of a task, along with the random delay added to each of its occurrences
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize, diesel::AsExpression)]
+#[diesel(sql_type = diesel::sql_types::Jsonb)]
+pub struct ProcessTrackerSchedule {
+    #[serde(flatten)]
+    pub recurrence: ProcessTrackerRecurrence,
+    /// Upper bound of the random delay added to each occurrence, spreads the tasks scheduled for
+    /// the same occurrence
+    #[serde(default)]
+    pub jitter_seconds: u32,
+}
+
+common_utils::impl_to_sql_from_sql_json!(ProcessTrackerSchedule);
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProcessTrackerRecurrence {
+    /// Five field cron expression (minute, hour, day of month, month, day of week) in UTC
+    Cron { expression: String },
+    /// Fixed interval counted from the time the previous occurrence finished
+    Interval { interval_seconds: u32 },
+}
+
+/// Maximum number of retries retained in the retry history of a task
+const RETRY_HISTORY_MAX_LENGTH: usize = 50;
+
+/// The retries of a task, oldest first
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize, diesel::AsExpression)]
+#[diesel(sql_type = diesel::sql_types::Jsonb)]
+pub struct RetryHistory(pub Vec<ProcessTrackerRetry>);
+
+common_utils::impl_to_sql_from_sql_json!(RetryHistory);
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ProcessTrackerRetry {
+    /// Retry count of the task after the retry
+    pub retry_count: i32,
+    /// Business status of the task when it was retried
+    pub business_status: String,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub retried_at: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub schedule_time: PrimitiveDateTime,
+    /// Whether the retry was forced by an operator, rather than scheduled by the workflow
+    #[serde(default)]
+    pub is_forced: bool,
+}
+
+impl ProcessTracker {
+    /// Retry history of the task along with a new retry, the oldest retries are dropped once the
+    /// history grows beyond its maximum length
+    pub fn get_retry_history_with(
+        &self,
+        retry_count: i32,
+        schedule_time: PrimitiveDateTime,
+        is_forced: bool,
+    ) -> RetryHistory {
+        self.get_retry_history_with_business_status(
+            retry_count,
+            self.business_status.clone(),
+            schedule_time,
+            is_forced,
+        )
+    }
+
+    /// Retry history of the task along with a new retry of the task in the given business status,
+    /// such as the business status an occurrence of a recurring task finished with
+    pub fn get_retry_history_with_business_status(
+        &self,
+        retry_count: i32,
+        business_status: String,
+        schedule_time: PrimitiveDateTime,
+        is_forced: bool,
+    ) -> RetryHistory {
+        let mut retries = self
+            .retry_history
+            .clone()
+            .map(|retry_history| retry_history.0)
+            .unwrap_or_default();
+        retries.push(ProcessTrackerRetry {
+            retry_count,
+            business_status,
+            retried_at: common_utils::date_time::now(),
+            schedule_time,
+            is_forced,
+        });
+
+        let excess_retries = retries.len().saturating_sub(RETRY_HISTORY_MAX_LENGTH);
+        retries.drain(..excess_retries);
+        RetryHistory(retries)
+    }
 }
 
 #[derive(Debug)]
@@ -126,6 +243,20 @@ pub enum ProcessTrackerUpdate {
         retry_count: i32,
         schedule_time: PrimitiveDateTime,
     },
+    /// Schedules the next occurrence of a recurring task, along with the retry history recording
+    /// the failure of the previous occurrence
+    ScheduleUpdate {
+        schedule_time: PrimitiveDateTime,
+        retry_history: Option<RetryHistory>,
+    },
+    /// Replaces the schedule of a recurring task, its next occurrence is left as it is
+    RecurrenceUpdate { schedule: ProcessTrackerSchedule },
+    RetryUpdate {
+        retry_count: i32,
+        schedule_time: PrimitiveDateTime,
+        business_status: Option<String>,
+        retry_history: RetryHistory,
+    },
 }
 
 #[derive(Debug, Clone, AsChangeset, router_derive::DebugAsDisplay)]
@@ -138,6 +269,8 @@ pub struct ProcessTrackerUpdateInternal {
     business_status: Option<String>,
     status: Option<storage_enums::ProcessTrackerStatus>,
     updated_at: Option<PrimitiveDateTime>,
+    retry_history: Option<RetryHistory>,
+    schedule: Option<ProcessTrackerSchedule>,
 }
 
 impl Default for ProcessTrackerUpdateInternal {
@@ -150,6 +283,36 @@ impl Default for ProcessTrackerUpdateInternal {
             business_status: Option::default(),
             status: Option::default(),
             updated_at: Some(common_utils::date_time::now()),
+            retry_history: Option::default(),
+            schedule: Option::default(),
+        }
+    }
+}
+
+impl ProcessTrackerUpdateInternal {
+    pub fn apply_changeset(self, source: ProcessTracker) -> ProcessTracker {
+        let Self {
+            name,
+            retry_count,
+            schedule_time,
+            tracking_data,
+            business_status,
+            status,
+            updated_at,
+            retry_history,
+            schedule,
+        } = self;
+        ProcessTracker {
+            name: name.or(source.name),
+            retry_count: retry_count.unwrap_or(source.retry_count),
+            schedule_time: schedule_time.or(source.schedule_time),
+            tracking_data: tracking_data.unwrap_or(source.tracking_data),
+            business_status: business_status.unwrap_or(source.business_status),
+            status: status.unwrap_or(source.status),
+            updated_at: updated_at.unwrap_or(source.updated_at),
+            retry_history: retry_history.or(source.retry_history),
+            schedule: schedule.or(source.schedule),
+            ..source
         }
     }
 }
@@ -173,6 +336,8 @@ impl From<ProcessTrackerUpdate> for ProcessTrackerUpdateInternal {
                 business_status,
                 status,
                 updated_at,
+                retry_history: None,
+                schedule: None,
             },
             ProcessTrackerUpdate::StatusUpdate {
                 status,
@@ -192,6 +357,34 @@ impl From<ProcessTrackerUpdate> for ProcessTrackerUpdateInternal {
                 schedule_time: Some(schedule_time),
                 ..Default::default()
             },
+            ProcessTrackerUpdate::ScheduleUpdate {
+                schedule_time,
+                retry_history,
+            } => Self {
+                status: Some(storage_enums::ProcessTrackerStatus::New),
+                retry_count: Some(0),
+                schedule_time: Some(schedule_time),
+                business_status: Some(String::from(business_status::PENDING)),
+                retry_history,
+                ..Default::default()
+            },
+            ProcessTrackerUpdate::RecurrenceUpdate { schedule } => Self {
+                schedule: Some(schedule),
+                ..Default::default()
+            },
+            ProcessTrackerUpdate::RetryUpdate {
+                retry_count,
+                schedule_time,
+                business_status,
+                retry_history,
+            } => Self {
+                status: Some(storage_enums::ProcessTrackerStatus::Pending),
+                retry_count: Some(retry_count),
+                schedule_time: Some(schedule_time),
+                business_status,
+                retry_history: Some(retry_history),
+                ..Default::default()
+            },
         }
     }
 }
@@ -250,6 +443,29 @@ pub mod business_status {
     /// Business status set for newly created tasks.
     pub const PENDING: &str = "Pending";
 
+    /// The task was cancelled by an operator before it was executed.
+    /// A task that reaches this status should not be retried (rescheduled for execution) later.
+    pub const CANCELLED_BY_OPERATOR: &str = "CANCELLED_BY_OPERATOR";
+
+    /// One of the parent tasks of the task failed, due to which the task was not executed.
+    /// A task that reaches this status should not be retried (rescheduled for execution) later.
+    pub const PARENT_FAILED: &str = "PARENT_FAILED";
+
+    /// Whether a finished task with the business status failed, the tasks which depend on a failed
+    /// task are not executed
+    pub fn is_failure(business_status: &str) -> bool {
+        [
+            GLOBAL_FAILURE,
+            FAILURE,
+            REVOKED,
+            RETRIES_EXCEEDED,
+            GLOBAL_ERROR,
+            CANCELLED_BY_OPERATOR,
+            PARENT_FAILED,
+        ]
+        .contains(&business_status)
+    }
+
     /// For the PCR Workflow
     ///
     /// This status indicates the completion of a execute task
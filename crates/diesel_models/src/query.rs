@@ -14,6 +14,7 @@ pub mod callback_mapper;
 pub mod customers;
 pub mod dashboard_metadata;
 pub mod dispute;
+pub mod drainer_applied_entry;
 pub mod dynamic_routing_stats;
 pub mod events;
 pub mod file;
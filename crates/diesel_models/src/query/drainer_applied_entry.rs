@@ -0,0 +1,29 @@
+use diesel::{associations::HasTable, ExpressionMethods};
+
+use super::generics;
+use crate::{
+    drainer_applied_entry::DrainerAppliedEntry, errors, schema::drainer_applied_entry::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl DrainerAppliedEntry {
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<Self> {
+        generics::generic_insert(conn, self).await
+    }
+
+    /// Deletes the entries applied before the given time, returns whether any entries were deleted
+    pub async fn delete_applied_before(
+        conn: &PgPooledConn,
+        applied_before: time::PrimitiveDateTime,
+    ) -> StorageResult<bool> {
+        generics::generic_delete::<<Self as HasTable>::Table, _>(
+            conn,
+            dsl::applied_at.lt(applied_before),
+        )
+        .await
+        .or_else(|error| match error.current_context() {
+            errors::DatabaseError::NotFound => Ok(false),
+            _ => Err(error),
+        })
+    }
+}
@@ -61,6 +61,7 @@ impl Event {
         offset: Option<i64>,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
     ) -> StorageResult<Vec<Self>> {
         use async_bb8_diesel::AsyncRunQueryDsl;
         use diesel::{debug_query, pg::Pg, QueryDsl};
@@ -88,6 +89,7 @@ impl Event {
             offset,
             event_types,
             is_delivered,
+            webhook_endpoint_id,
         );
 
         logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
@@ -144,6 +146,7 @@ impl Event {
         offset: Option<i64>,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
     ) -> StorageResult<Vec<Self>> {
         use async_bb8_diesel::AsyncRunQueryDsl;
         use diesel::{debug_query, pg::Pg, QueryDsl};
@@ -171,6 +174,7 @@ impl Event {
             offset,
             event_types,
             is_delivered,
+            webhook_endpoint_id,
         );
 
         logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
@@ -181,6 +185,50 @@ impl Event {
             .attach_printable("Error filtering events by constraints")
     }
 
+    /// Lists the initial attempts of the events of the business profile that are in the
+    /// dead-letter queue, oldest first
+    pub async fn list_dead_lettered_initial_attempts_by_profile_id(
+        conn: &PgPooledConn,
+        profile_id: &common_utils::id_type::ProfileId,
+        created_after: time::PrimitiveDateTime,
+        created_before: time::PrimitiveDateTime,
+        event_types: HashSet<common_enums::EventType>,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        use async_bb8_diesel::AsyncRunQueryDsl;
+        use diesel::{debug_query, pg::Pg, QueryDsl};
+        use error_stack::ResultExt;
+        use router_env::logger;
+
+        use super::generics::db_metrics::{track_database_call, DatabaseOperation};
+        use crate::errors::DatabaseError;
+
+        let mut query = Self::table()
+            .filter(
+                dsl::event_id
+                    .nullable()
+                    .eq(dsl::initial_attempt_id) // Filter initial attempts only
+                    .and(dsl::business_profile_id.eq(profile_id.to_owned()))
+                    .and(dsl::dead_lettered_at.is_not_null())
+                    .and(dsl::created_at.ge(created_after))
+                    .and(dsl::created_at.le(created_before)),
+            )
+            .order(dsl::created_at.asc())
+            .limit(limit)
+            .into_boxed();
+
+        if !event_types.is_empty() {
+            query = query.filter(dsl::event_type.eq_any(event_types));
+        }
+
+        logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
+
+        track_database_call::<Self, _, _>(query.get_results_async(conn), DatabaseOperation::Filter)
+            .await
+            .change_context(DatabaseError::Others) // Query returns empty Vec when no records are found
+            .attach_printable("Error filtering dead-lettered events")
+    }
+
     pub async fn list_by_profile_id_initial_attempt_id(
         conn: &PgPooledConn,
         profile_id: &common_utils::id_type::ProfileId,
@@ -219,6 +267,7 @@ impl Event {
         .await
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn apply_filters<T>(
         mut query: T,
         profile_id: Option<common_utils::id_type::ProfileId>,
@@ -231,6 +280,7 @@ impl Event {
         offset: Option<i64>,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
     ) -> T
     where
         T: diesel::query_dsl::methods::LimitDsl<Output = T>
@@ -255,6 +305,10 @@ impl Event {
             diesel::dsl::Eq<dsl::is_overall_delivery_successful, bool>,
             Output = T,
         >,
+        T: diesel::query_dsl::methods::FilterDsl<
+            diesel::dsl::Eq<dsl::webhook_endpoint_id, String>,
+            Output = T,
+        >,
     {
         if let Some(profile_id) = profile_id {
             query = query.filter(dsl::business_profile_id.eq(profile_id));
@@ -280,9 +334,14 @@ impl Event {
             query = query.filter(dsl::is_overall_delivery_successful.eq(is_delivered));
         }
 
+        if let Some(webhook_endpoint_id) = webhook_endpoint_id {
+            query = query.filter(dsl::webhook_endpoint_id.eq(webhook_endpoint_id));
+        }
+
         query
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn count_initial_attempts_by_constraints(
         conn: &PgPooledConn,
         merchant_id: &common_utils::id_type::MerchantId,
@@ -291,6 +350,7 @@ impl Event {
         created_before: time::PrimitiveDateTime,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
     ) -> StorageResult<i64> {
         use async_bb8_diesel::AsyncRunQueryDsl;
         use diesel::{debug_query, pg::Pg, QueryDsl};
@@ -318,6 +378,7 @@ impl Event {
             None,
             event_types,
             is_delivered,
+            webhook_endpoint_id,
         );
 
         logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
@@ -1,4 +1,10 @@
-use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods, Table};
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::{
+    associations::HasTable, debug_query, define_sql_function, pg::Pg, result::Error as DieselError,
+    sql_types, BoolExpressionMethods, ExpressionMethods, PgAnyJsonExpressionMethods,
+    PgArrayExpressionMethods, QueryDsl, Table,
+};
+use error_stack::{report, ResultExt};
 use router_env::{instrument, tracing};
 use time::PrimitiveDateTime;
 
@@ -6,12 +12,21 @@ use super::generics;
 use crate::{
     enums, errors,
     process_tracker::{
-        ProcessTracker, ProcessTrackerNew, ProcessTrackerUpdate, ProcessTrackerUpdateInternal,
+        business_status, ProcessTracker, ProcessTrackerNew, ProcessTrackerUpdate,
+        ProcessTrackerUpdateInternal,
     },
     schema::process_tracker::dsl,
     PgPooledConn, StorageResult,
 };
 
+define_sql_function! {
+    /// Removes all the occurrences of the element from the array
+    fn array_remove(
+        array: sql_types::Nullable<sql_types::Array<sql_types::Nullable<sql_types::Text>>>,
+        element: sql_types::Text,
+    ) -> sql_types::Nullable<sql_types::Array<sql_types::Nullable<sql_types::Text>>>;
+}
+
 impl ProcessTrackerNew {
     #[instrument(skip(conn))]
     pub async fn insert_process(self, conn: &PgPooledConn) -> StorageResult<ProcessTracker> {
@@ -55,6 +70,152 @@ impl ProcessTracker {
         .await
     }
 
+    /// Updates the tasks with the given IDs which are in one of the given statuses, and returns the
+    /// updated tasks
+    #[instrument(skip(conn))]
+    pub async fn update_processes_by_ids_and_statuses(
+        conn: &PgPooledConn,
+        task_ids: Vec<String>,
+        statuses: Vec<enums::ProcessTrackerStatus>,
+        task_update: ProcessTrackerUpdate,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_update_with_results::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::id.eq_any(task_ids).and(dsl::status.eq_any(statuses)),
+            ProcessTrackerUpdateInternal::from(task_update),
+        )
+        .await
+    }
+
+    /// Moves the schedule time of the tasks of the runner which are yet to be scheduled, and were
+    /// due before the given schedule time, to the given schedule time. Returns the number of
+    /// rescheduled tasks.
+    #[instrument(skip(conn))]
+    pub async fn reschedule_overdue_processes_by_runner(
+        conn: &PgPooledConn,
+        runner: String,
+        schedule_time: PrimitiveDateTime,
+    ) -> StorageResult<usize> {
+        generics::generic_update::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::runner
+                .eq(runner)
+                .and(dsl::status.eq_any(vec![
+                    enums::ProcessTrackerStatus::New,
+                    enums::ProcessTrackerStatus::Pending,
+                ]))
+                .and(dsl::schedule_time.lt(schedule_time)),
+            (
+                dsl::schedule_time.eq(schedule_time),
+                dsl::updated_at.eq(common_utils::date_time::now()),
+            ),
+        )
+        .await
+    }
+
+    /// Removes the parent from the parents of the tasks waiting for it, and returns the updated
+    /// tasks
+    #[instrument(skip(conn))]
+    pub async fn remove_parent_from_waiting_processes(
+        conn: &PgPooledConn,
+        parent_id: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_update_with_results::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::status
+                .eq(enums::ProcessTrackerStatus::Waiting)
+                .and(dsl::parent_ids.contains(vec![parent_id.to_owned()])),
+            (
+                dsl::parent_ids.eq(array_remove(dsl::parent_ids, parent_id.to_owned())),
+                dsl::updated_at.eq(common_utils::date_time::now()),
+            ),
+        )
+        .await
+    }
+
+    /// Updates the tasks waiting for the parent, and returns the updated tasks
+    #[instrument(skip(conn))]
+    pub async fn update_waiting_processes_by_parent_id(
+        conn: &PgPooledConn,
+        parent_id: &str,
+        task_update: ProcessTrackerUpdate,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_update_with_results::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::status
+                .eq(enums::ProcessTrackerStatus::Waiting)
+                .and(dsl::parent_ids.contains(vec![parent_id.to_owned()])),
+            ProcessTrackerUpdateInternal::from(task_update),
+        )
+        .await
+    }
+
+    /// Updates the tasks which were finished without being executed as the parent failed, and
+    /// returns the updated tasks
+    #[instrument(skip(conn))]
+    pub async fn update_parent_failed_processes_by_parent_id(
+        conn: &PgPooledConn,
+        parent_id: &str,
+        task_update: ProcessTrackerUpdate,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_update_with_results::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::status
+                .eq(enums::ProcessTrackerStatus::Finish)
+                .and(dsl::business_status.eq(business_status::PARENT_FAILED))
+                .and(dsl::parent_ids.contains(vec![parent_id.to_owned()])),
+            ProcessTrackerUpdateInternal::from(task_update),
+        )
+        .await
+    }
+
+    /// Lists the tasks matching the given filters, most recently created first
+    #[instrument(skip(conn))]
+    pub async fn filter_processes(
+        conn: &PgPooledConn,
+        runner: Option<String>,
+        status: Option<enums::ProcessTrackerStatus>,
+        merchant_id: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> StorageResult<Vec<Self>> {
+        let mut query = <Self as HasTable>::table().into_boxed();
+
+        if let Some(runner) = runner {
+            query = query.filter(dsl::runner.eq(runner));
+        }
+
+        if let Some(status) = status {
+            query = query.filter(dsl::status.eq(status));
+        }
+
+        if let Some(merchant_id) = merchant_id {
+            query = query.filter(
+                dsl::tracking_data
+                    .retrieve_as_text("merchant_id")
+                    .eq(merchant_id),
+            );
+        }
+
+        query = query
+            .order(dsl::created_at.desc())
+            .limit(limit)
+            .offset(offset);
+
+        router_env::logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
+
+        generics::db_metrics::track_database_call::<Self, _, _>(
+            query.get_results_async(conn),
+            generics::db_metrics::DatabaseOperation::Filter,
+        )
+        .await
+        .map_err(|error| match error {
+            DieselError::NotFound => report!(error).change_context(errors::DatabaseError::NotFound),
+            _ => report!(error).change_context(errors::DatabaseError::Others),
+        })
+        .attach_printable("Error filtering process tracker entries")
+    }
+
     #[instrument(skip(conn))]
     pub async fn find_process_by_id(conn: &PgPooledConn, id: &str) -> StorageResult<Option<Self>> {
         generics::generic_find_by_id_optional::<<Self as HasTable>::Table, _, _>(
@@ -64,6 +225,20 @@ impl ProcessTracker {
         .await
     }
 
+    #[instrument(skip(conn))]
+    pub async fn find_processes_by_ids(
+        conn: &PgPooledConn,
+        ids: Vec<String>,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<
+            <Self as HasTable>::Table,
+            _,
+            <<Self as HasTable>::Table as Table>::PrimaryKey,
+            _,
+        >(conn, dsl::id.eq_any(ids), None, None, None)
+        .await
+    }
+
     #[instrument(skip(conn))]
     pub async fn find_processes_by_time_status(
         conn: &PgPooledConn,
@@ -429,6 +429,21 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    drainer_applied_entry (stream_name, entry_id) {
+        #[max_length = 255]
+        stream_name -> Varchar,
+        #[max_length = 64]
+        entry_id -> Varchar,
+        #[max_length = 64]
+        table_name -> Varchar,
+        applied_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -490,6 +505,9 @@ diesel::table! {
         delivery_attempt -> Nullable<WebhookDeliveryAttempt>,
         metadata -> Nullable<Jsonb>,
         is_overall_delivery_successful -> Nullable<Bool>,
+        #[max_length = 64]
+        webhook_endpoint_id -> Nullable<Varchar>,
+        dead_lettered_at -> Nullable<Timestamp>,
     }
 }
 
@@ -1180,6 +1198,9 @@ diesel::table! {
         created_at -> Timestamp,
         updated_at -> Timestamp,
         version -> ApiVersion,
+        retry_history -> Nullable<Jsonb>,
+        schedule -> Nullable<Jsonb>,
+        parent_ids -> Nullable<Array<Nullable<Text>>>,
     }
 }
 
@@ -1502,6 +1523,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     customers,
     dashboard_metadata,
     dispute,
+    drainer_applied_entry,
     dynamic_routing_stats,
     events,
     file_metadata,
@@ -11,8 +11,9 @@ use tokio::{
 };
 
 use crate::{
-    errors, instrument, logger, metrics, query::ExecuteQuery, tracing, utils, DrainerSettings,
-    Store, StreamData,
+    errors, instrument, logger, metrics, quarantine,
+    query::{self, EntryApplication, ExecuteQuery},
+    tracing, utils, DrainerSettings, Store, StreamData,
 };
 
 /// Handler handles the spawning and closing of drainer
@@ -82,6 +83,7 @@ impl Handler {
                             store.clone(),
                             stream_index,
                             self.conf.max_read_count,
+                            self.conf.max_entry_attempts,
                             self.active_tasks.clone(),
                             jobs_picked.clone(),
                         )
@@ -100,6 +102,37 @@ impl Handler {
         Ok(())
     }
 
+    /// Periodically deletes the IDs of the applied entries which are past their retention period
+    pub fn spawn_applied_entries_cleanup(&self) {
+        let handler = self.clone();
+        let _task_handle = tokio::spawn(
+            async move {
+                let cleanup_interval =
+                    Duration::from_secs(handler.conf.applied_entry_cleanup_interval.into());
+
+                while handler.running.load(atomic::Ordering::SeqCst) {
+                    time::sleep(cleanup_interval).await;
+
+                    for store in handler.stores.values() {
+                        match query::delete_expired_applied_entries(
+                            store,
+                            handler.conf.applied_entry_retention,
+                        )
+                        .await
+                        {
+                            Ok(true) => logger::info!("Deleted expired applied entries"),
+                            Ok(false) => (),
+                            Err(error) => {
+                                logger::error!(operation = "delete_applied_entries", ?error)
+                            }
+                        }
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
     pub(crate) async fn shutdown_listener(&self, mut rx: mpsc::Receiver<()>) {
         while let Some(_c) = rx.recv().await {
             logger::info!("Awaiting shutdown!");
@@ -174,6 +207,7 @@ async fn drainer_handler(
     store: Arc<Store>,
     stream_index: u8,
     max_read_count: u64,
+    max_entry_attempts: u32,
     active_tasks: Arc<atomic::AtomicU64>,
     jobs_picked: Arc<atomic::AtomicU8>,
 ) -> errors::DrainerResult<()> {
@@ -184,6 +218,7 @@ async fn drainer_handler(
     let drainer_result = Box::pin(drainer(
         store.clone(),
         max_read_count,
+        max_entry_attempts,
         stream_name.as_str(),
         jobs_picked,
     ))
@@ -204,6 +239,7 @@ async fn drainer_handler(
 async fn drainer(
     store: Arc<Store>,
     max_read_count: u64,
+    max_entry_attempts: u32,
     stream_name: &str,
     jobs_picked: Arc<atomic::AtomicU8>,
 ) -> errors::DrainerResult<()> {
@@ -245,7 +281,7 @@ async fn drainer(
     let mut last_processed_id = String::new();
 
     for (entry_id, entry) in entries.clone() {
-        let data = match StreamData::from_hashmap(entry) {
+        let data = match StreamData::from_hashmap(entry.clone()) {
             Ok(data) => data,
             Err(err) => {
                 logger::error!(operation = "deserialization", err=?err);
@@ -254,17 +290,36 @@ async fn drainer(
                     router_env::metric_attributes!(("operation", "deserialization")),
                 );
 
-                // break from the loop in case of a deser error
-                break;
+                // Entries which cannot be deserialized are never going to be applied, so they
+                // are quarantined right away
+                match store
+                    .quarantine_entry(stream_name, &entry_id, entry, format!("{err:?}"), 1)
+                    .await
+                {
+                    Ok(()) => {
+                        last_processed_id = entry_id;
+                        continue;
+                    }
+                    Err(error) => {
+                        logger::error!(operation = "quarantine", ?error);
+                        break;
+                    }
+                }
             }
         };
 
+        let applied_entry_id = data.get_applied_entry_id(&entry_id).to_owned();
+
         tracing::Span::current().record("request_id", data.request_id);
         tracing::Span::current().record("global_id", data.global_id);
         tracing::Span::current().record("session_id", &session_id);
 
-        match data.typed_sql.execute_query(&store, data.pushed_at).await {
-            Ok(_) => {
+        match data
+            .typed_sql
+            .execute_query(&store, stream_name, &applied_entry_id, data.pushed_at)
+            .await
+        {
+            Ok(EntryApplication::Applied | EntryApplication::AlreadyApplied) => {
                 last_processed_id = entry_id;
             }
             Err(err) => match err.current_context() {
@@ -273,8 +328,43 @@ async fn drainer(
                 diesel_models::errors::DatabaseError::UniqueViolation => {
                     last_processed_id = entry_id;
                 }
-                // break from the loop in case of an error in query
-                _ => break,
+                // Errors due to the database rather than the entry, such as the connection to the
+                // database being lost, are not counted as attempts of the entry, so that entries
+                // are not quarantined while the database is unavailable
+                _ if query::is_transient_error(&err) => break,
+                // break from the loop in case of an error in query, unless the entry has failed
+                // too many times, in which case it is quarantined so that the entries after it
+                // are applied
+                _ => {
+                    let attempts = match store.record_entry_failure(stream_name, &entry_id).await {
+                        Ok(attempts) => attempts,
+                        Err(error) => {
+                            logger::error!(operation = "record_entry_failure", ?error);
+                            break;
+                        }
+                    };
+
+                    if !quarantine::should_quarantine_entry(attempts, max_entry_attempts) {
+                        break;
+                    }
+
+                    match store
+                        .quarantine_entry(
+                            stream_name,
+                            &entry_id,
+                            entry,
+                            format!("{err:?}"),
+                            attempts,
+                        )
+                        .await
+                    {
+                        Ok(()) => last_processed_id = entry_id,
+                        Err(error) => {
+                            logger::error!(operation = "quarantine", ?error);
+                            break;
+                        }
+                    }
+                }
             },
         }
     }
@@ -4,6 +4,7 @@ mod handler;
 mod health_check;
 pub mod logger;
 pub(crate) mod metrics;
+pub mod quarantine;
 mod query;
 pub mod services;
 pub mod settings;
@@ -50,6 +51,7 @@ pub async fn start_drainer(
     tokio::task::spawn(async move { handler_clone.shutdown_listener(rx).await });
 
     drainer_handler.spawn_error_handlers(tx)?;
+    drainer_handler.spawn_applied_entries_cleanup();
     drainer_handler.spawn().await?;
 
     handle.close();
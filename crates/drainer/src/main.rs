@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use drainer::{errors::DrainerResult, logger, services, settings, start_drainer, start_web_server};
+use drainer::{
+    errors::DrainerResult, logger, quarantine, services, settings, start_drainer, start_web_server,
+};
 use router_env::tracing::Instrument;
 
 #[tokio::main]
@@ -35,6 +37,10 @@ async fn main() -> DrainerResult<()> {
         [router_env::service_name!()],
     );
 
+    if let Some(settings::Command::Quarantine(command)) = cmd_line.command {
+        return quarantine::run_quarantine_command(&stores, command).await;
+    }
+
     #[allow(clippy::expect_used)]
     let web_server = Box::pin(start_web_server(
         state.conf.as_ref().clone(),
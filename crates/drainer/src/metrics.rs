@@ -12,6 +12,8 @@ counter_metric!(SUCCESSFUL_SHUTDOWN, DRAINER_METER);
 counter_metric!(STREAM_EMPTY, DRAINER_METER);
 counter_metric!(STREAM_PARSE_FAIL, DRAINER_METER);
 counter_metric!(DRAINER_HEALTH, DRAINER_METER);
+counter_metric!(DUPLICATE_ENTRIES_SKIPPED, DRAINER_METER);
+counter_metric!(ENTRIES_QUARANTINED, DRAINER_METER);
 
 histogram_metric_f64!(QUERY_EXECUTION_TIME, DRAINER_METER); // Time in (ms) milliseconds
 histogram_metric_f64!(REDIS_STREAM_READ_TIME, DRAINER_METER); // Time in (ms) milliseconds
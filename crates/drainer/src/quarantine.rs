@@ -0,0 +1,520 @@
+//! Quarantine of stream entries which cannot be applied to the database
+//!
+//! An entry which fails to be applied is attempted again in the next cycles, as the entries after
+//! it are not applied until it is. Once it fails `max_entry_attempts` times, it is moved to the
+//! quarantine stream so that the entries after it are applied. Failures due to the database rather
+//! than the entry, such as the connection to the database being lost, are not counted as attempts.
+//! Entries which cannot be deserialized are moved right away. The quarantined entries can be listed, appended back to
+//! their streams once the cause of their failure is fixed, or discarded, using the `quarantine`
+//! command of the drainer. Replayed entries carry the ID they were first appended with, which the
+//! entries are recorded with once applied, so that entries applied before being quarantined are
+//! not applied again.
+
+use std::{collections::HashMap, sync::Arc};
+
+use common_utils::id_type;
+use error_stack::{report, ResultExt};
+use redis_interface::{errors::RedisError, RedisConnectionPool, RedisEntryId, RedisKey};
+use serde::{de::value::MapDeserializer, Deserialize, Serialize};
+
+use crate::{
+    errors, logger, metrics,
+    settings::QuarantineCommand,
+    types::ORIGINAL_ENTRY_ID_FIELD,
+    utils::{deserialize_i64, deserialize_json_string},
+    Store,
+};
+
+/// Entries in the stream are applied in order, so only the first entry of a stream can fail more
+/// than once. The number of attempts is kept for a single entry per stream, and is reset when a
+/// different entry fails.
+///
+/// KEYS: failed entry of the stream
+/// ARGV: entry id, expiry in seconds
+const RECORD_ENTRY_FAILURE_SCRIPT: &str = r#"
+local failed_entry = redis.call('HMGET', KEYS[1], 'entry_id', 'attempts')
+local attempts = 1
+if failed_entry[1] == ARGV[1] then
+    attempts = tonumber(failed_entry[2]) + 1
+end
+redis.call('HSET', KEYS[1], 'entry_id', ARGV[1], 'attempts', attempts)
+redis.call('EXPIRE', KEYS[1], ARGV[2])
+return attempts
+"#;
+
+/// Attempts of a failed entry are forgotten if it is not attempted again for this long
+const FAILED_ENTRY_EXPIRY_SECONDS: i64 = 24 * 60 * 60;
+/// Number of quarantined entries read at once while looking for entries to replay
+const QUARANTINE_READ_COUNT: u64 = 100;
+
+/// An entry moved to the quarantine stream, along with the stream it was read from
+#[derive(Debug, Deserialize, Serialize)]
+pub struct QuarantinedEntry {
+    pub source_stream: String,
+    pub source_entry_id: String,
+    #[serde(deserialize_with = "deserialize_json_string")]
+    pub entry: HashMap<String, String>,
+    pub error: String,
+    #[serde(deserialize_with = "deserialize_i64")]
+    pub attempts: i64,
+    #[serde(deserialize_with = "deserialize_i64")]
+    pub quarantined_at: i64,
+}
+
+impl QuarantinedEntry {
+    fn from_hashmap(
+        hashmap: HashMap<String, String>,
+    ) -> common_utils::errors::CustomResult<Self, common_utils::errors::ParsingError> {
+        let iter = MapDeserializer::<
+            '_,
+            std::collections::hash_map::IntoIter<String, String>,
+            serde_json::error::Error,
+        >::new(hashmap.into_iter());
+
+        Self::deserialize(iter).change_context(
+            common_utils::errors::ParsingError::StructParseFailure("QuarantinedEntry"),
+        )
+    }
+
+    fn to_field_value_pairs(&self) -> errors::DrainerResult<Vec<(&str, String)>> {
+        Ok(vec![
+            ("source_stream", self.source_stream.clone()),
+            ("source_entry_id", self.source_entry_id.clone()),
+            (
+                "entry",
+                serde_json::to_string(&self.entry).change_context(
+                    errors::DrainerError::UnexpectedError(
+                        "Failed to serialize quarantined entry".into(),
+                    ),
+                )?,
+            ),
+            ("error", self.error.clone()),
+            ("attempts", self.attempts.to_string()),
+            ("quarantined_at", self.quarantined_at.to_string()),
+        ])
+    }
+
+    /// Fields of the entry to be appended back to its stream, along with the ID the entry was
+    /// first appended with. Entries quarantined again after being replayed keep their original ID.
+    fn into_replayed_entry(self) -> Vec<(String, String)> {
+        let mut entry = self.entry;
+        entry
+            .entry(ORIGINAL_ENTRY_ID_FIELD.to_owned())
+            .or_insert(self.source_entry_id);
+        entry.into_iter().collect()
+    }
+}
+
+/// Whether the entry has failed to be applied too many times, and is to be quarantined
+pub(crate) fn should_quarantine_entry(attempts: i64, max_entry_attempts: u32) -> bool {
+    attempts >= i64::from(max_entry_attempts)
+}
+
+/// Records a failed attempt of the entry of the stream, returns the number of attempts of the entry
+async fn record_entry_failure_in_stream(
+    redis_conn: &RedisConnectionPool,
+    stream_name: &str,
+    entry_id: &str,
+) -> errors::DrainerResult<i64> {
+    let key = RedisKey::from(Store::get_failed_entry_key(stream_name).as_str())
+        .tenant_aware_key(redis_conn);
+
+    redis_conn
+        .evaluate_redis_script::<_, i64>(
+            RECORD_ENTRY_FAILURE_SCRIPT,
+            vec![key],
+            vec![entry_id.to_owned(), FAILED_ENTRY_EXPIRY_SECONDS.to_string()],
+        )
+        .await
+        .map_err(|error| report!(errors::DrainerError::from(error)))
+}
+
+#[derive(Debug, Serialize)]
+struct QuarantinedStreamEntry {
+    id: String,
+    #[serde(flatten)]
+    entry: QuarantinedEntry,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct QuarantineCommandResponse {
+    entry_ids: Vec<String>,
+    not_found_entry_ids: Vec<String>,
+}
+
+impl Store {
+    #[inline(always)]
+    pub(crate) fn get_quarantine_stream_name(&self) -> String {
+        format!("{}_quarantine", self.config.drainer_stream_name)
+    }
+
+    #[inline(always)]
+    fn get_failed_entry_key(stream_name: &str) -> String {
+        format!("{stream_name}_failed_entry")
+    }
+
+    /// Records a failed attempt of the entry, returns the number of attempts of the entry
+    pub(crate) async fn record_entry_failure(
+        &self,
+        stream_name: &str,
+        entry_id: &str,
+    ) -> errors::DrainerResult<i64> {
+        record_entry_failure_in_stream(&self.redis_conn, stream_name, entry_id).await
+    }
+
+    /// Moves the entry to the quarantine stream, the entry is to be trimmed from its stream
+    /// afterwards
+    pub(crate) async fn quarantine_entry(
+        &self,
+        stream_name: &str,
+        entry_id: &str,
+        entry: HashMap<String, String>,
+        error: String,
+        attempts: i64,
+    ) -> errors::DrainerResult<()> {
+        let quarantined_entry = QuarantinedEntry {
+            source_stream: stream_name.to_owned(),
+            source_entry_id: entry_id.to_owned(),
+            entry,
+            error,
+            attempts,
+            quarantined_at: common_utils::date_time::now_unix_timestamp(),
+        };
+
+        self.redis_conn
+            .stream_append_entry(
+                &self.get_quarantine_stream_name().as_str().into(),
+                &RedisEntryId::AutoGeneratedID,
+                quarantined_entry.to_field_value_pairs()?,
+            )
+            .await
+            .map_err(errors::DrainerError::from)?;
+
+        logger::warn!(
+            stream = stream_name,
+            entry_id,
+            attempts,
+            "Moved entry to the quarantine stream"
+        );
+        metrics::ENTRIES_QUARANTINED.add(
+            1,
+            router_env::metric_attributes!(("stream", stream_name.to_owned())),
+        );
+
+        Ok(())
+    }
+
+    /// Reads the quarantined entries following the given entry ID, in the order they were
+    /// quarantined
+    async fn read_quarantined_entries(
+        &self,
+        after_entry_id: &str,
+        count: u64,
+    ) -> errors::DrainerResult<Vec<QuarantinedStreamEntry>> {
+        let stream_name = self.get_quarantine_stream_name();
+        let mut stream_read = match self
+            .redis_conn
+            .stream_read_entries(stream_name.as_str(), after_entry_id, Some(count))
+            .await
+        {
+            Ok(stream_read) => stream_read,
+            Err(error) => match error.current_context() {
+                RedisError::StreamEmptyOrNotAvailable => return Ok(Vec::new()),
+                _ => return Err(errors::DrainerError::from(error).into()),
+            },
+        };
+
+        stream_read
+            .remove(&self.redis_conn.add_prefix(&stream_name))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(id, entry)| {
+                QuarantinedEntry::from_hashmap(entry)
+                    .map(|entry| QuarantinedStreamEntry { id, entry })
+                    .map_err(|error| report!(errors::DrainerError::ParsingError(error)))
+            })
+            .collect()
+    }
+
+    /// Reads all the quarantined entries with the given IDs, along with the IDs which were not
+    /// found
+    async fn find_quarantined_entries(
+        &self,
+        entry_ids: &[String],
+        all: bool,
+    ) -> errors::DrainerResult<(Vec<QuarantinedStreamEntry>, Vec<String>)> {
+        let mut found_entries = Vec::new();
+        let mut after_entry_id = String::from("0-0");
+
+        loop {
+            let entries = self
+                .read_quarantined_entries(&after_entry_id, QUARANTINE_READ_COUNT)
+                .await?;
+            let Some(last_entry) = entries.last() else {
+                break;
+            };
+            after_entry_id = last_entry.id.clone();
+
+            found_entries.extend(
+                entries
+                    .into_iter()
+                    .filter(|entry| all || entry_ids.contains(&entry.id)),
+            );
+        }
+
+        let not_found_entry_ids = entry_ids
+            .iter()
+            .filter(|entry_id| !found_entries.iter().any(|entry| &entry.id == *entry_id))
+            .cloned()
+            .collect();
+
+        Ok((found_entries, not_found_entry_ids))
+    }
+
+    async fn delete_quarantined_entries(
+        &self,
+        entry_ids: Vec<String>,
+    ) -> errors::DrainerResult<()> {
+        if entry_ids.is_empty() {
+            return Ok(());
+        }
+
+        self.redis_conn
+            .stream_delete_entries(
+                &self.get_quarantine_stream_name().as_str().into(),
+                entry_ids,
+            )
+            .await
+            .map_err(errors::DrainerError::from)?;
+
+        Ok(())
+    }
+
+    /// Appends the quarantined entries back to the streams they were read from, where they are
+    /// applied after the entries which are in the streams already
+    async fn replay_quarantined_entries(
+        &self,
+        entry_ids: &[String],
+        all: bool,
+    ) -> errors::DrainerResult<QuarantineCommandResponse> {
+        let (entries, not_found_entry_ids) = self.find_quarantined_entries(entry_ids, all).await?;
+
+        let mut replayed_entry_ids = Vec::with_capacity(entries.len());
+        for QuarantinedStreamEntry { id, entry } in entries {
+            let source_stream = entry.source_stream.clone();
+            self.redis_conn
+                .stream_append_entry(
+                    &source_stream.as_str().into(),
+                    &RedisEntryId::AutoGeneratedID,
+                    entry.into_replayed_entry(),
+                )
+                .await
+                .map_err(errors::DrainerError::from)?;
+
+            // Deleting each entry once it is appended, so that a failure midway does not leave
+            // replayed entries in the quarantine stream
+            self.delete_quarantined_entries(vec![id.clone()]).await?;
+            logger::info!(
+                entry_id = %id,
+                stream = %source_stream,
+                "Replayed quarantined entry"
+            );
+            replayed_entry_ids.push(id);
+        }
+
+        Ok(QuarantineCommandResponse {
+            entry_ids: replayed_entry_ids,
+            not_found_entry_ids,
+        })
+    }
+
+    async fn discard_quarantined_entries(
+        &self,
+        entry_ids: &[String],
+    ) -> errors::DrainerResult<QuarantineCommandResponse> {
+        let (entries, not_found_entry_ids) =
+            self.find_quarantined_entries(entry_ids, false).await?;
+        let discarded_entry_ids = entries
+            .into_iter()
+            .map(|entry| entry.id)
+            .collect::<Vec<_>>();
+
+        self.delete_quarantined_entries(discarded_entry_ids.clone())
+            .await?;
+        logger::info!(entry_ids = ?discarded_entry_ids, "Discarded quarantined entries");
+
+        Ok(QuarantineCommandResponse {
+            entry_ids: discarded_entry_ids,
+            not_found_entry_ids,
+        })
+    }
+}
+
+/// Runs the quarantine command against the quarantine stream of the tenant, the output of the
+/// command is printed as JSON
+pub async fn run_quarantine_command(
+    stores: &HashMap<id_type::TenantId, Arc<Store>>,
+    command: QuarantineCommand,
+) -> errors::DrainerResult<()> {
+    let output = match command {
+        QuarantineCommand::List {
+            tenant,
+            after,
+            count,
+        } => {
+            let entries = get_store(stores, tenant)?
+                .read_quarantined_entries(&after, count)
+                .await?;
+            serde_json::to_string_pretty(&entries)
+        }
+        QuarantineCommand::Replay {
+            tenant,
+            entry_ids,
+            all,
+        } => {
+            let response = get_store(stores, tenant)?
+                .replay_quarantined_entries(&entry_ids, all)
+                .await?;
+            serde_json::to_string_pretty(&response)
+        }
+        QuarantineCommand::Discard { tenant, entry_ids } => {
+            let response = get_store(stores, tenant)?
+                .discard_quarantined_entries(&entry_ids)
+                .await?;
+            serde_json::to_string_pretty(&response)
+        }
+    }
+    .change_context(errors::DrainerError::UnexpectedError(
+        "Failed to serialize quarantine command output".into(),
+    ))?;
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!("{output}");
+    }
+
+    Ok(())
+}
+
+fn get_store(
+    stores: &HashMap<id_type::TenantId, Arc<Store>>,
+    tenant: String,
+) -> errors::DrainerResult<&Arc<Store>> {
+    let tenant_id = id_type::TenantId::try_from_string(tenant.clone()).change_context(
+        errors::DrainerError::ConfigParsingError(format!("invalid tenant id `{tenant}`")),
+    )?;
+
+    stores.get(&tenant_id).ok_or_else(|| {
+        report!(errors::DrainerError::ConfigParsingError(format!(
+            "tenant `{tenant}` is not configured"
+        )))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use diesel_models::{kv, reverse_lookup::ReverseLookupNew};
+    use redis_interface::RedisSettings;
+
+    use super::*;
+    use crate::StreamData;
+
+    const MAX_ENTRY_ATTEMPTS: u32 = 3;
+
+    async fn redis_conn() -> RedisConnectionPool {
+        RedisConnectionPool::new(&RedisSettings::default())
+            .await
+            .expect("failed to create redis connection pool")
+    }
+
+    fn stream_entry() -> HashMap<String, String> {
+        let typed_sql = kv::TypedSql {
+            op: kv::DBOperation::Insert {
+                insertable: Box::new(kv::Insertable::ReverseLookUp(ReverseLookupNew {
+                    lookup_id: "lookup_id".to_string(),
+                    pk_id: "pk_id".to_string(),
+                    sk_id: "sk_id".to_string(),
+                    source: "payment_attempt".to_string(),
+                    updated_by: "redis_kv".to_string(),
+                })),
+            },
+        };
+
+        typed_sql
+            .to_field_value_pairs("request_id".to_string(), "global_id".to_string())
+            .unwrap()
+            .into_iter()
+            .map(|(field, value)| (field.to_owned(), value))
+            .collect()
+    }
+
+    /// Moves the entry through the quarantine stream and back to its stream, as replaying does
+    fn quarantine_and_replay(
+        entry: HashMap<String, String>,
+        entry_id: &str,
+    ) -> HashMap<String, String> {
+        let quarantined_entry = QuarantinedEntry {
+            source_stream: "drainer_stream".to_string(),
+            source_entry_id: entry_id.to_string(),
+            entry,
+            error: "query failed".to_string(),
+            attempts: i64::from(MAX_ENTRY_ATTEMPTS),
+            quarantined_at: common_utils::date_time::now_unix_timestamp(),
+        };
+        let quarantined_fields = quarantined_entry
+            .to_field_value_pairs()
+            .unwrap()
+            .into_iter()
+            .map(|(field, value)| (field.to_owned(), value))
+            .collect();
+
+        QuarantinedEntry::from_hashmap(quarantined_fields)
+            .unwrap()
+            .into_replayed_entry()
+            .into_iter()
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_entry_is_quarantined_after_max_entry_attempts() {
+        let redis_conn = redis_conn().await;
+        let stream_name = common_utils::generate_id_with_default_len("drainer_test_stream");
+
+        for expected_attempts in 1..=i64::from(MAX_ENTRY_ATTEMPTS) {
+            let attempts = record_entry_failure_in_stream(&redis_conn, &stream_name, "1-0")
+                .await
+                .unwrap();
+            assert_eq!(attempts, expected_attempts);
+            assert_eq!(
+                should_quarantine_entry(attempts, MAX_ENTRY_ATTEMPTS),
+                attempts == i64::from(MAX_ENTRY_ATTEMPTS)
+            );
+        }
+
+        // The attempts start over once a different entry of the stream fails
+        let attempts = record_entry_failure_in_stream(&redis_conn, &stream_name, "2-0")
+            .await
+            .unwrap();
+        assert_eq!(attempts, 1);
+        assert!(!should_quarantine_entry(attempts, MAX_ENTRY_ATTEMPTS));
+    }
+
+    #[test]
+    fn test_replayed_entry_is_applied_with_original_entry_id() {
+        let entry = stream_entry();
+        let data = StreamData::from_hashmap(entry.clone()).unwrap();
+        assert_eq!(data.get_applied_entry_id("1-0"), "1-0");
+
+        let replayed_entry = quarantine_and_replay(entry, "1-0");
+        let data = StreamData::from_hashmap(replayed_entry.clone()).unwrap();
+        assert_eq!(data.original_entry_id.as_deref(), Some("1-0"));
+        assert_eq!(data.get_applied_entry_id("5-0"), "1-0");
+
+        // Entries quarantined again after being replayed keep their original ID
+        let replayed_entry = quarantine_and_replay(replayed_entry, "5-0");
+        let data = StreamData::from_hashmap(replayed_entry).unwrap();
+        assert_eq!(data.get_applied_entry_id("9-0"), "1-0");
+    }
+}
@@ -1,34 +1,117 @@
 use std::sync::Arc;
 
+use async_bb8_diesel::AsyncConnection;
 use common_utils::errors::CustomResult;
-use diesel_models::errors::DatabaseError;
+use diesel_models::{drainer_applied_entry::DrainerAppliedEntry, errors::DatabaseError};
+use error_stack::{report, ResultExt};
 
 use crate::{kv, logger, metrics, pg_connection, services::Store};
 
+/// Outcome of applying a stream entry to the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryApplication {
+    Applied,
+    /// The entry was applied before it was read from the stream again
+    AlreadyApplied,
+}
+
+/// Errors which roll back the transaction applying an entry
+enum ApplyEntryError {
+    AlreadyApplied,
+    Query(error_stack::Report<DatabaseError>),
+}
+
+impl ApplyEntryError {
+    /// The ID of the entry is recorded before the entry is applied, so an entry which has been
+    /// applied already fails to be recorded due to the unique constraint on its ID
+    fn from_record_entry_error(error: error_stack::Report<DatabaseError>) -> Self {
+        match error.current_context() {
+            DatabaseError::UniqueViolation => Self::AlreadyApplied,
+            _ => Self::Query(error),
+        }
+    }
+}
+
+impl From<diesel::result::Error> for ApplyEntryError {
+    fn from(error: diesel::result::Error) -> Self {
+        if is_transient_diesel_error(&error) {
+            Self::Query(report!(error).change_context(DatabaseError::DatabaseConnectionError))
+        } else {
+            Self::Query(report!(DatabaseError::from(error)))
+        }
+    }
+}
+
+/// Whether the entry failed to be applied due to the database rather than the entry itself, such
+/// as the connection to the database being lost, in which case applying it again may succeed
+pub fn is_transient_error(error: &error_stack::Report<DatabaseError>) -> bool {
+    matches!(
+        error.current_context(),
+        DatabaseError::DatabaseConnectionError
+    ) || error
+        .downcast_ref::<diesel::result::Error>()
+        .is_some_and(is_transient_diesel_error)
+}
+
+fn is_transient_diesel_error(error: &diesel::result::Error) -> bool {
+    matches!(
+        error,
+        diesel::result::Error::DatabaseError(
+            diesel::result::DatabaseErrorKind::ClosedConnection
+                | diesel::result::DatabaseErrorKind::SerializationFailure,
+            _
+        ) | diesel::result::Error::BrokenTransactionManager
+    )
+}
+
 #[async_trait::async_trait]
 pub trait ExecuteQuery {
     async fn execute_query(
         self,
         store: &Arc<Store>,
+        stream_name: &str,
+        entry_id: &str,
         pushed_at: i64,
-    ) -> CustomResult<(), DatabaseError>;
+    ) -> CustomResult<EntryApplication, DatabaseError>;
 }
 
 #[async_trait::async_trait]
 impl ExecuteQuery for kv::DBOperation {
+    /// Applies the entry along with recording its ID in the same transaction, so that an entry
+    /// which is read from the stream again, such as when the drainer stops before trimming the
+    /// stream, is not applied twice. Entries replayed from the quarantine stream are given with the
+    /// ID they were first appended with, as they are appended to the stream with a new ID.
     async fn execute_query(
         self,
         store: &Arc<Store>,
+        stream_name: &str,
+        entry_id: &str,
         pushed_at: i64,
-    ) -> CustomResult<(), DatabaseError> {
+    ) -> CustomResult<EntryApplication, DatabaseError> {
         let conn = pg_connection(&store.master_pool).await;
         let operation = self.operation();
         let table = self.table();
 
         let tags = router_env::metric_attributes!(("operation", operation), ("table", table));
 
-        let (result, execution_time) =
-            Box::pin(common_utils::date_time::time_it(|| self.execute(&conn))).await;
+        let applied_entry = DrainerAppliedEntry {
+            stream_name: stream_name.to_owned(),
+            entry_id: entry_id.to_owned(),
+            table_name: table.to_owned(),
+            applied_at: common_utils::date_time::now(),
+        };
+
+        let (result, execution_time) = Box::pin(common_utils::date_time::time_it(|| {
+            conn.transaction_async(|conn| async move {
+                applied_entry
+                    .insert(&conn)
+                    .await
+                    .map_err(ApplyEntryError::from_record_entry_error)?;
+
+                self.execute(&conn).await.map_err(ApplyEntryError::Query)
+            })
+        }))
+        .await;
 
         push_drainer_delay(pushed_at, operation, table, tags);
         metrics::QUERY_EXECUTION_TIME.record(execution_time, tags);
@@ -37,9 +120,19 @@ impl ExecuteQuery for kv::DBOperation {
             Ok(result) => {
                 logger::info!(operation = operation, table = table, ?result);
                 metrics::SUCCESSFUL_QUERY_EXECUTION.add(1, tags);
-                Ok(())
+                Ok(EntryApplication::Applied)
             }
-            Err(err) => {
+            Err(ApplyEntryError::AlreadyApplied) => {
+                logger::warn!(
+                    operation = operation,
+                    table = table,
+                    entry_id,
+                    "Skipping entry which has been applied already"
+                );
+                metrics::DUPLICATE_ENTRIES_SKIPPED.add(1, tags);
+                Ok(EntryApplication::AlreadyApplied)
+            }
+            Err(ApplyEntryError::Query(err)) => {
                 logger::error!(operation = operation, table = table, ?err);
                 metrics::ERRORS_WHILE_QUERY_EXECUTION.add(1, tags);
                 Err(err)
@@ -48,6 +141,29 @@ impl ExecuteQuery for kv::DBOperation {
     }
 }
 
+/// Deletes the IDs of the entries applied before the retention period, entries are trimmed from
+/// the streams once they are applied, so their IDs are only needed for a short while
+pub async fn delete_expired_applied_entries(
+    store: &Arc<Store>,
+    retention_seconds: u32,
+) -> CustomResult<bool, DatabaseError> {
+    let conn = pg_connection(&store.master_pool).await;
+    let applied_before =
+        get_applied_entries_expiry(common_utils::date_time::now(), retention_seconds)?;
+
+    DrainerAppliedEntry::delete_applied_before(&conn, applied_before).await
+}
+
+/// Time before which the applied entries are past the retention period
+fn get_applied_entries_expiry(
+    now: time::PrimitiveDateTime,
+    retention_seconds: u32,
+) -> CustomResult<time::PrimitiveDateTime, DatabaseError> {
+    now.checked_sub(time::Duration::seconds(i64::from(retention_seconds)))
+        .ok_or(DatabaseError::Others)
+        .attach_printable("Retention period of applied entries is out of range")
+}
+
 #[inline(always)]
 fn push_drainer_delay(
     pushed_at: i64,
@@ -71,3 +187,62 @@ fn push_drainer_delay(
         ),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_entry_applied_already_is_skipped() {
+        assert!(matches!(
+            ApplyEntryError::from_record_entry_error(report!(DatabaseError::UniqueViolation)),
+            ApplyEntryError::AlreadyApplied
+        ));
+        assert!(matches!(
+            ApplyEntryError::from_record_entry_error(report!(DatabaseError::Others)),
+            ApplyEntryError::Query(_)
+        ));
+    }
+
+    #[test]
+    fn test_connection_errors_are_transient() {
+        let closed_connection = diesel::result::Error::DatabaseError(
+            diesel::result::DatabaseErrorKind::ClosedConnection,
+            Box::new(String::from("server closed the connection unexpectedly")),
+        );
+        assert!(matches!(
+            ApplyEntryError::from(closed_connection),
+            ApplyEntryError::Query(error) if is_transient_error(&error)
+        ));
+
+        // Errors of the queries generated by `diesel_models` keep the diesel error in their stack
+        let serialization_failure = report!(diesel::result::Error::DatabaseError(
+            diesel::result::DatabaseErrorKind::SerializationFailure,
+            Box::new(String::from("could not serialize access")),
+        ))
+        .change_context(DatabaseError::Others);
+        assert!(is_transient_error(&serialization_failure));
+
+        let not_null_violation = report!(diesel::result::Error::DatabaseError(
+            diesel::result::DatabaseErrorKind::NotNullViolation,
+            Box::new(String::from(
+                "null value in column violates not-null constraint"
+            )),
+        ))
+        .change_context(DatabaseError::Others);
+        assert!(!is_transient_error(&not_null_violation));
+        assert!(!is_transient_error(&report!(DatabaseError::Others)));
+    }
+
+    #[test]
+    fn test_applied_entries_expire_after_retention_period() {
+        let now = common_utils::date_time::now();
+
+        let applied_before = get_applied_entries_expiry(now, 3600).unwrap();
+        assert_eq!(applied_before, now - time::Duration::hours(1));
+        assert_eq!(get_applied_entries_expiry(now, 0).unwrap(), now);
+        assert!(get_applied_entries_expiry(time::PrimitiveDateTime::MIN, 1).is_err());
+    }
+}
@@ -26,6 +26,54 @@ pub struct CmdLineConf {
     /// Application will look for "config/config.toml" if this option isn't specified.
     #[arg(short = 'f', long, value_name = "FILE")]
     pub config_path: Option<PathBuf>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(clap::Subcommand)]
+pub enum Command {
+    /// Inspect, replay or discard the entries moved to the quarantine stream.
+    #[command(subcommand)]
+    Quarantine(QuarantineCommand),
+}
+
+#[derive(clap::Subcommand)]
+pub enum QuarantineCommand {
+    /// List the quarantined entries, in the order they were quarantined.
+    List {
+        /// Tenant whose quarantine stream is read.
+        #[arg(long)]
+        tenant: String,
+        /// List the entries quarantined after the entry with this ID.
+        #[arg(long, default_value = "0-0")]
+        after: String,
+        /// Maximum number of entries listed.
+        #[arg(long, default_value_t = 100)]
+        count: u64,
+    },
+    /// Append the quarantined entries back to the streams they were read from, so that they are
+    /// applied again. The entries are applied after the entries which are in the streams already,
+    /// which may be later changes to the same rows.
+    Replay {
+        /// Tenant whose quarantine stream is read.
+        #[arg(long)]
+        tenant: String,
+        /// IDs of the quarantined entries.
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        entry_ids: Vec<String>,
+        /// Replay all the quarantined entries.
+        #[arg(long)]
+        all: bool,
+    },
+    /// Delete the quarantined entries without applying them.
+    Discard {
+        /// Tenant whose quarantine stream is read.
+        #[arg(long)]
+        tenant: String,
+        /// IDs of the quarantined entries.
+        #[arg(required = true)]
+        entry_ids: Vec<String>,
+    },
 }
 
 #[derive(Clone)]
@@ -114,6 +162,10 @@ pub struct DrainerSettings {
     pub max_read_count: u64,
     pub shutdown_interval: u32, // in milliseconds
     pub loop_interval: u32,     // in milliseconds
+    /// Number of times an entry is attempted before it is moved to the quarantine stream
+    pub max_entry_attempts: u32,
+    pub applied_entry_retention: u32,        // in seconds
+    pub applied_entry_cleanup_interval: u32, // in seconds
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -224,6 +276,9 @@ impl Default for DrainerSettings {
             max_read_count: 100,
             shutdown_interval: 1000, // in milliseconds
             loop_interval: 100,      // in milliseconds
+            max_entry_attempts: 5,
+            applied_entry_retention: 86400,       // in seconds
+            applied_entry_cleanup_interval: 3600, // in seconds
         }
     }
 }
@@ -270,10 +325,24 @@ impl Database {
 
 impl DrainerSettings {
     fn validate(&self) -> Result<(), errors::DrainerError> {
-        common_utils::fp_utils::when(self.stream_name.is_default_or_empty(), || {
+        use common_utils::fp_utils::when;
+
+        when(self.stream_name.is_default_or_empty(), || {
             Err(errors::DrainerError::ConfigParsingError(
                 "drainer stream name must not be empty".into(),
             ))
+        })?;
+
+        when(self.max_entry_attempts == 0, || {
+            Err(errors::DrainerError::ConfigParsingError(
+                "drainer max entry attempts must be greater than zero".into(),
+            ))
+        })?;
+
+        when(self.applied_entry_cleanup_interval == 0, || {
+            Err(errors::DrainerError::ConfigParsingError(
+                "drainer applied entry cleanup interval must be greater than zero".into(),
+            ))
         })
     }
 }
@@ -6,9 +6,13 @@ use serde::{de::value::MapDeserializer, Deserialize, Serialize};
 
 use crate::{
     kv,
-    utils::{deserialize_db_op, deserialize_i64},
+    utils::{deserialize_db_op, deserialize_i64, deserialize_optional_string},
 };
 
+/// Field of the entries replayed from the quarantine stream which holds the ID the entry was first
+/// appended to its stream with
+pub(crate) const ORIGINAL_ENTRY_ID_FIELD: &str = "original_entry_id";
+
 #[derive(Deserialize, Serialize)]
 pub struct StreamData {
     pub request_id: String,
@@ -17,6 +21,12 @@ pub struct StreamData {
     pub typed_sql: kv::DBOperation,
     #[serde(deserialize_with = "deserialize_i64")]
     pub pushed_at: i64,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub original_entry_id: Option<String>,
 }
 
 impl StreamData {
@@ -32,4 +42,11 @@ impl StreamData {
         Self::deserialize(iter)
             .change_context(errors::ParsingError::StructParseFailure("StreamData"))
     }
+
+    /// ID the entry is recorded with once it is applied, entries replayed from the quarantine
+    /// stream are recorded with the ID they were first appended with, so that they are not applied
+    /// twice if they were applied before being quarantined
+    pub fn get_applied_entry_id<'a>(&'a self, entry_id: &'a str) -> &'a str {
+        self.original_entry_id.as_deref().unwrap_or(entry_id)
+    }
 }
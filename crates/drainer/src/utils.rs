@@ -54,6 +54,31 @@ where
     }
 }
 
+pub(crate) fn deserialize_json_string<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let s = serde_json::Value::deserialize(deserializer)?;
+    match s {
+        serde_json::Value::String(str_val) => {
+            serde_json::from_str(&str_val).map_err(serde::de::Error::custom)
+        }
+        other => Err(serde::de::Error::custom(format!(
+            "unexpected data format - expected string got: {other:?}"
+        ))),
+    }
+}
+
+pub(crate) fn deserialize_optional_string<'de, D>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    String::deserialize(deserializer).map(Some)
+}
+
 // Here the output is in the format (stream_index, jobs_picked),
 // similar to the first argument of the function
 #[inline(always)]
@@ -58,6 +58,7 @@ fn get_program_data() -> (ast::Program<DummyOutput>, inputs::BackendInput) {
             mandate_type: None,
             payment_type: None,
         },
+        time: None,
     };
 
     let (_, program) = parser::program(code1).expect("Parser");
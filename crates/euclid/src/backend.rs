@@ -1,3 +1,5 @@
+#[cfg(feature = "valued_jit")]
+pub mod fixtures;
 pub mod inputs;
 pub mod interpreter;
 #[cfg(feature = "valued_jit")]
@@ -8,7 +10,10 @@ pub use interpreter::InterpreterBackend;
 #[cfg(feature = "valued_jit")]
 pub use vir_interpreter::VirInterpreterBackend;
 
-use crate::frontend::ast;
+use crate::{
+    frontend::{ast, vir},
+    types::EuclidValue,
+};
 
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct BackendOutput<O> {
@@ -16,6 +21,72 @@ pub struct BackendOutput<O> {
     pub connector_selection: O,
 }
 
+/// The rule which fired for an input, along with the if statement within it that matched.
+///
+/// `statement_path` holds the index of the matching statement at every level of nesting,
+/// starting with the index among the top-level statements of the rule.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct MatchedStatement {
+    pub rule_name: String,
+    pub statement_path: Vec<usize>,
+}
+
+/// A record of how a program was evaluated against an input
+///
+/// Rules are listed in the order they were visited. Evaluation stops at the first rule which
+/// matches, so every rule but the last one in the trace did not match. If no rule matched,
+/// `default_selection_used` is set.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EvaluationTrace {
+    pub rules: Vec<RuleTrace>,
+    pub default_selection_used: bool,
+}
+
+impl EvaluationTrace {
+    pub fn summary(&self) -> EvaluationSummary {
+        EvaluationSummary {
+            matched_rule: self
+                .rules
+                .iter()
+                .find(|rule| rule.matched)
+                .map(|rule| rule.rule_name.clone()),
+            rules_evaluated: self.rules.len(),
+            default_selection_used: self.default_selection_used,
+        }
+    }
+}
+
+/// A compact form of the [`EvaluationTrace`], small enough to be recorded for every evaluation
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EvaluationSummary {
+    pub matched_rule: Option<String>,
+    pub rules_evaluated: usize,
+    pub default_selection_used: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RuleTrace {
+    pub rule_name: String,
+    pub matched: bool,
+    pub statements: Vec<StatementTrace>,
+}
+
+/// The evaluation of a single if statement. Nested statements are only traced when the
+/// condition of the enclosing statement held.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StatementTrace {
+    pub comparisons: Vec<ComparisonTrace>,
+    pub matched: bool,
+    pub nested: Option<Vec<StatementTrace>>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ComparisonTrace {
+    pub values: Vec<EuclidValue>,
+    pub logic: vir::ValuedComparisonLogic,
+    pub result: bool,
+}
+
 pub trait EuclidBackend<O>: Sized {
     type Error: serde::Serialize;
 
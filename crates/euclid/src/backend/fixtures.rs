@@ -0,0 +1,364 @@
+//! Declarative regression fixtures for routing programs
+//!
+//! A fixture pairs a [`inputs::BackendInput`] with the output it is expected to produce. The
+//! runner executes every fixture against both the [`InterpreterBackend`] and the
+//! [`VirInterpreterBackend`], checks that the two backends agree with each other as well as with
+//! the expectation, and reports the rule and the statement which fired. Fixture suites are
+//! written as JSON files, see [`RoutingFixtureSuite`].
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    backend::{
+        inputs, interpreter::types::InterpreterError, vir_interpreter::types::VirInterpreterError,
+        BackendOutput, EuclidBackend, InterpreterBackend, MatchedStatement, VirInterpreterBackend,
+    },
+    frontend::{ast, dir::EuclidDirFilter},
+};
+
+/// A single input and the output the program is expected to produce for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingFixture<O> {
+    pub name: String,
+    pub input: inputs::BackendInput,
+    pub expected: FixtureExpectation<O>,
+}
+
+/// A suite of fixtures attached to a routing program, in the format of a fixture file
+///
+/// ```text
+/// {
+///     "fixtures": [
+///         {
+///             "name": "card_payment",
+///             "input": { "payment": { .. }, "payment_method": { .. }, "mandate": { .. } },
+///             "expected": { "rule_name": "card_rule", "connector_selection": { .. } }
+///         }
+///     ]
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingFixtureSuite<O> {
+    pub fixtures: Vec<RoutingFixture<O>>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FixtureLoadError {
+    #[error("Unable to read the fixture file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid fixture suite: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+impl<O: DeserializeOwned> RoutingFixtureSuite<O> {
+    pub fn from_json(suite: &str) -> Result<Self, FixtureLoadError> {
+        Ok(serde_json::from_str(suite)?)
+    }
+
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self, FixtureLoadError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureExpectation<O> {
+    /// The rule which is expected to fire, `None` if the default selection is expected
+    #[serde(default)]
+    pub rule_name: Option<String>,
+    pub connector_selection: O,
+}
+
+/// The outcome of evaluating a fixture on one backend
+#[derive(Debug, Clone, Serialize)]
+pub struct FiredRule<O> {
+    pub output: BackendOutput<O>,
+    pub matched_statement: Option<MatchedStatement>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum FixtureFailure<O> {
+    InterpreterError(InterpreterError),
+    BackendMismatch {
+        interpreter: FiredRule<O>,
+        vir_interpreter: FiredRule<O>,
+    },
+    UnexpectedRule {
+        expected: Option<String>,
+        got: Option<String>,
+    },
+    UnexpectedOutput {
+        expected: O,
+        got: O,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FixtureResult<O> {
+    pub name: String,
+    /// The result of the VIR interpreter, which is the backend used while routing payments
+    pub fired: FiredRule<O>,
+    pub failures: Vec<FixtureFailure<O>>,
+}
+
+impl<O> FixtureResult<O> {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FixtureSuiteReport<O> {
+    pub passed: usize,
+    pub failed: usize,
+    pub results: Vec<FixtureResult<O>>,
+}
+
+#[derive(Debug, Clone, Serialize, thiserror::Error)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum FixtureRunnerError {
+    #[error("Unable to initialize the interpreter backend: {0}")]
+    Interpreter(InterpreterError),
+    #[error("Unable to initialize the VIR interpreter backend: {0}")]
+    VirInterpreter(VirInterpreterError),
+}
+
+pub struct FixtureRunner<O> {
+    interpreter: InterpreterBackend<O>,
+    vir_interpreter: VirInterpreterBackend<O>,
+}
+
+impl<O> FixtureRunner<O>
+where
+    O: Clone + PartialEq + EuclidDirFilter,
+{
+    pub fn new(program: ast::Program<O>) -> Result<Self, FixtureRunnerError> {
+        let vir_interpreter = VirInterpreterBackend::with_program(program.clone())
+            .map_err(FixtureRunnerError::VirInterpreter)?;
+        let interpreter =
+            InterpreterBackend::with_program(program).map_err(FixtureRunnerError::Interpreter)?;
+
+        Ok(Self {
+            interpreter,
+            vir_interpreter,
+        })
+    }
+
+    pub fn run_fixture(&self, fixture: &RoutingFixture<O>) -> FixtureResult<O> {
+        let mut failures = Vec::new();
+
+        let (output, matched_statement) = self
+            .vir_interpreter
+            .execute_with_matched_statement(fixture.input.clone());
+        let fired = FiredRule {
+            output,
+            matched_statement,
+        };
+
+        match self
+            .interpreter
+            .execute_with_matched_statement(fixture.input.clone())
+        {
+            Ok((output, matched_statement)) => {
+                if output.rule_name != fired.output.rule_name
+                    || output.connector_selection != fired.output.connector_selection
+                    || matched_statement != fired.matched_statement
+                {
+                    failures.push(FixtureFailure::BackendMismatch {
+                        interpreter: FiredRule {
+                            output,
+                            matched_statement,
+                        },
+                        vir_interpreter: fired.clone(),
+                    });
+                }
+            }
+            Err(error) => failures.push(FixtureFailure::InterpreterError(error)),
+        }
+
+        if fixture.expected.rule_name != fired.output.rule_name {
+            failures.push(FixtureFailure::UnexpectedRule {
+                expected: fixture.expected.rule_name.clone(),
+                got: fired.output.rule_name.clone(),
+            });
+        }
+
+        if fixture.expected.connector_selection != fired.output.connector_selection {
+            failures.push(FixtureFailure::UnexpectedOutput {
+                expected: fixture.expected.connector_selection.clone(),
+                got: fired.output.connector_selection.clone(),
+            });
+        }
+
+        FixtureResult {
+            name: fixture.name.clone(),
+            fired,
+            failures,
+        }
+    }
+
+    pub fn run_suite(&self, fixtures: &[RoutingFixture<O>]) -> FixtureSuiteReport<O> {
+        let results = fixtures
+            .iter()
+            .map(|fixture| self.run_fixture(fixture))
+            .collect::<Vec<_>>();
+        let passed = results.iter().filter(|result| result.passed()).count();
+
+        FixtureSuiteReport {
+            passed,
+            failed: results.len() - passed,
+            results,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ast_parser"))]
+mod tests {
+    #![allow(clippy::expect_used, clippy::indexing_slicing)]
+
+    use common_utils::types::MinorUnit;
+
+    use super::*;
+    use crate::{enums, types::DummyOutput};
+
+    fn input(payment_method: enums::PaymentMethod, amount: i64) -> inputs::BackendInput {
+        inputs::BackendInput {
+            metadata: None,
+            payment: inputs::PaymentInput {
+                amount: MinorUnit::new(amount),
+                card_bin: None,
+                currency: enums::Currency::USD,
+                authentication_type: None,
+                capture_method: None,
+                business_country: None,
+                billing_country: None,
+                business_label: None,
+                setup_future_usage: None,
+            },
+            payment_method: inputs::PaymentMethodInput {
+                payment_method: Some(payment_method),
+                payment_method_type: None,
+                card_network: None,
+            },
+            mandate: inputs::MandateData {
+                mandate_acceptance_type: None,
+                mandate_type: None,
+                payment_type: None,
+            },
+            time: None,
+        }
+    }
+
+    fn fixture(
+        name: &str,
+        input: inputs::BackendInput,
+        rule_name: Option<&str>,
+        output: &str,
+    ) -> RoutingFixture<DummyOutput> {
+        RoutingFixture {
+            name: name.to_string(),
+            input,
+            expected: FixtureExpectation {
+                rule_name: rule_name.map(ToString::to_string),
+                connector_selection: DummyOutput {
+                    outputs: vec![output.to_string()],
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn test_fixture_suite() {
+        let program_str = r#"
+        default: ["stripe"]
+
+        card_rule: ["adyen"]
+        {
+            payment_method = card {
+                amount > 1000
+                currency = (USD, EUR)
+            }
+        }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let runner = FixtureRunner::new(program).expect("Fixture runner");
+
+        let report = runner.run_suite(&[
+            fixture(
+                "card_payment",
+                input(enums::PaymentMethod::Card, 500),
+                Some("card_rule"),
+                "adyen",
+            ),
+            fixture(
+                "wallet_payment",
+                input(enums::PaymentMethod::Wallet, 500),
+                None,
+                "stripe",
+            ),
+            fixture(
+                "wrong_expectation",
+                input(enums::PaymentMethod::Wallet, 500),
+                Some("card_rule"),
+                "adyen",
+            ),
+        ]);
+
+        assert_eq!(report.passed, 2);
+        assert_eq!(report.failed, 1);
+
+        assert_eq!(
+            report.results[0].fired.matched_statement,
+            Some(MatchedStatement {
+                rule_name: "card_rule".to_string(),
+                statement_path: vec![0, 1],
+            })
+        );
+        assert!(report.results[1].fired.matched_statement.is_none());
+        assert!(matches!(
+            report.results[2].failures.as_slice(),
+            [
+                FixtureFailure::UnexpectedRule { .. },
+                FixtureFailure::UnexpectedOutput { .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_fixture_suite_from_json() {
+        let suite = r#"
+        {
+            "fixtures": [
+                {
+                    "name": "card_payment",
+                    "input": {
+                        "metadata": null,
+                        "payment": { "amount": 500, "currency": "USD" },
+                        "payment_method": { "payment_method": "card" },
+                        "mandate": {}
+                    },
+                    "expected": {
+                        "rule_name": "card_rule",
+                        "connector_selection": { "outputs": ["adyen"] }
+                    }
+                }
+            ]
+        }
+        "#;
+
+        let suite =
+            RoutingFixtureSuite::<DummyOutput>::from_json(suite).expect("Fixture suite parsing");
+
+        assert_eq!(suite.fixtures.len(), 1);
+        assert_eq!(suite.fixtures[0].input.payment.amount, MinorUnit::new(500));
+        assert_eq!(
+            suite.fixtures[0].expected.rule_name.as_deref(),
+            Some("card_rule")
+        );
+        assert!(matches!(
+            RoutingFixtureSuite::<DummyOutput>::from_json(r#"{ "fixtures": [{}] }"#),
+            Err(FixtureLoadError::Parse(_))
+        ));
+    }
+}
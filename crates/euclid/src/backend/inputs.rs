@@ -30,10 +30,36 @@ pub struct PaymentInput {
     pub setup_future_usage: Option<enums::SetupFutureUsage>,
 }
 
+/// The time at which a payment is routed, in UTC
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeInput {
+    /// Time of the day in the 24-hour HHMM format, for example 1830 for 18:30
+    pub time_of_day: u16,
+    pub day_of_week: enums::DayOfWeek,
+    /// Date in the YYYYMMDD format, for example 20250131 for 31st January 2025
+    pub date: u32,
+}
+
+impl TimeInput {
+    pub fn from_timestamp(timestamp: time::PrimitiveDateTime) -> Self {
+        let year = u32::try_from(timestamp.year()).unwrap_or_default();
+
+        Self {
+            time_of_day: u16::from(timestamp.hour()) * 100 + u16::from(timestamp.minute()),
+            day_of_week: timestamp.weekday().into(),
+            date: year * 10000
+                + u32::from(u8::from(timestamp.month())) * 100
+                + u32::from(timestamp.day()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackendInput {
     pub metadata: Option<FxHashMap<String, String>>,
     pub payment: PaymentInput,
     pub payment_method: PaymentMethodInput,
     pub mandate: MandateData,
+    #[serde(default)]
+    pub time: Option<TimeInput>,
 }
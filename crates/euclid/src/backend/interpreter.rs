@@ -95,73 +95,88 @@ where
         Ok(true)
     }
 
+    /// Returns the path to the innermost statement that matched, if any
     fn eval_if_statement(
         stmt: &ast::IfStatement,
         ctx: &types::Context,
-    ) -> Result<bool, types::InterpreterError> {
+    ) -> Result<Option<Vec<usize>>, types::InterpreterError> {
         let cond_res = Self::eval_if_condition(&stmt.condition, ctx)?;
 
         if !cond_res {
-            return Ok(false);
+            return Ok(None);
         }
 
         if let Some(ref nested) = stmt.nested {
-            for nested_if in nested {
-                let res = Self::eval_if_statement(nested_if, ctx)?;
-
-                if res {
-                    return Ok(true);
-                }
-            }
-
-            return Ok(false);
+            return Self::eval_rule_statements(nested, ctx);
         }
 
-        Ok(true)
+        Ok(Some(Vec::new()))
     }
 
     fn eval_rule_statements(
         statements: &[ast::IfStatement],
         ctx: &types::Context,
-    ) -> Result<bool, types::InterpreterError> {
-        for stmt in statements {
-            let res = Self::eval_if_statement(stmt, ctx)?;
-
-            if res {
-                return Ok(true);
+    ) -> Result<Option<Vec<usize>>, types::InterpreterError> {
+        for (idx, stmt) in statements.iter().enumerate() {
+            if let Some(mut path) = Self::eval_if_statement(stmt, ctx)? {
+                path.insert(0, idx);
+                return Ok(Some(path));
             }
         }
 
-        Ok(false)
+        Ok(None)
     }
 
     #[inline]
     fn eval_rule(
         rule: &ast::Rule<O>,
         ctx: &types::Context,
-    ) -> Result<bool, types::InterpreterError> {
+    ) -> Result<Option<Vec<usize>>, types::InterpreterError> {
         Self::eval_rule_statements(&rule.statements, ctx)
     }
 
     fn eval_program(
         program: &ast::Program<O>,
         ctx: &types::Context,
-    ) -> Result<backend::BackendOutput<O>, types::InterpreterError> {
+    ) -> Result<
+        (backend::BackendOutput<O>, Option<backend::MatchedStatement>),
+        types::InterpreterError,
+    > {
         for rule in &program.rules {
-            let res = Self::eval_rule(rule, ctx)?;
-
-            if res {
-                return Ok(backend::BackendOutput {
-                    connector_selection: rule.connector_selection.clone(),
-                    rule_name: Some(rule.name.clone()),
-                });
+            if let Some(statement_path) = Self::eval_rule(rule, ctx)? {
+                return Ok((
+                    backend::BackendOutput {
+                        connector_selection: rule.connector_selection.clone(),
+                        rule_name: Some(rule.name.clone()),
+                    },
+                    Some(backend::MatchedStatement {
+                        rule_name: rule.name.clone(),
+                        statement_path,
+                    }),
+                ));
             }
         }
 
-        Ok(backend::BackendOutput {
-            connector_selection: program.default_selection.clone(),
-            rule_name: None,
-        })
+        Ok((
+            backend::BackendOutput {
+                connector_selection: program.default_selection.clone(),
+                rule_name: None,
+            },
+            None,
+        ))
+    }
+
+    /// Executes the program, additionally returning the statement which matched the input. No
+    /// statement is returned if the default selection was chosen.
+    pub fn execute_with_matched_statement(
+        &self,
+        input: inputs::BackendInput,
+    ) -> Result<
+        (backend::BackendOutput<O>, Option<backend::MatchedStatement>),
+        types::InterpreterError,
+    > {
+        let ctx: types::Context = input.into();
+        Self::eval_program(&self.program, &ctx)
     }
 }
 
@@ -176,7 +191,7 @@ where
     }
 
     fn execute(&self, input: inputs::BackendInput) -> Result<super::BackendOutput<O>, Self::Error> {
-        let ctx: types::Context = input.into();
-        Self::eval_program(&self.program, &ctx)
+        self.execute_with_matched_statement(input)
+            .map(|(output, _)| output)
     }
 }
@@ -39,43 +39,176 @@ where
         cond.iter().all(|comp| Self::eval_comparison(comp, ctx))
     }
 
-    fn eval_statement(stmt: &vir::ValuedIfStatement, ctx: &types::Context) -> bool {
+    /// Returns the path to the innermost statement that matched, if any
+    fn eval_statement(stmt: &vir::ValuedIfStatement, ctx: &types::Context) -> Option<Vec<usize>> {
         if Self::eval_condition(&stmt.condition, ctx) {
-            {
-                stmt.nested.as_ref().map_or(true, |nested_stmts| {
-                    nested_stmts.iter().any(|s| Self::eval_statement(s, ctx))
+            stmt.nested
+                .as_ref()
+                .map_or(Some(Vec::new()), |nested_stmts| {
+                    Self::eval_statements(nested_stmts, ctx)
                 })
-            }
         } else {
-            false
+            None
         }
     }
 
-    fn eval_rule(rule: &vir::ValuedRule<O>, ctx: &types::Context) -> bool {
-        rule.statements
-            .iter()
-            .any(|stmt| Self::eval_statement(stmt, ctx))
+    fn eval_statements(
+        stmts: &[vir::ValuedIfStatement],
+        ctx: &types::Context,
+    ) -> Option<Vec<usize>> {
+        stmts.iter().enumerate().find_map(|(idx, stmt)| {
+            Self::eval_statement(stmt, ctx).map(|mut path| {
+                path.insert(0, idx);
+                path
+            })
+        })
+    }
+
+    fn eval_rule(rule: &vir::ValuedRule<O>, ctx: &types::Context) -> Option<Vec<usize>> {
+        Self::eval_statements(&rule.statements, ctx)
     }
 
     fn eval_program(
         program: &vir::ValuedProgram<O>,
         ctx: &types::Context,
-    ) -> backend::BackendOutput<O> {
+    ) -> (backend::BackendOutput<O>, Option<backend::MatchedStatement>) {
         program
             .rules
             .iter()
-            .find(|rule| Self::eval_rule(rule, ctx))
+            .find_map(|rule| Self::eval_rule(rule, ctx).map(|path| (rule, path)))
             .map_or_else(
-                || backend::BackendOutput {
-                    connector_selection: program.default_selection.clone(),
-                    rule_name: None,
+                || {
+                    (
+                        backend::BackendOutput {
+                            connector_selection: program.default_selection.clone(),
+                            rule_name: None,
+                        },
+                        None,
+                    )
                 },
-                |rule| backend::BackendOutput {
-                    connector_selection: rule.connector_selection.clone(),
-                    rule_name: Some(rule.name.clone()),
+                |(rule, statement_path)| {
+                    (
+                        backend::BackendOutput {
+                            connector_selection: rule.connector_selection.clone(),
+                            rule_name: Some(rule.name.clone()),
+                        },
+                        Some(backend::MatchedStatement {
+                            rule_name: rule.name.clone(),
+                            statement_path,
+                        }),
+                    )
                 },
             )
     }
+
+    /// Executes the program, additionally returning the statement which matched the input. No
+    /// statement is returned if the default selection was chosen.
+    pub fn execute_with_matched_statement(
+        &self,
+        input: inputs::BackendInput,
+    ) -> (backend::BackendOutput<O>, Option<backend::MatchedStatement>) {
+        let ctx = types::Context::from_input(input);
+        Self::eval_program(&self.program, &ctx)
+    }
+
+    fn trace_statement(
+        stmt: &vir::ValuedIfStatement,
+        ctx: &types::Context,
+    ) -> backend::StatementTrace {
+        let comparisons = stmt
+            .condition
+            .iter()
+            .map(|comp| backend::ComparisonTrace {
+                values: comp.values.clone(),
+                logic: comp.logic.clone(),
+                result: Self::eval_comparison(comp, ctx),
+            })
+            .collect::<Vec<_>>();
+
+        let condition_held = comparisons.iter().all(|comp| comp.result);
+
+        let nested = stmt
+            .nested
+            .as_ref()
+            .filter(|_| condition_held)
+            .map(|nested_stmts| Self::trace_statements(nested_stmts, ctx));
+
+        let matched = condition_held
+            && nested
+                .as_ref()
+                .map_or(true, |nested| nested.iter().any(|stmt| stmt.matched));
+
+        backend::StatementTrace {
+            comparisons,
+            matched,
+            nested,
+        }
+    }
+
+    /// Traces statements up to and including the first one which matches
+    fn trace_statements(
+        stmts: &[vir::ValuedIfStatement],
+        ctx: &types::Context,
+    ) -> Vec<backend::StatementTrace> {
+        let mut traces = Vec::with_capacity(stmts.len());
+
+        for stmt in stmts {
+            let trace = Self::trace_statement(stmt, ctx);
+            let matched = trace.matched;
+            traces.push(trace);
+
+            if matched {
+                break;
+            }
+        }
+
+        traces
+    }
+
+    /// Executes the program, additionally returning a trace of every rule visited and the truth
+    /// value of each comparison evaluated along the way.
+    pub fn execute_with_trace(
+        &self,
+        input: inputs::BackendInput,
+    ) -> (backend::BackendOutput<O>, backend::EvaluationTrace) {
+        let ctx = types::Context::from_input(input);
+        let mut rules = Vec::new();
+
+        for rule in &self.program.rules {
+            let statements = Self::trace_statements(&rule.statements, &ctx);
+            let matched = statements.iter().any(|stmt| stmt.matched);
+
+            rules.push(backend::RuleTrace {
+                rule_name: rule.name.clone(),
+                matched,
+                statements,
+            });
+
+            if matched {
+                return (
+                    backend::BackendOutput {
+                        connector_selection: rule.connector_selection.clone(),
+                        rule_name: Some(rule.name.clone()),
+                    },
+                    backend::EvaluationTrace {
+                        rules,
+                        default_selection_used: false,
+                    },
+                );
+            }
+        }
+
+        (
+            backend::BackendOutput {
+                connector_selection: self.program.default_selection.clone(),
+                rule_name: None,
+            },
+            backend::EvaluationTrace {
+                rules,
+                default_selection_used: true,
+            },
+        )
+    }
 }
 
 impl<O> EuclidBackend<O> for VirInterpreterBackend<O>
@@ -100,8 +233,7 @@ where
         &self,
         input: inputs::BackendInput,
     ) -> Result<backend::BackendOutput<O>, Self::Error> {
-        let ctx = types::Context::from_input(input);
-        Ok(Self::eval_program(&self.program, &ctx))
+        Ok(self.execute_with_matched_statement(input).0)
     }
 }
 #[cfg(all(test, feature = "ast_parser"))]
@@ -153,6 +285,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -193,6 +326,7 @@ mod test {
                 mandate_type: None,
                 payment_type: Some(enums::PaymentType::SetupMandate),
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -234,6 +368,7 @@ mod test {
                 mandate_type: None,
                 payment_type: Some(enums::PaymentType::PptMandate),
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -275,6 +410,7 @@ mod test {
                 mandate_type: Some(enums::MandateType::SingleUse),
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -316,6 +452,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -357,6 +494,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -398,6 +536,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -439,6 +578,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -480,6 +620,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -523,6 +664,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -564,6 +706,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
         let mut inp_equal = inp_greater.clone();
         inp_equal.payment.amount = MinorUnit::new(123);
@@ -614,6 +757,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            time: None,
         };
         let mut inp_equal = inp_lower.clone();
         inp_equal.payment.amount = MinorUnit::new(123);
@@ -629,4 +773,162 @@ mod test {
             "rule_1"
         );
     }
+
+    #[test]
+    fn test_execution_trace() {
+        let program_str = r#"
+        default: ["stripe", "adyen"]
+
+        rule_1: ["stripe"]
+        {
+            pay_later = klarna
+        }
+
+        rule_2: ["adyen"]
+        {
+            payment_method = pay_later & amount > 100
+            currency = USD {
+                pay_later = affirm
+            }
+        }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let inp = inputs::BackendInput {
+            metadata: None,
+            payment: inputs::PaymentInput {
+                amount: MinorUnit::new(32),
+                card_bin: None,
+                currency: enums::Currency::USD,
+                authentication_type: None,
+                capture_method: None,
+                business_country: None,
+                billing_country: None,
+                business_label: None,
+                setup_future_usage: None,
+            },
+            payment_method: inputs::PaymentMethodInput {
+                payment_method: Some(enums::PaymentMethod::PayLater),
+                payment_method_type: Some(enums::PaymentMethodType::Affirm),
+                card_network: None,
+            },
+            mandate: inputs::MandateData {
+                mandate_acceptance_type: None,
+                mandate_type: None,
+                payment_type: None,
+            },
+            time: None,
+        };
+
+        let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
+        let (result, trace) = backend.execute_with_trace(inp.clone());
+        assert_eq!(result.rule_name.as_deref(), Some("rule_2"));
+        assert!(!trace.default_selection_used);
+
+        let [rule_1, rule_2] = trace.rules.as_slice() else {
+            panic!("Expected both rules to be visited");
+        };
+        assert!(!rule_1.matched);
+        assert!(rule_2.matched);
+
+        let [amount_stmt, currency_stmt] = rule_2.statements.as_slice() else {
+            panic!("Expected both statements of rule_2 to be visited");
+        };
+        assert!(!amount_stmt.matched);
+        assert_eq!(
+            amount_stmt
+                .comparisons
+                .iter()
+                .map(|comp| comp.result)
+                .collect::<Vec<_>>(),
+            vec![true, false]
+        );
+        assert!(currency_stmt.matched);
+        assert!(currency_stmt.nested.is_some());
+
+        let mut default_inp = inp;
+        default_inp.payment.currency = enums::Currency::EUR;
+        let (result, trace) = backend.execute_with_trace(default_inp);
+        assert!(result.rule_name.is_none());
+        assert!(trace.default_selection_used);
+        assert_eq!(trace.rules.len(), 2);
+        assert!(trace.rules.iter().all(|rule| !rule.matched));
+    }
+
+    #[test]
+    fn test_time_conditions() {
+        let program_str = r#"
+        default: ["stripe", "adyen"]
+
+        rule_1: ["stripe"]
+        {
+            time_of_day >= 0 & time_of_day < 600
+        }
+
+        rule_2: ["adyen"]
+        {
+            day_of_week = (saturday, sunday)
+        }
+
+        rule_3: ["paypal"]
+        {
+            date >= 20251224 & date <= 20251226
+        }
+        "#;
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
+
+        let at = |month: time::Month, day: u8, hour: u8, minute: u8| {
+            time::Date::from_calendar_date(2025, month, day)
+                .and_then(|date| date.with_hms(hour, minute, 0))
+                .expect("Timestamp")
+        };
+        let input_at = |time: Option<time::PrimitiveDateTime>| inputs::BackendInput {
+            metadata: None,
+            payment: inputs::PaymentInput {
+                amount: MinorUnit::new(32),
+                card_bin: None,
+                currency: enums::Currency::USD,
+                authentication_type: None,
+                capture_method: None,
+                business_country: None,
+                billing_country: None,
+                business_label: None,
+                setup_future_usage: None,
+            },
+            payment_method: inputs::PaymentMethodInput {
+                payment_method: Some(enums::PaymentMethod::Card),
+                payment_method_type: None,
+                card_network: None,
+            },
+            mandate: inputs::MandateData {
+                mandate_acceptance_type: None,
+                mandate_type: None,
+                payment_type: None,
+            },
+            time: time.map(inputs::TimeInput::from_timestamp),
+        };
+
+        let rule_name = |time: Option<time::PrimitiveDateTime>| {
+            backend
+                .execute(input_at(time))
+                .expect("Execution")
+                .rule_name
+        };
+
+        assert_eq!(
+            rule_name(Some(at(time::Month::January, 6, 3, 15))).as_deref(),
+            Some("rule_1")
+        );
+        assert_eq!(
+            rule_name(Some(at(time::Month::January, 4, 10, 30))).as_deref(),
+            Some("rule_2")
+        );
+        assert_eq!(
+            rule_name(Some(at(time::Month::December, 24, 12, 0))).as_deref(),
+            Some("rule_3")
+        );
+        assert_eq!(rule_name(Some(at(time::Month::January, 6, 6, 0))), None);
+        assert_eq!(rule_name(None), None);
+    }
 }
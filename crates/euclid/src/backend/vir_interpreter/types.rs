@@ -1,3 +1,4 @@
+use common_utils::types::MinorUnit;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
@@ -55,6 +56,7 @@ impl Context {
         let payment_method = input.payment_method;
         let meta_data = input.metadata;
         let payment_mandate = input.mandate;
+        let time = input.time;
 
         let mut enum_values: FxHashSet<EuclidValue> =
             FxHashSet::from_iter([EuclidValue::PaymentCurrency(payment.currency)]);
@@ -113,7 +115,7 @@ impl Context {
             enum_values.insert(EuclidValue::MandateAcceptanceType(mandate_acceptance_type));
         }
 
-        let numeric_values: FxHashMap<EuclidKey, EuclidValue> = FxHashMap::from_iter([(
+        let mut numeric_values: FxHashMap<EuclidKey, EuclidValue> = FxHashMap::from_iter([(
             EuclidKey::PaymentAmount,
             EuclidValue::PaymentAmount(types::NumValue {
                 number: payment.amount,
@@ -121,6 +123,24 @@ impl Context {
             }),
         )]);
 
+        if let Some(time) = time {
+            enum_values.insert(EuclidValue::DayOfWeek(time.day_of_week));
+            numeric_values.insert(
+                EuclidKey::TimeOfDay,
+                EuclidValue::TimeOfDay(types::NumValue {
+                    number: MinorUnit::new(i64::from(time.time_of_day)),
+                    refinement: None,
+                }),
+            );
+            numeric_values.insert(
+                EuclidKey::Date,
+                EuclidValue::Date(types::NumValue {
+                    number: MinorUnit::new(i64::from(time.date)),
+                    refinement: None,
+                }),
+            );
+        }
+
         Self {
             atomic_values: enum_values,
             numeric_values,
@@ -1,6 +1,7 @@
 //! Domain Specific Static Analyzer
 pub mod analyzer;
 pub mod graph;
+pub mod reachability;
 pub mod state_machine;
 pub mod truth;
 pub mod types;
@@ -10,7 +10,7 @@ use rustc_hash::{FxHashMap, FxHashSet};
 use crate::{
     dssa::{
         graph::CgraphExt,
-        state_machine, truth,
+        reachability, state_machine, truth,
         types::{self, EuclidAnalysable},
     },
     frontend::{
@@ -220,14 +220,12 @@ fn perform_context_analyses(
     Ok(())
 }
 
-pub fn analyze<O: EuclidAnalysable + EuclidDirFilter>(
-    program: ast::Program<O>,
+fn perform_program_analyses<O: EuclidAnalysable>(
+    dir_program: &dir::DirProgram<O>,
     knowledge_graph: Option<&ConstraintGraph<dir::DirValue>>,
-) -> Result<vir::ValuedProgram<O>, types::AnalysisError> {
-    let dir_program = ast::lowering::lower_program(program)?;
-
-    let selection_data = state_machine::make_connector_selection_data(&dir_program);
-    let mut ctx_manager = state_machine::AnalysisContextManager::new(&dir_program, &selection_data);
+) -> Result<(), types::AnalysisError> {
+    let selection_data = state_machine::make_connector_selection_data(dir_program);
+    let mut ctx_manager = state_machine::AnalysisContextManager::new(dir_program, &selection_data);
     while let Some(ctx) = ctx_manager.advance().map_err(|err| types::AnalysisError {
         metadata: Default::default(),
         error_type: types::AnalysisErrorType::StateMachine(err),
@@ -235,9 +233,32 @@ pub fn analyze<O: EuclidAnalysable + EuclidDirFilter>(
         perform_context_analyses(ctx, knowledge_graph.unwrap_or(&truth::ANALYSIS_GRAPH))?;
     }
 
+    Ok(())
+}
+
+pub fn analyze<O: EuclidAnalysable + EuclidDirFilter>(
+    program: ast::Program<O>,
+    knowledge_graph: Option<&ConstraintGraph<dir::DirValue>>,
+) -> Result<vir::ValuedProgram<O>, types::AnalysisError> {
+    let dir_program = ast::lowering::lower_program(program)?;
+    perform_program_analyses(&dir_program, knowledge_graph)?;
     dir::lowering::lower_program(dir_program)
 }
 
+/// Performs the same analyses as [`analyze`], additionally returning warnings about rules which
+/// are unreachable or overlap with earlier rules, and number comparisons which can never be
+/// satisfied. See [`reachability::analyze_rule_reachability`].
+pub fn analyze_with_warnings<O: EuclidAnalysable + EuclidDirFilter + PartialEq>(
+    program: ast::Program<O>,
+    knowledge_graph: Option<&ConstraintGraph<dir::DirValue>>,
+) -> Result<(vir::ValuedProgram<O>, Vec<types::AnalysisWarning>), types::AnalysisError> {
+    let dir_program = ast::lowering::lower_program(program)?;
+    perform_program_analyses(&dir_program, knowledge_graph)?;
+    let warnings = reachability::analyze_rule_reachability(&dir_program);
+
+    Ok((dir::lowering::lower_program(dir_program)?, warnings))
+}
+
 #[cfg(all(test, feature = "ast_parser"))]
 mod tests {
     #![allow(clippy::panic, clippy::expect_used)]
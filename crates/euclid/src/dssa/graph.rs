@@ -5,7 +5,7 @@ use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
     dssa::types,
-    frontend::dir,
+    frontend::{ast, dir},
     types::{DataType, Metadata},
 };
 
@@ -37,6 +37,92 @@ impl cgraph::ValueNode for dir::DirValue {
     }
 }
 
+/// Snapshot form of a [`dir::DirValue`]
+///
+/// Connector values are rejected when deserializing a [`dir::DirValue`], so snapshots store them
+/// through a separate variant which accepts them.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum DirValueSnapshot {
+    Connector(ConnectorValueSnapshot),
+    Value(dir::DirValue),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "key", content = "value")]
+pub enum ConnectorValueSnapshot {
+    #[serde(rename = "connector")]
+    Connector(Box<ast::ConnectorChoice>),
+}
+
+/// Snapshot form of a [`dir::DirKey`], see [`DirValueSnapshot`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DirKeySnapshot {
+    pub kind: DirKeyKindSnapshot,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum DirKeyKindSnapshot {
+    Connector(ConnectorKeyKindSnapshot),
+    Kind(dir::DirKeyKind),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ConnectorKeyKindSnapshot {
+    #[serde(rename = "connector")]
+    Connector,
+}
+
+impl cgraph::SnapshotValueNode for dir::DirValue {
+    type ValueSnapshot = DirValueSnapshot;
+    type KeySnapshot = DirKeySnapshot;
+
+    fn to_value_snapshot(&self) -> Self::ValueSnapshot {
+        match self {
+            Self::Connector(connector) => {
+                DirValueSnapshot::Connector(ConnectorValueSnapshot::Connector(connector.clone()))
+            }
+            value => DirValueSnapshot::Value(value.clone()),
+        }
+    }
+
+    fn from_value_snapshot(snapshot: Self::ValueSnapshot) -> Self {
+        match snapshot {
+            DirValueSnapshot::Connector(ConnectorValueSnapshot::Connector(connector)) => {
+                Self::Connector(connector)
+            }
+            DirValueSnapshot::Value(value) => value,
+        }
+    }
+
+    fn to_key_snapshot(key: &Self::Key) -> Self::KeySnapshot {
+        let kind = match &key.kind {
+            dir::DirKeyKind::Connector => {
+                DirKeyKindSnapshot::Connector(ConnectorKeyKindSnapshot::Connector)
+            }
+            kind => DirKeyKindSnapshot::Kind(kind.clone()),
+        };
+
+        DirKeySnapshot {
+            kind,
+            value: key.value.clone(),
+        }
+    }
+
+    fn from_key_snapshot(snapshot: Self::KeySnapshot) -> Self::Key {
+        let kind = match snapshot.kind {
+            DirKeyKindSnapshot::Connector(ConnectorKeyKindSnapshot::Connector) => {
+                dir::DirKeyKind::Connector
+            }
+            DirKeyKindSnapshot::Kind(kind) => kind,
+        };
+
+        dir::DirKey::new(kind, snapshot.value)
+    }
+}
+
 impl cgraph::NodeViz for dir::DirValue {
     fn viz(&self) -> String {
         match self {
@@ -71,6 +157,9 @@ impl cgraph::NodeViz for dir::DirValue {
             Self::RealTimePaymentType(rtpt) => rtpt.to_string(),
             Self::OpenBankingType(ob) => ob.to_string(),
             Self::MobilePaymentType(mpt) => mpt.to_string(),
+            Self::TimeOfDay(tod) => tod.number.to_string(),
+            Self::DayOfWeek(dow) => dow.to_string(),
+            Self::Date(date) => date.number.to_string(),
         }
     }
 }
@@ -1116,4 +1205,35 @@ mod test {
 
         assert!(_result.is_ok());
     }
+
+    #[test]
+    fn test_connector_values_round_trip_through_snapshots() {
+        use cgraph::SnapshotValueNode;
+
+        let connector = dirval!(Connector = Stripe);
+        let serialized = serde_json::to_value(&connector).expect("Failed serialization");
+        assert!(serde_json::from_value::<dir::DirValue>(serialized.clone()).is_err());
+
+        let snapshot = serde_json::from_value::<DirValueSnapshot>(serialized.clone())
+            .expect("Failed snapshot deserialization");
+        assert_eq!(
+            serde_json::to_value(connector.to_value_snapshot()).expect("Failed serialization"),
+            serialized
+        );
+        assert_eq!(dir::DirValue::from_value_snapshot(snapshot), connector);
+
+        let card = dirval!(PaymentMethod = Card);
+        let snapshot = serde_json::from_value::<DirValueSnapshot>(
+            serde_json::to_value(&card).expect("Failed serialization"),
+        )
+        .expect("Failed snapshot deserialization");
+        assert_eq!(dir::DirValue::from_value_snapshot(snapshot), card);
+
+        let key = connector.get_key();
+        let snapshot = serde_json::from_value::<DirKeySnapshot>(
+            serde_json::to_value(&key).expect("Failed serialization"),
+        )
+        .expect("Failed snapshot deserialization");
+        assert_eq!(dir::DirValue::from_key_snapshot(snapshot), key);
+    }
 }
@@ -0,0 +1,525 @@
+//! Rule reachability analysis
+//!
+//! Programs are evaluated top to bottom and the first rule which matches is the one that fires.
+//! The analyses here look for rules which can never fire because earlier rules already match
+//! every input they would, rules which overlap with an earlier rule selecting a different output,
+//! and number comparisons which no value can satisfy.
+//!
+//! Every path through the if statements of a rule is reduced to a set of constraints on
+//! individual keys. Keys are constrained independently of each other, so relationships between
+//! keys (such as a payment method type implying its payment method) are not taken into account.
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    dssa::types::AnalysisWarning,
+    frontend::dir,
+    types::{DataType, Metadata, NumValueRefinement},
+};
+
+/// An inclusive range of numbers
+type Interval = (i64, i64);
+
+#[derive(Debug, Clone)]
+enum KeyConstraint {
+    /// Values the key may take. An `allowed` set of `None` accepts every value that has not been
+    /// excluded, which is only the case for keys without a finite set of values.
+    Values {
+        allowed: Option<FxHashSet<dir::DirValue>>,
+        excluded: FxHashSet<dir::DirValue>,
+    },
+    /// Sorted and disjoint intervals of numbers the key may take
+    Range(Vec<Interval>),
+}
+
+impl KeyConstraint {
+    fn from_comparison(key: &dir::DirKey, comparison: &dir::DirComparison) -> Self {
+        match key.kind.get_type() {
+            DataType::Number => {
+                let presence = normalize_intervals(
+                    comparison
+                        .values
+                        .iter()
+                        .flat_map(presence_intervals)
+                        .collect(),
+                );
+
+                Self::Range(match comparison.logic {
+                    dir::DirComparisonLogic::PositiveDisjunction => presence,
+                    dir::DirComparisonLogic::NegativeConjunction => complement_intervals(&presence),
+                })
+            }
+
+            DataType::EnumVariant | DataType::StrValue | DataType::MetadataValue => {
+                let values = comparison.values.iter().cloned().collect();
+
+                match comparison.logic {
+                    dir::DirComparisonLogic::PositiveDisjunction => {
+                        Self::values(key, Some(values), FxHashSet::default())
+                    }
+                    dir::DirComparisonLogic::NegativeConjunction => Self::values(key, None, values),
+                }
+            }
+        }
+    }
+
+    /// Builds a value constraint, folding the exclusions into the allowed set wherever the key
+    /// has a finite set of values
+    fn values(
+        key: &dir::DirKey,
+        allowed: Option<FxHashSet<dir::DirValue>>,
+        excluded: FxHashSet<dir::DirValue>,
+    ) -> Self {
+        let allowed = allowed.or_else(|| {
+            key.kind
+                .get_value_set()
+                .map(|set| set.into_iter().collect())
+        });
+
+        match allowed {
+            Some(mut allowed) => {
+                allowed.retain(|value| !excluded.contains(value));
+
+                Self::Values {
+                    allowed: Some(allowed),
+                    excluded: FxHashSet::default(),
+                }
+            }
+            None => Self::Values {
+                allowed: None,
+                excluded,
+            },
+        }
+    }
+
+    fn intersect(&self, other: &Self, key: &dir::DirKey) -> Self {
+        match (self, other) {
+            (Self::Range(this), Self::Range(other)) => {
+                Self::Range(intersect_intervals(this, other))
+            }
+
+            (
+                Self::Values {
+                    allowed: this_allowed,
+                    excluded: this_excluded,
+                },
+                Self::Values {
+                    allowed: other_allowed,
+                    excluded: other_excluded,
+                },
+            ) => {
+                let allowed = match (this_allowed, other_allowed) {
+                    (Some(this), Some(other)) => Some(this.intersection(other).cloned().collect()),
+                    (Some(allowed), None) | (None, Some(allowed)) => Some(allowed.clone()),
+                    (None, None) => None,
+                };
+
+                Self::values(
+                    key,
+                    allowed,
+                    this_excluded.union(other_excluded).cloned().collect(),
+                )
+            }
+
+            // A key always has a single data type, so the kinds of constraints never differ
+            (this, _) => this.clone(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Range(intervals) => intervals.is_empty(),
+            Self::Values {
+                allowed: Some(allowed),
+                ..
+            } => allowed.is_empty(),
+            Self::Values { allowed: None, .. } => false,
+        }
+    }
+
+    /// Whether every value satisfying this constraint also satisfies `other`
+    fn is_subset(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Range(this), Self::Range(other)) => this.iter().all(|(lo, hi)| {
+                other
+                    .iter()
+                    .any(|(other_lo, other_hi)| other_lo <= lo && hi <= other_hi)
+            }),
+
+            (
+                Self::Values {
+                    allowed: this_allowed,
+                    excluded: this_excluded,
+                },
+                Self::Values {
+                    allowed: other_allowed,
+                    excluded: other_excluded,
+                },
+            ) => {
+                let within_allowed = match (this_allowed, other_allowed) {
+                    (_, None) => true,
+                    (Some(this), Some(other)) => this.is_subset(other),
+                    (None, Some(_)) => false,
+                };
+
+                within_allowed
+                    && other_excluded.iter().all(|value| {
+                        this_excluded.contains(value)
+                            || this_allowed
+                                .as_ref()
+                                .is_some_and(|allowed| !allowed.contains(value))
+                    })
+            }
+
+            _ => false,
+        }
+    }
+}
+
+/// The numbers for which a value is considered present by the interpreters
+fn presence_intervals(value: &dir::DirValue) -> Vec<Interval> {
+    let Some(num_value) = value.get_num_value() else {
+        return Vec::new();
+    };
+    let number = num_value.number.get_amount_as_i64();
+
+    let below = |bound: i64| Some((i64::MIN, bound));
+    let above = |bound: i64| Some((bound, i64::MAX));
+
+    let intervals = match num_value.refinement {
+        None => vec![Some((number, number))],
+        Some(NumValueRefinement::NotEqual) => vec![
+            number.checked_sub(1).and_then(below),
+            number.checked_add(1).and_then(above),
+        ],
+        Some(NumValueRefinement::GreaterThan) => vec![number.checked_add(1).and_then(above)],
+        Some(NumValueRefinement::GreaterThanEqual) => vec![above(number)],
+        Some(NumValueRefinement::LessThan) => vec![number.checked_sub(1).and_then(below)],
+        Some(NumValueRefinement::LessThanEqual) => vec![below(number)],
+    };
+
+    intervals.into_iter().flatten().collect()
+}
+
+/// Sorts the intervals and merges the ones which overlap or are adjacent
+fn normalize_intervals(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.sort_unstable();
+
+    let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+    for (lo, hi) in intervals {
+        match merged.last_mut() {
+            Some((_, last_hi)) if *last_hi == i64::MAX || lo <= *last_hi + 1 => {
+                *last_hi = (*last_hi).max(hi);
+            }
+            _ => merged.push((lo, hi)),
+        }
+    }
+
+    merged
+}
+
+fn intersect_intervals(this: &[Interval], other: &[Interval]) -> Vec<Interval> {
+    normalize_intervals(
+        this.iter()
+            .flat_map(|(lo, hi)| {
+                other.iter().filter_map(move |(other_lo, other_hi)| {
+                    let lo = *lo.max(other_lo);
+                    let hi = *hi.min(other_hi);
+                    (lo <= hi).then_some((lo, hi))
+                })
+            })
+            .collect(),
+    )
+}
+
+fn complement_intervals(intervals: &[Interval]) -> Vec<Interval> {
+    let mut complement = Vec::with_capacity(intervals.len() + 1);
+    let mut start = Some(i64::MIN);
+
+    for (lo, hi) in intervals {
+        if let Some(start) = start.filter(|start| start < lo) {
+            complement.push((start, lo - 1));
+        }
+        start = hi.checked_add(1);
+    }
+
+    if let Some(start) = start {
+        complement.push((start, i64::MAX));
+    }
+
+    complement
+}
+
+/// The conjunction of all comparisons along one path of if statements within a rule
+struct ConditionPath<'a> {
+    constraints: FxHashMap<dir::DirKey, KeyConstraint>,
+    metadata: FxHashMap<dir::DirKey, Vec<&'a Metadata>>,
+}
+
+impl<'a> ConditionPath<'a> {
+    fn new(comparisons: &[&'a dir::DirComparison]) -> Self {
+        let mut constraints: FxHashMap<dir::DirKey, KeyConstraint> = FxHashMap::default();
+        let mut metadata: FxHashMap<dir::DirKey, Vec<&'a Metadata>> = FxHashMap::default();
+
+        for comparison in comparisons {
+            let Some(key) = comparison.values.first().map(dir::DirValue::get_key) else {
+                continue;
+            };
+
+            let constraint = KeyConstraint::from_comparison(&key, comparison);
+            let constraint = match constraints.get(&key) {
+                Some(existing) => existing.intersect(&constraint, &key),
+                None => constraint,
+            };
+
+            metadata
+                .entry(key.clone())
+                .or_default()
+                .push(&comparison.metadata);
+            constraints.insert(key, constraint);
+        }
+
+        Self {
+            constraints,
+            metadata,
+        }
+    }
+
+    fn is_satisfiable(&self) -> bool {
+        self.constraints
+            .values()
+            .all(|constraint| !constraint.is_empty())
+    }
+
+    /// Whether every input matching this path also matches `other`
+    fn implies(&self, other: &Self) -> bool {
+        other.constraints.iter().all(|(key, other_constraint)| {
+            self.constraints
+                .get(key)
+                .is_some_and(|constraint| constraint.is_subset(other_constraint))
+        })
+    }
+
+    /// Whether some input could match both this path and `other`
+    fn overlaps(&self, other: &Self) -> bool {
+        other.constraints.iter().all(|(key, other_constraint)| {
+            self.constraints.get(key).map_or(true, |constraint| {
+                !constraint.intersect(other_constraint, key).is_empty()
+            })
+        })
+    }
+}
+
+fn collect_paths<'a>(
+    statements: &'a [dir::DirIfStatement],
+    prefix: &[&'a dir::DirComparison],
+    paths: &mut Vec<Vec<&'a dir::DirComparison>>,
+) {
+    for statement in statements {
+        let mut path = prefix.to_vec();
+        path.extend(statement.condition.iter());
+
+        match &statement.nested {
+            Some(nested) => collect_paths(nested, &path, paths),
+            None => paths.push(path),
+        }
+    }
+}
+
+/// Analyses the rules of a program for unreachable rules, rules overlapping with an earlier rule
+/// having a different output and number comparisons that can never be satisfied.
+///
+/// For example, `rule_2` below can never fire since every payment it matches is matched by
+/// `rule_1` first,
+/// ```text
+/// rule_1: ["stripe"] { payment_method = card }
+/// rule_2: ["adyen"] { payment_method = card & amount > 500 }
+/// ```
+pub fn analyze_rule_reachability<O: PartialEq>(
+    program: &dir::DirProgram<O>,
+) -> Vec<AnalysisWarning> {
+    let mut warnings = Vec::new();
+    let mut visited: Vec<(&dir::DirRule<O>, Vec<ConditionPath<'_>>)> = Vec::new();
+
+    for rule in &program.rules {
+        let mut comparison_paths = Vec::new();
+        collect_paths(&rule.statements, &[], &mut comparison_paths);
+        let paths = comparison_paths
+            .iter()
+            .map(|comparisons| ConditionPath::new(comparisons))
+            .collect::<Vec<_>>();
+
+        let mut empty_range_keys: Vec<&dir::DirKey> = Vec::new();
+        for path in &paths {
+            for (key, constraint) in &path.constraints {
+                if matches!(constraint, KeyConstraint::Range(_))
+                    && constraint.is_empty()
+                    && !empty_range_keys.contains(&key)
+                {
+                    empty_range_keys.push(key);
+                    warnings.push(AnalysisWarning::EmptyNumberRange {
+                        rule_name: rule.name.clone(),
+                        key: key.kind.to_string(),
+                        metadata: path
+                            .metadata
+                            .get(key)
+                            .map(|metadata| metadata.iter().copied().cloned().collect())
+                            .unwrap_or_default(),
+                    });
+                }
+            }
+        }
+
+        let has_paths = !paths.is_empty();
+        let paths = paths
+            .into_iter()
+            .filter(ConditionPath::is_satisfiable)
+            .collect::<Vec<_>>();
+
+        if paths.is_empty() {
+            if has_paths {
+                warnings.push(AnalysisWarning::UnreachableRule {
+                    rule_name: rule.name.clone(),
+                    shadowed_by: Vec::new(),
+                });
+            }
+        } else {
+            let mut shadowed_by: Vec<String> = Vec::new();
+            let unreachable = paths.iter().all(|path| {
+                visited
+                    .iter()
+                    .find(|(_, earlier_paths)| {
+                        earlier_paths
+                            .iter()
+                            .any(|earlier_path| path.implies(earlier_path))
+                    })
+                    .map(|(earlier_rule, _)| {
+                        if !shadowed_by.contains(&earlier_rule.name) {
+                            shadowed_by.push(earlier_rule.name.clone());
+                        }
+                    })
+                    .is_some()
+            });
+
+            if unreachable {
+                warnings.push(AnalysisWarning::UnreachableRule {
+                    rule_name: rule.name.clone(),
+                    shadowed_by,
+                });
+            } else {
+                warnings.extend(
+                    visited
+                        .iter()
+                        .filter(|(earlier_rule, earlier_paths)| {
+                            earlier_rule.connector_selection != rule.connector_selection
+                                && paths.iter().any(|path| {
+                                    earlier_paths
+                                        .iter()
+                                        .any(|earlier_path| path.overlaps(earlier_path))
+                                })
+                        })
+                        .map(|(earlier_rule, _)| AnalysisWarning::OverlappingRules {
+                            rule_name: rule.name.clone(),
+                            overlapping_rule: earlier_rule.name.clone(),
+                        }),
+                );
+            }
+        }
+
+        visited.push((rule, paths));
+    }
+
+    warnings
+}
+
+#[cfg(all(test, feature = "ast_parser"))]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+    use crate::{dssa::analyzer, frontend::ast, types::DummyOutput};
+
+    fn warnings(program_str: &str) -> Vec<AnalysisWarning> {
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let (_, warnings) = analyzer::analyze_with_warnings(program, None).expect("Analysis");
+        warnings
+    }
+
+    #[test]
+    fn test_shadowed_rule_detection() {
+        let warnings = warnings(
+            r#"
+            default: ["stripe"]
+
+            card_rule: ["stripe"]
+            {
+                payment_method = card
+            }
+
+            high_value_rule: ["adyen"]
+            {
+                payment_method = card & amount > 500
+            }
+            "#,
+        );
+
+        assert!(matches!(
+            warnings.as_slice(),
+            [AnalysisWarning::UnreachableRule { rule_name, shadowed_by }]
+                if rule_name == "high_value_rule" && shadowed_by == &["card_rule".to_string()]
+        ));
+    }
+
+    #[test]
+    fn test_overlapping_rule_detection() {
+        let warnings = warnings(
+            r#"
+            default: ["stripe"]
+
+            low_value_rule: ["stripe"]
+            {
+                amount < 1000
+            }
+
+            card_rule: ["adyen"]
+            {
+                payment_method = card
+            }
+
+            wallet_rule: ["stripe"]
+            {
+                payment_method = wallet & amount >= 1000
+            }
+            "#,
+        );
+
+        assert!(matches!(
+            warnings.as_slice(),
+            [AnalysisWarning::OverlappingRules { rule_name, overlapping_rule }]
+                if rule_name == "card_rule" && overlapping_rule == "low_value_rule"
+        ));
+    }
+
+    #[test]
+    fn test_empty_number_range_detection() {
+        let warnings = warnings(
+            r#"
+            default: ["stripe"]
+
+            impossible_rule: ["adyen"]
+            {
+                payment_method = card {
+                    amount > 500 & amount < 501
+                }
+            }
+            "#,
+        );
+
+        assert!(matches!(
+            warnings.as_slice(),
+            [
+                AnalysisWarning::EmptyNumberRange { rule_name, key, .. },
+                AnalysisWarning::UnreachableRule { shadowed_by, .. },
+            ] if rule_name == "impossible_rule" && key == "amount" && shadowed_by.is_empty()
+        ));
+    }
+}
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, fmt};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     dssa::{self, graph},
@@ -154,6 +154,30 @@ pub enum AnalysisErrorType {
     NotSupported,
 }
 
+/// Issues which do not make a program invalid, but which likely do not reflect the intent of its
+/// author
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "info", rename_all = "snake_case")]
+pub enum AnalysisWarning {
+    /// The rule can never fire. If `shadowed_by` is empty, its conditions can never hold at all,
+    /// otherwise the listed earlier rules match every input the rule would.
+    UnreachableRule {
+        rule_name: String,
+        shadowed_by: Vec<String>,
+    },
+    /// Some input matches both rules, which select different outputs. The earlier rule wins.
+    OverlappingRules {
+        rule_name: String,
+        overlapping_rule: String,
+    },
+    /// No number satisfies all of the comparisons made on the key within a single condition
+    EmptyNumberRange {
+        rule_name: String,
+        key: String,
+        metadata: Vec<Metadata>,
+    },
+}
+
 #[derive(Debug, Clone)]
 pub enum ValueType {
     EnumVariants(Vec<EuclidValue>),
@@ -34,6 +34,7 @@ collect_variants!(CaptureMethod);
 collect_variants!(Currency);
 collect_variants!(Country);
 collect_variants!(SetupFutureUsage);
+collect_variants!(DayOfWeek);
 #[cfg(feature = "payouts")]
 collect_variants!(PayoutType);
 #[cfg(feature = "payouts")]
@@ -104,6 +105,46 @@ pub enum MandateType {
     MultiUse,
 }
 
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Hash,
+    PartialEq,
+    Eq,
+    strum::Display,
+    strum::VariantNames,
+    strum::EnumIter,
+    strum::EnumString,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DayOfWeek {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<time::Weekday> for DayOfWeek {
+    fn from(weekday: time::Weekday) -> Self {
+        match weekday {
+            time::Weekday::Monday => Self::Monday,
+            time::Weekday::Tuesday => Self::Tuesday,
+            time::Weekday::Wednesday => Self::Wednesday,
+            time::Weekday::Thursday => Self::Thursday,
+            time::Weekday::Friday => Self::Friday,
+            time::Weekday::Saturday => Self::Saturday,
+            time::Weekday::Sunday => Self::Sunday,
+        }
+    }
+}
+
 #[cfg(feature = "payouts")]
 #[derive(
     Clone,
@@ -1,3 +1,6 @@
+#[cfg(feature = "ast_parser")]
+pub mod diagnostics;
+pub mod formatter;
 pub mod lowering;
 #[cfg(feature = "ast_parser")]
 pub mod parser;
@@ -80,7 +83,7 @@ pub enum ComparisonType {
 }
 
 /// Represents a single comparison condition.
-#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Comparison {
     /// The left hand side which will always be a domain input identifier like "payment.method.cardtype"
@@ -113,7 +116,7 @@ pub type IfCondition = Vec<Comparison>;
 ///     }
 /// }
 /// ```
-#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IfStatement {
     #[schema(value_type=Vec<Comparison>)]
@@ -135,7 +138,7 @@ pub struct IfStatement {
 ///     }
 /// }
 /// ```
-#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[aliases(RuleConnectorSelection = Rule<ConnectorSelection>)]
 pub struct Rule<O> {
@@ -147,7 +150,7 @@ pub struct Rule<O> {
 
 /// The program, having a default connector selection and
 /// a bunch of rules. Also can hold arbitrary metadata.
-#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[aliases(ProgramConnectorSelection = Program<ConnectorSelection>)]
 pub struct Program<O> {
@@ -158,7 +161,7 @@ pub struct Program<O> {
     pub metadata: Metadata,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct RoutableConnectorChoice {
     #[serde(skip)]
     pub choice_kind: RoutableChoiceKind,
@@ -166,20 +169,20 @@ pub struct RoutableConnectorChoice {
     pub merchant_connector_id: Option<common_utils::id_type::MerchantConnectorAccountId>,
 }
 
-#[derive(Debug, Default, Clone, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, ToSchema)]
 pub enum RoutableChoiceKind {
     OnlyConnector,
     #[default]
     FullStruct,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct ConnectorVolumeSplit {
     pub connector: RoutableConnectorChoice,
     pub split: u8,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum ConnectorSelection {
     Priority(Vec<RoutableConnectorChoice>),
@@ -0,0 +1,710 @@
+//! Diagnostics for the euclid DSL
+//!
+//! Parses DSL source text while keeping track of source locations, recovering from syntax errors
+//! at rule boundaries so that every problem in the program is reported at once. Each diagnostic
+//! carries a line/column range and, where possible, suggestions for what was meant.
+
+use common_enums::RoutableConnectors;
+use nom::{
+    branch, bytes::complete, character::complete as pchar, combinator, error, sequence, Offset,
+};
+use serde::Serialize;
+use strum::VariantNames;
+
+use super::parser::{self, skip_ws, EuclidParsable, ParseResult};
+use crate::{
+    dssa::types::AnalysisErrorType,
+    frontend::{
+        ast,
+        dir::{DirKeyKind, EuclidDirFilter},
+    },
+    types::Metadata,
+};
+
+const MAX_SUGGESTIONS: usize = 3;
+
+/// A location in the source text. Both the line and the column are 1-based, and the column is
+/// counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A half-open range of the source text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    Syntax,
+    UnknownKey,
+    KeyNotAllowed,
+    InvalidType,
+    InvalidVariant,
+    InvalidComparison,
+    InvalidValue,
+    InvalidPercentage,
+    InvalidConnector,
+    EmptyProgram,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    pub message: String,
+    pub span: Span,
+    pub suggestions: Vec<String>,
+}
+
+/// The result of checking a program. The program is only present if no diagnostics were
+/// reported.
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticReport<O> {
+    pub program: Option<ast::Program<O>>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+struct SpannedComparison<'a> {
+    comparison: ast::Comparison,
+    source: &'a str,
+    key: &'a str,
+    value: &'a str,
+}
+
+/// Parses and type checks the provided source text, reporting every syntax error as well as every
+/// comparison whose key or value is invalid for the keys allowed by `F`.
+pub fn check_program<O, F>(source: &str) -> DiagnosticReport<O>
+where
+    O: EuclidParsable + 'static,
+    F: EuclidDirFilter,
+{
+    let mut diagnostics = Vec::new();
+    let mut rules = Vec::new();
+    let mut input = source;
+
+    // The metadata header is optional, it is only parsed when present so that a malformed header
+    // is reported as such rather than as a missing default selection
+    let metadata = if input.trim_start().starts_with("@metadata") {
+        match parser::program_metadata(input) {
+            Ok((rest, metadata)) => {
+                input = rest;
+                metadata
+            }
+            Err(err) => {
+                diagnostics.push(syntax_diagnostic(source, input, err));
+                input = recover(source, input);
+                Metadata::new()
+            }
+        }
+    } else {
+        Metadata::new()
+    };
+
+    let default_selection = match parser::default_output::<O>(input) {
+        Ok((rest, default_selection)) => {
+            input = rest;
+            Some(default_selection)
+        }
+        Err(err) => {
+            diagnostics.push(syntax_diagnostic(source, input, err));
+            input = recover(source, input);
+            None
+        }
+    };
+
+    while !input.trim().is_empty() {
+        match parser::rule::<O>(input) {
+            Ok((rest, rule)) => {
+                check_rule_conditions::<O, F>(source, input, rest, &mut diagnostics);
+                rules.push(rule);
+                input = rest;
+            }
+            Err(err) => {
+                diagnostics.push(syntax_diagnostic(source, input, err));
+                input = recover(source, input);
+            }
+        }
+    }
+
+    if rules.is_empty() && diagnostics.is_empty() {
+        let end = position(source, source.len());
+        diagnostics.push(Diagnostic {
+            kind: DiagnosticKind::EmptyProgram,
+            message: "The program must contain at least one rule".to_string(),
+            span: Span { start: end, end },
+            suggestions: Vec::new(),
+        });
+    }
+
+    let program = default_selection
+        .filter(|_| diagnostics.is_empty())
+        .map(|default_selection| ast::Program {
+            default_selection,
+            rules,
+            metadata,
+        });
+
+    DiagnosticReport {
+        program,
+        diagnostics,
+    }
+}
+
+/// Type checks every comparison of an already parsed rule, which spans `input` up to `rest`
+fn check_rule_conditions<O, F>(
+    source: &str,
+    input: &str,
+    rest: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) where
+    O: EuclidParsable + 'static,
+    F: EuclidDirFilter,
+{
+    let mut header = sequence::tuple((
+        skip_ws(parser::identifier),
+        skip_ws(complete::tag(":")),
+        parser::output::<O>,
+    ));
+
+    let Ok((mut remaining, _)) = header(input) else {
+        return;
+    };
+    let end = input.offset(rest);
+
+    loop {
+        remaining = remaining
+            .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '{' | '}' | '&'));
+
+        if remaining.is_empty() || input.offset(remaining) >= end {
+            break;
+        }
+
+        match spanned_comparison(remaining) {
+            Ok((next, comparison)) => {
+                diagnostics.extend(check_comparison::<F>(source, comparison));
+                remaining = next;
+            }
+            Err(_) => break,
+        }
+    }
+}
+
+fn spanned_comparison(input: &str) -> ParseResult<&str, SpannedComparison<'_>> {
+    let comparison_metadata = sequence::preceded(
+        skip_ws(complete::tag("@")),
+        skip_ws(parser::metadata_object),
+    );
+
+    let keyed_comparison = combinator::map(
+        sequence::pair(
+            combinator::consumed(sequence::tuple((
+                parser::comparison_key,
+                skip_ws(parser::comparison_type),
+                skip_ws(combinator::consumed(parser::value_type)),
+            ))),
+            combinator::opt(comparison_metadata),
+        ),
+        |((source, (key, comparison, (value_source, value))), metadata)| SpannedComparison {
+            comparison: ast::Comparison {
+                lhs: key.to_string(),
+                comparison,
+                value,
+                metadata: metadata.unwrap_or_default(),
+            },
+            source,
+            key,
+            value: value_source,
+        },
+    );
+
+    let metadata_comparison = combinator::map(
+        combinator::consumed(parser::arbitrary_comparison),
+        |(source, comparison)| SpannedComparison {
+            comparison,
+            source,
+            key: source,
+            value: source,
+        },
+    );
+
+    error::context(
+        "spanned_comparison",
+        branch::alt((keyed_comparison, metadata_comparison)),
+    )(input)
+}
+
+fn check_comparison<F: EuclidDirFilter>(
+    source: &str,
+    spanned: SpannedComparison<'_>,
+) -> Option<Diagnostic> {
+    let error = ast::lowering::lower_comparison_inner::<F>(spanned.comparison).err()?;
+
+    let (kind, message, span_of, suggestions) = match error {
+        AnalysisErrorType::InvalidKey(key) => {
+            if key.parse::<DirKeyKind>().is_ok() {
+                (
+                    DiagnosticKind::KeyNotAllowed,
+                    format!("The key '{key}' cannot be used in this algorithm"),
+                    spanned.key,
+                    Vec::new(),
+                )
+            } else {
+                let allowed = F::ALLOWED
+                    .iter()
+                    .map(|key| key.to_string())
+                    .collect::<Vec<_>>();
+                (
+                    DiagnosticKind::UnknownKey,
+                    format!("Unknown key '{key}'"),
+                    spanned.key,
+                    suggest(&key, allowed.iter().map(String::as_str)),
+                )
+            }
+        }
+        AnalysisErrorType::InvalidType { key, expected, got } => (
+            DiagnosticKind::InvalidType,
+            format!("The key '{key}' expects a value of type {expected}, got {got}"),
+            spanned.value,
+            Vec::new(),
+        ),
+        AnalysisErrorType::InvalidVariant { key, expected, got } => (
+            DiagnosticKind::InvalidVariant,
+            format!("'{got}' is not a valid variant for the key '{key}'"),
+            spanned.value,
+            suggest(&got, expected.iter().map(String::as_str)),
+        ),
+        AnalysisErrorType::InvalidComparison {
+            operator,
+            value_type,
+        } => (
+            DiagnosticKind::InvalidComparison,
+            format!(
+                "The comparison '{operator:?}' cannot be used with values of type {value_type}"
+            ),
+            spanned.source,
+            Vec::new(),
+        ),
+        AnalysisErrorType::InvalidValue {
+            key,
+            value,
+            message,
+        } => (
+            DiagnosticKind::InvalidValue,
+            match message {
+                Some(message) => format!("Invalid value '{value}' for the key '{key}': {message}"),
+                None => format!("Invalid value '{value}' for the key '{key}'"),
+            },
+            spanned.value,
+            Vec::new(),
+        ),
+        other => (
+            DiagnosticKind::InvalidValue,
+            other.to_string(),
+            spanned.source,
+            Vec::new(),
+        ),
+    };
+
+    Some(Diagnostic {
+        kind,
+        message,
+        span: span(source, span_of),
+        suggestions,
+    })
+}
+
+fn syntax_diagnostic(
+    source: &str,
+    input: &str,
+    err: nom::Err<error::VerboseError<&str>>,
+) -> Diagnostic {
+    let err = match err {
+        nom::Err::Error(err) | nom::Err::Failure(err) => err,
+        nom::Err::Incomplete(_) => error::VerboseError { errors: Vec::new() },
+    };
+
+    let location = err
+        .errors
+        .first()
+        .map(|(location, _)| *location)
+        .unwrap_or(input)
+        .trim_start();
+    let contexts = err
+        .errors
+        .iter()
+        .filter_map(|(_, kind)| match kind {
+            error::VerboseErrorKind::Context(context) => Some(*context),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let is_rejected_value = err.errors.first().is_some_and(|(_, kind)| {
+        matches!(kind, error::VerboseErrorKind::Nom(error::ErrorKind::MapRes))
+    });
+
+    let token = next_token(location);
+    let found = if token.is_empty() {
+        "end of input".to_string()
+    } else {
+        format!("'{token}'")
+    };
+
+    let (kind, message, suggestions) = if contexts.contains(&"volume_split_percentage") {
+        (
+            DiagnosticKind::InvalidPercentage,
+            format!("Invalid volume split percentage {found}, expected a number between 0 and 100 followed by '%'"),
+            Vec::new(),
+        )
+    } else if is_rejected_value && contexts.first() == Some(&"connector_choice") {
+        (
+            DiagnosticKind::InvalidConnector,
+            format!("Invalid connector {found}"),
+            suggest(token, RoutableConnectors::VARIANTS.iter().copied()),
+        )
+    } else {
+        let expected = match err.errors.first().map(|(_, kind)| kind) {
+            Some(error::VerboseErrorKind::Char(c)) => format!(", expected '{c}'"),
+            _ => String::new(),
+        };
+        let context = contexts
+            .first()
+            .map(|context| format!(" while parsing {}", context.replace('_', " ")))
+            .unwrap_or_default();
+
+        (
+            DiagnosticKind::Syntax,
+            format!("Unexpected {found}{context}{expected}"),
+            Vec::new(),
+        )
+    };
+
+    Diagnostic {
+        kind,
+        message,
+        span: span(source, token_or_char(location, token)),
+        suggestions,
+    }
+}
+
+/// Skips the rule (or default selection) that failed to parse, returning the remaining input
+/// starting at the next line which looks like the header of a rule
+fn recover<'a>(source: &'a str, input: &'a str) -> &'a str {
+    let failed_line = position(source, source.offset(input.trim_start())).line;
+
+    let mut offset = 0;
+    for (line_index, line) in source.split_inclusive('\n').enumerate() {
+        if line_index + 1 > failed_line && is_rule_header(line) {
+            return source.get(offset..).unwrap_or_default();
+        }
+        offset += line.len();
+    }
+
+    ""
+}
+
+fn is_rule_header(line: &str) -> bool {
+    sequence::tuple((
+        pchar::space0::<&str, error::VerboseError<&str>>,
+        parser::identifier,
+        pchar::space0,
+        complete::tag(":"),
+        pchar::space0,
+        branch::alt((complete::tag("["), complete::tag("{"))),
+    ))(line)
+    .is_ok()
+}
+
+fn next_token(input: &str) -> &str {
+    let end = input
+        .find(|c: char| {
+            c.is_whitespace() || matches!(c, '[' | ']' | '{' | '}' | '(' | ')' | ',' | ':' | '&')
+        })
+        .unwrap_or(input.len());
+
+    input.get(..end).unwrap_or(input)
+}
+
+fn token_or_char<'a>(input: &'a str, token: &'a str) -> &'a str {
+    if token.is_empty() {
+        input
+            .chars()
+            .next()
+            .and_then(|c| input.get(..c.len_utf8()))
+            .unwrap_or(token)
+    } else {
+        token
+    }
+}
+
+/// Computes the span of `slice`, which must be a subslice of `source`
+fn span(source: &str, slice: &str) -> Span {
+    let start = source.offset(slice);
+
+    Span {
+        start: position(source, start),
+        end: position(source, start + slice.len()),
+    }
+}
+
+fn position(source: &str, offset: usize) -> Position {
+    let preceding = source.get(..offset).unwrap_or(source);
+    let line_start = preceding.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+
+    Position {
+        line: preceding.matches('\n').count() + 1,
+        column: preceding
+            .get(line_start..)
+            .unwrap_or_default()
+            .chars()
+            .count()
+            + 1,
+    }
+}
+
+/// Returns the candidates closest to `got`, ordered by their edit distance
+fn suggest<'a>(got: &str, candidates: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let got = got.to_lowercase();
+    let max_distance = std::cmp::max(2, got.chars().count() / 3);
+
+    let mut suggestions = candidates
+        .filter_map(|candidate| {
+            let distance = edit_distance(&got, &candidate.to_lowercase());
+            (distance <= max_distance).then_some((distance, candidate))
+        })
+        .collect::<Vec<_>>();
+
+    suggestions.sort();
+    suggestions.dedup();
+
+    suggestions
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate.to_string())
+        .collect()
+}
+
+fn edit_distance(lhs: &str, rhs: &str) -> usize {
+    let rhs = rhs.chars().collect::<Vec<_>>();
+    let mut previous = (0..=rhs.len()).collect::<Vec<_>>();
+
+    for (i, lhs_char) in lhs.chars().enumerate() {
+        let mut current = Vec::with_capacity(rhs.len() + 1);
+        current.push(i + 1);
+
+        for (window, rhs_char) in previous.windows(2).zip(rhs.iter()) {
+            let [diagonal, above] = window else {
+                continue;
+            };
+            let left = current.last().copied().unwrap_or(i + 1);
+            let substitution = diagonal + usize::from(lhs_char != *rhs_char);
+
+            current.push(substitution.min(above + 1).min(left + 1));
+        }
+
+        previous = current;
+    }
+
+    previous.last().copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::indexing_slicing, clippy::expect_used)]
+
+    use super::*;
+    use crate::{frontend::ast::formatter, types::DummyOutput};
+
+    #[test]
+    fn test_valid_program() {
+        let code = r#"
+            default: ["stripe"]
+
+            rule_1: ["adyen"]
+            {
+                payment_method = card & amount > 100
+            }
+        "#;
+
+        let report = check_program::<DummyOutput, DummyOutput>(code);
+
+        assert!(report.diagnostics.is_empty());
+        assert!(report.program.is_some());
+    }
+
+    #[test]
+    fn test_reports_every_error_with_location() {
+        let code = r#"default: ["stripe"]
+
+rule_1: ["adyen"]
+{
+    payment_methd = card
+}
+
+rule_2 ["adyen"]
+{
+    payment_method = card
+}
+
+rule_3: ["checkout"]
+{
+    amount = card & payment_method = crad
+}
+"#;
+
+        let report = check_program::<DummyOutput, DummyOutput>(code);
+        let kinds = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.kind.clone())
+            .collect::<Vec<_>>();
+
+        assert!(report.program.is_none());
+        assert_eq!(
+            kinds,
+            vec![
+                DiagnosticKind::UnknownKey,
+                DiagnosticKind::Syntax,
+                DiagnosticKind::InvalidType,
+                DiagnosticKind::InvalidVariant,
+            ]
+        );
+
+        let unknown_key = &report.diagnostics[0];
+        assert_eq!(unknown_key.span.start, Position { line: 5, column: 5 });
+        assert_eq!(
+            unknown_key.span.end,
+            Position {
+                line: 5,
+                column: 18
+            }
+        );
+        assert_eq!(
+            unknown_key.suggestions.first().map(String::as_str),
+            Some("payment_method")
+        );
+
+        let syntax_error = &report.diagnostics[1];
+        assert_eq!(syntax_error.span.start.line, 8);
+
+        let invalid_variant = &report.diagnostics[3];
+        assert_eq!(
+            invalid_variant.span.start,
+            Position {
+                line: 15,
+                column: 38
+            }
+        );
+        assert_eq!(
+            invalid_variant.suggestions.first().map(String::as_str),
+            Some("card")
+        );
+    }
+
+    #[test]
+    fn test_invalid_percentage() {
+        let code = r#"
+            default: [stripe]
+
+            split_rule: { stripe: 60%, adyen: 140% }
+            {
+                payment_method = card
+            }
+        "#;
+
+        let report = check_program::<ast::ConnectorSelection, DummyOutput>(code);
+
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(
+            report.diagnostics[0].kind,
+            DiagnosticKind::InvalidPercentage
+        );
+        assert_eq!(
+            report.diagnostics[0].span.start,
+            Position {
+                line: 4,
+                column: 47
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_connector_suggestion() {
+        let code = r#"
+            default: [strpe]
+
+            rule_1: [adyen]
+            {
+                payment_method = card
+            }
+        "#;
+
+        let report = check_program::<ast::ConnectorSelection, DummyOutput>(code);
+
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(report.diagnostics[0].kind, DiagnosticKind::InvalidConnector);
+        assert!(report.diagnostics[0]
+            .suggestions
+            .contains(&"stripe".to_string()));
+    }
+
+    #[test]
+    fn test_formatted_program_with_metadata_round_trip() {
+        let code = r#"
+            @metadata {"owner": "payments", "version": 2}
+            default: [stripe]
+
+            rule_1: [adyen("mca_adyen")]
+            {
+                payment_method = card @{"reason": "cards"} & amount > 100
+            }
+        "#;
+
+        let (_, program) =
+            parser::program::<ast::ConnectorSelection>(code).expect("Program parsing");
+        let formatted = formatter::format_program(&program).expect("Program formatting");
+
+        let report = check_program::<ast::ConnectorSelection, DummyOutput>(&formatted);
+
+        assert!(report.diagnostics.is_empty(), "{:?}", report.diagnostics);
+        assert_eq!(report.program, Some(program));
+    }
+
+    #[test]
+    fn test_comparison_metadata_does_not_stop_checking() {
+        let code = r#"
+            default: ["stripe"]
+
+            rule_1: ["adyen"]
+            {
+                payment_method = card @{"reason": "cards"} & payment_methd = card
+            }
+        "#;
+
+        let report = check_program::<DummyOutput, DummyOutput>(code);
+
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(report.diagnostics[0].kind, DiagnosticKind::UnknownKey);
+    }
+
+    #[test]
+    fn test_malformed_metadata_header() {
+        let code = r#"@metadata {"owner": }
+default: ["stripe"]
+
+rule_1: ["adyen"]
+{
+    payment_method = card
+}
+"#;
+
+        let report = check_program::<DummyOutput, DummyOutput>(code);
+
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(report.diagnostics[0].kind, DiagnosticKind::Syntax);
+        assert_eq!(report.diagnostics[0].span.start.line, 1);
+    }
+}
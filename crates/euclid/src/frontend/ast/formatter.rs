@@ -0,0 +1,567 @@
+//! Formatter for the euclid DSL
+//!
+//! Emits canonical DSL text for an [`ast::Program`], such that parsing the formatted text with
+//! [`super::parser::program`] yields back the same program. Program metadata is emitted as an
+//! `@metadata { .. }` line before the default selection and comparison metadata as an `@{ .. }`
+//! suffix of the comparison, both as JSON objects with sorted keys.
+//!
+//! Connector selections are emitted with [`format_priority`] and [`format_volume_split`], which
+//! are shared by the connector selection types defined outside this crate.
+
+use common_enums::RoutableConnectors;
+
+use crate::{
+    frontend::ast,
+    types::{DummyOutput, Metadata},
+};
+
+const INDENT: &str = "    ";
+
+#[derive(Debug, Clone, thiserror::Error, serde::Serialize)]
+#[serde(tag = "type", content = "info", rename_all = "snake_case")]
+pub enum FormatError {
+    #[error("The program does not contain any rules")]
+    EmptyProgram,
+    #[error("The rule '{0}' does not contain any statements")]
+    EmptyRule(String),
+    #[error("An if statement does not contain any conditions")]
+    EmptyCondition,
+    #[error("The connector selection is empty")]
+    EmptyOutput,
+    #[error("'{0}' is not a valid identifier")]
+    InvalidIdentifier(String),
+    #[error("'{0}' is not a valid key")]
+    InvalidKey(String),
+    #[error("The string '{0}' cannot be represented in the DSL")]
+    InvalidString(String),
+    #[error("Negative number '{0}' cannot be represented in the DSL")]
+    NegativeNumber(i64),
+    #[error("Empty array received for key '{0}'")]
+    EmptyArray(String),
+    #[error("Comparison '{0:?}' cannot be used inside a number comparison array")]
+    InvalidArrayComparison(ast::ComparisonType),
+    #[error("Metadata value received for non-metadata key '{0}'")]
+    InvalidMetadataKey(String),
+    #[error("Invalid volume split percentage '{0}'")]
+    InvalidPercentage(u8),
+    #[error("Metadata cannot be represented in the DSL: {0}")]
+    InvalidMetadata(String),
+}
+
+/// Allows a routing output to be emitted in the DSL. The emitted text must be accepted by the
+/// corresponding `EuclidParsable` implementation of the output.
+pub trait EuclidFormattable {
+    fn format_output(&self) -> Result<String, FormatError>;
+}
+
+impl EuclidFormattable for DummyOutput {
+    fn format_output(&self) -> Result<String, FormatError> {
+        if self.outputs.is_empty() {
+            return Err(FormatError::EmptyOutput);
+        }
+
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|output| {
+                if output.contains('"') {
+                    Err(FormatError::InvalidString(output.clone()))
+                } else {
+                    Ok(format!("\"{output}\""))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(format!("[{}]", outputs.join(", ")))
+    }
+}
+
+impl EuclidFormattable for ast::ConnectorSelection {
+    fn format_output(&self) -> Result<String, FormatError> {
+        match self {
+            Self::Priority(choices) => format_priority(
+                choices
+                    .iter()
+                    .map(|choice| (&choice.connector, choice.merchant_connector_id.as_ref())),
+            ),
+            Self::VolumeSplit(splits) => format_volume_split(splits.iter().map(|split| {
+                (
+                    &split.connector.connector,
+                    split.connector.merchant_connector_id.as_ref(),
+                    split.split,
+                )
+            })),
+        }
+    }
+}
+
+/// Formats a single connector choice as `connector` or `connector("merchant_connector_id")`
+fn format_connector_choice(
+    connector: &RoutableConnectors,
+    merchant_connector_id: Option<&common_utils::id_type::MerchantConnectorAccountId>,
+) -> String {
+    match merchant_connector_id {
+        Some(mca_id) => format!("{connector}(\"{}\")", mca_id.get_string_repr()),
+        None => connector.to_string(),
+    }
+}
+
+/// Formats a priority list of connectors as `[stripe, adyen("mca_123")]`
+pub fn format_priority<'a>(
+    choices: impl Iterator<
+        Item = (
+            &'a RoutableConnectors,
+            Option<&'a common_utils::id_type::MerchantConnectorAccountId>,
+        ),
+    >,
+) -> Result<String, FormatError> {
+    let choices = choices
+        .map(|(connector, mca_id)| format_connector_choice(connector, mca_id))
+        .collect::<Vec<_>>();
+
+    if choices.is_empty() {
+        return Err(FormatError::EmptyOutput);
+    }
+
+    Ok(format!("[{}]", choices.join(", ")))
+}
+
+/// Formats a volume split between connectors as `{ stripe: 70%, adyen: 30% }`
+pub fn format_volume_split<'a>(
+    splits: impl Iterator<
+        Item = (
+            &'a RoutableConnectors,
+            Option<&'a common_utils::id_type::MerchantConnectorAccountId>,
+            u8,
+        ),
+    >,
+) -> Result<String, FormatError> {
+    let splits = splits
+        .map(|(connector, mca_id, split)| {
+            if split > 100 {
+                Err(FormatError::InvalidPercentage(split))
+            } else {
+                Ok(format!(
+                    "{}: {split}%",
+                    format_connector_choice(connector, mca_id)
+                ))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if splits.is_empty() {
+        return Err(FormatError::EmptyOutput);
+    }
+
+    Ok(format!("{{ {} }}", splits.join(", ")))
+}
+
+/// Formats the complete program, starting with the default selection followed by every rule
+/// separated by a blank line.
+///
+/// ```text
+/// @metadata {"owner":"payments"}
+/// default: ["stripe", "adyen"]
+///
+/// card_rule: ["stripe"]
+/// {
+///     payment_method = card & amount > 100 @{"reason":"high_value"} {
+///         card_network = (visa, mastercard)
+///     }
+/// }
+/// ```
+pub fn format_program<O: EuclidFormattable>(
+    program: &ast::Program<O>,
+) -> Result<String, FormatError> {
+    if program.rules.is_empty() {
+        return Err(FormatError::EmptyProgram);
+    }
+
+    let mut out = String::new();
+
+    if !program.metadata.is_empty() {
+        out.push_str(&format!(
+            "@metadata {}\n",
+            format_metadata(&program.metadata)?
+        ));
+    }
+
+    out.push_str(&format!(
+        "default: {}\n",
+        program.default_selection.format_output()?
+    ));
+
+    for rule in &program.rules {
+        out.push('\n');
+        out.push_str(&format_rule(rule)?);
+    }
+
+    Ok(out)
+}
+
+pub fn format_rule<O: EuclidFormattable>(rule: &ast::Rule<O>) -> Result<String, FormatError> {
+    if !is_identifier(&rule.name) {
+        return Err(FormatError::InvalidIdentifier(rule.name.clone()));
+    }
+
+    if rule.statements.is_empty() {
+        return Err(FormatError::EmptyRule(rule.name.clone()));
+    }
+
+    let mut out = format!(
+        "{}: {}\n{{\n",
+        rule.name,
+        rule.connector_selection.format_output()?
+    );
+
+    for statement in &rule.statements {
+        format_if_statement(statement, 1, &mut out)?;
+    }
+
+    out.push_str("}\n");
+
+    Ok(out)
+}
+
+fn format_if_statement(
+    statement: &ast::IfStatement,
+    depth: usize,
+    out: &mut String,
+) -> Result<(), FormatError> {
+    let indent = INDENT.repeat(depth);
+
+    out.push_str(&indent);
+    out.push_str(&format_condition(&statement.condition)?);
+
+    if let Some(nested) = &statement.nested {
+        out.push_str(" {\n");
+        for nested_statement in nested {
+            format_if_statement(nested_statement, depth + 1, out)?;
+        }
+        out.push_str(&indent);
+        out.push('}');
+    }
+
+    out.push('\n');
+
+    Ok(())
+}
+
+pub fn format_condition(condition: &ast::IfCondition) -> Result<String, FormatError> {
+    if condition.is_empty() {
+        return Err(FormatError::EmptyCondition);
+    }
+
+    Ok(condition
+        .iter()
+        .map(format_comparison)
+        .collect::<Result<Vec<_>, _>>()?
+        .join(" & "))
+}
+
+pub fn format_comparison(comparison: &ast::Comparison) -> Result<String, FormatError> {
+    let formatted = format_comparison_expression(comparison)?;
+
+    if comparison.metadata.is_empty() {
+        Ok(formatted)
+    } else {
+        Ok(format!(
+            "{formatted} @{}",
+            format_metadata(&comparison.metadata)?
+        ))
+    }
+}
+
+fn format_comparison_expression(comparison: &ast::Comparison) -> Result<String, FormatError> {
+    let operator = format_comparison_type(&comparison.comparison);
+
+    if let ast::ValueType::MetadataVariant(metadata) = &comparison.value {
+        if comparison.lhs != "metadata" {
+            return Err(FormatError::InvalidMetadataKey(comparison.lhs.clone()));
+        }
+
+        return Ok(format!(
+            "{} {operator} {}",
+            format_string(&metadata.key)?,
+            format_string(&metadata.value)?
+        ));
+    }
+
+    if comparison.lhs.is_empty()
+        || !comparison
+            .lhs
+            .chars()
+            .all(|c| c.is_ascii_alphabetic() || c == '.' || c == '_')
+    {
+        return Err(FormatError::InvalidKey(comparison.lhs.clone()));
+    }
+
+    Ok(format!(
+        "{} {operator} {}",
+        comparison.lhs,
+        format_value(&comparison.lhs, &comparison.value)?
+    ))
+}
+
+fn format_value(key: &str, value: &ast::ValueType) -> Result<String, FormatError> {
+    match value {
+        ast::ValueType::Number(number) => format_number(number.get_amount_as_i64()),
+        ast::ValueType::EnumVariant(variant) => format_enum_variant(variant),
+        ast::ValueType::StrValue(string) => format_string(string),
+        ast::ValueType::MetadataVariant(_) => Err(FormatError::InvalidMetadataKey(key.to_string())),
+        ast::ValueType::NumberArray(numbers) => format_array(
+            key,
+            numbers
+                .iter()
+                .map(|number| format_number(number.get_amount_as_i64())),
+        ),
+        ast::ValueType::EnumVariantArray(variants) => {
+            format_array(key, variants.iter().map(|v| format_enum_variant(v)))
+        }
+        ast::ValueType::NumberComparisonArray(comparisons) => format_array(
+            key,
+            comparisons.iter().map(|comparison| {
+                let operator = match comparison.comparison_type {
+                    ast::ComparisonType::GreaterThan
+                    | ast::ComparisonType::GreaterThanEqual
+                    | ast::ComparisonType::LessThan
+                    | ast::ComparisonType::LessThanEqual => {
+                        format_comparison_type(&comparison.comparison_type)
+                    }
+                    ast::ComparisonType::Equal | ast::ComparisonType::NotEqual => {
+                        return Err(FormatError::InvalidArrayComparison(
+                            comparison.comparison_type.clone(),
+                        ))
+                    }
+                };
+
+                Ok(format!(
+                    "{operator} {}",
+                    format_number(comparison.number.get_amount_as_i64())?
+                ))
+            }),
+        ),
+    }
+}
+
+/// Formats metadata as a single line JSON object with sorted keys, so that the output is stable
+fn format_metadata(metadata: &Metadata) -> Result<String, FormatError> {
+    let sorted = metadata
+        .iter()
+        .collect::<std::collections::BTreeMap<_, _>>();
+
+    serde_json::to_string(&sorted).map_err(|err| FormatError::InvalidMetadata(err.to_string()))
+}
+
+fn format_array(
+    key: &str,
+    elements: impl Iterator<Item = Result<String, FormatError>>,
+) -> Result<String, FormatError> {
+    let elements = elements.collect::<Result<Vec<_>, _>>()?;
+
+    if elements.is_empty() {
+        return Err(FormatError::EmptyArray(key.to_string()));
+    }
+
+    Ok(format!("({})", elements.join(", ")))
+}
+
+fn format_comparison_type(comparison: &ast::ComparisonType) -> &'static str {
+    match comparison {
+        ast::ComparisonType::Equal => "=",
+        ast::ComparisonType::NotEqual => "/=",
+        ast::ComparisonType::LessThan => "<",
+        ast::ComparisonType::LessThanEqual => "<=",
+        ast::ComparisonType::GreaterThan => ">",
+        ast::ComparisonType::GreaterThanEqual => ">=",
+    }
+}
+
+fn format_number(number: i64) -> Result<String, FormatError> {
+    if number < 0 {
+        Err(FormatError::NegativeNumber(number))
+    } else {
+        Ok(number.to_string())
+    }
+}
+
+fn format_enum_variant(variant: &str) -> Result<String, FormatError> {
+    if is_identifier(variant) {
+        Ok(variant.to_string())
+    } else {
+        Err(FormatError::InvalidIdentifier(variant.to_string()))
+    }
+}
+
+fn format_string(string: &str) -> Result<String, FormatError> {
+    if string.is_empty() || string.contains('"') {
+        Err(FormatError::InvalidString(string.to_string()))
+    } else {
+        Ok(format!("\"{string}\""))
+    }
+}
+
+fn is_identifier(ident: &str) -> bool {
+    let mut chars = ident.chars();
+
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(all(test, feature = "ast_parser"))]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+    use crate::frontend::ast::parser;
+
+    fn assert_round_trip<O>(program: &ast::Program<O>)
+    where
+        O: EuclidFormattable + parser::EuclidParsable + PartialEq + std::fmt::Debug + 'static,
+    {
+        let formatted = format_program(program).expect("Program formatting");
+        let (remaining, parsed) =
+            parser::program::<O>(&formatted).expect("Formatted program parsing");
+
+        assert!(remaining.trim().is_empty());
+        assert_eq!(&parsed, program);
+        assert_eq!(
+            format_program(&parsed).expect("Parsed program formatting"),
+            formatted
+        );
+    }
+
+    #[test]
+    fn test_dummy_output_round_trip() {
+        let code = r#"
+            default: ["stripe", "adyen", "checkout"]
+
+            stripe_first: ["stripe", "aci"]
+            {
+                payment_method = card & amount = 40 & "merchant_tier" = "gold" {
+                    payment_method = (card, bank_redirect)
+                    amount = (40, 50) & capture_method /= manual
+                    amount = (> 100, <= 1000)
+                }
+            }
+
+            adyen_first: ["adyen", "checkout"]
+            {
+                card_bin = "424242" & "merchant_tier" = "silver"
+                payment_method = wallet {
+                }
+            }
+        "#;
+
+        let (_, program) = parser::program::<DummyOutput>(code).expect("Program parsing");
+        assert_round_trip(&program);
+    }
+
+    #[test]
+    fn test_connector_selection_round_trip() {
+        let code = r#"
+            default: [stripe, adyen("mca_adyen")]
+
+            split_rule: { stripe: 70%, checkout("mca_checkout"): 30% }
+            {
+                payment_method = card
+            }
+
+            single_split_rule: { adyen: 100% }
+            {
+                currency = (USD, EUR)
+            }
+        "#;
+
+        let (_, program) =
+            parser::program::<ast::ConnectorSelection>(code).expect("Program parsing");
+        assert_round_trip(&program);
+    }
+
+    #[test]
+    fn test_metadata_round_trip() {
+        let code = r#"
+            @metadata {"owner": "payments", "version": 2}
+            default: ["stripe"]
+
+            rule_1: ["adyen"]
+            {
+                payment_method = card @{"reason": "cards", "weight": 1.5} & amount > 10 {
+                    "merchant_tier" = "gold" @{"source": {"team": "risk"}}
+                }
+            }
+        "#;
+
+        let (_, program) = parser::program::<DummyOutput>(code).expect("Program parsing");
+
+        assert_eq!(
+            program.metadata.get("owner"),
+            Some(&serde_json::json!("payments"))
+        );
+        let condition = &program
+            .rules
+            .first()
+            .expect("Rule")
+            .statements
+            .first()
+            .expect("Statement")
+            .condition;
+        assert_eq!(
+            condition
+                .first()
+                .expect("Comparison")
+                .metadata
+                .get("reason"),
+            Some(&serde_json::json!("cards"))
+        );
+        assert!(condition.get(1).expect("Comparison").metadata.is_empty());
+
+        assert_round_trip(&program);
+    }
+
+    #[test]
+    fn test_format_canonical_layout() {
+        let code = r#"default: ["stripe"] rule_1: ["adyen"] { payment_method = card { card_network = visa } amount > 10 }"#;
+        let (_, program) = parser::program::<DummyOutput>(code).expect("Program parsing");
+
+        let expected = r#"default: ["stripe"]
+
+rule_1: ["adyen"]
+{
+    payment_method = card {
+        card_network = visa
+    }
+    amount > 10
+}
+"#;
+
+        assert_eq!(
+            format_program(&program).expect("Program formatting"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_unrepresentable_program() {
+        let program = ast::Program {
+            default_selection: DummyOutput {
+                outputs: vec!["stripe".to_string()],
+            },
+            rules: vec![ast::Rule {
+                name: "invalid rule name".to_string(),
+                connector_selection: DummyOutput {
+                    outputs: vec!["adyen".to_string()],
+                },
+                statements: vec![],
+            }],
+            metadata: std::collections::HashMap::new(),
+        };
+
+        assert!(matches!(
+            format_program(&program),
+            Err(FormatError::InvalidIdentifier(_))
+        ));
+    }
+}
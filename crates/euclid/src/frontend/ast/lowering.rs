@@ -5,6 +5,8 @@
 
 use std::str::FromStr;
 
+use common_utils::types::MinorUnit;
+
 use crate::{
     dssa::types::{AnalysisError, AnalysisErrorType},
     enums::CollectVariants,
@@ -67,19 +69,23 @@ macro_rules! lower_enum {
 /// payment_amount = 17052001
 /// ```notrust
 /// This is for the cases in which there are numerical values involved and they are lowered
-/// accordingly on basis of the supplied key, currently payment_amount is the only key having this
-/// use case
+/// accordingly on basis of the supplied key, such as payment_amount, time_of_day and date. An
+/// optional validation closure can be supplied to restrict the numbers a key accepts.
 macro_rules! lower_number {
-    ($key:ident, $value:ident, $comp:ident) => {
+    ($key:ident, $value:ident, $comp:ident $(, $validation_closure:expr)?) => {
         match $value {
-            ast::ValueType::Number(num) => Ok(vec![dir::DirValue::$key(types::NumValue {
-                number: num,
-                refinement: $comp.into(),
-            })]),
+            ast::ValueType::Number(num) => {
+                $($validation_closure(num)?;)?
+                Ok(vec![dir::DirValue::$key(types::NumValue {
+                    number: num,
+                    refinement: $comp.into(),
+                })])
+            }
 
             ast::ValueType::NumberArray(na) => na
                 .into_iter()
                 .map(|num| {
+                    $($validation_closure(num)?;)?
                     Ok(dir::DirValue::$key(types::NumValue {
                         number: num,
                         refinement: $comp.clone().into(),
@@ -90,6 +96,7 @@ macro_rules! lower_number {
             ast::ValueType::NumberComparisonArray(nca) => nca
                 .into_iter()
                 .map(|nc| {
+                    $($validation_closure(nc.number)?;)?
                     Ok(dir::DirValue::$key(types::NumValue {
                         number: nc.number,
                         refinement: nc.comparison_type.into(),
@@ -152,7 +159,7 @@ macro_rules! lower_metadata {
 /// by throwing required errors for comparisons that can't be performed for a certain value type
 /// for example
 /// can't have greater/less than operations on enum types
-fn lower_comparison_inner<O: EuclidDirFilter>(
+pub(crate) fn lower_comparison_inner<O: EuclidDirFilter>(
     comp: ast::Comparison,
 ) -> Result<Vec<dir::DirValue>, AnalysisErrorType> {
     let key_enum = dir::DirKeyKind::from_str(comp.lhs.as_str())
@@ -295,6 +302,44 @@ fn lower_comparison_inner<O: EuclidDirFilter>(
 
         dir::DirKeyKind::PaymentAmount => lower_number!(PaymentAmount, value, comparison),
 
+        dir::DirKeyKind::TimeOfDay => {
+            let validation_closure = |num: MinorUnit| -> Result<(), AnalysisErrorType> {
+                let time = num.get_amount_as_i64();
+                if (0..2400).contains(&time) && time % 100 < 60 {
+                    Ok(())
+                } else {
+                    Err(AnalysisErrorType::InvalidValue {
+                        key: dir::DirKeyKind::TimeOfDay,
+                        value: time.to_string(),
+                        message: Some("Expected a time in the HHMM format".to_string()),
+                    })
+                }
+            };
+            lower_number!(TimeOfDay, value, comparison, validation_closure)
+        }
+
+        dir::DirKeyKind::DayOfWeek => lower_enum!(DayOfWeek, value),
+
+        dir::DirKeyKind::Date => {
+            let validation_closure = |num: MinorUnit| -> Result<(), AnalysisErrorType> {
+                let date = num.get_amount_as_i64();
+                let (month, day) = (date / 100 % 100, date % 100);
+                if (10000101..=99991231).contains(&date)
+                    && (1..=12).contains(&month)
+                    && (1..=31).contains(&day)
+                {
+                    Ok(())
+                } else {
+                    Err(AnalysisErrorType::InvalidValue {
+                        key: dir::DirKeyKind::Date,
+                        value: date.to_string(),
+                        message: Some("Expected a date in the YYYYMMDD format".to_string()),
+                    })
+                }
+            };
+            lower_number!(Date, value, comparison, validation_closure)
+        }
+
         dir::DirKeyKind::Connector => Err(AnalysisErrorType::InvalidKey(
             dir::DirKeyKind::Connector.to_string(),
         )),
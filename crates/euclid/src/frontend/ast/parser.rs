@@ -1,9 +1,20 @@
-use common_utils::types::MinorUnit;
+use std::str::FromStr;
+
+use common_enums::RoutableConnectors;
+use common_utils::{id_type::MerchantConnectorAccountId, types::MinorUnit};
 use nom::{
-    branch, bytes::complete, character::complete as pchar, combinator, error, multi, sequence,
+    branch,
+    bytes::complete,
+    character::complete as pchar,
+    combinator,
+    error::{self, ParseError},
+    multi, sequence,
 };
 
-use crate::{frontend::ast, types::DummyOutput};
+use crate::{
+    frontend::ast,
+    types::{DummyOutput, Metadata},
+};
 pub type ParseResult<T, U> = nom::IResult<T, U, error::VerboseError<T>>;
 
 pub enum EuclidError {
@@ -51,6 +62,80 @@ impl EuclidParsable for DummyOutput {
         )(input)
     }
 }
+impl EuclidParsable for ast::ConnectorSelection {
+    fn parse_output(input: &str) -> ParseResult<&str, Self> {
+        // Once the opening delimiter is matched, the selection kind is known and any
+        // subsequent error is reported as is instead of backtracking into the other alternative.
+        let priority = combinator::map(
+            sequence::preceded(
+                skip_ws(complete::tag("[")),
+                combinator::cut(sequence::terminated(
+                    multi::separated_list1(
+                        skip_ws(complete::tag(",")),
+                        combinator::cut(connector_choice),
+                    ),
+                    skip_ws(complete::tag("]")),
+                )),
+            ),
+            Self::Priority,
+        );
+
+        let volume_split = combinator::map(
+            sequence::preceded(
+                skip_ws(complete::tag("{")),
+                combinator::cut(sequence::terminated(
+                    multi::separated_list1(
+                        skip_ws(complete::tag(",")),
+                        combinator::map(
+                            sequence::separated_pair(
+                                combinator::cut(connector_choice),
+                                skip_ws(complete::tag(":")),
+                                combinator::cut(skip_ws(percentage)),
+                            ),
+                            |(connector, split)| ast::ConnectorVolumeSplit { connector, split },
+                        ),
+                    ),
+                    skip_ws(complete::tag("}")),
+                )),
+            ),
+            Self::VolumeSplit,
+        );
+
+        error::context("connector_selection", branch::alt((priority, volume_split)))(input)
+    }
+}
+
+pub fn connector_choice(input: &str) -> ParseResult<&str, ast::RoutableConnectorChoice> {
+    let merchant_connector_id = sequence::delimited(
+        skip_ws(complete::tag("(")),
+        skip_ws(string_str),
+        skip_ws(complete::tag(")")),
+    );
+
+    error::context(
+        "connector_choice",
+        combinator::map_res(
+            sequence::pair(skip_ws(identifier), combinator::opt(merchant_connector_id)),
+            |(connector, mca_id): (String, Option<String>)| {
+                let connector = RoutableConnectors::from_str(&connector)
+                    .map_err(|_| EuclidError::InvalidConnector(connector.clone()))?;
+                let merchant_connector_id = mca_id
+                    .map(|mca_id| {
+                        MerchantConnectorAccountId::wrap(mca_id.clone())
+                            .map_err(|_| EuclidError::InvalidConnector(mca_id))
+                    })
+                    .transpose()?;
+
+                Ok::<_, EuclidError>(ast::RoutableConnectorChoice {
+                    choice_kind: ast::RoutableChoiceKind::FullStruct,
+                    connector,
+                    merchant_connector_id,
+                })
+            },
+        ),
+    )(input)
+}
+
 pub fn skip_ws<'a, F, O>(inner: F) -> impl FnMut(&'a str) -> ParseResult<&'a str, O>
 where
     F: FnMut(&'a str) -> ParseResult<&'a str, O> + 'a,
@@ -102,12 +187,14 @@ pub fn percentage(input: &str) -> ParseResult<&str, u8> {
         "volume_split_percentage",
         combinator::map_res(
             sequence::terminated(
-                complete::take_while_m_n(1, 2, |c: char| c.is_ascii_digit()),
+                complete::take_while_m_n(1, 3, |c: char| c.is_ascii_digit()),
                 complete::tag("%"),
             ),
             |o: &str| {
                 o.parse::<u8>()
-                    .map_err(|_| EuclidError::InvalidPercentage(o.to_string()))
+                    .ok()
+                    .filter(|percentage| *percentage <= 100)
+                    .ok_or_else(|| EuclidError::InvalidPercentage(o.to_string()))
             },
         ),
     )(input)
@@ -216,7 +303,7 @@ pub fn number_comparison(input: &str) -> ParseResult<&str, ast::NumberComparison
     error::context(
         "number_comparison",
         combinator::map(
-            sequence::pair(operator, num_i64),
+            sequence::pair(operator, skip_ws(num_i64)),
             |tup: (ast::ComparisonType, i64)| ast::NumberComparison {
                 comparison_type: tup.0,
                 number: MinorUnit::new(tup.1),
@@ -289,14 +376,19 @@ pub fn comparison_type(input: &str) -> ParseResult<&str, ast::ComparisonType> {
     )(input)
 }
 
+pub fn comparison_key(input: &str) -> ParseResult<&str, &str> {
+    error::context(
+        "comparison_key",
+        complete::take_while1(|c: char| c.is_ascii_alphabetic() || c == '.' || c == '_'),
+    )(input)
+}
+
 pub fn comparison(input: &str) -> ParseResult<&str, ast::Comparison> {
     error::context(
         "condition",
         combinator::map(
             sequence::tuple((
-                skip_ws(complete::take_while1(|c: char| {
-                    c.is_ascii_alphabetic() || c == '.' || c == '_'
-                })),
+                skip_ws(comparison_key),
                 skip_ws(comparison_type),
                 skip_ws(value_type),
             )),
@@ -332,16 +424,57 @@ pub fn arbitrary_comparison(input: &str) -> ParseResult<&str, ast::Comparison> {
     )(input)
 }
 
+/// Parses a JSON object, used to carry the metadata of comparisons and programs
+pub fn metadata_object(input: &str) -> ParseResult<&str, Metadata> {
+    let mut stream = serde_json::Deserializer::from_str(input).into_iter::<Metadata>();
+
+    match stream.next() {
+        Some(Ok(metadata)) => input
+            .get(stream.byte_offset()..)
+            .map(|rest| (rest, metadata))
+            .ok_or_else(|| {
+                nom::Err::Error(error::VerboseError::from_error_kind(
+                    input,
+                    error::ErrorKind::Eof,
+                ))
+            }),
+        _ => Err(nom::Err::Error(error::VerboseError::from_error_kind(
+            input,
+            error::ErrorKind::Verify,
+        ))),
+    }
+}
+
+pub fn comparison_with_metadata(input: &str) -> ParseResult<&str, ast::Comparison> {
+    let metadata = error::context(
+        "comparison_metadata",
+        sequence::preceded(skip_ws(complete::tag("@")), skip_ws(metadata_object)),
+    );
+
+    combinator::map(
+        sequence::pair(
+            skip_ws(branch::alt((comparison, arbitrary_comparison))),
+            combinator::opt(metadata),
+        ),
+        |(mut comparison, metadata): (ast::Comparison, Option<Metadata>)| {
+            if let Some(metadata) = metadata {
+                comparison.metadata = metadata;
+            }
+            comparison
+        },
+    )(input)
+}
+
 pub fn comparison_array(input: &str) -> ParseResult<&str, Vec<ast::Comparison>> {
     let many_with_ampersand = error::context(
         "many_with_amp",
-        multi::many0(sequence::preceded(skip_ws(complete::tag("&")), comparison)),
+        multi::many0(sequence::preceded(
+            skip_ws(complete::tag("&")),
+            comparison_with_metadata,
+        )),
     );
 
-    let full_sequence = sequence::pair(
-        skip_ws(branch::alt((comparison, arbitrary_comparison))),
-        many_with_ampersand,
-    );
+    let full_sequence = sequence::pair(comparison_with_metadata, many_with_ampersand);
 
     error::context(
         "comparison_array",
@@ -430,15 +563,29 @@ pub fn default_output<O: EuclidParsable + 'static>(input: &str) -> ParseResult<&
     )(input)
 }
 
+pub fn program_metadata(input: &str) -> ParseResult<&str, Metadata> {
+    error::context(
+        "program_metadata",
+        sequence::preceded(
+            skip_ws(complete::tag("@metadata")),
+            skip_ws(metadata_object),
+        ),
+    )(input)
+}
+
 pub fn program<O: EuclidParsable + 'static>(input: &str) -> ParseResult<&str, ast::Program<O>> {
     error::context(
         "program",
         combinator::map(
-            sequence::pair(default_output, multi::many1(skip_ws(rule::<O>))),
-            |tup: (O, Vec<ast::Rule<O>>)| ast::Program {
-                default_selection: tup.0,
-                rules: tup.1,
-                metadata: std::collections::HashMap::new(),
+            sequence::tuple((
+                combinator::opt(program_metadata),
+                default_output,
+                multi::many1(skip_ws(rule::<O>)),
+            )),
+            |tup: (Option<Metadata>, O, Vec<ast::Rule<O>>)| ast::Program {
+                default_selection: tup.1,
+                rules: tup.2,
+                metadata: tup.0.unwrap_or_default(),
             },
         ),
     )(input)
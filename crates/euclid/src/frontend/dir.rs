@@ -64,6 +64,7 @@ impl DirKey {
     PartialEq,
     Eq,
     serde::Serialize,
+    serde::Deserialize,
     strum::Display,
     strum::EnumIter,
     strum::VariantNames,
@@ -285,6 +286,27 @@ pub enum DirKeyKind {
         props(Category = "Payment Method Types")
     )]
     MobilePaymentType,
+    #[serde(rename = "time_of_day")]
+    #[strum(
+        serialize = "time_of_day",
+        detailed_message = "Time of the day in UTC, in the 24-hour HHMM format - eg. 1830 for 18:30",
+        props(Category = "Time")
+    )]
+    TimeOfDay,
+    #[serde(rename = "day_of_week")]
+    #[strum(
+        serialize = "day_of_week",
+        detailed_message = "Day of the week in UTC",
+        props(Category = "Time")
+    )]
+    DayOfWeek,
+    #[serde(rename = "date")]
+    #[strum(
+        serialize = "date",
+        detailed_message = "Date in UTC, in the YYYYMMDD format - eg. 20250131",
+        props(Category = "Time")
+    )]
+    Date,
 }
 
 pub trait EuclidDirFilter: Sized
@@ -335,6 +357,9 @@ impl DirKeyKind {
             Self::RealTimePaymentType => types::DataType::EnumVariant,
             Self::OpenBankingType => types::DataType::EnumVariant,
             Self::MobilePaymentType => types::DataType::EnumVariant,
+            Self::TimeOfDay => types::DataType::Number,
+            Self::DayOfWeek => types::DataType::EnumVariant,
+            Self::Date => types::DataType::Number,
         }
     }
     pub fn get_value_set(&self) -> Option<Vec<DirValue>> {
@@ -472,12 +497,23 @@ impl DirKeyKind {
                     .map(DirValue::MobilePaymentType)
                     .collect(),
             ),
+            Self::TimeOfDay => None,
+            Self::DayOfWeek => Some(enums::DayOfWeek::iter().map(DirValue::DayOfWeek).collect()),
+            Self::Date => None,
         }
     }
 }
 
 #[derive(
-    Debug, Clone, Hash, PartialEq, Eq, serde::Serialize, strum::Display, strum::VariantNames,
+    Debug,
+    Clone,
+    Hash,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum::Display,
+    strum::VariantNames,
 )]
 #[serde(tag = "key", content = "value")]
 pub enum DirValue {
@@ -543,6 +579,12 @@ pub enum DirValue {
     OpenBankingType(enums::OpenBankingType),
     #[serde(rename = "mobile_payment")]
     MobilePaymentType(enums::MobilePaymentType),
+    #[serde(rename = "time_of_day")]
+    TimeOfDay(types::NumValue),
+    #[serde(rename = "day_of_week")]
+    DayOfWeek(enums::DayOfWeek),
+    #[serde(rename = "date")]
+    Date(types::NumValue),
 }
 
 impl DirValue {
@@ -579,6 +621,9 @@ impl DirValue {
             Self::RealTimePaymentType(_) => (DirKeyKind::RealTimePaymentType, None),
             Self::OpenBankingType(_) => (DirKeyKind::OpenBankingType, None),
             Self::MobilePaymentType(_) => (DirKeyKind::MobilePaymentType, None),
+            Self::TimeOfDay(_) => (DirKeyKind::TimeOfDay, None),
+            Self::DayOfWeek(_) => (DirKeyKind::DayOfWeek, None),
+            Self::Date(_) => (DirKeyKind::Date, None),
         };
 
         DirKey::new(kind, data)
@@ -616,6 +661,9 @@ impl DirValue {
             Self::RealTimePaymentType(_) => None,
             Self::OpenBankingType(_) => None,
             Self::MobilePaymentType(_) => None,
+            Self::TimeOfDay(_) => None,
+            Self::DayOfWeek(_) => None,
+            Self::Date(_) => None,
         }
     }
 
@@ -628,7 +676,7 @@ impl DirValue {
 
     pub fn get_num_value(&self) -> Option<types::NumValue> {
         match self {
-            Self::PaymentAmount(val) => Some(val.clone()),
+            Self::PaymentAmount(val) | Self::TimeOfDay(val) | Self::Date(val) => Some(val.clone()),
             _ => None,
         }
     }
@@ -662,6 +710,7 @@ impl DirValue {
             (Self::UpiType(ut1), Self::UpiType(ut2)) => ut1 == ut2,
             (Self::VoucherType(vt1), Self::VoucherType(vt2)) => vt1 == vt2,
             (Self::CardRedirectType(crt1), Self::CardRedirectType(crt2)) => crt1 == crt2,
+            (Self::DayOfWeek(dow1), Self::DayOfWeek(dow2)) => dow1 == dow2,
             _ => false,
         }
     }
@@ -846,6 +895,9 @@ mod test {
             dirval!(CaptureMethod = Manual),
             dirval!(BillingCountry = UnitedStatesOfAmerica),
             dirval!(BusinessCountry = France),
+            dirval!(TimeOfDay = 1830),
+            dirval!(DayOfWeek = Saturday),
+            dirval!(Date = 20250131),
         ];
 
         for val in values {
@@ -902,4 +954,31 @@ mod test {
         let out = ast::lowering::lower_program::<DummyOutput>(program);
         assert!(out.is_err())
     }
+
+    #[cfg(feature = "ast_parser")]
+    #[test]
+    fn test_invalid_time_values() {
+        use crate::types::DummyOutput;
+
+        for condition in [
+            "time_of_day < 2400",
+            "time_of_day = 1260",
+            "date >= 20251301",
+        ] {
+            let program_str = format!(
+                r#"
+                default: ["stripe", "adyen"]
+
+                rule_1: ["stripe"]
+                {{
+                   {condition}
+                }}
+                "#
+            );
+            let (_, program) = ast::parser::program::<DummyOutput>(&program_str).expect("Program");
+
+            let out = ast::lowering::lower_program::<DummyOutput>(program);
+            assert!(out.is_err(), "{condition} should be rejected")
+        }
+    }
 }
@@ -262,6 +262,9 @@ fn lower_value(dir_value: dir::DirValue) -> Result<EuclidValue, AnalysisErrorTyp
         dir::DirValue::SetupFutureUsage(sfu) => EuclidValue::SetupFutureUsage(sfu),
         dir::DirValue::OpenBankingType(ob) => EuclidValue::PaymentMethodType(ob.into()),
         dir::DirValue::MobilePaymentType(mp) => EuclidValue::PaymentMethodType(mp.into()),
+        dir::DirValue::TimeOfDay(tod) => EuclidValue::TimeOfDay(tod),
+        dir::DirValue::DayOfWeek(dow) => EuclidValue::DayOfWeek(dow),
+        dir::DirValue::Date(date) => EuclidValue::Date(date),
     })
 }
 
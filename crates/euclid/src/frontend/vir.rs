@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::types::{EuclidValue, Metadata};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ValuedComparisonLogic {
     NegativeConjunction,
     PositiveDisjunction,
@@ -60,6 +60,12 @@ pub enum EuclidKey {
     BusinessLabel,
     #[strum(serialize = "setup_future_usage")]
     SetupFutureUsage,
+    #[strum(serialize = "time_of_day")]
+    TimeOfDay,
+    #[strum(serialize = "day_of_week")]
+    DayOfWeek,
+    #[strum(serialize = "date")]
+    Date,
 }
 impl EuclidDirFilter for DummyOutput {
     const ALLOWED: &'static [DirKeyKind] = &[
@@ -77,6 +83,9 @@ impl EuclidDirFilter for DummyOutput {
         DirKeyKind::MandateType,
         DirKeyKind::PaymentType,
         DirKeyKind::SetupFutureUsage,
+        DirKeyKind::TimeOfDay,
+        DirKeyKind::DayOfWeek,
+        DirKeyKind::Date,
     ];
 }
 impl EuclidAnalysable for DummyOutput {
@@ -104,7 +113,7 @@ impl EuclidAnalysable for DummyOutput {
             .collect()
     }
 }
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DummyOutput {
     pub outputs: Vec<String>,
 }
@@ -138,6 +147,9 @@ impl EuclidKey {
             Self::PaymentType => DataType::EnumVariant,
             Self::BusinessLabel => DataType::StrValue,
             Self::SetupFutureUsage => DataType::EnumVariant,
+            Self::TimeOfDay => DataType::Number,
+            Self::DayOfWeek => DataType::EnumVariant,
+            Self::Date => DataType::Number,
         }
     }
 }
@@ -253,12 +265,15 @@ pub enum EuclidValue {
     BillingCountry(enums::Country),
     BusinessLabel(StrValue),
     SetupFutureUsage(enums::SetupFutureUsage),
+    TimeOfDay(NumValue),
+    DayOfWeek(enums::DayOfWeek),
+    Date(NumValue),
 }
 
 impl EuclidValue {
     pub fn get_num_value(&self) -> Option<NumValue> {
         match self {
-            Self::PaymentAmount(val) => Some(val.clone()),
+            Self::PaymentAmount(val) | Self::TimeOfDay(val) | Self::Date(val) => Some(val.clone()),
             _ => None,
         }
     }
@@ -281,6 +296,9 @@ impl EuclidValue {
             Self::BillingCountry(_) => EuclidKey::BillingCountry,
             Self::BusinessLabel(_) => EuclidKey::BusinessLabel,
             Self::SetupFutureUsage(_) => EuclidKey::SetupFutureUsage,
+            Self::TimeOfDay(_) => EuclidKey::TimeOfDay,
+            Self::DayOfWeek(_) => EuclidKey::DayOfWeek,
+            Self::Date(_) => EuclidKey::Date,
         }
     }
 }
@@ -19,7 +19,7 @@ use currency_conversion::{
     conversion::convert as convert_currency, types as currency_conversion_types,
 };
 use euclid::{
-    backend::{inputs, interpreter::InterpreterBackend, EuclidBackend},
+    backend::{fixtures, inputs, interpreter::InterpreterBackend, EuclidBackend},
     dssa::{self, analyzer, graph::CgraphExt, state_machine},
     frontend::{
         ast,
@@ -197,8 +197,10 @@ pub fn get_valid_connectors_for_rule(rule: JsValue) -> JsResult {
 #[wasm_bindgen(js_name = analyzeProgram)]
 pub fn analyze_program(js_program: JsValue) -> JsResult {
     let program: ast::Program<ConnectorSelection> = serde_wasm_bindgen::from_value(js_program)?;
-    analyzer::analyze(program, SEED_DATA.get().map(|sd| &sd.cgraph)).err_to_js()?;
-    Ok(JsValue::NULL)
+    let (_, warnings) =
+        analyzer::analyze_with_warnings(program, SEED_DATA.get().map(|sd| &sd.cgraph))
+            .err_to_js()?;
+    Ok(serde_wasm_bindgen::to_value(&warnings)?)
 }
 
 #[wasm_bindgen(js_name = runProgram)]
@@ -214,6 +216,21 @@ pub fn run_program(program: JsValue, input: JsValue) -> JsResult {
     Ok(serde_wasm_bindgen::to_value(&res)?)
 }
 
+/// This function can be used by the frontend to run a suite of regression fixtures against a
+/// routing program before activating it. Every fixture is evaluated with both the interpreter and
+/// the VIR interpreter backends, and the report contains the rule and statement which fired.
+#[wasm_bindgen(js_name = runFixtureSuite)]
+pub fn run_fixture_suite(program: JsValue, fixtures: JsValue) -> JsResult {
+    let program: ast::Program<ConnectorSelection> = serde_wasm_bindgen::from_value(program)?;
+    let fixtures: Vec<fixtures::RoutingFixture<ConnectorSelection>> =
+        serde_wasm_bindgen::from_value(fixtures)?;
+
+    let runner = fixtures::FixtureRunner::new(program).err_to_js()?;
+    let report = runner.run_suite(&fixtures);
+
+    Ok(serde_wasm_bindgen::to_value(&report)?)
+}
+
 #[wasm_bindgen(js_name = getAllConnectors)]
 pub fn get_all_connectors() -> JsResult {
     Ok(serde_wasm_bindgen::to_value(RoutableConnectors::VARIANTS)?)
@@ -253,6 +270,25 @@ pub fn parser(val: String) -> String {
     ron_parser::my_parse(val)
 }
 
+/// This function can be used by the frontend to parse and type check a routing program written
+/// in the DSL. Every syntax and type error is reported with its line/column range and, where
+/// possible, suggestions, so that the rule editor can underline them.
+#[wasm_bindgen(js_name = checkProgram)]
+pub fn check_program(source: String) -> JsResult {
+    let report =
+        ast::diagnostics::check_program::<ast::ConnectorSelection, ConnectorSelection>(&source);
+    Ok(serde_wasm_bindgen::to_value(&report)?)
+}
+
+/// This function can be used by the frontend to get the canonical DSL text of a routing
+/// program, which can be stored and reviewed as text.
+#[wasm_bindgen(js_name = formatProgram)]
+pub fn format_program(js_program: JsValue) -> Result<String, JsValue> {
+    let program: ast::Program<ast::ConnectorSelection> =
+        serde_wasm_bindgen::from_value(js_program)?;
+    ast::formatter::format_program(&program).err_to_js()
+}
+
 #[wasm_bindgen(js_name = getVariantValues)]
 pub fn get_variant_values(key: &str) -> Result<JsValue, JsValue> {
     let key = dir::DirKeyKind::from_str(key).map_err(|_| "Invalid key received".to_string())?;
@@ -284,8 +320,11 @@ pub fn get_variant_values(key: &str) -> Result<JsValue, JsValue> {
         dir::DirKeyKind::RealTimePaymentType => dir_enums::RealTimePaymentType::VARIANTS,
         dir::DirKeyKind::OpenBankingType => dir_enums::OpenBankingType::VARIANTS,
         dir::DirKeyKind::MobilePaymentType => dir_enums::MobilePaymentType::VARIANTS,
+        dir::DirKeyKind::DayOfWeek => dir_enums::DayOfWeek::VARIANTS,
 
         dir::DirKeyKind::PaymentAmount
+        | dir::DirKeyKind::TimeOfDay
+        | dir::DirKeyKind::Date
         | dir::DirKeyKind::Connector
         | dir::DirKeyKind::CardBin
         | dir::DirKeyKind::BusinessLabel
@@ -0,0 +1,492 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    error::GraphError,
+    graph::ConstraintGraph,
+    snapshot::{NodeTypeSnapshot, SnapshotValueNode},
+    types::{NodeId, NodeType, NodeValue, Relation, Strength},
+};
+
+/// Structural difference between two [`ConstraintGraph`]s
+///
+/// Node ids are not stable across graph builds, so nodes are matched by a signature derived from
+/// their content. Value and `in` aggregator nodes are identified by their values, while `all` and
+/// `any` aggregators are identified by the edges leading into them, so their signature nests the
+/// signatures of their predecessors.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct GraphDiff<V: SnapshotValueNode> {
+    pub added_nodes: Vec<NodeDiff<V>>,
+    pub removed_nodes: Vec<NodeDiff<V>>,
+    pub added_edges: Vec<EdgeDiff>,
+    pub removed_edges: Vec<EdgeDiff>,
+    pub changed_edges: Vec<EdgeChange>,
+}
+
+impl<V: SnapshotValueNode> GraphDiff<V> {
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_edges.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct NodeDiff<V: SnapshotValueNode> {
+    pub signature: String,
+    pub node_type: NodeTypeSnapshot<V>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct EdgeDiff {
+    pub pred: String,
+    pub succ: String,
+    pub domain: Option<String>,
+    pub strength: Strength,
+    pub relation: Relation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct EdgeChange {
+    pub pred: String,
+    pub succ: String,
+    pub domain: Option<String>,
+    pub old_strength: Strength,
+    pub new_strength: Strength,
+    pub old_relation: Relation,
+    pub new_relation: Relation,
+}
+
+type EdgeKey = (String, String, Option<String>);
+
+struct SignedGraph<'a, V: SnapshotValueNode> {
+    graph: &'a ConstraintGraph<V>,
+    signatures: FxHashMap<NodeId, String>,
+}
+
+impl<'a, V: SnapshotValueNode> SignedGraph<'a, V> {
+    fn new(graph: &'a ConstraintGraph<V>) -> Result<Self, GraphError<V>> {
+        let mut signed = Self {
+            graph,
+            signatures: FxHashMap::default(),
+        };
+
+        for node_id in graph.nodes.keys() {
+            signed.sign(node_id)?;
+        }
+
+        Ok(signed)
+    }
+
+    /// Computes the signature of the node and of every node it depends on
+    ///
+    /// Aggregator nodes are expanded on an explicit stack rather than through recursion, so
+    /// that long chains of aggregators cannot overflow the call stack.
+    fn sign(&mut self, root: NodeId) -> Result<(), GraphError<V>> {
+        let graph = self.graph;
+        let mut in_progress = FxHashSet::default();
+        let mut stack = vec![(root, false)];
+
+        while let Some((node_id, expanded)) = stack.pop() {
+            if self.signatures.contains_key(&node_id) {
+                continue;
+            }
+
+            let node = graph.nodes.get(node_id).ok_or(GraphError::NodeNotFound)?;
+
+            let signature = match &node.node_type {
+                NodeType::Value(NodeValue::Value(value)) => format!("{value:?}"),
+                NodeType::Value(NodeValue::Key(key)) => format!("key {key:?}"),
+                NodeType::InAggregator(values) => {
+                    let mut values = values
+                        .iter()
+                        .map(|value| format!("{value:?}"))
+                        .collect::<Vec<_>>();
+                    values.sort();
+                    format!("in [{}]", values.join(", "))
+                }
+                NodeType::AllAggregator | NodeType::AnyAggregator if !expanded => {
+                    if !in_progress.insert(node_id) {
+                        return Err(GraphError::CycleDetected);
+                    }
+
+                    stack.push((node_id, true));
+                    for &edge_id in &node.preds {
+                        let edge = graph.edges.get(edge_id).ok_or(GraphError::EdgeNotFound)?;
+                        if in_progress.contains(&edge.pred) {
+                            return Err(GraphError::CycleDetected);
+                        }
+                        if !self.signatures.contains_key(&edge.pred) {
+                            stack.push((edge.pred, false));
+                        }
+                    }
+                    continue;
+                }
+                NodeType::AllAggregator | NodeType::AnyAggregator => {
+                    let mut preds = Vec::with_capacity(node.preds.len());
+                    for &edge_id in &node.preds {
+                        let edge = graph.edges.get(edge_id).ok_or(GraphError::EdgeNotFound)?;
+                        let pred = self
+                            .signatures
+                            .get(&edge.pred)
+                            .ok_or(GraphError::CycleDetected)?;
+                        preds.push(format!("{} {pred}", edge.relation));
+                    }
+                    preds.sort();
+
+                    in_progress.remove(&node_id);
+                    let aggregator = if matches!(node.node_type, NodeType::AllAggregator) {
+                        "all"
+                    } else {
+                        "any"
+                    };
+                    format!("{aggregator} [{}]", preds.join(", "))
+                }
+            };
+
+            self.signatures.insert(node_id, signature);
+        }
+
+        Ok(())
+    }
+
+    fn nodes(&self) -> FxHashMap<&str, NodeId> {
+        self.signatures
+            .iter()
+            .map(|(node_id, signature)| (signature.as_str(), *node_id))
+            .collect()
+    }
+
+    fn edges(&self) -> Result<FxHashMap<EdgeKey, (Strength, Relation)>, GraphError<V>> {
+        self.graph
+            .edges
+            .values()
+            .map(|edge| {
+                let pred = self
+                    .signatures
+                    .get(&edge.pred)
+                    .cloned()
+                    .ok_or(GraphError::NodeNotFound)?;
+                let succ = self
+                    .signatures
+                    .get(&edge.succ)
+                    .cloned()
+                    .ok_or(GraphError::NodeNotFound)?;
+                let domain = edge
+                    .domain
+                    .map(|domain_id| {
+                        self.graph
+                            .domain
+                            .get(domain_id)
+                            .map(|domain| domain.domain_identifier.into_inner())
+                            .ok_or(GraphError::DomainNotFound)
+                    })
+                    .transpose()?;
+
+                Ok(((pred, succ, domain), (edge.strength, edge.relation)))
+            })
+            .collect()
+    }
+
+    fn node_diff(&self, signature: &str, node_id: NodeId) -> Result<NodeDiff<V>, GraphError<V>> {
+        let node = self
+            .graph
+            .nodes
+            .get(node_id)
+            .ok_or(GraphError::NodeNotFound)?;
+
+        Ok(NodeDiff {
+            signature: signature.to_string(),
+            node_type: NodeTypeSnapshot::from_node_type(&node.node_type),
+        })
+    }
+}
+
+impl<V> ConstraintGraph<V>
+where
+    V: SnapshotValueNode,
+{
+    /// Computes the nodes and edges that were added, removed or changed going from `old` to `new`
+    pub fn diff(old: &Self, new: &Self) -> Result<GraphDiff<V>, GraphError<V>> {
+        let old = SignedGraph::new(old)?;
+        let new = SignedGraph::new(new)?;
+
+        let old_nodes = old.nodes();
+        let new_nodes = new.nodes();
+
+        let mut added_nodes = new_nodes
+            .iter()
+            .filter(|(signature, _)| !old_nodes.contains_key(*signature))
+            .map(|(signature, node_id)| new.node_diff(signature, *node_id))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut removed_nodes = old_nodes
+            .iter()
+            .filter(|(signature, _)| !new_nodes.contains_key(*signature))
+            .map(|(signature, node_id)| old.node_diff(signature, *node_id))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let old_edges = old.edges()?;
+        let new_edges = new.edges()?;
+
+        let mut added_edges = Vec::new();
+        let mut changed_edges = Vec::new();
+        for (key, &(new_strength, new_relation)) in &new_edges {
+            let (pred, succ, domain) = key;
+            match old_edges.get(key) {
+                None => added_edges.push(EdgeDiff {
+                    pred: pred.clone(),
+                    succ: succ.clone(),
+                    domain: domain.clone(),
+                    strength: new_strength,
+                    relation: new_relation,
+                }),
+                Some(&(old_strength, old_relation))
+                    if old_strength != new_strength || old_relation != new_relation =>
+                {
+                    changed_edges.push(EdgeChange {
+                        pred: pred.clone(),
+                        succ: succ.clone(),
+                        domain: domain.clone(),
+                        old_strength,
+                        new_strength,
+                        old_relation,
+                        new_relation,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut removed_edges = old_edges
+            .iter()
+            .filter(|(key, _)| !new_edges.contains_key(*key))
+            .map(|((pred, succ, domain), &(strength, relation))| EdgeDiff {
+                pred: pred.clone(),
+                succ: succ.clone(),
+                domain: domain.clone(),
+                strength,
+                relation,
+            })
+            .collect::<Vec<_>>();
+
+        added_nodes.sort_by(|a, b| a.signature.cmp(&b.signature));
+        removed_nodes.sort_by(|a, b| a.signature.cmp(&b.signature));
+        for edges in [&mut added_edges, &mut removed_edges] {
+            edges.sort_by(|a, b| (&a.pred, &a.succ, &a.domain).cmp(&(&b.pred, &b.succ, &b.domain)));
+        }
+        changed_edges
+            .sort_by(|a, b| (&a.pred, &a.succ, &a.domain).cmp(&(&b.pred, &b.succ, &b.domain)));
+
+        Ok(GraphDiff {
+            added_nodes,
+            removed_nodes,
+            added_edges,
+            removed_edges,
+            changed_edges,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+    use crate::{
+        builder::ConstraintGraphBuilder,
+        snapshot::tests::{currency, TestKey, TestValue},
+    };
+
+    fn build_graph(edges: &[(&str, Strength, Relation)]) -> ConstraintGraph<TestValue> {
+        let mut builder = ConstraintGraphBuilder::new();
+        let amount_key = builder.make_value_node(NodeValue::Key(TestKey::Amount), None, None::<()>);
+        for &(pred, strength, relation) in edges {
+            let pred = builder.make_value_node(currency(pred), None, None::<()>);
+            builder
+                .make_edge(pred, amount_key, strength, relation, None::<String>)
+                .expect("Failed edge creation");
+        }
+
+        builder.build()
+    }
+
+    #[test]
+    fn test_diff_of_identical_graphs_is_empty() {
+        let edges = [
+            ("USD", Strength::Strong, Relation::Positive),
+            ("EUR", Strength::Weak, Relation::Negative),
+        ];
+
+        let diff = ConstraintGraph::diff(&build_graph(&edges), &build_graph(&edges))
+            .expect("Failed graph diff");
+
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_added_removed_and_changed_edges() {
+        let old = build_graph(&[
+            ("USD", Strength::Strong, Relation::Positive),
+            ("EUR", Strength::Weak, Relation::Positive),
+        ]);
+        let new = build_graph(&[
+            ("USD", Strength::Strong, Relation::Negative),
+            ("GBP", Strength::Normal, Relation::Positive),
+        ]);
+
+        let diff = ConstraintGraph::diff(&old, &new).expect("Failed graph diff");
+
+        let key = format!("key {:?}", TestKey::Amount);
+        let usd = format!("{:?}", TestValue::Currency("USD".to_string()));
+        let eur = format!("{:?}", TestValue::Currency("EUR".to_string()));
+        let gbp = format!("{:?}", TestValue::Currency("GBP".to_string()));
+
+        assert_eq!(
+            diff.added_nodes
+                .iter()
+                .map(|node| node.signature.as_str())
+                .collect::<Vec<_>>(),
+            vec![gbp.as_str()]
+        );
+        assert_eq!(
+            diff.removed_nodes
+                .iter()
+                .map(|node| node.signature.as_str())
+                .collect::<Vec<_>>(),
+            vec![eur.as_str()]
+        );
+        assert_eq!(
+            diff.added_edges,
+            vec![EdgeDiff {
+                pred: gbp,
+                succ: key.clone(),
+                domain: None,
+                strength: Strength::Normal,
+                relation: Relation::Positive,
+            }]
+        );
+        assert_eq!(
+            diff.removed_edges,
+            vec![EdgeDiff {
+                pred: eur,
+                succ: key.clone(),
+                domain: None,
+                strength: Strength::Weak,
+                relation: Relation::Positive,
+            }]
+        );
+        assert_eq!(
+            diff.changed_edges,
+            vec![EdgeChange {
+                pred: usd,
+                succ: key,
+                domain: None,
+                old_strength: Strength::Strong,
+                new_strength: Strength::Strong,
+                old_relation: Relation::Positive,
+                new_relation: Relation::Negative,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diff_matches_aggregators_by_their_predecessors() {
+        let build = |relation| {
+            let mut builder = ConstraintGraphBuilder::new();
+            let usd = builder.make_value_node(currency("USD"), None, None::<()>);
+            let eur = builder.make_value_node(currency("EUR"), None, None::<()>);
+            builder
+                .make_any_aggregator(
+                    &[
+                        (usd, Relation::Positive, Strength::Strong),
+                        (eur, relation, Strength::Strong),
+                    ],
+                    None,
+                    None::<()>,
+                    None,
+                )
+                .expect("Failed any aggregator creation");
+            builder.build()
+        };
+
+        let diff = ConstraintGraph::diff(&build(Relation::Positive), &build(Relation::Negative))
+            .expect("Failed graph diff");
+
+        assert_eq!(diff.added_nodes.len(), 1);
+        assert_eq!(diff.removed_nodes.len(), 1);
+        assert_eq!(diff.added_edges.len(), 2);
+        assert_eq!(diff.removed_edges.len(), 2);
+        assert!(diff.changed_edges.is_empty());
+    }
+
+    #[test]
+    fn test_diff_of_long_aggregator_chain_does_not_recurse() {
+        let build = || {
+            let mut builder = ConstraintGraphBuilder::new();
+            let mut pred = builder.make_value_node(currency("USD"), None, None::<()>);
+            for _ in 0..1_000 {
+                pred = builder
+                    .make_any_aggregator(
+                        &[(pred, Relation::Positive, Strength::Strong)],
+                        None,
+                        None::<()>,
+                        None,
+                    )
+                    .expect("Failed any aggregator creation");
+            }
+            builder.build()
+        };
+
+        // A small stack which a recursive walk of the chain would overflow
+        let is_empty = std::thread::Builder::new()
+            .stack_size(128 * 1024)
+            .spawn(move || {
+                ConstraintGraph::diff(&build(), &build())
+                    .map(|diff| diff.is_empty())
+                    .unwrap_or(false)
+            })
+            .expect("Failed to spawn thread")
+            .join()
+            .expect("Graph diff thread panicked");
+
+        assert!(is_empty);
+    }
+
+    #[test]
+    fn test_diff_of_cyclic_graph_is_rejected() {
+        let mut builder = ConstraintGraphBuilder::new();
+        let usd = builder.make_value_node(currency("USD"), None, None::<()>);
+        let first = builder
+            .make_any_aggregator(
+                &[(usd, Relation::Positive, Strength::Strong)],
+                None,
+                None::<()>,
+                None,
+            )
+            .expect("Failed any aggregator creation");
+        let second = builder
+            .make_all_aggregator(
+                &[(first, Relation::Positive, Strength::Strong)],
+                None,
+                None::<()>,
+                None,
+            )
+            .expect("Failed all aggregator creation");
+        builder
+            .make_edge(
+                second,
+                first,
+                Strength::Strong,
+                Relation::Positive,
+                None::<String>,
+            )
+            .expect("Failed edge creation");
+        let graph = builder.build();
+
+        assert!(matches!(
+            ConstraintGraph::diff(&graph, &graph),
+            Err(GraphError::CycleDetected)
+        ));
+    }
+}
@@ -1,12 +1,19 @@
 pub mod builder;
 mod dense_map;
+pub mod diff;
 pub mod error;
 pub mod graph;
+pub mod snapshot;
 pub mod types;
 
 pub use builder::ConstraintGraphBuilder;
+pub use diff::{EdgeChange, EdgeDiff, GraphDiff, NodeDiff};
 pub use error::{AnalysisTrace, GraphError};
 pub use graph::ConstraintGraph;
+pub use snapshot::{
+    DomainSnapshot, EdgeSnapshot, GraphSnapshot, NodeSnapshot, NodeTypeSnapshot, NodeValueSnapshot,
+    SnapshotValueNode,
+};
 #[cfg(feature = "viz")]
 pub use types::NodeViz;
 pub use types::{
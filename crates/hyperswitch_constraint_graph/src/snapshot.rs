@@ -0,0 +1,424 @@
+use std::fmt;
+
+use crate::{
+    builder::ConstraintGraphBuilder,
+    dense_map::{DenseMap, EntityId},
+    error::GraphError,
+    graph::ConstraintGraph,
+    types::{NodeId, NodeType, NodeValue, Relation, Strength, ValueNode},
+};
+
+/// A [`ValueNode`] whose values and keys can be stored in a [`GraphSnapshot`]
+///
+/// Snapshots store values and keys in their own serde types rather than in the serde
+/// representation of the values, which is not required to accept every value it produces.
+pub trait SnapshotValueNode: ValueNode {
+    type ValueSnapshot: fmt::Debug
+        + Clone
+        + PartialEq
+        + Eq
+        + serde::Serialize
+        + serde::de::DeserializeOwned;
+    type KeySnapshot: fmt::Debug
+        + Clone
+        + PartialEq
+        + Eq
+        + serde::Serialize
+        + serde::de::DeserializeOwned;
+
+    fn to_value_snapshot(&self) -> Self::ValueSnapshot;
+
+    fn from_value_snapshot(snapshot: Self::ValueSnapshot) -> Self;
+
+    fn to_key_snapshot(key: &Self::Key) -> Self::KeySnapshot;
+
+    fn from_key_snapshot(snapshot: Self::KeySnapshot) -> Self::Key;
+}
+
+/// A serializable representation of a [`ConstraintGraph`]
+///
+/// Nodes and edges refer to each other by their position in the snapshot, so a snapshot can be
+/// stored and imported back to rebuild the graph. Node metadata is not part of the snapshot, and
+/// node info is exported for reference but not restored on import.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(bound = "")]
+pub struct GraphSnapshot<V: SnapshotValueNode> {
+    pub domains: Vec<DomainSnapshot>,
+    pub nodes: Vec<NodeSnapshot<V>>,
+    pub edges: Vec<EdgeSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DomainSnapshot {
+    pub identifier: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(bound = "")]
+pub struct NodeSnapshot<V: SnapshotValueNode> {
+    pub node_type: NodeTypeSnapshot<V>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+#[serde(bound = "")]
+pub enum NodeTypeSnapshot<V: SnapshotValueNode> {
+    AllAggregator,
+    AnyAggregator,
+    /// The values of the aggregator, sorted so that snapshots of the same graph are identical
+    InAggregator(Vec<V::ValueSnapshot>),
+    Value(NodeValueSnapshot<V>),
+}
+
+/// The snapshot of a [`NodeValue`], serialized in the same shape
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+#[serde(bound = "")]
+pub enum NodeValueSnapshot<V: SnapshotValueNode> {
+    Key(V::KeySnapshot),
+    Value(V::ValueSnapshot),
+}
+
+impl<V: SnapshotValueNode> NodeTypeSnapshot<V> {
+    pub(crate) fn from_node_type(node_type: &NodeType<V>) -> Self {
+        match node_type {
+            NodeType::AllAggregator => Self::AllAggregator,
+            NodeType::AnyAggregator => Self::AnyAggregator,
+            NodeType::InAggregator(values) => {
+                let mut values = values.iter().collect::<Vec<_>>();
+                values.sort_by_cached_key(|value| format!("{value:?}"));
+                Self::InAggregator(values.into_iter().map(V::to_value_snapshot).collect())
+            }
+            NodeType::Value(NodeValue::Key(key)) => {
+                Self::Value(NodeValueSnapshot::Key(V::to_key_snapshot(key)))
+            }
+            NodeType::Value(NodeValue::Value(value)) => {
+                Self::Value(NodeValueSnapshot::Value(value.to_value_snapshot()))
+            }
+        }
+    }
+}
+
+impl<V: SnapshotValueNode> From<NodeValueSnapshot<V>> for NodeValue<V> {
+    fn from(snapshot: NodeValueSnapshot<V>) -> Self {
+        match snapshot {
+            NodeValueSnapshot::Key(key) => Self::Key(V::from_key_snapshot(key)),
+            NodeValueSnapshot::Value(value) => Self::Value(V::from_value_snapshot(value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct EdgeSnapshot {
+    /// Position of the predecessor node in the snapshot
+    pub pred: usize,
+    /// Position of the successor node in the snapshot
+    pub succ: usize,
+    pub strength: Strength,
+    pub relation: Relation,
+    /// Identifier of the domain of the edge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+}
+
+impl<V> ConstraintGraph<V>
+where
+    V: SnapshotValueNode,
+{
+    pub fn to_snapshot(&self) -> Result<GraphSnapshot<V>, GraphError<V>> {
+        let domains = self
+            .domain
+            .values()
+            .map(|domain| DomainSnapshot {
+                identifier: domain.domain_identifier.into_inner(),
+                description: domain.domain_description.clone(),
+            })
+            .collect();
+
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|(node_id, node)| NodeSnapshot {
+                node_type: NodeTypeSnapshot::from_node_type(&node.node_type),
+                info: self
+                    .node_info
+                    .get(node_id)
+                    .copied()
+                    .flatten()
+                    .map(str::to_string),
+            })
+            .collect();
+
+        let edges = self
+            .edges
+            .values()
+            .map(|edge| {
+                let domain = edge
+                    .domain
+                    .map(|domain_id| {
+                        self.domain
+                            .get(domain_id)
+                            .map(|domain| domain.domain_identifier.into_inner())
+                            .ok_or(GraphError::DomainNotFound)
+                    })
+                    .transpose()?;
+
+                Ok(EdgeSnapshot {
+                    pred: edge.pred.get_id(),
+                    succ: edge.succ.get_id(),
+                    strength: edge.strength,
+                    relation: edge.relation,
+                    domain,
+                })
+            })
+            .collect::<Result<_, GraphError<V>>>()?;
+
+        Ok(GraphSnapshot {
+            domains,
+            nodes,
+            edges,
+        })
+    }
+
+    pub fn from_snapshot(snapshot: GraphSnapshot<V>) -> Result<Self, GraphError<V>> {
+        let mut builder = ConstraintGraphBuilder::new();
+        let mut snapshot_to_node_id = DenseMap::<NodeId, NodeId>::new();
+
+        for domain in snapshot.domains {
+            builder.make_domain(domain.identifier, &domain.description)?;
+        }
+
+        for node in snapshot.nodes {
+            let node_id = match node.node_type {
+                NodeTypeSnapshot::Value(value) => {
+                    builder.make_value_node(value.into(), None, None::<()>)
+                }
+                NodeTypeSnapshot::AllAggregator => {
+                    builder.make_all_aggregator(&[], None, None::<()>, None)?
+                }
+                NodeTypeSnapshot::AnyAggregator => {
+                    builder.make_any_aggregator(&[], None, None::<()>, None)?
+                }
+                NodeTypeSnapshot::InAggregator(values) => builder.make_in_aggregator(
+                    values.into_iter().map(V::from_value_snapshot).collect(),
+                    None,
+                    None::<()>,
+                )?,
+            };
+            snapshot_to_node_id.push(node_id);
+        }
+
+        for edge in snapshot.edges {
+            let pred = snapshot_to_node_id
+                .get(NodeId::with_id(edge.pred))
+                .copied()
+                .ok_or(GraphError::NodeNotFound)?;
+            let succ = snapshot_to_node_id
+                .get(NodeId::with_id(edge.succ))
+                .copied()
+                .ok_or(GraphError::NodeNotFound)?;
+
+            builder.make_edge(pred, succ, edge.strength, edge.relation, edge.domain)?;
+        }
+
+        Ok(builder.build())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    #![allow(clippy::expect_used)]
+
+    use super::*;
+    use crate::types::KeyNode;
+
+    #[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    pub(crate) enum TestKey {
+        Currency,
+        Amount,
+    }
+
+    impl KeyNode for TestKey {}
+
+    #[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    pub(crate) enum TestValue {
+        Currency(String),
+        Amount(i64),
+    }
+
+    impl ValueNode for TestValue {
+        type Key = TestKey;
+
+        fn get_key(&self) -> Self::Key {
+            match self {
+                Self::Currency(_) => TestKey::Currency,
+                Self::Amount(_) => TestKey::Amount,
+            }
+        }
+    }
+
+    impl SnapshotValueNode for TestValue {
+        type ValueSnapshot = Self;
+        type KeySnapshot = TestKey;
+
+        fn to_value_snapshot(&self) -> Self::ValueSnapshot {
+            self.clone()
+        }
+
+        fn from_value_snapshot(snapshot: Self::ValueSnapshot) -> Self {
+            snapshot
+        }
+
+        fn to_key_snapshot(key: &Self::Key) -> Self::KeySnapshot {
+            key.clone()
+        }
+
+        fn from_key_snapshot(snapshot: Self::KeySnapshot) -> Self::Key {
+            snapshot
+        }
+    }
+
+    pub(crate) fn currency(currency: &str) -> NodeValue<TestValue> {
+        NodeValue::Value(TestValue::Currency(currency.to_string()))
+    }
+
+    fn build_test_graph() -> ConstraintGraph<TestValue> {
+        let mut builder = ConstraintGraphBuilder::new();
+        builder
+            .make_domain("payments".to_string(), "Payment constraints")
+            .expect("Failed domain creation");
+
+        let usd = builder.make_value_node(currency("USD"), None, None::<()>);
+        let amount =
+            builder.make_value_node(NodeValue::Value(TestValue::Amount(100)), None, None::<()>);
+        let amount_key = builder.make_value_node(NodeValue::Key(TestKey::Amount), None, None::<()>);
+        let currencies = builder
+            .make_in_aggregator(
+                vec![
+                    TestValue::Currency("EUR".to_string()),
+                    TestValue::Currency("USD".to_string()),
+                ],
+                None,
+                None::<()>,
+            )
+            .expect("Failed in aggregator creation");
+        let all = builder
+            .make_all_aggregator(
+                &[
+                    (usd, Relation::Positive, Strength::Strong),
+                    (currencies, Relation::Negative, Strength::Weak),
+                ],
+                None,
+                None::<()>,
+                None,
+            )
+            .expect("Failed all aggregator creation");
+        builder
+            .make_edge(
+                all,
+                amount_key,
+                Strength::Normal,
+                Relation::Positive,
+                Some("payments".to_string()),
+            )
+            .expect("Failed edge creation");
+        builder
+            .make_edge(
+                amount,
+                amount_key,
+                Strength::Strong,
+                Relation::Positive,
+                None::<String>,
+            )
+            .expect("Failed edge creation");
+
+        builder.build()
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let graph = build_test_graph();
+
+        let snapshot = graph.to_snapshot().expect("Failed graph export");
+        assert_eq!(snapshot.domains.len(), 1);
+        assert_eq!(snapshot.nodes.len(), 5);
+        assert_eq!(snapshot.edges.len(), 4);
+        assert!(snapshot
+            .edges
+            .iter()
+            .any(|edge| edge.domain.as_deref() == Some("payments")));
+
+        let imported =
+            ConstraintGraph::from_snapshot(snapshot.clone()).expect("Failed graph import");
+        assert_eq!(
+            imported.to_snapshot().expect("Failed graph export"),
+            snapshot
+        );
+        assert!(ConstraintGraph::diff(&graph, &imported)
+            .expect("Failed graph diff")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_in_aggregator_values_are_sorted() {
+        let mut builder = ConstraintGraphBuilder::<TestValue>::new();
+        builder
+            .make_in_aggregator(
+                vec![
+                    TestValue::Currency("USD".to_string()),
+                    TestValue::Currency("EUR".to_string()),
+                    TestValue::Currency("GBP".to_string()),
+                ],
+                None,
+                None::<()>,
+            )
+            .expect("Failed in aggregator creation");
+
+        let snapshot = builder.build().to_snapshot().expect("Failed graph export");
+        let values = snapshot
+            .nodes
+            .into_iter()
+            .map(|node| node.node_type)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            values,
+            vec![NodeTypeSnapshot::InAggregator(vec![
+                TestValue::Currency("EUR".to_string()),
+                TestValue::Currency("GBP".to_string()),
+                TestValue::Currency("USD".to_string()),
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_with_unknown_node_is_rejected() {
+        let mut snapshot = build_test_graph()
+            .to_snapshot()
+            .expect("Failed graph export");
+        if let Some(edge) = snapshot.edges.first_mut() {
+            edge.pred = snapshot.nodes.len();
+        }
+
+        assert!(matches!(
+            ConstraintGraph::from_snapshot(snapshot),
+            Err(GraphError::NodeNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_snapshot_with_unknown_domain_is_rejected() {
+        let mut snapshot = build_test_graph()
+            .to_snapshot()
+            .expect("Failed graph export");
+        snapshot.domains.clear();
+
+        assert!(matches!(
+            ConstraintGraph::from_snapshot(snapshot),
+            Err(GraphError::DomainNotFound)
+        ));
+    }
+}
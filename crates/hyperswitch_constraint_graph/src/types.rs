@@ -72,7 +72,17 @@ pub struct EdgeId(usize);
 impl_entity!(EdgeId);
 
 #[derive(
-    Debug, Clone, Copy, serde::Serialize, PartialEq, Eq, Hash, strum::Display, PartialOrd, Ord,
+    Debug,
+    Clone,
+    Copy,
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    strum::Display,
+    PartialOrd,
+    Ord,
 )]
 pub enum Strength {
     Weak,
@@ -86,7 +96,9 @@ impl Strength {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum Relation {
     Positive,
@@ -1,3 +1,5 @@
+use common_enums::CardTestingGuardDimension;
+use diesel_models::business_profile::CardTestingVelocityRule;
 use serde::{self, Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -9,4 +11,31 @@ pub struct CardTestingGuardData {
     pub is_customer_id_blocking_enabled: bool,
     pub customer_id_blocking_cache_key: String,
     pub card_testing_guard_expiry: i32,
+    #[serde(default)]
+    pub velocity_checks: Vec<CardTestingVelocityCheck>,
+}
+
+/// A velocity rule of the profile along with the value of the payment which it tracks
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CardTestingVelocityCheck {
+    pub profile_id: common_utils::id_type::ProfileId,
+    pub rule: CardTestingVelocityRule,
+    pub value: CardTestingGuardValue,
+}
+
+/// The value of a payment for a card testing guard dimension
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CardTestingGuardValue {
+    pub dimension: CardTestingGuardDimension,
+    /// Hash of the value, which is used in the redis keys
+    pub fingerprint: String,
+    /// The value as shown in the blocks of the profile
+    pub display_value: String,
+}
+
+impl CardTestingGuardValue {
+    /// Identifier of the block applied to the value
+    pub fn get_block_id(&self) -> String {
+        format!("{}_{}", self.dimension, self.fingerprint)
+    }
 }
@@ -283,6 +283,10 @@ pub enum ApiErrorResponse {
     PlatformAccountAuthNotSupported,
     #[error(error_type = ErrorType::InvalidRequestError, code = "IR_44", message = "Invalid platform account operation")]
     InvalidPlatformOperation,
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_45", message = "Keys for idempotent requests can only be used with the same parameters they were first used with")]
+    IdempotencyKeyReused,
+    #[error(error_type = ErrorType::DuplicateRequest, code = "IR_46", message = "Another request using this idempotency key is in progress")]
+    IdempotentRequestInProgress,
     #[error(error_type = ErrorType::InvalidRequestError, code = "WE_01", message = "Failed to authenticate the webhook")]
     WebhookAuthenticationFailed,
     #[error(error_type = ErrorType::InvalidRequestError, code = "WE_02", message = "Bad request received in webhook")]
@@ -676,6 +680,12 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
             Self::InvalidPlatformOperation => {
                 AER::Unauthorized(ApiError::new("IR", 44, "Invalid platform account operation", None))
             }
+            Self::IdempotencyKeyReused => {
+                AER::Unprocessable(ApiError::new("IR", 45, "Keys for idempotent requests can only be used with the same parameters they were first used with", None))
+            }
+            Self::IdempotentRequestInProgress => {
+                AER::Conflict(ApiError::new("IR", 46, "Another request using this idempotency key is in progress, please try again later", None))
+            }
         }
     }
 }
@@ -19,6 +19,12 @@ pub struct PaymentRoutingInfo {
     pub algorithm: Option<routing::StraightThroughAlgorithm>,
     pub pre_routing_results:
         Option<HashMap<api_enums::PaymentMethodType, PreRoutingConnectorChoice>>,
+    /// Which rule of the active routing algorithm was chosen for this attempt, recorded for
+    /// advanced (rule based) algorithms only
+    pub evaluation_summary: Option<routing::EvaluationSummary>,
+    /// How the active routing algorithm was evaluated for this attempt, recorded only when the
+    /// evaluation trace is enabled for the merchant
+    pub evaluation_trace: Option<routing::EvaluationTrace>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
@@ -33,6 +39,10 @@ pub struct PaymentRoutingInfoInner {
     pub algorithm: Option<routing::StraightThroughAlgorithm>,
     pub pre_routing_results:
         Option<HashMap<api_enums::PaymentMethodType, PreRoutingConnectorChoice>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evaluation_summary: Option<routing::EvaluationSummary>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evaluation_trace: Option<routing::EvaluationTrace>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -48,10 +58,14 @@ impl From<PaymentRoutingInfoSerde> for PaymentRoutingInfo {
             PaymentRoutingInfoSerde::OnlyAlgorithm(algo) => Self {
                 algorithm: Some(*algo),
                 pre_routing_results: None,
+                evaluation_summary: None,
+                evaluation_trace: None,
             },
             PaymentRoutingInfoSerde::WithDetails(details) => Self {
                 algorithm: details.algorithm,
                 pre_routing_results: details.pre_routing_results,
+                evaluation_summary: details.evaluation_summary,
+                evaluation_trace: details.evaluation_trace,
             },
         }
     }
@@ -62,6 +76,8 @@ impl From<PaymentRoutingInfo> for PaymentRoutingInfoSerde {
         Self::WithDetails(Box::new(PaymentRoutingInfoInner {
             algorithm: value.algorithm,
             pre_routing_results: value.pre_routing_results,
+            evaluation_summary: value.evaluation_summary,
+            evaluation_trace: value.evaluation_trace,
         }))
     }
 }
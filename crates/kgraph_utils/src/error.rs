@@ -17,4 +17,6 @@ pub enum KgraphError {
     ContextConstructionError(AnalysisErrorType),
     #[error("there was an unprecedented indexing error")]
     IndexingError,
+    #[error("Error while serializing or deserializing the graph: {0}")]
+    GraphSerializationError(String),
 }
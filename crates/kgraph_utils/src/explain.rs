@@ -0,0 +1,196 @@
+use std::sync::Weak;
+
+use euclid::{dssa::graph::AnalysisContext, frontend::dir};
+use hyperswitch_constraint_graph::{self as cgraph, error::ValueTracePredecessor, CheckingContext};
+
+/// A constraint of the graph that the checked context did not satisfy
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct UnsatisfiedConstraint {
+    pub key: dir::DirKeyKind,
+    /// Whether the context had to contain one of `values` (positive) or none of them (negative)
+    pub relation: cgraph::Relation,
+    pub values: Vec<dir::DirValue>,
+    /// Values present in the context for the key
+    pub found: Vec<dir::DirValue>,
+}
+
+impl UnsatisfiedConstraint {
+    fn describe(&self) -> String {
+        let key = self.key.to_string().replace('_', " ");
+
+        if self.found.is_empty() {
+            format!("payments without a {key}")
+        } else {
+            format!("{key} {}", join_values(&self.found))
+        }
+    }
+}
+
+fn join_values(values: &[dir::DirValue]) -> String {
+    values
+        .iter()
+        .map(cgraph::NodeViz::viz)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn sorted_values(mut values: Vec<dir::DirValue>) -> Vec<dir::DirValue> {
+    values.sort_by_cached_key(cgraph::NodeViz::viz);
+    values
+}
+
+/// Walks the analysis trace of a failed graph check and collects the constraints that were
+/// not satisfied by the context
+pub fn collect_unsatisfied_constraints(
+    error: &cgraph::GraphError<dir::DirValue>,
+    ctx: &AnalysisContext,
+) -> Vec<UnsatisfiedConstraint> {
+    let mut constraints = Vec::new();
+
+    if let cgraph::GraphError::AnalysisError(trace) = error {
+        collect_from_trace(trace, ctx, &mut constraints);
+    }
+
+    constraints
+}
+
+fn collect_from_trace(
+    trace: &Weak<cgraph::AnalysisTrace<dir::DirValue>>,
+    ctx: &AnalysisContext,
+    constraints: &mut Vec<UnsatisfiedConstraint>,
+) {
+    // The trace is owned by the memoization of the check, nothing can be explained once it's gone
+    let Some(trace) = trace.upgrade() else {
+        return;
+    };
+
+    let constraint = match trace.as_ref() {
+        cgraph::AnalysisTrace::Value {
+            predecessors: Some(ValueTracePredecessor::Mandatory(pred)),
+            ..
+        } => {
+            collect_from_trace(pred, ctx, constraints);
+            return;
+        }
+
+        cgraph::AnalysisTrace::Value {
+            predecessors: Some(ValueTracePredecessor::OneOf(preds)),
+            ..
+        }
+        | cgraph::AnalysisTrace::AllAggregation {
+            unsatisfied: preds, ..
+        }
+        | cgraph::AnalysisTrace::AnyAggregation {
+            unsatisfied: preds, ..
+        } => {
+            for pred in preds {
+                collect_from_trace(pred, ctx, constraints);
+            }
+            return;
+        }
+
+        cgraph::AnalysisTrace::Value {
+            value,
+            relation,
+            predecessors: None,
+            ..
+        } => {
+            let (key, values) = match value {
+                cgraph::NodeValue::Key(key) => (key.clone(), Vec::new()),
+                cgraph::NodeValue::Value(value) => (value.get_key(), vec![value.clone()]),
+            };
+
+            UnsatisfiedConstraint {
+                found: sorted_values(ctx.get_values_by_key(&key).unwrap_or_default()),
+                key: key.kind,
+                relation: *relation,
+                values,
+            }
+        }
+
+        cgraph::AnalysisTrace::InAggregation {
+            expected,
+            found,
+            relation,
+            ..
+        } => {
+            let Some(key) = expected.first().map(dir::DirValue::get_key) else {
+                return;
+            };
+
+            UnsatisfiedConstraint {
+                key: key.kind,
+                relation: *relation,
+                values: sorted_values(expected.clone()),
+                found: found.iter().cloned().collect(),
+            }
+        }
+
+        cgraph::AnalysisTrace::Contradiction { .. } => return,
+    };
+
+    if !constraints.contains(&constraint) {
+        constraints.push(constraint);
+    }
+}
+
+/// Builds a human-readable explanation of why `value` is not available for `connector`, e.g.
+/// "payment method credit is unavailable because connector stripe does not support currency EUR
+/// in billing country DE"
+pub fn explain_ineligibility(
+    value: &dir::DirValue,
+    connector: &str,
+    constraints: &[UnsatisfiedConstraint],
+) -> String {
+    let describe = |constraints: Vec<&UnsatisfiedConstraint>| {
+        let mut descriptions = Vec::<String>::new();
+        for description in constraints.into_iter().map(UnsatisfiedConstraint::describe) {
+            if !descriptions.contains(&description) {
+                descriptions.push(description);
+            }
+        }
+        descriptions.join(" and ")
+    };
+
+    let (countries, others): (Vec<_>, Vec<_>) = constraints.iter().partition(|constraint| {
+        matches!(
+            constraint.key,
+            dir::DirKeyKind::BillingCountry | dir::DirKeyKind::BusinessCountry
+        )
+    });
+    let countries = describe(countries);
+    let others = describe(others);
+
+    let payment_method = cgraph::NodeViz::viz(value);
+    let reason = match (others.is_empty(), countries.is_empty()) {
+        (true, true) => {
+            return format!(
+                "payment method {payment_method} is unavailable for connector {connector}"
+            )
+        }
+        (false, true) => others,
+        (true, false) => countries,
+        (false, false) => format!("{others} in {countries}"),
+    };
+
+    format!(
+        "payment method {payment_method} is unavailable because connector {connector} does not support {reason}"
+    )
+}
+
+/// Explains a failed [`CgraphExt::key_value_analysis`](euclid::dssa::graph::CgraphExt) of `value`
+///
+/// The memoization used for the analysis must still be alive, since the error only holds weak
+/// references into it.
+pub fn explain_analysis_error(
+    value: &dir::DirValue,
+    connector: &str,
+    error: &cgraph::GraphError<dir::DirValue>,
+    ctx: &AnalysisContext,
+) -> String {
+    explain_ineligibility(
+        value,
+        connector,
+        &collect_unsatisfied_constraints(error, ctx),
+    )
+}
@@ -1,4 +1,5 @@
 pub mod error;
+pub mod explain;
 pub mod mca;
 pub mod transformers;
 pub mod types;
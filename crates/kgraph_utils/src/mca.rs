@@ -1039,6 +1039,41 @@ pub fn make_mca_graph(
     Ok(builder.build())
 }
 
+/// Exports the graph as JSON, in a form that can be imported back with [`import_graph_json`]
+pub fn export_graph_json(
+    graph: &cgraph::ConstraintGraph<dir::DirValue>,
+) -> Result<serde_json::Value, KgraphError> {
+    let snapshot = graph
+        .to_snapshot()
+        .map_err(KgraphError::GraphConstructionError)?;
+
+    serde_json::to_value(snapshot)
+        .map_err(|err| KgraphError::GraphSerializationError(err.to_string()))
+}
+
+pub fn import_graph_json(
+    value: serde_json::Value,
+) -> Result<cgraph::ConstraintGraph<dir::DirValue>, KgraphError> {
+    let snapshot = serde_json::from_value::<cgraph::GraphSnapshot<dir::DirValue>>(value)
+        .map_err(|err| KgraphError::GraphSerializationError(err.to_string()))?;
+
+    cgraph::ConstraintGraph::from_snapshot(snapshot).map_err(KgraphError::GraphConstructionError)
+}
+
+/// Builds the graphs for two versions of a merchant's connector accounts and returns the
+/// structural difference between them
+pub fn make_mca_graph_diff(
+    old_accts: Vec<admin_api::MerchantConnectorResponse>,
+    new_accts: Vec<admin_api::MerchantConnectorResponse>,
+    config: &kgraph_types::CountryCurrencyFilter,
+) -> Result<cgraph::GraphDiff<dir::DirValue>, KgraphError> {
+    let old_graph = make_mca_graph(old_accts, config)?;
+    let new_graph = make_mca_graph(new_accts, config)?;
+
+    cgraph::ConstraintGraph::diff(&old_graph, &new_graph)
+        .map_err(KgraphError::GraphConstructionError)
+}
+
 #[cfg(feature = "v1")]
 #[cfg(test)]
 mod tests {
@@ -1056,7 +1091,10 @@ mod tests {
     use super::*;
     use crate::types as kgraph_types;
 
-    fn build_test_data() -> ConstraintGraph<dir::DirValue> {
+    fn build_test_inputs() -> (
+        Vec<admin_api::MerchantConnectorResponse>,
+        kgraph_types::CountryCurrencyFilter,
+    ) {
         use api_models::{admin::*, payment_methods::*};
         let profile_id = common_utils::generate_profile_id_of_default_length();
 
@@ -1215,7 +1253,12 @@ mod tests {
             default_configs: None,
         };
 
-        make_mca_graph(vec![stripe_account], &config_map).expect("Failed graph construction")
+        (vec![stripe_account], config_map)
+    }
+
+    fn build_test_data() -> ConstraintGraph<dir::DirValue> {
+        let (accts, config_map) = build_test_inputs();
+        make_mca_graph(accts, &config_map).expect("Failed graph construction")
     }
 
     #[test]
@@ -1560,4 +1603,106 @@ mod tests {
         );
         assert!(result.is_err(), "bluesnap validation failed");
     }
+
+    #[test]
+    fn test_graph_json_round_trip() {
+        let graph = build_test_data();
+
+        let exported = export_graph_json(&graph).expect("Failed graph export");
+        let imported = import_graph_json(exported.clone()).expect("Failed graph import");
+
+        let reexported = export_graph_json(&imported).expect("Failed graph export");
+        assert_eq!(reexported["domains"], exported["domains"]);
+        assert_eq!(reexported["edges"], exported["edges"]);
+        assert!(ConstraintGraph::diff(&graph, &imported)
+            .expect("Failed graph diff")
+            .is_empty());
+
+        let result = imported.key_value_analysis(
+            dirval!(Connector = Stripe),
+            &AnalysisContext::from_dir_values([
+                dirval!(Connector = Stripe),
+                dirval!(PaymentMethod = Card),
+                dirval!(CardType = Credit),
+                dirval!(CardNetwork = Visa),
+                dirval!(PaymentCurrency = INR),
+                dirval!(PaymentAmount = 101),
+            ]),
+            &mut Memoization::new(),
+            &mut CycleCheck::new(),
+            None,
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_mca_graph_diff() {
+        let (old_accts, config_map) = build_test_inputs();
+        let mut new_accts = old_accts.clone();
+        for pm_enabled in new_accts
+            .iter_mut()
+            .filter_map(|acct| acct.payment_methods_enabled.as_mut())
+            .flatten()
+        {
+            if let Some(pm_types) = pm_enabled.payment_method_types.as_mut() {
+                pm_types.retain(|pm_type| {
+                    pm_type.payment_method_type != api_enums::PaymentMethodType::Debit
+                });
+            }
+        }
+
+        let unchanged = make_mca_graph_diff(old_accts.clone(), old_accts.clone(), &config_map)
+            .expect("Failed graph diff");
+        assert!(unchanged.is_empty());
+
+        let diff =
+            make_mca_graph_diff(old_accts, new_accts, &config_map).expect("Failed graph diff");
+
+        let debit = format!("{:?}", dirval!(CardType = Debit));
+        assert!(diff
+            .removed_nodes
+            .iter()
+            .any(|node| node.signature == debit));
+        assert!(diff.removed_edges.iter().any(|edge| edge.pred == debit));
+    }
+
+    #[test]
+    fn test_ineligibility_explanation() {
+        let graph = build_test_data();
+        let ctx = AnalysisContext::from_dir_values([
+            dirval!(Connector = Stripe),
+            dirval!(PaymentMethod = Card),
+            dirval!(CardType = Credit),
+            dirval!(CardNetwork = Visa),
+            dirval!(PaymentCurrency = EUR),
+            dirval!(PaymentAmount = 101),
+        ]);
+        let mut memo = Memoization::new();
+
+        let error = graph
+            .key_value_analysis(
+                dirval!(Connector = Stripe),
+                &ctx,
+                &mut memo,
+                &mut CycleCheck::new(),
+                None,
+            )
+            .expect_err("Analysis should fail for an unsupported currency");
+
+        let constraints = crate::explain::collect_unsatisfied_constraints(&error, &ctx);
+        assert!(constraints.iter().any(|constraint| {
+            constraint.key == dir::DirKeyKind::PaymentCurrency
+                && constraint.found == vec![dirval!(PaymentCurrency = EUR)]
+        }));
+
+        let reason = crate::explain::explain_ineligibility(
+            &dirval!(CardType = Credit),
+            "stripe",
+            &constraints,
+        );
+        assert!(reason.starts_with("payment method"));
+        assert!(reason.contains("because connector stripe does not support"));
+        assert!(reason.contains("currency EUR"));
+    }
 }
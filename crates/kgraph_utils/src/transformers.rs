@@ -1,4 +1,5 @@
 use api_models::enums as api_enums;
+use common_utils::types::MinorUnit;
 use euclid::{
     backend::BackendInput,
     dirval,
@@ -72,6 +73,17 @@ impl IntoContext for BackendInput {
         if let Some(payment_type) = self.mandate.payment_type {
             ctx.push(dir::DirValue::PaymentType(payment_type));
         }
+        if let Some(time) = self.time {
+            ctx.push(dir::DirValue::TimeOfDay(NumValue {
+                number: MinorUnit::new(i64::from(time.time_of_day)),
+                refinement: None,
+            }));
+            ctx.push(dir::DirValue::DayOfWeek(time.day_of_week));
+            ctx.push(dir::DirValue::Date(NumValue {
+                number: MinorUnit::new(i64::from(time.date)),
+                refinement: None,
+            }));
+        }
 
         Ok(ctx)
     }
@@ -199,6 +199,9 @@ Never share your secret api keys. Keep them guarded and secure.
         routes::webhook_events::list_initial_webhook_delivery_attempts_with_jwtauth,
         routes::webhook_events::list_webhook_delivery_attempts,
         routes::webhook_events::retry_webhook_delivery_attempt,
+        routes::webhook_events::list_dead_lettered_webhook_events,
+        routes::webhook_events::redeliver_dead_lettered_webhook_events,
+        routes::webhook_events::list_webhook_circuit_breakers,
 
         // Routes for poll apis
         routes::poll::retrieve_poll_status,
@@ -254,6 +257,8 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::admin::BusinessPayoutLinkConfig,
         api_models::admin::CardTestingGuardConfig,
         api_models::admin::CardTestingGuardStatus,
+        api_models::admin::CardTestingVelocityRule,
+        api_models::admin::CardTestingGuardAllowlistEntry,
         api_models::customers::CustomerRequest,
         api_models::customers::CustomerUpdateRequest,
         api_models::customers::CustomerDeleteResponse,
@@ -261,6 +266,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payment_methods::PaymentMethodResponse,
         api_models::payment_methods::CustomerPaymentMethod,
         api_models::payment_methods::PaymentMethodListResponse,
+        api_models::payment_methods::PaymentMethodEligibilityFailure,
         api_models::payment_methods::ResponsePaymentMethodsEnabled,
         api_models::payment_methods::ResponsePaymentMethodTypes,
         api_models::payment_methods::PaymentExperienceTypes,
@@ -626,6 +632,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::admin::ToggleKVRequest,
         api_models::admin::ToggleKVResponse,
         api_models::admin::WebhookDetails,
+        api_models::admin::WebhookEndpoint,
         api_models::api_keys::ApiKeyExpiration,
         api_models::api_keys::CreateApiKeyRequest,
         api_models::api_keys::CreateApiKeyResponse,
@@ -684,12 +691,19 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::enums::BlocklistDataKind,
         api_models::enums::ErrorCategory,
         api_models::webhook_events::EventListConstraints,
+        api_models::webhook_events::DeadLetterEventListConstraints,
+        api_models::webhook_events::DeadLetterRedeliveryRequest,
+        api_models::webhook_events::DeadLetterRedeliveryResponse,
+        api_models::webhook_events::WebhookCircuitBreakerState,
+        api_models::webhook_events::WebhookCircuitBreakerResponse,
         api_models::webhook_events::EventListItemResponse,
         api_models::webhook_events::EventRetrieveResponse,
         api_models::webhook_events::OutgoingWebhookRequestContent,
         api_models::webhook_events::OutgoingWebhookResponseContent,
         api_models::webhook_events::TotalEventsResponse,
         api_models::enums::WebhookDeliveryAttempt,
+        api_models::enums::OutgoingWebhookFormat,
+        api_models::enums::CardTestingGuardDimension,
         api_models::enums::PaymentChargeType,
         api_models::enums::StripeChargeType,
         api_models::payments::CustomerDetailsResponse,
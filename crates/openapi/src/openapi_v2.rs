@@ -305,6 +305,8 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::admin::AdditionalMerchantData,
         api_models::admin::CardTestingGuardConfig,
         api_models::admin::CardTestingGuardStatus,
+        api_models::admin::CardTestingVelocityRule,
+        api_models::admin::CardTestingGuardAllowlistEntry,
         api_models::admin::ConnectorWalletDetails,
         api_models::admin::MerchantRecipientData,
         api_models::admin::MerchantAccountData,
@@ -617,6 +619,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::admin::ToggleKVRequest,
         api_models::admin::ToggleKVResponse,
         api_models::admin::WebhookDetails,
+        api_models::admin::WebhookEndpoint,
         api_models::api_keys::ApiKeyExpiration,
         api_models::api_keys::CreateApiKeyRequest,
         api_models::api_keys::CreateApiKeyResponse,
@@ -668,6 +671,8 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::webhook_events::OutgoingWebhookRequestContent,
         api_models::webhook_events::OutgoingWebhookResponseContent,
         api_models::enums::WebhookDeliveryAttempt,
+        api_models::enums::OutgoingWebhookFormat,
+        api_models::enums::CardTestingGuardDimension,
         api_models::enums::PaymentChargeType,
         api_models::enums::StripeChargeType,
         api_models::payments::CustomerDetailsResponse,
@@ -115,3 +115,67 @@ pub fn list_webhook_delivery_attempts() {}
     security(("admin_api_key" = []))
 )]
 pub fn retry_webhook_delivery_attempt() {}
+
+/// Events - List Dead-Lettered
+///
+/// List the Events of a Business Profile whose automatic delivery retries were exhausted.
+#[utoipa::path(
+    post,
+    path = "/events/{merchant_id}/dead_letter",
+    params(
+        ("merchant_id" = String, Path, description = "The unique identifier for the Merchant Account."),
+    ),
+    request_body(
+        content = DeadLetterEventListConstraints,
+        description = "The constraints that can be applied when listing dead-lettered Events.",
+    ),
+    responses(
+        (status = 200, description = "List of dead-lettered Events retrieved successfully", body = Vec<EventListItemResponse>),
+    ),
+    tag = "Event",
+    operation_id = "List all dead-lettered Events",
+    security(("admin_api_key" = []))
+)]
+pub fn list_dead_lettered_webhook_events() {}
+
+/// Events - Redeliver Dead-Lettered
+///
+/// Redeliver the dead-lettered Events of a Business Profile in bulk, at a limited rate.
+#[utoipa::path(
+    post,
+    path = "/events/{merchant_id}/dead_letter/redeliver",
+    params(
+        ("merchant_id" = String, Path, description = "The unique identifier for the Merchant Account."),
+    ),
+    request_body(
+        content = DeadLetterRedeliveryRequest,
+        description = "The dead-lettered Events to redeliver and the rate at which they are redelivered.",
+    ),
+    responses(
+        (status = 200, description = "The redelivery of the Events was scheduled", body = DeadLetterRedeliveryResponse),
+    ),
+    tag = "Event",
+    operation_id = "Redeliver dead-lettered Events",
+    security(("admin_api_key" = []))
+)]
+pub fn redeliver_dead_lettered_webhook_events() {}
+
+/// Events - List Circuit Breakers
+///
+/// List the state of the circuit breakers guarding the deliveries to the webhook endpoints of a
+/// Business Profile.
+#[utoipa::path(
+    get,
+    path = "/events/{merchant_id}/circuit_breakers/{profile_id}",
+    params(
+        ("merchant_id" = String, Path, description = "The unique identifier for the Merchant Account."),
+        ("profile_id" = String, Path, description = "The unique identifier for the Business Profile."),
+    ),
+    responses(
+        (status = 200, description = "List of webhook endpoint circuit breakers retrieved successfully", body = Vec<WebhookCircuitBreakerResponse>),
+    ),
+    tag = "Event",
+    operation_id = "List the circuit breakers of the webhook endpoints of a Business Profile",
+    security(("admin_api_key" = []))
+)]
+pub fn list_webhook_circuit_breakers() {}
@@ -324,12 +324,22 @@ impl ProcessTrackerWorkflows<routes::SessionState> for WorkflowRunner {
                 storage::ProcessTrackerRunner::PassiveRecoveryWorkflow => {
                     Ok(Box::new(workflows::revenue_recovery::ExecutePcrWorkflow))
                 }
+                storage::ProcessTrackerRunner::WebhookSigningSecretPromotionWorkflow => Ok(Box::new(
+                    workflows::webhook_signing_secret_promotion::WebhookSigningSecretPromotionWorkflow,
+                )),
+                storage::ProcessTrackerRunner::DeadLetterRedeliveryWorkflow => Ok(Box::new(
+                    workflows::dead_letter_redelivery::DeadLetterRedeliveryWorkflow,
+                )),
+                storage::ProcessTrackerRunner::ForexRatesRefreshWorkflow => Ok(Box::new(
+                    workflows::forex_rates_refresh::ForexRatesRefreshWorkflow,
+                )),
             }
         };
 
         let operation = get_operation(runner)?;
 
         let app_state = &state.clone();
+        let process_id = process.id.clone();
         let output = operation.execute_workflow(state, process.clone()).await;
         match output {
             Ok(_) => operation.success_handler(app_state, process).await,
@@ -358,6 +368,16 @@ impl ProcessTrackerWorkflows<routes::SessionState> for WorkflowRunner {
                 }
             },
         };
+
+        if let Err(error) = scheduler::dependencies::resolve_dependent_tasks(
+            state.get_db().as_scheduler(),
+            &process_id,
+        )
+        .await
+        {
+            // The tasks are resolved once the producer reconciles the tasks waiting for the process
+            logger::error!(?error, "Failed to resolve tasks depending on the process");
+        }
         Ok(())
     }
 }
@@ -372,6 +392,9 @@ async fn start_scheduler(
         .scheduler
         .clone()
         .ok_or(ProcessTrackerError::ConfigurationError)?;
+    if matches!(scheduler_flow, scheduler::SchedulerFlow::Producer) {
+        register_recurring_tasks(state).await?;
+    }
     scheduler::start_process_tracker(
         state,
         scheduler_flow,
@@ -385,3 +408,26 @@ async fn start_scheduler(
     )
     .await
 }
+
+/// Registers the recurring tasks of the application for every tenant. The registration is
+/// idempotent, so that the tasks are registered every time the producer starts.
+async fn register_recurring_tasks(
+    state: &routes::AppState,
+) -> CustomResult<(), ProcessTrackerError> {
+    for tenant in state.conf.multitenancy.get_tenant_ids() {
+        let session_state = Arc::new(state.clone())
+            .get_session_state(&tenant, None, || ProcessTrackerError::TenantNotFound.into())?;
+
+        if let Err(error) =
+            workflows::forex_rates_refresh::register_forex_rates_refresh_task(&session_state).await
+        {
+            logger::error!(
+                ?error,
+                ?tenant,
+                "Failed to register forex rates refresh task"
+            );
+        }
+    }
+
+    Ok(())
+}
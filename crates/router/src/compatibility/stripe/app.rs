@@ -2,6 +2,16 @@ use actix_web::{web, Scope};
 
 #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
 use super::customers::*;
+#[cfg(feature = "olap")]
+use super::events::*;
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "customer_v2"),
+    not(feature = "payment_methods_v2")
+))]
+use super::payment_methods::*;
+#[cfg(feature = "v1")]
+use super::webhook_endpoints::*;
 #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
 use super::{payment_intents::*, setup_intents::*};
 use super::{refunds::*, webhooks::*};
@@ -119,12 +129,62 @@ impl Webhooks {
     }
 }
 
-pub struct Mandates;
+pub struct PaymentMethods;
 
-impl Mandates {
+impl PaymentMethods {
     pub fn server(config: routes::AppState) -> Scope {
-        web::scope("/payment_methods")
+        let mut route = web::scope("/payment_methods").app_data(web::Data::new(config));
+        #[cfg(all(
+            any(feature = "v1", feature = "v2"),
+            not(feature = "customer_v2"),
+            not(feature = "payment_methods_v2")
+        ))]
+        {
+            route = route
+                .service(
+                    web::resource("")
+                        .route(web::post().to(payment_method_create))
+                        .route(web::get().to(payment_method_list)),
+                )
+                .service(
+                    web::resource("/{payment_method_id}")
+                        .route(web::get().to(payment_method_retrieve))
+                        .route(web::post().to(payment_method_update)),
+                )
+        }
+        route.service(web::resource("/{id}/detach").route(web::post().to(mandates::revoke_mandate)))
+    }
+}
+
+pub struct Events;
+
+#[cfg(feature = "olap")]
+impl Events {
+    pub fn server(config: routes::AppState) -> Scope {
+        web::scope("/events")
             .app_data(web::Data::new(config))
-            .service(web::resource("/{id}/detach").route(web::post().to(mandates::revoke_mandate)))
+            .service(web::resource("").route(web::get().to(event_list)))
+            .service(web::resource("/{event_id}").route(web::get().to(event_retrieve)))
+    }
+}
+
+pub struct WebhookEndpoints;
+
+#[cfg(feature = "v1")]
+impl WebhookEndpoints {
+    pub fn server(config: routes::AppState) -> Scope {
+        web::scope("/webhook_endpoints")
+            .app_data(web::Data::new(config))
+            .service(
+                web::resource("")
+                    .route(web::post().to(webhook_endpoint_create))
+                    .route(web::get().to(webhook_endpoint_list)),
+            )
+            .service(
+                web::resource("/{webhook_endpoint_id}")
+                    .route(web::get().to(webhook_endpoint_retrieve))
+                    .route(web::post().to(webhook_endpoint_update))
+                    .route(web::delete().to(webhook_endpoint_delete)),
+            )
     }
 }
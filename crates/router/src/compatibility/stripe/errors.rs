@@ -282,6 +282,10 @@ pub enum StripeErrorCode {
     PlatformBadRequest,
     #[error(error_type = StripeErrorType::HyperswitchError, code = "", message = "Platform Unauthorized Request")]
     PlatformUnauthorizedRequest,
+    #[error(error_type = StripeErrorType::IdempotencyError, code = "idempotency_error", message = "Keys for idempotent requests can only be used with the same parameters they were first used with. Try using a key other than the one used with the original request.")]
+    IdempotencyKeyReused,
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "idempotency_key_in_use", message = "There is currently another in-progress request using this Idempotent Key. Please try again later.")]
+    IdempotencyKeyInUse,
     // [#216]: https://github.com/juspay/hyperswitch/issues/216
     // Implement the remaining stripe error codes
 
@@ -324,7 +328,6 @@ pub enum StripeErrorCode {
         DebitNotAuthorized,
         EmailInvalid,
         ExpiredCard,
-        IdempotencyKeyInUse,
         IncorrectAddress,
         IncorrectCvc,
         IncorrectNumber,
@@ -438,6 +441,7 @@ pub enum StripeErrorType {
     InvalidRequestError,
     ConnectorError,
     HyperswitchError,
+    IdempotencyError,
 }
 
 impl From<errors::ApiErrorResponse> for StripeErrorCode {
@@ -684,6 +688,8 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             }
             errors::ApiErrorResponse::PlatformAccountAuthNotSupported => Self::PlatformBadRequest,
             errors::ApiErrorResponse::InvalidPlatformOperation => Self::PlatformUnauthorizedRequest,
+            errors::ApiErrorResponse::IdempotencyKeyReused => Self::IdempotencyKeyReused,
+            errors::ApiErrorResponse::IdempotentRequestInProgress => Self::IdempotencyKeyInUse,
         }
     }
 }
@@ -758,6 +764,7 @@ impl actix_web::ResponseError for StripeErrorCode {
             | Self::PaymentMethodDeleteFailed
             | Self::ExtendedCardInfoNotFound
             | Self::PlatformBadRequest
+            | Self::IdempotencyKeyReused
             | Self::LinkConfigurationError { .. } => StatusCode::BAD_REQUEST,
             Self::RefundFailed
             | Self::PayoutFailed
@@ -777,6 +784,7 @@ impl actix_web::ResponseError for StripeErrorCode {
                 StatusCode::from_u16(*code).unwrap_or(StatusCode::OK)
             }
             Self::LockTimeout => StatusCode::LOCKED,
+            Self::IdempotencyKeyInUse => StatusCode::CONFLICT,
         }
     }
 
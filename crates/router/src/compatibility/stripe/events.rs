@@ -0,0 +1,101 @@
+pub mod types;
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::webhook_events::EventListConstraints;
+use router_env::{instrument, tracing, Flow};
+
+use crate::{
+    compatibility::{stripe::errors, wrap},
+    core::{api_locking, webhooks::webhook_events},
+    routes,
+    services::{api, authentication as auth},
+};
+
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEventInitialDeliveryAttemptList))]
+pub async fn event_list(
+    state: web::Data<routes::AppState>,
+    req: HttpRequest,
+    payload: web::Query<types::StripeEventListConstraints>,
+) -> HttpResponse {
+    let constraints = match EventListConstraints::try_from(payload.into_inner()) {
+        Ok(constraints) => constraints,
+        Err(err) => return api::log_and_return_error_response(err),
+    };
+
+    let flow = Flow::WebhookEventInitialDeliveryAttemptList;
+
+    Box::pin(wrap::compatibility_api_wrap::<
+        _,
+        _,
+        _,
+        _,
+        _,
+        types::StripeEventListResponse,
+        errors::StripeErrorCode,
+        _,
+    >(
+        flow,
+        state.into_inner(),
+        &req,
+        constraints,
+        |state, auth: auth::AuthenticationData, constraints, _| {
+            // Profile level API keys only list the events of their business profile
+            let constraints = EventListConstraints {
+                profile_id: auth.profile_id,
+                ..constraints
+            };
+            webhook_events::list_initial_delivery_attempts_with_content(
+                state,
+                auth.merchant_account.get_id().to_owned(),
+                constraints,
+            )
+        },
+        &auth::HeaderAuth(auth::ApiKeyAuth {
+            is_connected_allowed: false,
+            is_platform_allowed: false,
+        }),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEventRetrieve))]
+pub async fn event_retrieve(
+    state: web::Data<routes::AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let event_id = path.into_inner();
+
+    let flow = Flow::WebhookEventRetrieve;
+
+    Box::pin(wrap::compatibility_api_wrap::<
+        _,
+        _,
+        _,
+        _,
+        _,
+        types::StripeEventResponse,
+        errors::StripeErrorCode,
+        _,
+    >(
+        flow,
+        state.into_inner(),
+        &req,
+        event_id,
+        |state, auth: auth::AuthenticationData, event_id, _| {
+            webhook_events::retrieve_event_content(
+                state,
+                auth.merchant_account.get_id().to_owned(),
+                auth.profile_id,
+                event_id,
+            )
+        },
+        &auth::HeaderAuth(auth::ApiKeyAuth {
+            is_connected_allowed: false,
+            is_platform_allowed: false,
+        }),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
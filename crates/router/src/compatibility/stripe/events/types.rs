@@ -0,0 +1,359 @@
+use std::collections::HashMap;
+
+use api_models::webhook_events;
+use common_utils::date_time;
+use error_stack::report;
+use masking::PeekInterface;
+use router_env::logger;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::compatibility::stripe::{errors, webhooks};
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StripeEventListConstraints {
+    #[serde(default = "default_limit")]
+    pub limit: u16,
+    #[serde(rename = "type")]
+    pub event_type: Option<String>,
+    pub delivery_success: Option<bool>,
+    pub created: Option<i64>,
+    #[serde(rename = "created[lt]")]
+    pub created_lt: Option<i64>,
+    #[serde(rename = "created[gt]")]
+    pub created_gt: Option<i64>,
+    #[serde(rename = "created[lte]")]
+    pub created_lte: Option<i64>,
+    #[serde(rename = "created[gte]")]
+    pub created_gte: Option<i64>,
+    /// Not supported, events are paginated using the `created` filters
+    pub starting_after: Option<String>,
+    /// Not supported, events are paginated using the `created` filters
+    pub ending_before: Option<String>,
+}
+
+fn default_limit() -> u16 {
+    10
+}
+
+#[derive(Debug, Serialize)]
+pub struct StripeEventData {
+    pub object: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StripeEventResponse {
+    pub id: String,
+    pub object: &'static str,
+    #[serde(rename = "type")]
+    pub stype: &'static str,
+    pub created: u64,
+    pub data: StripeEventData,
+    pub pending_webhooks: u8,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StripeEventListResponse {
+    pub object: &'static str,
+    pub data: Vec<StripeEventResponse>,
+    pub has_more: bool,
+    pub url: &'static str,
+}
+
+impl TryFrom<StripeEventListConstraints> for webhook_events::EventListConstraints {
+    type Error = error_stack::Report<errors::StripeErrorCode>;
+
+    fn try_from(item: StripeEventListConstraints) -> Result<Self, Self::Error> {
+        if item.starting_after.is_some() || item.ending_before.is_some() {
+            return Err(report!(errors::StripeErrorCode::InvalidRequestData {
+                message: "`starting_after` and `ending_before` are not supported for events, \
+                    use `created[lt]` with the creation time of the last event instead"
+                    .to_string(),
+            }));
+        }
+
+        let event_types = item
+            .event_type
+            .map(|stripe_event_type| {
+                let event_types =
+                    webhooks::get_event_types_from_stripe_event_type(&stripe_event_type);
+                if event_types.is_empty() {
+                    Err(report!(errors::StripeErrorCode::ParameterUnknown {
+                        field_name: "type".to_string(),
+                        expected_format: format!(
+                            "a supported event type, `{stripe_event_type}` is not supported"
+                        ),
+                    }))
+                } else {
+                    Ok(event_types)
+                }
+            })
+            .transpose()?;
+
+        // The `created[gt]` and `created[lt]` bounds are exclusive, while the bounds of the list
+        // constraints are inclusive
+        let created_gt = item
+            .created_gt
+            .map(|created_gt| created_gt.saturating_add(1));
+        let created_lt = from_timestamp_to_datetime(item.created_lt)?
+            .map(|created_lt| created_lt - time::Duration::MICROSECOND);
+
+        Ok(Self {
+            created_after: from_timestamp_to_datetime(
+                item.created_gte.or(created_gt).or(item.created),
+            )?,
+            created_before: from_timestamp_to_datetime(item.created_lte)?
+                .or(created_lt)
+                .or(from_timestamp_to_datetime(item.created)?),
+            limit: Some(item.limit),
+            offset: None,
+            object_id: None,
+            profile_id: None,
+            event_classes: None,
+            event_types,
+            is_delivered: item.delivery_success,
+            webhook_endpoint_id: None,
+        })
+    }
+}
+
+#[inline]
+fn from_timestamp_to_datetime(
+    time: Option<i64>,
+) -> Result<Option<PrimitiveDateTime>, error_stack::Report<errors::StripeErrorCode>> {
+    time.map(|time| {
+        time::OffsetDateTime::from_unix_timestamp(time)
+            .map(|time| PrimitiveDateTime::new(time.date(), time.time()))
+            .map_err(|_| {
+                report!(errors::StripeErrorCode::ParameterUnknown {
+                    field_name: "created".to_string(),
+                    expected_format: "a unix timestamp".to_string(),
+                })
+            })
+    })
+    .transpose()
+}
+
+/// The object the event is about is read back from the webhook body, which is either in the
+/// stripe compatible format (`data.object`) or in the hyperswitch format (`content.object`)
+fn get_event_object(request: &webhook_events::OutgoingWebhookRequestContent) -> serde_json::Value {
+    serde_json::from_str::<serde_json::Value>(request.body.peek())
+        .map_err(|error| logger::error!(?error, "Failed to parse webhook event request body"))
+        .ok()
+        .and_then(|body| {
+            body.pointer("/data/object")
+                .or_else(|| body.pointer("/content/object"))
+                .cloned()
+        })
+        .unwrap_or_default()
+}
+
+impl From<webhook_events::EventContentResponse> for StripeEventResponse {
+    fn from(item: webhook_events::EventContentResponse) -> Self {
+        let event_information = item.event_information;
+        Self {
+            id: event_information.event_id,
+            object: "event",
+            stype: webhooks::get_stripe_event_type(event_information.event_type),
+            created: u64::try_from(event_information.created.assume_utc().unix_timestamp())
+                .unwrap_or_else(|error| {
+                    logger::error!(
+                        %error,
+                        "incorrect value for `event.created` provided {}", event_information.created
+                    );
+                    // Current timestamp converted to Unix timestamp should have a positive value
+                    // for many years to come
+                    u64::try_from(date_time::now().assume_utc().unix_timestamp())
+                        .unwrap_or_default()
+                }),
+            data: StripeEventData {
+                object: get_event_object(&item.request),
+            },
+            pending_webhooks: u8::from(event_information.is_delivery_successful != Some(true)),
+        }
+    }
+}
+
+impl From<webhook_events::TotalEventContentsResponse> for StripeEventListResponse {
+    fn from(item: webhook_events::TotalEventContentsResponse) -> Self {
+        let has_more = usize::try_from(item.total_count)
+            .map(|total_count| total_count > item.events.len())
+            .unwrap_or_default();
+
+        // An event sent to several webhook endpoints of the profile is stored once per endpoint,
+        // while Stripe lists it once, with the number of endpoints it is still pending for
+        let mut source_events = HashMap::new();
+        let mut data: Vec<StripeEventResponse> = Vec::new();
+        for event in item.events {
+            let source_event = (
+                event.event_information.object_id.clone(),
+                event.event_information.event_type,
+            );
+            match source_events
+                .get(&source_event)
+                .and_then(|index| data.get_mut(*index))
+            {
+                Some(stripe_event) => {
+                    stripe_event.pending_webhooks = stripe_event.pending_webhooks.saturating_add(
+                        u8::from(event.event_information.is_delivery_successful != Some(true)),
+                    );
+                }
+                None => {
+                    source_events.insert(source_event, data.len());
+                    data.push(event.into());
+                }
+            }
+        }
+
+        Self {
+            object: "list",
+            data,
+            has_more,
+            url: "/v1/events",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use common_enums::{EventClass, EventType};
+    use masking::Secret;
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn list_constraints() -> StripeEventListConstraints {
+        StripeEventListConstraints {
+            limit: default_limit(),
+            event_type: None,
+            delivery_success: None,
+            created: None,
+            created_lt: None,
+            created_gt: None,
+            created_lte: None,
+            created_gte: None,
+            starting_after: None,
+            ending_before: None,
+        }
+    }
+
+    fn event(
+        event_id: &str,
+        object_id: &str,
+        event_type: EventType,
+        is_delivery_successful: bool,
+    ) -> webhook_events::EventContentResponse {
+        webhook_events::EventContentResponse {
+            event_information: webhook_events::EventListItemResponse {
+                event_id: event_id.to_string(),
+                merchant_id: common_utils::id_type::MerchantId::try_from(std::borrow::Cow::from(
+                    "merchant_1",
+                ))
+                .unwrap(),
+                profile_id: common_utils::id_type::ProfileId::try_from(std::borrow::Cow::from(
+                    "profile_1",
+                ))
+                .unwrap(),
+                object_id: object_id.to_string(),
+                event_type,
+                event_class: EventClass::Payments,
+                is_delivery_successful: Some(is_delivery_successful),
+                initial_attempt_id: event_id.to_string(),
+                webhook_endpoint_id: None,
+                dead_lettered_at: None,
+                created: datetime!(2024-01-01 0:00),
+            },
+            request: webhook_events::OutgoingWebhookRequestContent {
+                body: Secret::new(r#"{"data":{"object":{"id":"pay_1"}}}"#.to_string()),
+                headers: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn test_exclusive_created_bounds() {
+        let constraints =
+            webhook_events::EventListConstraints::try_from(StripeEventListConstraints {
+                created_gt: Some(1_704_067_200),
+                created_lt: Some(1_704_153_600),
+                ..list_constraints()
+            })
+            .unwrap();
+        assert_eq!(
+            constraints.created_after,
+            Some(datetime!(2024-01-01 0:00:01))
+        );
+        assert_eq!(
+            constraints.created_before,
+            Some(datetime!(2024-01-01 23:59:59.999999))
+        );
+
+        let constraints =
+            webhook_events::EventListConstraints::try_from(StripeEventListConstraints {
+                created_gte: Some(1_704_067_200),
+                created_lte: Some(1_704_153_600),
+                ..list_constraints()
+            })
+            .unwrap();
+        assert_eq!(constraints.created_after, Some(datetime!(2024-01-01 0:00)));
+        assert_eq!(constraints.created_before, Some(datetime!(2024-01-02 0:00)));
+    }
+
+    #[test]
+    fn test_event_list_rejects_cursors() {
+        for constraints in [
+            StripeEventListConstraints {
+                starting_after: Some("evt_1".to_string()),
+                ..list_constraints()
+            },
+            StripeEventListConstraints {
+                ending_before: Some("evt_1".to_string()),
+                ..list_constraints()
+            },
+        ] {
+            let error = webhook_events::EventListConstraints::try_from(constraints).unwrap_err();
+            assert!(matches!(
+                error.current_context(),
+                errors::StripeErrorCode::InvalidRequestData { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_event_list_deduplicates_source_events() {
+        let list = StripeEventListResponse::from(webhook_events::TotalEventContentsResponse {
+            events: vec![
+                event("evt_1", "pay_1", EventType::PaymentSucceeded, true),
+                event("evt_2", "pay_1", EventType::PaymentSucceeded, false),
+                event("evt_3", "pay_1", EventType::PaymentSucceeded, false),
+                event("evt_4", "pay_1", EventType::PaymentProcessing, true),
+                event("evt_5", "pay_2", EventType::PaymentSucceeded, true),
+            ],
+            total_count: 5,
+        });
+
+        let events = list
+            .data
+            .iter()
+            .map(|event| (event.id.as_str(), event.stype, event.pending_webhooks))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                ("evt_1", "payment_intent.succeeded", 2),
+                ("evt_4", "payment_intent.processing", 0),
+                ("evt_5", "payment_intent.succeeded", 0),
+            ]
+        );
+        assert!(!list.has_more);
+        assert_eq!(
+            list.data
+                .first()
+                .and_then(|event| event.data.object.pointer("/id")),
+            Some(&serde_json::json!("pay_1"))
+        );
+    }
+}
@@ -0,0 +1,257 @@
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "customer_v2"),
+    not(feature = "payment_methods_v2")
+))]
+use ::payment_methods::controller::PaymentMethodsController;
+use common_utils::id_type;
+use error_stack::ResultExt;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+#[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
+use crate::{compatibility::stripe::customers::types as customer_types, core::customers};
+use crate::{
+    compatibility::stripe::payment_intents::types as payment_intent_types,
+    core::errors::{self, RouterResult, StorageErrorExt},
+    routes::SessionState,
+    services::ApplicationResponse,
+    types::domain,
+};
+#[cfg(all(
+    any(feature = "v1", feature = "v2"),
+    not(feature = "customer_v2"),
+    not(feature = "payment_methods_v2")
+))]
+use crate::{
+    compatibility::stripe::payment_methods::types as payment_method_types,
+    core::payment_methods::cards, types::api::payment_methods as api_payment_methods,
+};
+
+/// An object referenced by a field of a Stripe object, which can be expanded
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ExpandableReference {
+    #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
+    Customer(id_type::CustomerId),
+    #[cfg(all(
+        any(feature = "v1", feature = "v2"),
+        not(feature = "customer_v2"),
+        not(feature = "payment_methods_v2")
+    ))]
+    PaymentMethod(String),
+    Charge(String),
+    /// The latest charge of a payment intent which does not reference it in its response
+    LatestChargeOfPaymentIntent(id_type::PaymentId),
+}
+
+/// Maximum number of levels of an `expand[]` path, same as Stripe
+const MAX_EXPANSION_DEPTH: usize = 4;
+
+fn is_expandable_field(field: &str) -> bool {
+    match field {
+        #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
+        "customer" => true,
+        #[cfg(all(
+            any(feature = "v1", feature = "v2"),
+            not(feature = "customer_v2"),
+            not(feature = "payment_methods_v2")
+        ))]
+        "payment_method" => true,
+        "latest_charge" => true,
+        _ => false,
+    }
+}
+
+/// Validates an `expand[]` path, so that requests which cannot be expanded are rejected before
+/// they are processed
+pub fn validate_path(path: &str) -> RouterResult<()> {
+    let fields = path.split('.').collect::<Vec<_>>();
+    let is_valid = fields.len() <= MAX_EXPANSION_DEPTH
+        && !fields.iter().any(|field| field.is_empty())
+        && fields
+            .last()
+            .is_some_and(|field| is_expandable_field(field));
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("This property cannot be expanded ({path})"),
+        })?
+    }
+}
+
+/// Gets the reference held by `field` in the `parent` Stripe object, the expandable fields are
+/// `customer`, `latest_charge` and `payment_method`.
+///
+/// Returns `None` when the parent object does not reference any object through the field.
+pub fn get_reference(parent: &Value, field: &str) -> RouterResult<Option<ExpandableReference>> {
+    let reference = match field {
+        #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
+        "customer" => parse_reference(parent, field)?.map(ExpandableReference::Customer),
+        #[cfg(all(
+            any(feature = "v1", feature = "v2"),
+            not(feature = "customer_v2"),
+            not(feature = "payment_methods_v2")
+        ))]
+        // Payment intents hold the type of the payment method in `payment_method` and reference
+        // the payment method through `payment_method_id`
+        "payment_method" => match parent.get("payment_method_id") {
+            Some(_) => parse_reference(parent, "payment_method_id")?,
+            None => parse_reference(parent, field)?,
+        }
+        .map(ExpandableReference::PaymentMethod),
+        "latest_charge" => match parse_reference(parent, field)? {
+            Some(attempt_id) => Some(ExpandableReference::Charge(attempt_id)),
+            // The latest charge of a payment intent is its active attempt, which is not always
+            // part of the payment intent response
+            None if parent.get("object").and_then(Value::as_str) == Some("payment_intent") => {
+                parse_reference(parent, "id")?.map(ExpandableReference::LatestChargeOfPaymentIntent)
+            }
+            None => None,
+        },
+        _ => Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("This property cannot be expanded ({field})"),
+        })?,
+    };
+
+    Ok(reference)
+}
+
+/// Fetches the object identified by the reference
+pub async fn expand_reference(
+    state: &SessionState,
+    merchant_context: &domain::MerchantContext,
+    reference: &ExpandableReference,
+) -> RouterResult<Value> {
+    match reference {
+        #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
+        ExpandableReference::Customer(customer_id) => {
+            let customer = get_json_response(
+                customers::retrieve_customer(
+                    state.clone(),
+                    merchant_context.clone(),
+                    None,
+                    customer_id.clone(),
+                )
+                .await?,
+            )?;
+            to_value(customer_types::CreateCustomerResponse::from(customer))
+        }
+        #[cfg(all(
+            any(feature = "v1", feature = "v2"),
+            not(feature = "customer_v2"),
+            not(feature = "payment_methods_v2")
+        ))]
+        ExpandableReference::PaymentMethod(payment_method_id) => {
+            let payment_method = get_json_response(
+                cards::PmCards {
+                    state,
+                    merchant_context,
+                }
+                .retrieve_payment_method(api_payment_methods::PaymentMethodId {
+                    payment_method_id: payment_method_id.clone(),
+                })
+                .await?,
+            )?;
+            to_value(payment_method_types::StripePaymentMethodResponse::from(
+                payment_method,
+            ))
+        }
+        ExpandableReference::Charge(attempt_id) => {
+            get_charge(state, merchant_context, attempt_id).await
+        }
+        ExpandableReference::LatestChargeOfPaymentIntent(payment_id) => {
+            let attempt_id = get_active_attempt_id(state, merchant_context, payment_id).await?;
+            get_charge(state, merchant_context, &attempt_id).await
+        }
+    }
+}
+
+async fn get_charge(
+    state: &SessionState,
+    merchant_context: &domain::MerchantContext,
+    attempt_id: &str,
+) -> RouterResult<Value> {
+    let attempt = state
+        .store
+        .find_payment_attempt_by_attempt_id_merchant_id(
+            attempt_id,
+            merchant_context.get_merchant_account().get_id(),
+            merchant_context.get_merchant_account().storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    to_value(payment_intent_types::StripeCharge::from(attempt))
+}
+
+async fn get_active_attempt_id(
+    state: &SessionState,
+    merchant_context: &domain::MerchantContext,
+    payment_id: &id_type::PaymentId,
+) -> RouterResult<String> {
+    let payment_intent = state
+        .store
+        .find_payment_intent_by_payment_id_merchant_id(
+            &state.into(),
+            payment_id,
+            merchant_context.get_merchant_account().get_id(),
+            merchant_context.get_merchant_key_store(),
+            merchant_context.get_merchant_account().storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    Ok(payment_intent.active_attempt.get_id())
+}
+
+/// Parses the identifier held by `field`, fields which are absent, `null` or already expanded
+/// are not references to other objects
+fn parse_reference<T: DeserializeOwned>(parent: &Value, field: &str) -> RouterResult<Option<T>> {
+    match parent.get(field) {
+        Some(reference @ Value::String(_)) => serde_json::from_value(reference.clone())
+            .map(Some)
+            .change_context(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!("Invalid reference in {field}"),
+            }),
+        _ => Ok(None),
+    }
+}
+
+fn get_json_response<T>(response: ApplicationResponse<T>) -> RouterResult<T> {
+    match response {
+        ApplicationResponse::Json(response)
+        | ApplicationResponse::JsonWithHeaders((response, _)) => Ok(response),
+        _ => Err(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to get the response in json"),
+    }
+}
+
+fn to_value<T: serde::Serialize>(object: T) -> RouterResult<Value> {
+    serde_json::to_value(object)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize the expanded object")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_path() {
+        assert!(validate_path("latest_charge").is_ok());
+        assert!(validate_path("data.latest_charge").is_ok());
+
+        for path in [
+            "",
+            "data.",
+            "data..latest_charge",
+            "amount",
+            "latest_charge.amount",
+            "a.b.c.d.latest_charge",
+        ] {
+            assert!(validate_path(path).is_err(), "{path} should be rejected");
+        }
+    }
+}
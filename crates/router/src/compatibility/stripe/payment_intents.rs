@@ -2,8 +2,12 @@ pub mod types;
 
 use actix_web::{web, HttpRequest, HttpResponse};
 use api_models::payments as payment_types;
+#[cfg(feature = "olap")]
+use common_utils::consts::PAYMENTS_LIST_MAX_LIMIT_V1;
 #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
 use error_stack::report;
+#[cfg(feature = "olap")]
+use error_stack::ResultExt;
 #[cfg(all(any(feature = "v1", feature = "v2"), not(feature = "customer_v2")))]
 use router_env::Tag;
 use router_env::{instrument, tracing, Flow};
@@ -633,7 +637,7 @@ pub async fn payment_intent_list(
             let merchant_context = domain::MerchantContext::NormalMerchant(Box::new(
                 domain::Context(auth.merchant_account, auth.key_store),
             ));
-            payments::list_payments(state, merchant_context, None, req)
+            list_payments_with_next(state, merchant_context, req)
         },
         &auth::HeaderAuth(auth::ApiKeyAuth {
             is_connected_allowed: false,
@@ -643,3 +647,245 @@ pub async fn payment_intent_list(
     ))
     .await
 }
+
+/// Lists the payments of the requested page along with the payment following the page, if any, so
+/// that the compatibility wrapper can determine whether there are more payments after the page.
+///
+/// Payments are listed the latest first, with the payment ID breaking ties between payments
+/// created at the same time, and the `starting_after` and `ending_before` cursors are applied in
+/// that order. Ties are assumed to consist of fewer payments than the maximum list limit.
+#[cfg(feature = "olap")]
+async fn list_payments_with_next(
+    state: routes::SessionState,
+    merchant_context: domain::MerchantContext,
+    constraints: payment_types::PaymentListConstraints,
+) -> crate::core::errors::RouterResponse<payment_types::PaymentListResponse> {
+    payments::helpers::validate_payment_list_request(&constraints)?;
+
+    let page_size = usize::try_from(constraints.limit)
+        .unwrap_or(usize::MAX)
+        .saturating_add(1);
+    let starting_at = constraints
+        .created_gte
+        .or(constraints.created_gt)
+        .or(constraints.created);
+    let ending_at = constraints
+        .created_lte
+        .or(constraints.created_lt)
+        .or(constraints.created);
+    let payment_list = PaymentListQuery {
+        state: &state,
+        merchant_context: &merchant_context,
+        customer_id: constraints.customer_id.clone(),
+    };
+
+    let data = match (&constraints.starting_after, &constraints.ending_before) {
+        (Some(starting_after), _) => {
+            let cursor = payment_list.get_cursor(starting_after).await?;
+            // The payments created at the same time as the cursor which follow it
+            let mut payments = payment_list
+                .list_created_at(cursor.0, starting_at, ending_at)
+                .await?;
+            payments.retain(|payment| get_list_position(payment) < cursor);
+            let ending_at = min_datetime(ending_at, cursor.0 - time::Duration::MICROSECOND);
+            let remaining = page_size.saturating_sub(payments.len());
+            payments.extend(
+                payment_list
+                    .list_before(starting_at, ending_at, remaining)
+                    .await?,
+            );
+            payments
+        }
+        // The page ending before the cursor consists of the payments closest to it, which are
+        // found by listing every payment created after the cursor, as payments are listed from
+        // the latest
+        (None, Some(ending_before)) => {
+            let cursor = payment_list.get_cursor(ending_before).await?;
+            let mut ties = payment_list
+                .list_created_at(cursor.0, starting_at, ending_at)
+                .await?;
+            ties.retain(|payment| get_list_position(payment) > cursor);
+            let starting_at = max_datetime(starting_at, cursor.0 + time::Duration::MICROSECOND);
+            let mut payments = payment_list
+                .list_before(starting_at, ending_at, usize::MAX)
+                .await?;
+            payments.append(&mut ties);
+            let excess = payments.len().saturating_sub(page_size);
+            payments.drain(..excess);
+            payments
+        }
+        (None, None) => {
+            payment_list
+                .list_before(starting_at, ending_at, page_size)
+                .await?
+        }
+    };
+
+    Ok(api::ApplicationResponse::Json(
+        payment_types::PaymentListResponse {
+            size: data.len(),
+            data,
+        },
+    ))
+}
+
+/// The position of a payment in the list, payments which are listed earlier are greater
+#[cfg(feature = "olap")]
+fn get_list_position(
+    payment: &payment_types::PaymentsResponse,
+) -> (time::PrimitiveDateTime, String) {
+    (
+        payment.created.unwrap_or(time::PrimitiveDateTime::MIN),
+        payment.payment_id.get_string_repr().to_owned(),
+    )
+}
+
+#[cfg(feature = "olap")]
+fn sort_payment_list(payments: &mut [payment_types::PaymentsResponse]) {
+    payments.sort_by_key(|payment| std::cmp::Reverse(get_list_position(payment)));
+}
+
+#[cfg(feature = "olap")]
+fn min_datetime(
+    datetime: Option<time::PrimitiveDateTime>,
+    other: time::PrimitiveDateTime,
+) -> Option<time::PrimitiveDateTime> {
+    Some(datetime.map_or(other, |datetime| datetime.min(other)))
+}
+
+#[cfg(feature = "olap")]
+fn max_datetime(
+    datetime: Option<time::PrimitiveDateTime>,
+    other: time::PrimitiveDateTime,
+) -> Option<time::PrimitiveDateTime> {
+    Some(datetime.map_or(other, |datetime| datetime.max(other)))
+}
+
+/// Lists the payments of a customer, or of the merchant, within a range of creation times
+#[cfg(feature = "olap")]
+struct PaymentListQuery<'a> {
+    state: &'a routes::SessionState,
+    merchant_context: &'a domain::MerchantContext,
+    customer_id: Option<common_utils::id_type::CustomerId>,
+}
+
+#[cfg(feature = "olap")]
+impl PaymentListQuery<'_> {
+    /// The position of the payment referenced by a cursor in the list
+    async fn get_cursor(
+        &self,
+        payment_id: &common_utils::id_type::PaymentId,
+    ) -> crate::core::errors::RouterResult<(time::PrimitiveDateTime, String)> {
+        use crate::core::errors::StorageErrorExt;
+
+        let payment_intent = self
+            .state
+            .store
+            .find_payment_intent_by_payment_id_merchant_id(
+                &self.state.into(),
+                payment_id,
+                self.merchant_context.get_merchant_account().get_id(),
+                self.merchant_context.get_merchant_key_store(),
+                self.merchant_context.get_merchant_account().storage_scheme,
+            )
+            .await
+            .to_not_found_response(crate::core::errors::ApiErrorResponse::PaymentNotFound)?;
+
+        Ok((
+            payment_intent.created_at,
+            payment_id.get_string_repr().to_owned(),
+        ))
+    }
+
+    /// Lists at most `limit` payments within the range, the latest first
+    async fn list(
+        &self,
+        starting_at: Option<time::PrimitiveDateTime>,
+        ending_at: Option<time::PrimitiveDateTime>,
+        limit: u32,
+    ) -> crate::core::errors::RouterResult<Vec<payment_types::PaymentsResponse>> {
+        if starting_at
+            .zip(ending_at)
+            .is_some_and(|(starting_at, ending_at)| starting_at > ending_at)
+        {
+            return Ok(Vec::new());
+        }
+
+        let response = payments::list_payments(
+            self.state.clone(),
+            self.merchant_context.clone(),
+            None,
+            payment_types::PaymentListConstraints {
+                customer_id: self.customer_id.clone(),
+                starting_after: None,
+                ending_before: None,
+                limit,
+                created: None,
+                created_lt: None,
+                created_gt: None,
+                created_lte: ending_at,
+                created_gte: starting_at,
+            },
+        )
+        .await?;
+        let api::ApplicationResponse::Json(mut payments) = response else {
+            return Err(crate::core::errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to get the payment list in json");
+        };
+        sort_payment_list(&mut payments.data);
+
+        Ok(payments.data)
+    }
+
+    /// Lists the payments created at `created_at`, if it is within the range
+    async fn list_created_at(
+        &self,
+        created_at: time::PrimitiveDateTime,
+        starting_at: Option<time::PrimitiveDateTime>,
+        ending_at: Option<time::PrimitiveDateTime>,
+    ) -> crate::core::errors::RouterResult<Vec<payment_types::PaymentsResponse>> {
+        self.list(
+            max_datetime(starting_at, created_at),
+            min_datetime(ending_at, created_at),
+            PAYMENTS_LIST_MAX_LIMIT_V1,
+        )
+        .await
+    }
+
+    /// Lists at most `count` payments within the range, the latest first. The payments are fetched
+    /// in batches, and every batch is completed with the payments created at the same time as the
+    /// last payment of the batch, so that ties are never split between batches or pages.
+    async fn list_before(
+        &self,
+        starting_at: Option<time::PrimitiveDateTime>,
+        mut ending_at: Option<time::PrimitiveDateTime>,
+        count: usize,
+    ) -> crate::core::errors::RouterResult<Vec<payment_types::PaymentsResponse>> {
+        let mut payments = Vec::new();
+        while payments.len() < count {
+            let limit = u32::try_from(count.saturating_sub(payments.len()))
+                .unwrap_or(u32::MAX)
+                .min(PAYMENTS_LIST_MAX_LIMIT_V1);
+            let mut batch = self.list(starting_at, ending_at, limit).await?;
+            let is_exhausted = usize::try_from(limit).is_ok_and(|limit| batch.len() < limit);
+
+            let last_created_at = batch.last().and_then(|payment| payment.created);
+            if let (false, Some(last_created_at)) = (is_exhausted, last_created_at) {
+                batch.retain(|payment| payment.created != Some(last_created_at));
+                batch.extend(
+                    self.list_created_at(last_created_at, starting_at, ending_at)
+                        .await?,
+                );
+                sort_payment_list(&mut batch);
+                ending_at = Some(last_created_at - time::Duration::MICROSECOND);
+            }
+
+            payments.append(&mut batch);
+            if is_exhausted || last_created_at.is_none() {
+                break;
+            }
+        }
+
+        Ok(payments)
+    }
+}
@@ -473,6 +473,7 @@ pub struct StripePaymentIntentResponse {
     pub mandate: Option<String>,
     pub metadata: Option<serde_json::Value>,
     pub charges: Charges,
+    pub latest_charge: Option<String>,
     pub connector: Option<String>,
     pub description: Option<String>,
     pub mandate_data: Option<payments::MandateData>,
@@ -482,6 +483,7 @@ pub struct StripePaymentIntentResponse {
     pub next_action: Option<StripeNextAction>,
     pub cancellation_reason: Option<String>,
     pub payment_method: Option<api_models::enums::PaymentMethod>,
+    pub payment_method_id: Option<String>,
     pub payment_method_data: Option<payments::PaymentMethodDataResponse>,
     pub shipping: Option<payments::Address>,
     pub billing: Option<payments::Address>,
@@ -535,6 +537,7 @@ impl From<payments::PaymentsResponse> for StripePaymentIntentResponse {
             capture_on: resp.capture_on,
             capture_method: resp.capture_method,
             payment_method: resp.payment_method,
+            payment_method_id: resp.payment_method_id,
             payment_method_data: resp
                 .payment_method_data
                 .and_then(|pmd| pmd.payment_method_data),
@@ -551,6 +554,12 @@ impl From<payments::PaymentsResponse> for StripePaymentIntentResponse {
             cancellation_reason: resp.cancellation_reason,
             metadata: resp.metadata,
             charges: Charges::new(),
+            latest_charge: resp.attempts.and_then(|attempts| {
+                attempts
+                    .into_iter()
+                    .max_by_key(|attempt| attempt.created_at)
+                    .map(|attempt| attempt.attempt_id)
+            }),
             last_payment_error: resp.error_code.map(|code| LastPaymentError {
                 charge: None,
                 code: Some(code.to_owned()),
@@ -608,8 +617,96 @@ impl Charges {
     }
 }
 
+#[derive(Eq, PartialEq, Serialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum StripeChargeStatus {
+    Succeeded,
+    Pending,
+    Failed,
+}
+
+impl From<api_enums::AttemptStatus> for StripeChargeStatus {
+    fn from(item: api_enums::AttemptStatus) -> Self {
+        match item {
+            api_enums::AttemptStatus::Charged
+            | api_enums::AttemptStatus::PartialCharged
+            | api_enums::AttemptStatus::PartialChargedAndChargeable
+            | api_enums::AttemptStatus::AutoRefunded => Self::Succeeded,
+            api_enums::AttemptStatus::AuthenticationFailed
+            | api_enums::AttemptStatus::RouterDeclined
+            | api_enums::AttemptStatus::AuthorizationFailed
+            | api_enums::AttemptStatus::CaptureFailed
+            | api_enums::AttemptStatus::VoidFailed
+            | api_enums::AttemptStatus::Failure
+            | api_enums::AttemptStatus::Voided => Self::Failed,
+            api_enums::AttemptStatus::Started
+            | api_enums::AttemptStatus::AuthenticationPending
+            | api_enums::AttemptStatus::AuthenticationSuccessful
+            | api_enums::AttemptStatus::Authorized
+            | api_enums::AttemptStatus::Authorizing
+            | api_enums::AttemptStatus::CodInitiated
+            | api_enums::AttemptStatus::VoidInitiated
+            | api_enums::AttemptStatus::CaptureInitiated
+            | api_enums::AttemptStatus::Unresolved
+            | api_enums::AttemptStatus::Pending
+            | api_enums::AttemptStatus::PaymentMethodAwaited
+            | api_enums::AttemptStatus::ConfirmationAwaited
+            | api_enums::AttemptStatus::DeviceDataCollectionPending => Self::Pending,
+        }
+    }
+}
+
+/// Stripe `charge` object, built from the payment attempt it corresponds to
+#[derive(Eq, PartialEq, Serialize, Debug)]
+pub struct StripeCharge {
+    pub id: String,
+    pub object: &'static str,
+    pub amount: i64,
+    pub amount_captured: i64,
+    pub currency: Option<String>,
+    pub status: StripeChargeStatus,
+    pub paid: bool,
+    pub payment_intent: id_type::PaymentId,
+    pub payment_method: Option<String>,
+    pub created: i64,
+    pub failure_code: Option<String>,
+    pub failure_message: Option<String>,
+}
+
+impl From<hyperswitch_domain_models::payments::payment_attempt::PaymentAttempt> for StripeCharge {
+    fn from(attempt: hyperswitch_domain_models::payments::payment_attempt::PaymentAttempt) -> Self {
+        let status = StripeChargeStatus::from(attempt.status);
+        let amount = attempt.get_total_amount();
+        let paid = status == StripeChargeStatus::Succeeded;
+        Self {
+            amount: amount.get_amount_as_i64(),
+            amount_captured: if paid {
+                attempt
+                    .amount_to_capture
+                    .unwrap_or(amount)
+                    .get_amount_as_i64()
+            } else {
+                0
+            },
+            paid,
+            id: attempt.attempt_id,
+            object: "charge",
+            currency: attempt
+                .currency
+                .map(|currency| currency.to_string().to_lowercase()),
+            status,
+            payment_intent: attempt.payment_id,
+            payment_method: attempt.payment_method_id,
+            created: attempt.created_at.assume_utc().unix_timestamp(),
+            failure_code: attempt.error_code,
+            failure_message: attempt.error_message,
+        }
+    }
+}
+
+// Unknown fields are not denied since the `expand[]` parameters are handled by the compatibility
+// wrapper
 #[derive(Clone, Debug, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct StripePaymentListConstraints {
     pub customer: Option<id_type::CustomerId>,
     pub starting_after: Option<id_type::PaymentId>,
@@ -640,8 +737,14 @@ impl TryFrom<StripePaymentListConstraints> for payments::PaymentListConstraints
             ending_before: item.ending_before,
             limit: item.limit,
             created: from_timestamp_to_datetime(item.created)?,
-            created_lt: from_timestamp_to_datetime(item.created_lt)?,
-            created_gt: from_timestamp_to_datetime(item.created_gt)?,
+            // The `created[gt]` and `created[lt]` bounds are exclusive, while the list constraints
+            // treat them as inclusive bounds
+            created_lt: from_timestamp_to_datetime(item.created_lt)?
+                .map(|created_lt| created_lt - time::Duration::MICROSECOND),
+            created_gt: from_timestamp_to_datetime(
+                item.created_gt
+                    .map(|created_gt| created_gt.saturating_add(1)),
+            )?,
             created_lte: from_timestamp_to_datetime(item.created_lte)?,
             created_gte: from_timestamp_to_datetime(item.created_gte)?,
         })
@@ -0,0 +1,216 @@
+pub mod types;
+
+use ::payment_methods::controller::PaymentMethodsController;
+use actix_web::{web, HttpRequest, HttpResponse};
+use error_stack::report;
+use router_env::{instrument, tracing, Flow};
+
+use crate::{
+    compatibility::{stripe::errors, wrap},
+    core::{api_locking, payment_methods::cards},
+    routes,
+    services::{api, authentication as auth},
+    types::{api::payment_methods as payment_method_types, domain},
+};
+
+#[instrument(skip_all, fields(flow = ?Flow::PaymentMethodsCreate))]
+pub async fn payment_method_create(
+    state: web::Data<routes::AppState>,
+    qs_config: web::Data<serde_qs::Config>,
+    req: HttpRequest,
+    form_payload: web::Bytes,
+) -> HttpResponse {
+    let payload: types::StripePaymentMethodCreateRequest = match qs_config
+        .deserialize_bytes(&form_payload)
+    {
+        Ok(p) => p,
+        Err(err) => {
+            return api::log_and_return_error_response(report!(errors::StripeErrorCode::from(err)))
+        }
+    };
+
+    let create_payment_method_req =
+        match payment_method_types::PaymentMethodCreate::try_from(payload) {
+            Ok(req) => req,
+            Err(err) => return api::log_and_return_error_response(err),
+        };
+
+    let flow = Flow::PaymentMethodsCreate;
+
+    Box::pin(wrap::compatibility_api_wrap::<
+        _,
+        _,
+        _,
+        _,
+        _,
+        types::StripePaymentMethodResponse,
+        errors::StripeErrorCode,
+        _,
+    >(
+        flow,
+        state.into_inner(),
+        &req,
+        create_payment_method_req,
+        |state, auth: auth::AuthenticationData, req, _| async move {
+            let merchant_context = domain::MerchantContext::NormalMerchant(Box::new(
+                domain::Context(auth.merchant_account, auth.key_store),
+            ));
+            Box::pin(cards::get_client_secret_or_add_payment_method(
+                &state,
+                req,
+                &merchant_context,
+            ))
+            .await
+        },
+        &auth::HeaderAuth(auth::ApiKeyAuth {
+            is_connected_allowed: false,
+            is_platform_allowed: false,
+        }),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::PaymentMethodsRetrieve))]
+pub async fn payment_method_retrieve(
+    state: web::Data<routes::AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let payload = payment_method_types::PaymentMethodId {
+        payment_method_id: path.into_inner(),
+    };
+
+    let flow = Flow::PaymentMethodsRetrieve;
+
+    Box::pin(wrap::compatibility_api_wrap::<
+        _,
+        _,
+        _,
+        _,
+        _,
+        types::StripePaymentMethodResponse,
+        errors::StripeErrorCode,
+        _,
+    >(
+        flow,
+        state.into_inner(),
+        &req,
+        payload,
+        |state, auth: auth::AuthenticationData, pm, _| async move {
+            let merchant_context = domain::MerchantContext::NormalMerchant(Box::new(
+                domain::Context(auth.merchant_account, auth.key_store),
+            ));
+            cards::PmCards {
+                state: &state,
+                merchant_context: &merchant_context,
+            }
+            .retrieve_payment_method(pm)
+            .await
+        },
+        &auth::HeaderAuth(auth::ApiKeyAuth {
+            is_connected_allowed: false,
+            is_platform_allowed: false,
+        }),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::PaymentMethodsUpdate))]
+pub async fn payment_method_update(
+    state: web::Data<routes::AppState>,
+    qs_config: web::Data<serde_qs::Config>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    form_payload: web::Bytes,
+) -> HttpResponse {
+    let payload: types::StripePaymentMethodUpdateRequest = match qs_config
+        .deserialize_bytes(&form_payload)
+    {
+        Ok(p) => p,
+        Err(err) => {
+            return api::log_and_return_error_response(report!(errors::StripeErrorCode::from(err)))
+        }
+    };
+
+    let payment_method_id = path.into_inner();
+    let update_payment_method_req = payment_method_types::PaymentMethodUpdate::from(payload);
+
+    let flow = Flow::PaymentMethodsUpdate;
+
+    Box::pin(wrap::compatibility_api_wrap::<
+        _,
+        _,
+        _,
+        _,
+        _,
+        types::StripePaymentMethodResponse,
+        errors::StripeErrorCode,
+        _,
+    >(
+        flow,
+        state.into_inner(),
+        &req,
+        update_payment_method_req,
+        |state, auth: auth::AuthenticationData, req, _| {
+            let merchant_context = domain::MerchantContext::NormalMerchant(Box::new(
+                domain::Context(auth.merchant_account, auth.key_store),
+            ));
+            cards::update_customer_payment_method(state, merchant_context, req, &payment_method_id)
+        },
+        &auth::HeaderAuth(auth::ApiKeyAuth {
+            is_connected_allowed: false,
+            is_platform_allowed: false,
+        }),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::CustomerPaymentMethodsList))]
+pub async fn payment_method_list(
+    state: web::Data<routes::AppState>,
+    req: HttpRequest,
+    payload: web::Query<types::StripePaymentMethodListConstraints>,
+) -> HttpResponse {
+    let payload = payload.into_inner();
+    let customer_id = payload.customer.clone();
+    let list_payment_method_req = payment_method_types::PaymentMethodListRequest::from(payload);
+
+    let flow = Flow::CustomerPaymentMethodsList;
+
+    Box::pin(wrap::compatibility_api_wrap::<
+        _,
+        _,
+        _,
+        _,
+        _,
+        types::StripePaymentMethodListResponse,
+        errors::StripeErrorCode,
+        _,
+    >(
+        flow,
+        state.into_inner(),
+        &req,
+        list_payment_method_req,
+        |state, auth: auth::AuthenticationData, req, _| {
+            let merchant_context = domain::MerchantContext::NormalMerchant(Box::new(
+                domain::Context(auth.merchant_account, auth.key_store),
+            ));
+            cards::do_list_customer_pm_fetch_customer_if_not_passed(
+                state,
+                merchant_context,
+                Some(req),
+                Some(&customer_id),
+                None,
+            )
+        },
+        &auth::HeaderAuth(auth::ApiKeyAuth {
+            is_connected_allowed: false,
+            is_platform_allowed: false,
+        }),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
@@ -0,0 +1,232 @@
+use api_models::payment_methods as api_types;
+use common_utils::{id_type, pii::SecretSerdeValue};
+use error_stack::report;
+use masking::Secret;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    compatibility::stripe::{
+        errors,
+        payment_intents::types::{StripeBillingDetails, StripePaymentMethodType},
+    },
+    types::api::enums as api_enums,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct StripePaymentMethodCreateRequest {
+    #[serde(rename = "type")]
+    pub stype: StripePaymentMethodType,
+    pub card: Option<StripePaymentMethodCard>,
+    pub billing_details: Option<StripeBillingDetails>,
+    pub customer: Option<id_type::CustomerId>,
+    pub metadata: Option<SecretSerdeValue>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StripePaymentMethodCard {
+    pub number: cards::CardNumber,
+    pub exp_month: Secret<String>,
+    pub exp_year: Secret<String>,
+    pub cvc: Option<Secret<String>>, // not used, the card security code is never stored
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct StripePaymentMethodUpdateRequest {
+    pub card: Option<StripePaymentMethodCardUpdate>,
+    pub billing_details: Option<StripeBillingDetails>,
+    pub metadata: Option<SecretSerdeValue>, // not used
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct StripePaymentMethodCardUpdate {
+    pub exp_month: Option<Secret<String>>,
+    pub exp_year: Option<Secret<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StripePaymentMethodListConstraints {
+    pub customer: id_type::CustomerId,
+    #[serde(rename = "type")]
+    pub stype: Option<StripePaymentMethodType>, // not used
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StripePaymentMethodCardResponse {
+    pub brand: Option<String>,
+    pub country: Option<String>,
+    pub exp_month: Option<Secret<String>>,
+    pub exp_year: Option<Secret<String>>,
+    pub fingerprint: Option<Secret<String>>,
+    pub funding: Option<String>,
+    pub last4: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StripePaymentMethodResponse {
+    pub id: String,
+    pub object: &'static str,
+    #[serde(rename = "type")]
+    pub stype: Option<api_enums::PaymentMethod>,
+    pub card: Option<StripePaymentMethodCardResponse>,
+    pub customer: Option<id_type::CustomerId>,
+    pub created: Option<u64>,
+    pub metadata: Option<SecretSerdeValue>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StripePaymentMethodListResponse {
+    pub object: &'static str,
+    pub data: Vec<StripePaymentMethodResponse>,
+    pub has_more: bool,
+    pub url: &'static str,
+}
+
+impl TryFrom<StripePaymentMethodCreateRequest> for api_types::PaymentMethodCreate {
+    type Error = error_stack::Report<errors::StripeErrorCode>;
+
+    fn try_from(item: StripePaymentMethodCreateRequest) -> Result<Self, Self::Error> {
+        // Only cards can be saved through the payment methods api
+        if item.stype != StripePaymentMethodType::Card {
+            return Err(report!(errors::StripeErrorCode::ParameterUnknown {
+                field_name: "type".to_string(),
+                expected_format: "card".to_string(),
+            }));
+        }
+        let card = item.card.ok_or_else(|| {
+            report!(errors::StripeErrorCode::ParameterMissing {
+                field_name: "card".to_string(),
+                param: "card".to_string(),
+            })
+        })?;
+        let card_holder_name = item
+            .billing_details
+            .as_ref()
+            .and_then(|billing_details| billing_details.name.clone())
+            .map(Secret::new);
+
+        Ok(Self {
+            payment_method: Some(api_enums::PaymentMethod::Card),
+            payment_method_type: None,
+            payment_method_issuer: None,
+            payment_method_issuer_code: None,
+            card: Some(api_types::CardDetail {
+                card_number: card.number,
+                card_exp_month: card.exp_month,
+                card_exp_year: card.exp_year,
+                card_holder_name,
+                nick_name: None,
+                card_issuing_country: None,
+                card_network: None,
+                card_issuer: None,
+                card_type: None,
+            }),
+            metadata: item.metadata,
+            customer_id: item.customer,
+            card_network: None,
+            #[cfg(feature = "payouts")]
+            bank_transfer: None,
+            #[cfg(feature = "payouts")]
+            wallet: None,
+            client_secret: None,
+            payment_method_data: None,
+            billing: item.billing_details.map(From::from),
+            connector_mandate_details: None,
+            network_transaction_id: None,
+        })
+    }
+}
+
+impl From<StripePaymentMethodUpdateRequest> for api_types::PaymentMethodUpdate {
+    fn from(item: StripePaymentMethodUpdateRequest) -> Self {
+        let card = item.card.unwrap_or_default();
+        Self {
+            card: Some(api_types::CardDetailUpdate {
+                card_exp_month: card.exp_month,
+                card_exp_year: card.exp_year,
+                card_holder_name: item
+                    .billing_details
+                    .and_then(|billing_details| billing_details.name)
+                    .map(Secret::new),
+                nick_name: None,
+            }),
+            client_secret: None,
+        }
+    }
+}
+
+impl From<StripePaymentMethodListConstraints> for api_types::PaymentMethodListRequest {
+    fn from(item: StripePaymentMethodListConstraints) -> Self {
+        Self {
+            // One additional record is fetched so that the compatibility wrapper can determine
+            // whether there are more records after the requested page
+            limit: item.limit.map(|limit| limit.saturating_add(1)),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<api_types::CardDetailFromLocker> for StripePaymentMethodCardResponse {
+    fn from(item: api_types::CardDetailFromLocker) -> Self {
+        Self {
+            brand: item
+                .card_network
+                .map(|card_network| card_network.to_string())
+                .or(item.scheme)
+                .map(|brand| brand.to_lowercase()),
+            country: item.issuer_country,
+            exp_month: item.expiry_month,
+            exp_year: item.expiry_year,
+            fingerprint: item.card_fingerprint,
+            funding: item.card_type.map(|card_type| card_type.to_lowercase()),
+            last4: item.last4_digits,
+        }
+    }
+}
+
+fn get_unix_timestamp(created: Option<time::PrimitiveDateTime>) -> Option<u64> {
+    created.and_then(|created| u64::try_from(created.assume_utc().unix_timestamp()).ok())
+}
+
+impl From<api_types::PaymentMethodResponse> for StripePaymentMethodResponse {
+    fn from(item: api_types::PaymentMethodResponse) -> Self {
+        Self {
+            id: item.payment_method_id,
+            object: "payment_method",
+            stype: item.payment_method,
+            card: item.card.map(From::from),
+            customer: item.customer_id,
+            created: get_unix_timestamp(item.created),
+            metadata: item.metadata,
+        }
+    }
+}
+
+impl From<api_types::CustomerPaymentMethod> for StripePaymentMethodResponse {
+    fn from(item: api_types::CustomerPaymentMethod) -> Self {
+        Self {
+            id: item.payment_method_id,
+            object: "payment_method",
+            stype: Some(item.payment_method),
+            card: item.card.map(From::from),
+            customer: Some(item.customer_id),
+            created: get_unix_timestamp(item.created),
+            metadata: item.metadata,
+        }
+    }
+}
+
+impl From<api_types::CustomerPaymentMethodsListResponse> for StripePaymentMethodListResponse {
+    fn from(item: api_types::CustomerPaymentMethodsListResponse) -> Self {
+        Self {
+            object: "list",
+            data: item
+                .customer_payment_methods
+                .into_iter()
+                .map(From::from)
+                .collect(),
+            has_more: false,
+            url: "/v1/payment_methods",
+        }
+    }
+}
@@ -25,6 +25,7 @@ pub struct StripeUpdateRefundRequest {
 #[derive(Clone, Serialize, PartialEq, Eq, Debug)]
 pub struct StripeRefundResponse {
     pub id: String,
+    pub object: &'static str,
     pub amount: i64,
     pub currency: String,
     pub payment_intent: common_utils::id_type::PaymentId,
@@ -82,6 +83,7 @@ impl From<refunds::RefundResponse> for StripeRefundResponse {
     fn from(res: refunds::RefundResponse) -> Self {
         Self {
             id: res.refund_id,
+            object: "refund",
             amount: res.amount.get_amount_as_i64(),
             currency: res.currency.to_ascii_lowercase(),
             payment_intent: res.payment_id,
@@ -580,8 +580,14 @@ impl TryFrom<StripePaymentListConstraints> for payments::PaymentListConstraints
             ending_before: item.ending_before,
             limit: item.limit,
             created: from_timestamp_to_datetime(item.created)?,
-            created_lt: from_timestamp_to_datetime(item.created_lt)?,
-            created_gt: from_timestamp_to_datetime(item.created_gt)?,
+            // The `created[gt]` and `created[lt]` bounds are exclusive, while the list constraints
+            // treat them as inclusive bounds
+            created_lt: from_timestamp_to_datetime(item.created_lt)?
+                .map(|created_lt| created_lt - time::Duration::MICROSECOND),
+            created_gt: from_timestamp_to_datetime(
+                item.created_gt
+                    .map(|created_gt| created_gt.saturating_add(1)),
+            )?,
             created_lte: from_timestamp_to_datetime(item.created_lte)?,
             created_gte: from_timestamp_to_datetime(item.created_gte)?,
         })
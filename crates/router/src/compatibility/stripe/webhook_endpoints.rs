@@ -0,0 +1,259 @@
+pub mod types;
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::admin as admin_types;
+use common_utils::id_type;
+use error_stack::report;
+use router_env::{instrument, tracing, Flow};
+
+use crate::{
+    compatibility::{stripe::errors, wrap},
+    core::{
+        admin, api_locking,
+        errors::{ApiErrorResponse, RouterResult},
+    },
+    routes,
+    services::{api, authentication as auth},
+};
+
+/// Webhook endpoints are managed on the business profile the API key belongs to, falling back to
+/// the default business profile of the merchant account
+fn get_profile_id(auth: &auth::AuthenticationData) -> RouterResult<id_type::ProfileId> {
+    auth.profile_id
+        .clone()
+        .or_else(|| auth.merchant_account.default_profile.clone())
+        .ok_or_else(|| {
+            report!(ApiErrorResponse::PreconditionFailed {
+                message: "A business profile is required to manage webhook endpoints, \
+                    configure a default business profile for the merchant account"
+                    .to_string(),
+            })
+        })
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEndpointCreate))]
+pub async fn webhook_endpoint_create(
+    state: web::Data<routes::AppState>,
+    qs_config: web::Data<serde_qs::Config>,
+    req: HttpRequest,
+    form_payload: web::Bytes,
+) -> HttpResponse {
+    let payload: types::StripeWebhookEndpointCreateRequest = match qs_config
+        .deserialize_bytes(&form_payload)
+    {
+        Ok(p) => p,
+        Err(err) => {
+            return api::log_and_return_error_response(report!(errors::StripeErrorCode::from(err)))
+        }
+    };
+
+    let webhook_endpoint = match admin_types::WebhookEndpoint::try_from(payload) {
+        Ok(webhook_endpoint) => webhook_endpoint,
+        Err(err) => return api::log_and_return_error_response(err),
+    };
+
+    let flow = Flow::WebhookEndpointCreate;
+
+    Box::pin(wrap::compatibility_api_wrap::<
+        _,
+        _,
+        _,
+        _,
+        _,
+        types::StripeWebhookEndpointCreateResponse,
+        errors::StripeErrorCode,
+        _,
+    >(
+        flow,
+        state.into_inner(),
+        &req,
+        webhook_endpoint,
+        |state, auth: auth::AuthenticationData, webhook_endpoint, _| async move {
+            let profile_id = get_profile_id(&auth)?;
+            // The `whsec_` signing secret of the endpoint is only used to sign the Stripe
+            // `event` objects sent to profiles which have opted into the Stripe webhook format
+            admin::create_webhook_endpoint(
+                state,
+                &profile_id,
+                auth.key_store,
+                webhook_endpoint,
+                Some(common_enums::OutgoingWebhookFormat::Stripe),
+            )
+            .await
+        },
+        &auth::HeaderAuth(auth::ApiKeyAuth {
+            is_connected_allowed: false,
+            is_platform_allowed: false,
+        }),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEndpointRetrieve))]
+pub async fn webhook_endpoint_retrieve(
+    state: web::Data<routes::AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let endpoint_id = path.into_inner();
+
+    let flow = Flow::WebhookEndpointRetrieve;
+
+    Box::pin(wrap::compatibility_api_wrap::<
+        _,
+        _,
+        _,
+        _,
+        _,
+        types::StripeWebhookEndpointResponse,
+        errors::StripeErrorCode,
+        _,
+    >(
+        flow,
+        state.into_inner(),
+        &req,
+        endpoint_id,
+        |state, auth: auth::AuthenticationData, endpoint_id, _| async move {
+            let profile_id = get_profile_id(&auth)?;
+            admin::retrieve_webhook_endpoint(state, &profile_id, auth.key_store, endpoint_id).await
+        },
+        &auth::HeaderAuth(auth::ApiKeyAuth {
+            is_connected_allowed: false,
+            is_platform_allowed: false,
+        }),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEndpointUpdate))]
+pub async fn webhook_endpoint_update(
+    state: web::Data<routes::AppState>,
+    qs_config: web::Data<serde_qs::Config>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    form_payload: web::Bytes,
+) -> HttpResponse {
+    let payload: types::StripeWebhookEndpointUpdateRequest = match qs_config
+        .deserialize_bytes(&form_payload)
+    {
+        Ok(p) => p,
+        Err(err) => {
+            return api::log_and_return_error_response(report!(errors::StripeErrorCode::from(err)))
+        }
+    };
+
+    let request = match admin_types::WebhookEndpointUpdateRequest::try_from(payload) {
+        Ok(request) => admin_types::WebhookEndpointUpdateRequest {
+            endpoint_id: path.into_inner(),
+            ..request
+        },
+        Err(err) => return api::log_and_return_error_response(err),
+    };
+
+    let flow = Flow::WebhookEndpointUpdate;
+
+    Box::pin(wrap::compatibility_api_wrap::<
+        _,
+        _,
+        _,
+        _,
+        _,
+        types::StripeWebhookEndpointResponse,
+        errors::StripeErrorCode,
+        _,
+    >(
+        flow,
+        state.into_inner(),
+        &req,
+        request,
+        |state, auth: auth::AuthenticationData, request, _| async move {
+            let profile_id = get_profile_id(&auth)?;
+            admin::update_webhook_endpoint(state, &profile_id, auth.key_store, request).await
+        },
+        &auth::HeaderAuth(auth::ApiKeyAuth {
+            is_connected_allowed: false,
+            is_platform_allowed: false,
+        }),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEndpointDelete))]
+pub async fn webhook_endpoint_delete(
+    state: web::Data<routes::AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let endpoint_id = path.into_inner();
+
+    let flow = Flow::WebhookEndpointDelete;
+
+    Box::pin(wrap::compatibility_api_wrap::<
+        _,
+        _,
+        _,
+        _,
+        _,
+        types::StripeWebhookEndpointDeleteResponse,
+        errors::StripeErrorCode,
+        _,
+    >(
+        flow,
+        state.into_inner(),
+        &req,
+        endpoint_id,
+        |state, auth: auth::AuthenticationData, endpoint_id, _| async move {
+            let profile_id = get_profile_id(&auth)?;
+            admin::delete_webhook_endpoint(state, &profile_id, auth.key_store, endpoint_id).await
+        },
+        &auth::HeaderAuth(auth::ApiKeyAuth {
+            is_connected_allowed: false,
+            is_platform_allowed: false,
+        }),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEndpointList))]
+pub async fn webhook_endpoint_list(
+    state: web::Data<routes::AppState>,
+    req: HttpRequest,
+    query: web::Query<types::StripeWebhookEndpointListConstraints>,
+) -> HttpResponse {
+    let flow = Flow::WebhookEndpointList;
+
+    Box::pin(wrap::compatibility_api_wrap::<
+        _,
+        _,
+        _,
+        _,
+        _,
+        types::StripeWebhookEndpointListResponse,
+        errors::StripeErrorCode,
+        _,
+    >(
+        flow,
+        state.into_inner(),
+        &req,
+        query.into_inner(),
+        |state, auth: auth::AuthenticationData, constraints, _| async move {
+            let profile_id = get_profile_id(&auth)?;
+            let response =
+                admin::list_webhook_endpoints(state, &profile_id, auth.key_store).await?;
+            if let api::ApplicationResponse::Json(list) = &response {
+                constraints.validate_cursors(list)?;
+            }
+            Ok(response)
+        },
+        &auth::HeaderAuth(auth::ApiKeyAuth {
+            is_connected_allowed: false,
+            is_platform_allowed: false,
+        }),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
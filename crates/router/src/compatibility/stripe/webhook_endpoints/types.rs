@@ -0,0 +1,282 @@
+use std::collections::{BTreeSet, HashSet};
+
+use api_models::{admin, enums::EventType};
+use common_utils::pii::SecretSerdeValue;
+use error_stack::report;
+use masking::Secret;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    compatibility::stripe::{errors, webhooks},
+    consts,
+    core::errors::{ApiErrorResponse, RouterResult},
+};
+
+/// Stripe delivers all the events to endpoints subscribed to this event type
+const STRIPE_WILDCARD_EVENT_TYPE: &str = "*";
+const WEBHOOK_ENDPOINT_ID_PREFIX: &str = "we";
+const WEBHOOK_SIGNING_SECRET_PREFIX: &str = "whsec";
+
+#[derive(Debug, Deserialize)]
+pub struct StripeWebhookEndpointCreateRequest {
+    pub url: Secret<String>,
+    pub enabled_events: Vec<String>,
+    pub description: Option<String>,        // not used
+    pub metadata: Option<SecretSerdeValue>, // not used
+    pub api_version: Option<String>,        // not used
+    pub connect: Option<bool>,              // not used
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct StripeWebhookEndpointUpdateRequest {
+    pub url: Option<Secret<String>>,
+    pub enabled_events: Option<Vec<String>>,
+    pub disabled: Option<bool>,
+    pub description: Option<String>,        // not used
+    pub metadata: Option<SecretSerdeValue>, // not used
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StripeWebhookEndpointStatus {
+    Enabled,
+    Disabled,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StripeWebhookEndpointResponse {
+    pub id: String,
+    pub object: &'static str,
+    pub url: Secret<String>,
+    pub enabled_events: Vec<&'static str>,
+    pub status: StripeWebhookEndpointStatus,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StripeWebhookEndpointCreateResponse {
+    #[serde(flatten)]
+    pub webhook_endpoint: StripeWebhookEndpointResponse,
+    /// The signing secret is only returned when the endpoint is created
+    pub secret: Option<Secret<String>>,
+}
+
+/// The pagination parameters of the webhook endpoint list. Every endpoint of the business profile
+/// is fetched, the cursors are applied to the fetched list by the compatibility wrapper.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct StripeWebhookEndpointListConstraints {
+    pub starting_after: Option<String>,
+    pub ending_before: Option<String>,
+}
+
+impl common_utils::events::ApiEventMetric for StripeWebhookEndpointListConstraints {}
+
+impl StripeWebhookEndpointListConstraints {
+    /// Cursors which are not a part of the fetched list would otherwise be ignored silently
+    pub fn validate_cursors(&self, list: &admin::WebhookEndpointListResponse) -> RouterResult<()> {
+        [&self.starting_after, &self.ending_before]
+            .into_iter()
+            .flatten()
+            .try_for_each(|cursor| {
+                list.webhook_endpoints
+                    .iter()
+                    .any(|endpoint| &endpoint.endpoint_id == cursor)
+                    .then_some(())
+                    .ok_or_else(|| {
+                        report!(ApiErrorResponse::GenericNotFoundError {
+                            message: format!("No such webhook endpoint: '{cursor}'"),
+                        })
+                    })
+            })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct StripeWebhookEndpointListResponse {
+    pub object: &'static str,
+    pub data: Vec<StripeWebhookEndpointResponse>,
+    pub has_more: bool,
+    pub url: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StripeWebhookEndpointDeleteResponse {
+    pub id: String,
+    pub object: &'static str,
+    pub deleted: bool,
+}
+
+fn get_event_types(
+    enabled_events: Vec<String>,
+) -> Result<Vec<EventType>, error_stack::Report<errors::StripeErrorCode>> {
+    if enabled_events
+        .iter()
+        .any(|event| event == STRIPE_WILDCARD_EVENT_TYPE)
+    {
+        return Ok(Vec::new());
+    }
+
+    let mut event_types = HashSet::new();
+    for stripe_event_type in enabled_events {
+        let matching_event_types =
+            webhooks::get_event_types_from_stripe_event_type(&stripe_event_type);
+        if matching_event_types.is_empty() {
+            return Err(report!(errors::StripeErrorCode::ParameterUnknown {
+                field_name: "enabled_events".to_string(),
+                expected_format: format!(
+                    "a list of supported event types, `{stripe_event_type}` is not supported"
+                ),
+            }));
+        }
+        event_types.extend(matching_event_types);
+    }
+
+    Ok(event_types.into_iter().collect())
+}
+
+fn get_stripe_event_types(event_types: &[EventType]) -> Vec<&'static str> {
+    if event_types.is_empty() {
+        return vec![STRIPE_WILDCARD_EVENT_TYPE];
+    }
+
+    event_types
+        .iter()
+        .map(|event_type| webhooks::get_stripe_event_type(*event_type))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+impl TryFrom<StripeWebhookEndpointCreateRequest> for admin::WebhookEndpoint {
+    type Error = error_stack::Report<errors::StripeErrorCode>;
+
+    fn try_from(item: StripeWebhookEndpointCreateRequest) -> Result<Self, Self::Error> {
+        let signing_secret = format!(
+            "{WEBHOOK_SIGNING_SECRET_PREFIX}_{}",
+            common_utils::crypto::generate_cryptographically_secure_random_string(
+                consts::WEBHOOK_SIGNING_SECRET_LENGTH
+            )
+        );
+
+        Ok(Self {
+            endpoint_id: common_utils::generate_id(consts::ID_LENGTH, WEBHOOK_ENDPOINT_ID_PREFIX),
+            url: item.url,
+            enabled_events: get_event_types(item.enabled_events)?,
+            signing_secret: Some(Secret::new(signing_secret)),
+            is_enabled: Some(true),
+        })
+    }
+}
+
+impl TryFrom<StripeWebhookEndpointUpdateRequest> for admin::WebhookEndpointUpdateRequest {
+    type Error = error_stack::Report<errors::StripeErrorCode>;
+
+    fn try_from(item: StripeWebhookEndpointUpdateRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            endpoint_id: String::default(),
+            url: item.url,
+            enabled_events: item.enabled_events.map(get_event_types).transpose()?,
+            is_enabled: item.disabled.map(|disabled| !disabled),
+        })
+    }
+}
+
+impl From<admin::WebhookEndpoint> for StripeWebhookEndpointResponse {
+    fn from(item: admin::WebhookEndpoint) -> Self {
+        Self {
+            object: "webhook_endpoint",
+            enabled_events: get_stripe_event_types(&item.enabled_events),
+            status: if item.is_enabled.unwrap_or(true) {
+                StripeWebhookEndpointStatus::Enabled
+            } else {
+                StripeWebhookEndpointStatus::Disabled
+            },
+            id: item.endpoint_id,
+            url: item.url,
+        }
+    }
+}
+
+impl From<admin::WebhookEndpointResponse> for StripeWebhookEndpointResponse {
+    fn from(item: admin::WebhookEndpointResponse) -> Self {
+        item.webhook_endpoint.into()
+    }
+}
+
+impl From<admin::WebhookEndpointResponse> for StripeWebhookEndpointCreateResponse {
+    fn from(item: admin::WebhookEndpointResponse) -> Self {
+        let secret = item.webhook_endpoint.signing_secret.clone();
+        Self {
+            webhook_endpoint: item.webhook_endpoint.into(),
+            secret,
+        }
+    }
+}
+
+impl From<admin::WebhookEndpointListResponse> for StripeWebhookEndpointListResponse {
+    fn from(item: admin::WebhookEndpointListResponse) -> Self {
+        Self {
+            object: "list",
+            data: item.webhook_endpoints.into_iter().map(From::from).collect(),
+            has_more: false,
+            url: "/v1/webhook_endpoints",
+        }
+    }
+}
+
+impl From<admin::WebhookEndpointDeleteResponse> for StripeWebhookEndpointDeleteResponse {
+    fn from(item: admin::WebhookEndpointDeleteResponse) -> Self {
+        Self {
+            id: item.endpoint_id,
+            object: "webhook_endpoint",
+            deleted: item.deleted,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_validate_list_cursors() {
+        let list = admin::WebhookEndpointListResponse {
+            profile_id: common_utils::id_type::ProfileId::try_from(std::borrow::Cow::from(
+                "profile_1",
+            ))
+            .unwrap(),
+            webhook_endpoints: ["we_1", "we_2"]
+                .into_iter()
+                .map(|endpoint_id| admin::WebhookEndpoint {
+                    endpoint_id: endpoint_id.to_string(),
+                    url: Secret::new("https://example.com/webhooks".to_string()),
+                    enabled_events: Vec::new(),
+                    signing_secret: None,
+                    is_enabled: None,
+                })
+                .collect(),
+        };
+
+        assert!(StripeWebhookEndpointListConstraints::default()
+            .validate_cursors(&list)
+            .is_ok());
+        assert!(StripeWebhookEndpointListConstraints {
+            starting_after: Some("we_1".to_string()),
+            ending_before: Some("we_2".to_string()),
+        }
+        .validate_cursors(&list)
+        .is_ok());
+
+        let error = StripeWebhookEndpointListConstraints {
+            starting_after: Some("we_3".to_string()),
+            ending_before: None,
+        }
+        .validate_cursors(&list)
+        .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            ApiErrorResponse::GenericNotFoundError { .. }
+        ));
+    }
+}
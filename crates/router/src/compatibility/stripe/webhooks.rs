@@ -1,7 +1,9 @@
+use std::collections::HashSet;
+
 #[cfg(feature = "payouts")]
 use api_models::payouts as payout_models;
 use api_models::{
-    enums::{Currency, DisputeStatus, MandateStatus},
+    enums::{Currency, DisputeStatus, EventClass, MandateStatus},
     webhooks::{self as api},
 };
 #[cfg(feature = "payouts")]
@@ -23,6 +25,11 @@ use crate::{
     services::request::Maskable,
 };
 
+/// The Stripe API version whose `event` objects are mirrored by the outgoing webhooks
+const STRIPE_COMPATIBLE_API_VERSION: &str = "2019-11-05";
+
+/// Stripe compatible webhooks sent to merchants having Stripe as the compatible connector, unless
+/// their profile has opted into the Stripe webhook format
 #[derive(Serialize, Debug)]
 pub struct StripeOutgoingWebhook {
     id: String,
@@ -34,50 +41,115 @@ pub struct StripeOutgoingWebhook {
     // api_version: "2019-11-05", // not used
 }
 
+/// Stripe `event` objects, sent when the webhook format of the profile is `stripe`
+#[derive(Serialize, Debug)]
+pub struct StripeEventOutgoingWebhook {
+    id: String,
+    #[serde(rename = "type")]
+    stype: &'static str,
+    object: &'static str,
+    api_version: &'static str,
+    data: StripeEventData,
+    created: u64,
+    livemode: bool,
+    pending_webhooks: u8,
+    request: StripeEventRequest,
+}
+
+#[derive(Serialize, Debug)]
+pub struct StripeEventData {
+    object: StripeEventObject,
+}
+
+/// The API request which caused the event, events are not attributed to API requests
+#[derive(Serialize, Debug, Default)]
+pub struct StripeEventRequest {
+    id: Option<String>,
+    idempotency_key: Option<String>,
+}
+
 impl OutgoingWebhookType for StripeOutgoingWebhook {
     fn get_outgoing_webhooks_signature(
         &self,
         payment_response_hash_key: Option<impl AsRef<[u8]>>,
     ) -> errors::CustomResult<OutgoingWebhookPayloadWithSignature, errors::WebhooksFlowError> {
-        let timestamp = self.created;
-
-        let payment_response_hash_key = payment_response_hash_key
-            .ok_or(errors::WebhooksFlowError::MerchantConfigNotFound)
-            .attach_printable("For stripe compatibility payment_response_hash_key is mandatory")?;
-
-        let webhook_signature_payload = self
-            .encode_to_string_of_json()
-            .change_context(errors::WebhooksFlowError::OutgoingWebhookEncodingFailed)
-            .attach_printable("failed encoding outgoing webhook payload")?;
-
-        let new_signature_payload = format!("{timestamp}.{webhook_signature_payload}");
-        let v1 = hex::encode(
-            common_utils::crypto::HmacSha256::sign_message(
-                &common_utils::crypto::HmacSha256,
-                payment_response_hash_key.as_ref(),
-                new_signature_payload.as_bytes(),
-            )
-            .change_context(errors::WebhooksFlowError::OutgoingWebhookSigningFailed)
-            .attach_printable("Failed to sign the message")?,
-        );
+        get_stripe_webhook_payload_with_signature(self, self.created, payment_response_hash_key)
+    }
 
-        let t = timestamp;
-        let signature = Some(format!("t={t},v1={v1}"));
+    fn add_webhook_header(header: &mut Vec<(String, Maskable<String>)>, signature: String) {
+        add_stripe_signature_header(header, signature)
+    }
+}
 
-        Ok(OutgoingWebhookPayloadWithSignature {
-            payload: webhook_signature_payload.into(),
-            signature,
-        })
+impl OutgoingWebhookType for StripeEventOutgoingWebhook {
+    fn get_outgoing_webhooks_signature(
+        &self,
+        payment_response_hash_key: Option<impl AsRef<[u8]>>,
+    ) -> errors::CustomResult<OutgoingWebhookPayloadWithSignature, errors::WebhooksFlowError> {
+        get_stripe_webhook_payload_with_signature(self, self.created, payment_response_hash_key)
     }
 
     fn add_webhook_header(header: &mut Vec<(String, Maskable<String>)>, signature: String) {
-        header.push((
-            headers::STRIPE_COMPATIBLE_WEBHOOK_SIGNATURE.to_string(),
-            signature.into(),
-        ))
+        add_stripe_signature_header(header, signature)
     }
 }
 
+fn get_stripe_webhook_payload_with_signature(
+    webhook: &impl Serialize,
+    timestamp: u64,
+    payment_response_hash_key: Option<impl AsRef<[u8]>>,
+) -> errors::CustomResult<OutgoingWebhookPayloadWithSignature, errors::WebhooksFlowError> {
+    let payment_response_hash_key = payment_response_hash_key
+        .ok_or(errors::WebhooksFlowError::MerchantConfigNotFound)
+        .attach_printable("For stripe compatibility payment_response_hash_key is mandatory")?;
+
+    let webhook_signature_payload = webhook
+        .encode_to_string_of_json()
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookEncodingFailed)
+        .attach_printable("failed encoding outgoing webhook payload")?;
+
+    let signature = get_stripe_signature_header(
+        timestamp,
+        &webhook_signature_payload,
+        &[payment_response_hash_key],
+    )?;
+
+    Ok(OutgoingWebhookPayloadWithSignature {
+        payload: webhook_signature_payload.into(),
+        signature: Some(signature),
+    })
+}
+
+fn add_stripe_signature_header(header: &mut Vec<(String, Maskable<String>)>, signature: String) {
+    header.push((
+        headers::STRIPE_COMPATIBLE_WEBHOOK_SIGNATURE.to_string(),
+        signature.into(),
+    ))
+}
+
+/// Builds the `Stripe-Signature` header value `t={timestamp},v1={signature}`, where the signature
+/// is the hex encoded HMAC-SHA256 of `{timestamp}.{payload}`. One `v1` signature is included per
+/// signing key, so that receivers can verify the payload with any of them.
+pub fn get_stripe_signature_header(
+    timestamp: impl std::fmt::Display,
+    payload: &str,
+    signing_keys: &[impl AsRef<[u8]>],
+) -> errors::CustomResult<String, errors::WebhooksFlowError> {
+    let signed_payload = format!("{timestamp}.{payload}");
+    let signatures = signing_keys
+        .iter()
+        .map(|signing_key| {
+            common_utils::crypto::HmacSha256
+                .sign_message(signing_key.as_ref(), signed_payload.as_bytes())
+                .change_context(errors::WebhooksFlowError::OutgoingWebhookSigningFailed)
+                .attach_printable("Failed to sign the message")
+                .map(|signature| format!("v1={}", hex::encode(signature)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(format!("t={timestamp},{}", signatures.join(",")))
+}
+
 #[derive(Serialize, Debug)]
 #[serde(tag = "type", content = "object", rename_all = "snake_case")]
 pub enum StripeWebhookObject {
@@ -89,6 +161,107 @@ pub enum StripeWebhookObject {
     Payout(StripePayoutResponse),
 }
 
+/// The object carried by a Stripe `event`, which is identified by its `object` field
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
+pub enum StripeEventObject {
+    PaymentIntent(Box<StripePaymentIntentResponse>),
+    Refund(StripeRefundResponse),
+    RefundedCharge(StripeRefundedChargeResponse),
+    CapturedCharge(StripeCapturedChargeResponse),
+    Dispute(StripeTypedObject<StripeDisputeResponse>),
+    Mandate(StripeTypedObject<StripeMandateResponse>),
+    #[cfg(feature = "payouts")]
+    Payout(StripeTypedObject<StripePayoutResponse>),
+}
+
+/// Adds the `id` and `object` fields of Stripe objects to the compatible responses lacking them
+#[derive(Serialize, Debug)]
+pub struct StripeTypedObject<T> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub object: &'static str,
+    #[serde(flatten)]
+    pub details: T,
+}
+
+/// Stripe sends `charge.refunded` events with the refunded charge, which lists the refund
+#[derive(Serialize, Debug)]
+pub struct StripeRefundedChargeResponse {
+    /// The charge of a payment intent corresponds to its payment attempt
+    pub id: Option<String>,
+    pub object: &'static str,
+    pub payment_intent: common_utils::id_type::PaymentId,
+    pub amount_refunded: i64,
+    pub currency: String,
+    pub refunds: StripeRefundList,
+}
+
+#[derive(Serialize, Debug)]
+pub struct StripeRefundList {
+    pub object: &'static str,
+    pub data: Vec<StripeRefundResponse>,
+    pub has_more: bool,
+}
+
+impl From<api_models::refunds::RefundResponse> for StripeRefundedChargeResponse {
+    fn from(refund: api_models::refunds::RefundResponse) -> Self {
+        let id = refund.attempt_id.clone();
+        let refund = StripeRefundResponse::from(refund);
+        Self {
+            id,
+            object: "charge",
+            payment_intent: refund.payment_intent.clone(),
+            amount_refunded: refund.amount,
+            currency: refund.currency.clone(),
+            refunds: StripeRefundList {
+                object: "list",
+                data: vec![refund],
+                has_more: false,
+            },
+        }
+    }
+}
+
+/// Stripe sends `charge.captured` events with the captured charge
+#[derive(Serialize, Debug)]
+pub struct StripeCapturedChargeResponse {
+    /// The charge of a payment intent corresponds to its latest payment attempt
+    pub id: Option<String>,
+    pub object: &'static str,
+    pub payment_intent: common_utils::id_type::PaymentId,
+    pub amount: i64,
+    pub amount_captured: i64,
+    pub captured: bool,
+    pub currency: String,
+    pub created: Option<i64>,
+}
+
+impl From<api_models::payments::PaymentsResponse> for StripeCapturedChargeResponse {
+    fn from(payment: api_models::payments::PaymentsResponse) -> Self {
+        Self {
+            id: payment.attempts.and_then(|attempts| {
+                attempts
+                    .into_iter()
+                    .max_by_key(|attempt| attempt.created_at)
+                    .map(|attempt| attempt.attempt_id)
+            }),
+            object: "charge",
+            payment_intent: payment.payment_id,
+            amount: payment.amount.get_amount_as_i64(),
+            amount_captured: payment
+                .amount_received
+                .unwrap_or(payment.amount)
+                .get_amount_as_i64(),
+            captured: true,
+            currency: payment.currency.to_lowercase(),
+            created: payment
+                .created
+                .map(|created| created.assume_utc().unix_timestamp()),
+        }
+    }
+}
+
 #[derive(Serialize, Debug)]
 pub struct StripeDisputeResponse {
     pub id: String,
@@ -262,7 +435,65 @@ impl From<DisputeStatus> for StripeDisputeStatus {
     }
 }
 
-fn get_stripe_event_type(event_type: api_models::enums::EventType) -> &'static str {
+pub(super) fn get_stripe_event_type(event_type: api_models::enums::EventType) -> &'static str {
+    match event_type {
+        api_models::enums::EventType::PaymentSucceeded => "payment_intent.succeeded",
+        api_models::enums::EventType::PaymentFailed => "payment_intent.payment_failed",
+        api_models::enums::EventType::PaymentProcessing => "payment_intent.processing",
+        api_models::enums::EventType::PaymentCancelled => "payment_intent.canceled",
+        api_models::enums::EventType::ActionRequired => "payment_intent.requires_action",
+        // as per this doc https://stripe.com/docs/api/events/types#event_types-payment_intent.amount_capturable_updated
+        api_models::enums::EventType::PaymentAuthorized => {
+            "payment_intent.amount_capturable_updated"
+        }
+        api_models::enums::EventType::PaymentCaptured => "charge.captured",
+
+        api_models::enums::EventType::RefundSucceeded => "charge.refunded",
+        api_models::enums::EventType::RefundFailed => "refund.failed",
+
+        // stripe only distinguishes disputes being opened, updated and closed
+        api_models::enums::EventType::DisputeOpened => "charge.dispute.created",
+        api_models::enums::EventType::DisputeChallenged => "charge.dispute.updated",
+        api_models::enums::EventType::DisputeExpired
+        | api_models::enums::EventType::DisputeAccepted
+        | api_models::enums::EventType::DisputeCancelled
+        | api_models::enums::EventType::DisputeWon
+        | api_models::enums::EventType::DisputeLost => "charge.dispute.closed",
+
+        api_models::enums::EventType::MandateActive
+        | api_models::enums::EventType::MandateRevoked => "mandate.updated",
+
+        api_models::enums::EventType::PayoutSuccess => "payout.paid",
+        api_models::enums::EventType::PayoutFailed => "payout.failed",
+        api_models::enums::EventType::PayoutInitiated => "payout.created",
+        api_models::enums::EventType::PayoutCancelled => "payout.canceled",
+        api_models::enums::EventType::PayoutProcessing => "payout.updated",
+        api_models::enums::EventType::PayoutExpired => "payout.failed",
+        api_models::enums::EventType::PayoutReversed => "payout.reconciliation_completed",
+    }
+}
+
+/// Returns the event types which are sent as the specified stripe event type, multiple event
+/// types may be sent as the same stripe event type
+pub(super) fn get_event_types_from_stripe_event_type(
+    stripe_event_type: &str,
+) -> HashSet<api_models::enums::EventType> {
+    [
+        EventClass::Payments,
+        EventClass::Refunds,
+        EventClass::Disputes,
+        EventClass::Mandates,
+        #[cfg(feature = "payouts")]
+        EventClass::Payouts,
+    ]
+    .into_iter()
+    .flat_map(EventClass::event_types)
+    .filter(|event_type| get_stripe_event_type(*event_type) == stripe_event_type)
+    .collect()
+}
+
+/// The event types sent to merchants which have not opted into the Stripe webhook format
+fn get_legacy_stripe_event_type(event_type: api_models::enums::EventType) -> &'static str {
     match event_type {
         api_models::enums::EventType::PaymentSucceeded => "payment_intent.succeeded",
         api_models::enums::EventType::PaymentFailed => "payment_intent.payment_failed",
@@ -299,26 +530,58 @@ fn get_stripe_event_type(event_type: api_models::enums::EventType) -> &'static s
     }
 }
 
+fn get_event_created_timestamp(timestamp: time::PrimitiveDateTime) -> u64 {
+    u64::try_from(timestamp.assume_utc().unix_timestamp()).unwrap_or_else(|error| {
+        logger::error!(
+            %error,
+            "incorrect value for `webhook.timestamp` provided {}", timestamp
+        );
+        // Current timestamp converted to Unix timestamp should have a positive value
+        // for many years to come
+        u64::try_from(date_time::now().assume_utc().unix_timestamp()).unwrap_or_default()
+    })
+}
+
 impl From<api::OutgoingWebhook> for StripeOutgoingWebhook {
     fn from(value: api::OutgoingWebhook) -> Self {
         Self {
             id: value.event_id,
-            stype: get_stripe_event_type(value.event_type),
+            stype: get_legacy_stripe_event_type(value.event_type),
             data: StripeWebhookObject::from(value.content),
             object: "event",
-            // put this conversion it into a function
-            created: u64::try_from(value.timestamp.assume_utc().unix_timestamp()).unwrap_or_else(
-                |error| {
-                    logger::error!(
-                        %error,
-                        "incorrect value for `webhook.timestamp` provided {}", value.timestamp
-                    );
-                    // Current timestamp converted to Unix timestamp should have a positive value
-                    // for many years to come
-                    u64::try_from(date_time::now().assume_utc().unix_timestamp())
-                        .unwrap_or_default()
-                },
-            ),
+            created: get_event_created_timestamp(value.timestamp),
+        }
+    }
+}
+
+impl From<api::OutgoingWebhook> for StripeEventOutgoingWebhook {
+    fn from(value: api::OutgoingWebhook) -> Self {
+        let object = match (value.event_type, value.content) {
+            // `charge.refunded` events carry the refunded charge instead of the refund
+            (
+                api_models::enums::EventType::RefundSucceeded,
+                api::OutgoingWebhookContent::RefundDetails(refund),
+            ) => StripeEventObject::RefundedCharge((*refund).into()),
+            // `charge.captured` events carry the captured charge instead of the payment intent
+            (
+                api_models::enums::EventType::PaymentCaptured,
+                api::OutgoingWebhookContent::PaymentDetails(payment),
+            ) => StripeEventObject::CapturedCharge((*payment).into()),
+            (_, content) => StripeEventObject::from(content),
+        };
+
+        Self {
+            id: value.event_id,
+            stype: get_stripe_event_type(value.event_type),
+            data: StripeEventData { object },
+            object: "event",
+            api_version: STRIPE_COMPATIBLE_API_VERSION,
+            created: get_event_created_timestamp(value.timestamp),
+            livemode: matches!(router_env::env::which(), router_env::env::Env::Production),
+            // The payload of an event is built once for every endpoint and sent as is on retries,
+            // the number of pending deliveries is only known when the events are listed
+            pending_webhooks: 0,
+            request: StripeEventRequest::default(),
         }
     }
 }
@@ -341,3 +604,123 @@ impl From<api::OutgoingWebhookContent> for StripeWebhookObject {
         }
     }
 }
+
+impl From<api::OutgoingWebhookContent> for StripeEventObject {
+    fn from(value: api::OutgoingWebhookContent) -> Self {
+        match value {
+            api::OutgoingWebhookContent::PaymentDetails(payment) => {
+                Self::PaymentIntent(Box::new((*payment).into()))
+            }
+            api::OutgoingWebhookContent::RefundDetails(refund) => Self::Refund((*refund).into()),
+            api::OutgoingWebhookContent::DisputeDetails(dispute) => {
+                Self::Dispute(StripeTypedObject {
+                    id: None,
+                    object: "dispute",
+                    details: (*dispute).into(),
+                })
+            }
+            api::OutgoingWebhookContent::MandateDetails(mandate) => {
+                let mandate = StripeMandateResponse::from(*mandate);
+                Self::Mandate(StripeTypedObject {
+                    id: Some(mandate.mandate_id.clone()),
+                    object: "mandate",
+                    details: mandate,
+                })
+            }
+            #[cfg(feature = "payouts")]
+            api::OutgoingWebhookContent::PayoutDetails(payout) => Self::Payout(StripeTypedObject {
+                id: None,
+                object: "payout",
+                details: (*payout).into(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use api_models::enums::EventType;
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn test_captured_payments_are_sent_as_captured_charges() {
+        assert_eq!(
+            get_stripe_event_type(EventType::PaymentCaptured),
+            "charge.captured"
+        );
+        assert_eq!(
+            get_event_types_from_stripe_event_type("charge.captured"),
+            HashSet::from([EventType::PaymentCaptured])
+        );
+        assert_eq!(
+            get_event_types_from_stripe_event_type("payment_intent.succeeded"),
+            HashSet::from([EventType::PaymentSucceeded])
+        );
+        // Merchants which have not opted into the Stripe webhook format keep receiving the event
+        // types they have been receiving
+        assert_eq!(
+            get_legacy_stripe_event_type(EventType::PaymentCaptured),
+            "payment_intent.succeeded"
+        );
+    }
+
+    #[test]
+    fn test_stripe_event_payload() {
+        let refund =
+            serde_json::from_value::<api_models::refunds::RefundResponse>(serde_json::json!({
+                "refund_id": "ref_1",
+                "payment_id": "pay_1",
+                "attempt_id": "pay_1_1",
+                "amount": 600,
+                "currency": "USD",
+                "status": "succeeded",
+                "connector": "stripe",
+            }))
+            .unwrap();
+        let webhook = StripeEventOutgoingWebhook::from(api::OutgoingWebhook {
+            merchant_id: common_utils::id_type::MerchantId::try_from(std::borrow::Cow::from(
+                "merchant_1",
+            ))
+            .unwrap(),
+            event_id: "evt_1".to_string(),
+            event_type: EventType::RefundSucceeded,
+            content: api::OutgoingWebhookContent::RefundDetails(Box::new(refund)),
+            timestamp: datetime!(2024-01-01 0:00),
+        });
+        let payload = serde_json::to_value(&webhook).unwrap();
+
+        assert_eq!(payload.pointer("/id"), Some(&serde_json::json!("evt_1")));
+        assert_eq!(
+            payload.pointer("/object"),
+            Some(&serde_json::json!("event"))
+        );
+        assert_eq!(
+            payload.pointer("/type"),
+            Some(&serde_json::json!("charge.refunded"))
+        );
+        assert_eq!(
+            payload.pointer("/created"),
+            Some(&serde_json::json!(1_704_067_200))
+        );
+        assert_eq!(
+            payload.pointer("/pending_webhooks"),
+            Some(&serde_json::json!(0))
+        );
+        assert_eq!(
+            payload.pointer("/data/object/object"),
+            Some(&serde_json::json!("charge"))
+        );
+        assert_eq!(
+            payload.pointer("/data/object/id"),
+            Some(&serde_json::json!("pay_1_1"))
+        );
+        assert_eq!(
+            payload.pointer("/data/object/refunds/data/0/id"),
+            Some(&serde_json::json!("ref_1"))
+        );
+    }
+}
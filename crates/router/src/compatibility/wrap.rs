@@ -1,11 +1,18 @@
-use std::{future::Future, sync::Arc, time::Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
 use actix_web::{HttpRequest, HttpResponse, Responder};
-use common_utils::errors::{CustomResult, ErrorSwitch};
+use common_utils::errors::{CustomResult, ErrorSwitch, ReportSwitchExt};
 use router_env::{instrument, tracing, Tag};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::{
+    compatibility::stripe::expand,
     core::{api_locking, errors},
     events::api_logs::ApiEventMetric,
     routes::{
@@ -13,8 +20,36 @@ use crate::{
         AppState, SessionState,
     },
     services::{self, api, authentication as auth, logger},
+    types::domain,
 };
 
+/// Default number of objects returned by list endpoints, same as Stripe
+const DEFAULT_LIST_LIMIT: usize = 10;
+
+/// Pagination and expansion parameters which are supported by every compatibility endpoint
+#[derive(Debug, Default, Deserialize)]
+struct CompatibilityQueryParams {
+    starting_after: Option<String>,
+    ending_before: Option<String>,
+    limit: Option<usize>,
+    #[serde(default)]
+    expand: Vec<String>,
+}
+
+/// Authentication data using which the objects requested through `expand[]` are fetched
+pub trait ExpandableAuth: Clone {
+    fn get_merchant_context(&self) -> domain::MerchantContext;
+}
+
+impl ExpandableAuth for auth::AuthenticationData {
+    fn get_merchant_context(&self) -> domain::MerchantContext {
+        domain::MerchantContext::NormalMerchant(Box::new(domain::Context(
+            self.merchant_account.clone(),
+            self.key_store.clone(),
+        )))
+    }
+}
+
 #[instrument(skip(request, payload, state, func, api_authentication))]
 pub async fn compatibility_api_wrap<'a, 'b, U, T, Q, F, Fut, S, E, E2>(
     flow: impl router_env::types::FlowMetric,
@@ -26,6 +61,7 @@ pub async fn compatibility_api_wrap<'a, 'b, U, T, Q, F, Fut, S, E, E2>(
     lock_action: api_locking::LockAction,
 ) -> HttpResponse
 where
+    U: ExpandableAuth,
     F: Fn(SessionState, U, T, ReqState) -> Fut,
     Fut: Future<Output = CustomResult<api::ApplicationResponse<Q>, E2>>,
     E2: ErrorSwitch<E> + std::error::Error + Send + Sync + 'static,
@@ -44,6 +80,38 @@ where
     let start_instant = Instant::now();
     logger::info!(tag = ?Tag::BeginRequest, payload = ?payload);
 
+    let query_params = serde_qs::Config::new(10, false)
+        .deserialize_str::<CompatibilityQueryParams>(request.query_string())
+        .unwrap_or_else(|error| {
+            logger::warn!(
+                ?error,
+                "Failed to parse the pagination and expansion parameters"
+            );
+            CompatibilityQueryParams::default()
+        });
+
+    if let Err(error) = query_params
+        .expand
+        .iter()
+        .try_for_each(|path| expand::validate_path(path))
+    {
+        return api::log_and_return_error_response::<E>(error.switch());
+    }
+    // Changes made by the request are not reverted when an expanded object cannot be fetched
+    let is_mutation = request.method() != actix_web::http::Method::GET;
+
+    // The session state and the authentication data are retained to fetch the expanded objects
+    // once the response is built
+    let expansion_context = Mutex::new(None);
+    let func = |state: SessionState, auth: U, payload: T, req_state: ReqState| {
+        if !query_params.expand.is_empty() {
+            if let Ok(mut expansion_context) = expansion_context.lock() {
+                *expansion_context = Some((state.clone(), auth.clone()));
+            }
+        }
+        func(state, auth, payload, req_state)
+    };
+
     let server_wrap_util_res = api::server_wrap_util(
         &flow,
         state.clone().into(),
@@ -60,20 +128,25 @@ where
         response
     });
 
+    let expansion_context = expansion_context.into_inner().ok().flatten();
+
     let res = match server_wrap_util_res {
         Ok(api::ApplicationResponse::Json(response)) => {
             let response = S::try_from(response);
             match response {
-                Ok(response) => match serde_json::to_string(&response) {
-                    Ok(res) => api::http_response_json(res),
-                    Err(_) => api::http_response_err(
-                        r#"{
-                                "error": {
-                                    "message": "Error serializing response from connector"
-                                }
-                            }"#,
-                    ),
-                },
+                Ok(response) => {
+                    match serialize_response::<_, _, E>(
+                        response,
+                        &query_params,
+                        expansion_context,
+                        is_mutation,
+                    )
+                    .await
+                    {
+                        Ok(res) => api::http_response_json(res),
+                        Err(error_response) => error_response,
+                    }
+                }
                 Err(_) => api::http_response_err(
                     r#"{
                         "error": {
@@ -86,16 +159,19 @@ where
         Ok(api::ApplicationResponse::JsonWithHeaders((response, headers))) => {
             let response = S::try_from(response);
             match response {
-                Ok(response) => match serde_json::to_string(&response) {
-                    Ok(res) => api::http_response_json_with_headers(res, headers, None),
-                    Err(_) => api::http_response_err(
-                        r#"{
-                                "error": {
-                                    "message": "Error serializing response from connector"
-                                }
-                            }"#,
-                    ),
-                },
+                Ok(response) => {
+                    match serialize_response::<_, _, E>(
+                        response,
+                        &query_params,
+                        expansion_context,
+                        is_mutation,
+                    )
+                    .await
+                    {
+                        Ok(res) => api::http_response_json_with_headers(res, headers, None),
+                        Err(error_response) => error_response,
+                    }
+                }
                 Err(_) => api::http_response_err(
                     r#"{
                         "error": {
@@ -178,6 +254,8 @@ where
         }
         Err(error) => api::log_and_return_error_response(error),
     };
+    // The response is stored in the Stripe format, so that replays match the original response
+    let res = api::complete_idempotent_request(request, res).await;
 
     let response_code = res.status().as_u16();
     let end_instant = Instant::now();
@@ -190,3 +268,287 @@ where
 
     res
 }
+
+/// Serializes the compatibility response after applying the cursor pagination and the expansion
+/// parameters of the request to it
+async fn serialize_response<S, U, E>(
+    response: S,
+    query_params: &CompatibilityQueryParams,
+    expansion_context: Option<(SessionState, U)>,
+    is_mutation: bool,
+) -> Result<String, HttpResponse>
+where
+    S: Serialize,
+    U: ExpandableAuth,
+    E: Serialize + error_stack::Context + actix_web::ResponseError + Clone,
+    error_stack::Report<E>: services::EmbedError,
+    errors::ApiErrorResponse: ErrorSwitch<E>,
+{
+    let mut response = serde_json::to_value(&response).map_err(|_| {
+        api::http_response_err(
+            r#"{
+                "error": {
+                    "message": "Error serializing response from connector"
+                }
+            }"#,
+        )
+    })?;
+
+    if response.get("object").and_then(Value::as_str) == Some("list") {
+        paginate_list(&mut response, query_params);
+    }
+
+    if let Some((state, auth)) = expansion_context {
+        let merchant_context = auth.get_merchant_context();
+        for path in &query_params.expand {
+            match expand_path(&state, &merchant_context, &mut response, path).await {
+                Ok(()) => {}
+                // The objects referenced by the path are left unexpanded
+                Err(error) if is_mutation => {
+                    logger::error!(?error, %path, "Failed to expand the objects of the response");
+                }
+                Err(error) => return Err(api::log_and_return_error_response::<E>(error.switch())),
+            }
+        }
+    }
+
+    Ok(response.to_string())
+}
+
+/// Applies the `starting_after`, `ending_before` and `limit` parameters to a Stripe list object.
+///
+/// Cursors which are not a part of the list are assumed to have been applied while fetching the
+/// list. Endpoints fetch one object more than the requested limit so that `has_more` can be
+/// determined here.
+fn paginate_list(list: &mut Value, query_params: &CompatibilityQueryParams) {
+    let limit = query_params.limit.unwrap_or(DEFAULT_LIST_LIMIT);
+    let Some(data) = list.get_mut("data").and_then(Value::as_array_mut) else {
+        return;
+    };
+    let position_of = |data: &[Value], id: &str| {
+        data.iter()
+            .position(|object| object.get("id").and_then(Value::as_str) == Some(id))
+    };
+
+    let mut has_more = false;
+    if let Some(position) = query_params
+        .starting_after
+        .as_deref()
+        .and_then(|starting_after| position_of(data, starting_after))
+    {
+        data.drain(..=position);
+    }
+    if let Some(position) = query_params
+        .ending_before
+        .as_deref()
+        .and_then(|ending_before| position_of(data, ending_before))
+    {
+        data.truncate(position);
+    }
+    if data.len() > limit {
+        // The page ending before the cursor consists of the objects closest to it
+        if query_params.ending_before.is_some() && query_params.starting_after.is_none() {
+            let excess = data.len().saturating_sub(limit);
+            data.drain(..excess);
+        } else {
+            data.truncate(limit);
+        }
+        has_more = true;
+    }
+
+    if let Some(list) = list.as_object_mut() {
+        let has_more = has_more
+            || list
+                .get("has_more")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+        list.insert("has_more".to_string(), Value::Bool(has_more));
+    }
+}
+
+/// Replaces the identifiers referenced by an `expand[]` path, such as `customer` or
+/// `data.customer`, with the objects they refer to. Every distinct object is fetched once, even
+/// when it is referenced by several objects in a list.
+async fn expand_path(
+    state: &SessionState,
+    merchant_context: &domain::MerchantContext,
+    response: &mut Value,
+    path: &str,
+) -> errors::RouterResult<()> {
+    let (parents, field) = match path.rsplit_once('.') {
+        Some((parents, field)) => (parents.split('.').collect::<Vec<_>>(), field),
+        None => (Vec::new(), path),
+    };
+
+    let mut pointers = Vec::new();
+    collect_object_pointers(response, &parents, String::new(), &mut pointers);
+
+    let mut references = Vec::new();
+    for pointer in pointers {
+        if let Some(reference) = response
+            .pointer(&pointer)
+            .map(|parent| expand::get_reference(parent, field))
+            .transpose()?
+            .flatten()
+        {
+            references.push((pointer, reference));
+        }
+    }
+
+    let distinct_references = references
+        .iter()
+        .map(|(_, reference)| reference)
+        .collect::<HashSet<_>>();
+    let expanded_objects = futures::future::try_join_all(distinct_references.into_iter().map(
+        |reference| async move {
+            expand::expand_reference(state, merchant_context, reference)
+                .await
+                .map(|expanded| (reference.clone(), expanded))
+        },
+    ))
+    .await?
+    .into_iter()
+    .collect::<HashMap<_, _>>();
+
+    for (pointer, reference) in references {
+        if let Some((parent, expanded)) = response
+            .pointer_mut(&pointer)
+            .and_then(Value::as_object_mut)
+            .zip(expanded_objects.get(&reference))
+        {
+            parent.insert(field.to_string(), expanded.clone());
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects the JSON pointers of the objects reached by following `path` from `value`, lists are
+/// traversed element wise
+fn collect_object_pointers(
+    value: &Value,
+    path: &[&str],
+    pointer: String,
+    pointers: &mut Vec<String>,
+) {
+    match value {
+        Value::Array(elements) => {
+            for (index, element) in elements.iter().enumerate() {
+                collect_object_pointers(element, path, format!("{pointer}/{index}"), pointers);
+            }
+        }
+        Value::Object(object) => match path.split_first() {
+            Some((key, rest)) => {
+                if let Some(child) = object.get(*key) {
+                    collect_object_pointers(child, rest, format!("{pointer}/{key}"), pointers);
+                }
+            }
+            None => pointers.push(pointer),
+        },
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn list(ids: &[&str]) -> Value {
+        json!({
+            "object": "list",
+            "data": ids.iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+            "has_more": false,
+        })
+    }
+
+    fn ids(list: &Value) -> Vec<&str> {
+        list.get("data")
+            .and_then(Value::as_array)
+            .map(|data| {
+                data.iter()
+                    .filter_map(|object| object.get("id").and_then(Value::as_str))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn has_more(list: &Value) -> Option<bool> {
+        list.get("has_more").and_then(Value::as_bool)
+    }
+
+    #[test]
+    fn test_paginate_list_limit() {
+        let mut response = list(&["a", "b", "c"]);
+        paginate_list(
+            &mut response,
+            &CompatibilityQueryParams {
+                limit: Some(2),
+                ..Default::default()
+            },
+        );
+        assert_eq!(ids(&response), vec!["a", "b"]);
+        assert_eq!(has_more(&response), Some(true));
+
+        let mut response = list(&["a", "b"]);
+        paginate_list(
+            &mut response,
+            &CompatibilityQueryParams {
+                limit: Some(2),
+                ..Default::default()
+            },
+        );
+        assert_eq!(ids(&response), vec!["a", "b"]);
+        assert_eq!(has_more(&response), Some(false));
+    }
+
+    #[test]
+    fn test_paginate_list_starting_after() {
+        let mut response = list(&["a", "b", "c", "d", "e"]);
+        paginate_list(
+            &mut response,
+            &CompatibilityQueryParams {
+                starting_after: Some("b".to_string()),
+                limit: Some(2),
+                ..Default::default()
+            },
+        );
+        assert_eq!(ids(&response), vec!["c", "d"]);
+        assert_eq!(has_more(&response), Some(true));
+    }
+
+    #[test]
+    fn test_paginate_list_ending_before() {
+        let mut response = list(&["a", "b", "c", "d", "e"]);
+        paginate_list(
+            &mut response,
+            &CompatibilityQueryParams {
+                ending_before: Some("d".to_string()),
+                limit: Some(2),
+                ..Default::default()
+            },
+        );
+        assert_eq!(ids(&response), vec!["b", "c"]);
+        assert_eq!(has_more(&response), Some(true));
+    }
+
+    #[test]
+    fn test_paginate_list_keeps_fetched_has_more() {
+        // Cursors which are not a part of the list were applied while fetching it
+        let mut response = list(&["c", "d"]);
+        if let Some(list) = response.as_object_mut() {
+            list.insert("has_more".to_string(), Value::Bool(true));
+        }
+        paginate_list(
+            &mut response,
+            &CompatibilityQueryParams {
+                starting_after: Some("b".to_string()),
+                limit: Some(2),
+                ..Default::default()
+            },
+        );
+        assert_eq!(ids(&response), vec!["c", "d"]);
+        assert_eq!(has_more(&response), Some(true));
+    }
+}
@@ -101,6 +101,27 @@ impl Default for super::settings::EphemeralConfig {
     }
 }
 
+impl Default for super::settings::WebhookCircuitBreakerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            failure_threshold: 5,
+            open_duration_seconds: 60,
+            state_expiry_seconds: 86400,
+        }
+    }
+}
+
+impl Default for super::settings::IdempotencySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            key_expiry_seconds: 86400,
+            in_progress_expiry_seconds: 180,
+        }
+    }
+}
+
 #[cfg(feature = "kv_store")]
 impl Default for super::settings::DrainerSettings {
     fn default() -> Self {
@@ -501,6 +501,7 @@ pub(crate) async fn fetch_raw_secrets(
         multiple_api_version_supported_connectors: conf.multiple_api_version_supported_connectors,
         applepay_merchant_configs,
         lock_settings: conf.lock_settings,
+        idempotency: conf.idempotency,
         temp_locker_enable_config: conf.temp_locker_enable_config,
         generic_link: conf.generic_link,
         payment_link: conf.payment_link,
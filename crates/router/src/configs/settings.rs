@@ -121,6 +121,7 @@ pub struct Settings<S: SecretState> {
     pub multiple_api_version_supported_connectors: MultipleApiVersionSupportedConnectors,
     pub applepay_merchant_configs: SecretStateContainer<ApplepayMerchantConfigs, S>,
     pub lock_settings: LockSettings,
+    pub idempotency: IdempotencySettings,
     pub temp_locker_enable_config: TempLockerEnableConfig,
     pub generic_link: GenericLink,
     pub payment_link: PaymentLink,
@@ -443,6 +444,9 @@ pub struct ForexApi {
     pub data_expiration_delay_in_seconds: u32,
     pub redis_lock_timeout_in_seconds: u32,
     pub redis_ttl_in_seconds: u32,
+    /// Rates used when the forex APIs are unavailable or don't cover a currency
+    pub static_rates: Option<DefaultExchangeRates>,
+    pub rounding: currency_conversion::types::RoundingConfig,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -797,6 +801,20 @@ pub struct WebhooksSettings {
     pub outgoing_enabled: bool,
     pub ignore_error: WebhookIgnoreErrorSettings,
     pub redis_lock_expiry_seconds: u32,
+    pub circuit_breaker: WebhookCircuitBreakerSettings,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WebhookCircuitBreakerSettings {
+    pub enabled: bool,
+    /// Number of consecutive failed deliveries after which the circuit breaker of a webhook
+    /// endpoint is opened
+    pub failure_threshold: u32,
+    /// Duration for which deliveries are short-circuited before a probe delivery is allowed
+    pub open_duration_seconds: u32,
+    /// Expiry of the circuit breaker state of webhook endpoints which are no longer delivered to
+    pub state_expiry_seconds: u32,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -1000,6 +1018,7 @@ impl Settings<SecuredSecret> {
             .map_err(|err| ApplicationError::InvalidConfigurationValueError(err.to_string()))?;
 
         self.lock_settings.validate()?;
+        self.idempotency.validate()?;
         self.events.validate()?;
 
         #[cfg(feature = "olap")]
@@ -1072,6 +1091,13 @@ pub struct LockSettings {
     pub redis_lock_expiry_seconds: u32,
     pub delay_between_retries_in_milliseconds: u32,
     pub lock_retries: u32,
+    /// Maximum duration for which the lease of a held lock is extended while the request holding
+    /// it is being processed
+    pub max_lock_duration_seconds: u32,
+}
+
+impl LockSettings {
+    const DEFAULT_MAX_LOCK_DURATION_SECONDS: u32 = 600;
 }
 
 impl<'de> Deserialize<'de> for LockSettings {
@@ -1081,21 +1107,37 @@ impl<'de> Deserialize<'de> for LockSettings {
         struct Inner {
             redis_lock_expiry_seconds: u32,
             delay_between_retries_in_milliseconds: u32,
+            max_lock_duration_seconds: Option<u32>,
         }
 
         let Inner {
             redis_lock_expiry_seconds,
             delay_between_retries_in_milliseconds,
+            max_lock_duration_seconds,
         } = Inner::deserialize(deserializer)?;
         let redis_lock_expiry_milliseconds = redis_lock_expiry_seconds * 1000;
         Ok(Self {
             redis_lock_expiry_seconds,
             delay_between_retries_in_milliseconds,
             lock_retries: redis_lock_expiry_milliseconds / delay_between_retries_in_milliseconds,
+            max_lock_duration_seconds: max_lock_duration_seconds
+                .unwrap_or(Self::DEFAULT_MAX_LOCK_DURATION_SECONDS),
         })
     }
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct IdempotencySettings {
+    pub enabled: bool,
+    /// Duration for which the response of a request is replayed for retries using the same
+    /// idempotency key
+    pub key_expiry_seconds: u32,
+    /// Duration after which an idempotency key held by a request which has not completed is
+    /// released, so that the request can be retried
+    pub in_progress_expiry_seconds: u32,
+}
+
 #[cfg(feature = "olap")]
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct ConnectorOnboarding {
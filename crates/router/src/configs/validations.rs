@@ -177,7 +177,38 @@ impl super::settings::LockSettings {
             Err(ApplicationError::InvalidConfigurationValueError(
                 "lock_retries must not be empty or 0".into(),
             ))
-        })
+        })?;
+
+        when(
+            self.max_lock_duration_seconds < self.redis_lock_expiry_seconds,
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "max_lock_duration_seconds must not be less than redis_lock_expiry_seconds"
+                        .into(),
+                ))
+            },
+        )
+    }
+}
+
+impl super::settings::IdempotencySettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.enabled && self.in_progress_expiry_seconds == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "idempotency in_progress_expiry_seconds must not be 0".into(),
+            ))
+        })?;
+
+        when(
+            self.enabled && self.key_expiry_seconds < self.in_progress_expiry_seconds,
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "idempotency key_expiry_seconds must not be less than in_progress_expiry_seconds".into(),
+                ))
+            },
+        )
     }
 }
 
@@ -189,7 +220,36 @@ impl super::settings::WebhooksSettings {
             Err(ApplicationError::InvalidConfigurationValueError(
                 "redis_lock_expiry_seconds must not be empty or 0".into(),
             ))
-        })
+        })?;
+
+        self.circuit_breaker.validate()
+    }
+}
+
+impl super::settings::WebhookCircuitBreakerSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.enabled && self.failure_threshold == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "webhook circuit breaker failure_threshold must not be 0".into(),
+            ))
+        })?;
+
+        when(self.enabled && self.open_duration_seconds == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "webhook circuit breaker open_duration_seconds must not be 0".into(),
+            ))
+        })?;
+
+        when(
+            self.enabled && self.state_expiry_seconds <= self.open_duration_seconds,
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "webhook circuit breaker state_expiry_seconds must be greater than open_duration_seconds".into(),
+                ))
+            },
+        )
     }
 }
 
@@ -42,6 +42,17 @@ pub const FINGERPRINT_SECRET_LENGTH: usize = 64;
 
 pub const DEFAULT_LIST_API_LIMIT: u16 = 10;
 
+/// Default duration for which outgoing webhooks are signed with both the current and the staged
+/// signing secret (in seconds)
+pub const DEFAULT_WEBHOOK_SIGNING_SECRET_GRACE_PERIOD: u32 = 7 * 24 * 60 * 60; // 7 days
+
+/// Max duration for which outgoing webhooks are signed with both the current and the staged
+/// signing secret (in seconds)
+pub const MAX_WEBHOOK_SIGNING_SECRET_GRACE_PERIOD: u32 = 30 * 24 * 60 * 60; // 30 days
+
+/// The length of a generated webhook signing secret
+pub const WEBHOOK_SIGNING_SECRET_LENGTH: usize = 64;
+
 // String literals
 pub(crate) const UNSUPPORTED_ERROR_MESSAGE: &str = "Unsupported response type";
 
@@ -91,6 +102,8 @@ pub const GUEST_USER_CARD_BLOCKING_CACHE_KEY_PREFIX: &str = "GUEST_USER_CARD_BLO
 
 pub const CUSTOMER_ID_BLOCKING_PREFIX: &str = "CUSTOMER_ID_BLOCKING";
 
+pub const CARD_TESTING_GUARD_VELOCITY_PREFIX: &str = "CARD_TESTING_GUARD_VELOCITY";
+
 #[cfg(feature = "olap")]
 pub const VERIFY_CONNECTOR_ID_PREFIX: &str = "conn_verify";
 #[cfg(feature = "olap")]
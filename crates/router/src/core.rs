@@ -25,6 +25,7 @@ pub mod files;
 pub mod fraud_check;
 pub mod gsm;
 pub mod health_check;
+pub mod idempotency;
 #[cfg(feature = "v1")]
 pub mod locker_migration;
 pub mod mandate;
@@ -38,6 +39,8 @@ pub mod payout_link;
 pub mod payouts;
 pub mod pm_auth;
 pub mod poll;
+#[cfg(all(feature = "olap", feature = "v1"))]
+pub mod process_tracker;
 #[cfg(feature = "recon")]
 pub mod recon;
 #[cfg(feature = "v1")]
@@ -35,6 +35,7 @@ use crate::{
         payments::helpers,
         pm_auth::helpers::PaymentAuthConnectorDataExt,
         routing, utils as core_utils,
+        webhooks::utils as webhooks_utils,
     },
     db::{AccountsStorageInterface, StorageInterface},
     routes::{metrics, SessionState},
@@ -59,6 +60,10 @@ use crate::{
 const IBAN_MAX_LENGTH: usize = 34;
 const BACS_SORT_CODE_LENGTH: usize = 6;
 const BACS_MAX_ACCOUNT_NUMBER_LENGTH: usize = 8;
+#[cfg(feature = "v1")]
+const WEBHOOK_SIGNING_SECRET_PROMOTION_TASK: &str = "WEBHOOK_SIGNING_SECRET_PROMOTION";
+#[cfg(feature = "v1")]
+const WEBHOOK_SIGNING_SECRET_PROMOTION_TAG: &str = "WEBHOOK_SIGNING_SECRET";
 
 #[inline]
 pub fn create_merchant_publishable_key() -> String {
@@ -302,7 +307,16 @@ impl MerchantAccountCreateBridge for api::MerchantAccountCreate {
             },
         )?;
 
-        let webhook_details = self.webhook_details.clone().map(ForeignInto::foreign_into);
+        let webhook_details = self
+            .webhook_details
+            .clone()
+            .map(|webhook_details| match webhook_details.validate() {
+                Ok(_) => Ok(webhook_details.foreign_into()),
+                Err(message) => Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message
+                })),
+            })
+            .transpose()?;
 
         let pm_collect_link_config = self.get_pm_link_config_as_value().change_context(
             errors::ApiErrorResponse::InvalidDataValue {
@@ -933,7 +947,15 @@ impl MerchantAccountUpdateBridge for api::MerchantAccountUpdate {
             },
         )?;
 
-        let webhook_details = self.webhook_details.map(ForeignInto::foreign_into);
+        let webhook_details = self
+            .webhook_details
+            .map(|webhook_details| match webhook_details.validate() {
+                Ok(_) => Ok(webhook_details.foreign_into()),
+                Err(message) => Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message
+                })),
+            })
+            .transpose()?;
 
         let parent_merchant_id = get_parent_merchant(
             state,
@@ -3746,7 +3768,15 @@ impl ProfileCreateBridge for api::ProfileCreate {
 
         let current_time = date_time::now();
 
-        let webhook_details = self.webhook_details.map(ForeignInto::foreign_into);
+        let webhook_details = self
+            .webhook_details
+            .map(|webhook_details| match webhook_details.validate() {
+                Ok(_) => Ok(webhook_details.foreign_into()),
+                Err(message) => Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message
+                })),
+            })
+            .transpose()?;
 
         let payment_response_hash_key = self
             .payment_response_hash_key
@@ -3798,7 +3828,17 @@ impl ProfileCreateBridge for api::ProfileCreate {
 
         let card_testing_guard_config = self
             .card_testing_guard_config
-            .map(CardTestingGuardConfig::foreign_from)
+            .map(
+                |card_testing_guard_config| match card_testing_guard_config.validate() {
+                    Ok(_) => Ok(CardTestingGuardConfig::foreign_from(
+                        card_testing_guard_config,
+                    )),
+                    Err(message) => Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                        message
+                    })),
+                },
+            )
+            .transpose()?
             .or(Some(CardTestingGuardConfig::default()));
 
         Ok(domain::Profile::from(domain::ProfileSetter {
@@ -3931,7 +3971,15 @@ impl ProfileCreateBridge for api::ProfileCreate {
 
         let current_time = date_time::now();
 
-        let webhook_details = self.webhook_details.map(ForeignInto::foreign_into);
+        let webhook_details = self
+            .webhook_details
+            .map(|webhook_details| match webhook_details.validate() {
+                Ok(_) => Ok(webhook_details.foreign_into()),
+                Err(message) => Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message
+                })),
+            })
+            .transpose()?;
 
         let payment_response_hash_key = self
             .payment_response_hash_key
@@ -3971,7 +4019,17 @@ impl ProfileCreateBridge for api::ProfileCreate {
 
         let card_testing_guard_config = self
             .card_testing_guard_config
-            .map(CardTestingGuardConfig::foreign_from)
+            .map(
+                |card_testing_guard_config| match card_testing_guard_config.validate() {
+                    Ok(_) => Ok(CardTestingGuardConfig::foreign_from(
+                        card_testing_guard_config,
+                    )),
+                    Err(message) => Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                        message
+                    })),
+                },
+            )
+            .transpose()?
             .or(Some(CardTestingGuardConfig::default()));
 
         Ok(domain::Profile::from(domain::ProfileSetter {
@@ -4222,7 +4280,33 @@ impl ProfileUpdateBridge for api::ProfileUpdate {
             helpers::validate_intent_fulfillment_expiry(intent_fulfillment_expiry)?;
         }
 
-        let webhook_details = self.webhook_details.map(ForeignInto::foreign_into);
+        let card_testing_guard_config = self
+            .card_testing_guard_config
+            .map(
+                |card_testing_guard_config| match card_testing_guard_config.validate() {
+                    Ok(_) => Ok(card_testing_guard_config.foreign_into()),
+                    Err(message) => Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                        message
+                    })),
+                },
+            )
+            .transpose()?;
+
+        let webhook_details = self
+            .webhook_details
+            .map(|webhook_details| match webhook_details.validate() {
+                Ok(_) => Ok(diesel_models::business_profile::WebhookDetails {
+                    // A staged signing secret is only changed through the rotation APIs
+                    signing_secret_rotation: business_profile.webhook_details.as_ref().and_then(
+                        |webhook_details| webhook_details.signing_secret_rotation.clone(),
+                    ),
+                    ..webhook_details.foreign_into()
+                }),
+                Err(message) => Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message
+                })),
+            })
+            .transpose()?;
 
         if let Some(ref routing_algorithm) = self.routing_algorithm {
             let _: api_models::routing::RoutingAlgorithm = routing_algorithm
@@ -4349,9 +4433,7 @@ impl ProfileUpdateBridge for api::ProfileUpdate {
                 max_auto_retries_enabled: self.max_auto_retries_enabled.map(i16::from),
                 is_click_to_pay_enabled: self.is_click_to_pay_enabled,
                 authentication_product_ids: self.authentication_product_ids,
-                card_testing_guard_config: self
-                    .card_testing_guard_config
-                    .map(ForeignInto::foreign_into),
+                card_testing_guard_config,
                 card_testing_secret_key,
                 is_clear_pan_retries_enabled: self.is_clear_pan_retries_enabled,
                 force_3ds_challenge: self.force_3ds_challenge, //
@@ -4375,7 +4457,33 @@ impl ProfileUpdateBridge for api::ProfileUpdate {
             helpers::validate_session_expiry(session_expiry.to_owned())?;
         }
 
-        let webhook_details = self.webhook_details.map(ForeignInto::foreign_into);
+        let card_testing_guard_config = self
+            .card_testing_guard_config
+            .map(
+                |card_testing_guard_config| match card_testing_guard_config.validate() {
+                    Ok(_) => Ok(card_testing_guard_config.foreign_into()),
+                    Err(message) => Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                        message
+                    })),
+                },
+            )
+            .transpose()?;
+
+        let webhook_details = self
+            .webhook_details
+            .map(|webhook_details| match webhook_details.validate() {
+                Ok(_) => Ok(diesel_models::business_profile::WebhookDetails {
+                    // A staged signing secret is only changed through the rotation APIs
+                    signing_secret_rotation: business_profile.webhook_details.as_ref().and_then(
+                        |webhook_details| webhook_details.signing_secret_rotation.clone(),
+                    ),
+                    ..webhook_details.foreign_into()
+                }),
+                Err(message) => Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message
+                })),
+            })
+            .transpose()?;
 
         let payment_link_config = self
             .payment_link_config
@@ -4485,9 +4593,7 @@ impl ProfileUpdateBridge for api::ProfileUpdate {
                 is_click_to_pay_enabled: self.is_click_to_pay_enabled,
                 authentication_product_ids: self.authentication_product_ids,
                 three_ds_decision_manager_config: None,
-                card_testing_guard_config: self
-                    .card_testing_guard_config
-                    .map(ForeignInto::foreign_into),
+                card_testing_guard_config,
                 card_testing_secret_key,
                 is_debit_routing_enabled: self.is_debit_routing_enabled.unwrap_or_default(),
                 merchant_business_country: self.merchant_business_country,
@@ -4811,6 +4917,631 @@ pub async fn connector_agnostic_mit_toggle(
     ))
 }
 
+#[cfg(feature = "v1")]
+fn get_webhook_signing_secret_rotation_response(
+    business_profile: &domain::Profile,
+    staged_signing_secret: Option<Secret<String>>,
+) -> RouterResult<admin_types::WebhookSigningSecretRotationResponse> {
+    let signing_secret_rotation = business_profile
+        .webhook_details
+        .as_ref()
+        .and_then(|webhook_details| webhook_details.signing_secret_rotation.as_ref());
+
+    let current_key_id = business_profile
+        .payment_response_hash_key
+        .as_deref()
+        .map(webhooks_utils::get_webhook_signing_key_id)
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let staged_key_id = signing_secret_rotation
+        .map(|rotation| webhooks_utils::get_webhook_signing_key_id(rotation.staged_secret.peek()))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    Ok(admin_types::WebhookSigningSecretRotationResponse {
+        profile_id: business_profile.get_id().to_owned(),
+        current_key_id,
+        staged_key_id,
+        staged_signing_secret,
+        grace_period_ends_at: signing_secret_rotation.map(|rotation| rotation.grace_period_ends_at),
+    })
+}
+
+#[cfg(feature = "v1")]
+async fn find_business_profile_of_merchant(
+    state: &SessionState,
+    profile_id: &id_type::ProfileId,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<domain::Profile> {
+    let business_profile = state
+        .store
+        .find_business_profile_by_profile_id(&state.into(), key_store, profile_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::ProfileNotFound {
+            id: profile_id.get_string_repr().to_owned(),
+        })?;
+
+    if business_profile.merchant_id != key_store.merchant_id {
+        Err(errors::ApiErrorResponse::AccessForbidden {
+            resource: profile_id.get_string_repr().to_owned(),
+        })?
+    }
+
+    Ok(business_profile)
+}
+
+/// Stages a new signing secret for the outgoing webhooks of the business profile, outgoing webhooks
+/// are signed with both the current and the staged secret until the grace period ends, after which
+/// the staged secret is promoted to the current secret
+#[cfg(feature = "v1")]
+pub async fn rotate_webhook_signing_secret(
+    state: SessionState,
+    profile_id: &id_type::ProfileId,
+    key_store: domain::MerchantKeyStore,
+    request: admin_types::WebhookSigningSecretRotateRequest,
+) -> RouterResponse<admin_types::WebhookSigningSecretRotationResponse> {
+    let grace_period_in_seconds = request
+        .grace_period_in_seconds
+        .unwrap_or(consts::DEFAULT_WEBHOOK_SIGNING_SECRET_GRACE_PERIOD);
+    fp_utils::when(
+        grace_period_in_seconds > consts::MAX_WEBHOOK_SIGNING_SECRET_GRACE_PERIOD,
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "`grace_period_in_seconds` must not exceed {}",
+                    consts::MAX_WEBHOOK_SIGNING_SECRET_GRACE_PERIOD
+                ),
+            })
+        },
+    )?;
+
+    let staged_secret = match request.signing_secret {
+        Some(signing_secret) if signing_secret.peek().trim().is_empty() => {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: "`signing_secret` must not be empty".to_string(),
+            })?
+        }
+        Some(signing_secret) => signing_secret,
+        None => Secret::new(
+            common_utils::crypto::generate_cryptographically_secure_random_string(
+                consts::WEBHOOK_SIGNING_SECRET_LENGTH,
+            ),
+        ),
+    };
+
+    let business_profile =
+        find_business_profile_of_merchant(&state, profile_id, &key_store).await?;
+
+    let webhook_details = business_profile.webhook_details.clone().unwrap_or(
+        diesel_models::business_profile::WebhookDetails {
+            webhook_version: None,
+            webhook_username: None,
+            webhook_password: None,
+            webhook_url: None,
+            payment_created_enabled: None,
+            payment_succeeded_enabled: None,
+            payment_failed_enabled: None,
+            webhook_endpoints: None,
+            signing_secret_rotation: None,
+            webhook_format: None,
+        },
+    );
+
+    fp_utils::when(webhook_details.signing_secret_rotation.is_some(), || {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "A signing secret rotation is already in progress for the business profile, \
+                retire the current signing secret before staging a new one"
+                .to_string(),
+        })
+    })?;
+
+    let staged_at = date_time::now();
+    let signing_secret_rotation = diesel_models::business_profile::WebhookSigningSecretRotation {
+        staged_secret: staged_secret.clone(),
+        staged_at,
+        grace_period_ends_at: staged_at
+            + time::Duration::seconds(i64::from(grace_period_in_seconds)),
+    };
+
+    // The task is added first, it does nothing if the rotation is not stored
+    add_webhook_signing_secret_promotion_task(&state, &business_profile, &signing_secret_rotation)
+        .await?;
+
+    let profile_update = domain::ProfileUpdate::WebhookSigningSecretUpdate {
+        payment_response_hash_key: None,
+        webhook_details: Some(diesel_models::business_profile::WebhookDetails {
+            signing_secret_rotation: Some(signing_secret_rotation),
+            ..webhook_details
+        }),
+    };
+
+    let updated_business_profile = state
+        .store
+        .update_profile_by_profile_id(
+            &(&state).into(),
+            &key_store,
+            business_profile,
+            profile_update,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::ProfileNotFound {
+            id: profile_id.get_string_repr().to_owned(),
+        })?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        get_webhook_signing_secret_rotation_response(
+            &updated_business_profile,
+            Some(staged_secret),
+        )?,
+    ))
+}
+
+/// Retires the current signing secret of the outgoing webhooks of the business profile, the staged
+/// secret becomes the only secret outgoing webhooks are signed with
+#[cfg(feature = "v1")]
+pub async fn retire_webhook_signing_secret(
+    state: SessionState,
+    profile_id: &id_type::ProfileId,
+    key_store: domain::MerchantKeyStore,
+) -> RouterResponse<admin_types::WebhookSigningSecretRotationResponse> {
+    let business_profile =
+        find_business_profile_of_merchant(&state, profile_id, &key_store).await?;
+
+    let updated_business_profile =
+        promote_staged_webhook_signing_secret(&state, &key_store, business_profile).await?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        get_webhook_signing_secret_rotation_response(&updated_business_profile, None)?,
+    ))
+}
+
+/// Promotes the staged signing secret of the business profile to its `payment_response_hash_key`,
+/// which completes the signing secret rotation in progress
+#[cfg(feature = "v1")]
+pub async fn promote_staged_webhook_signing_secret(
+    state: &SessionState,
+    key_store: &domain::MerchantKeyStore,
+    business_profile: domain::Profile,
+) -> RouterResult<domain::Profile> {
+    let profile_id = business_profile.get_id().to_owned();
+    let (webhook_details, signing_secret_rotation) = business_profile
+        .webhook_details
+        .clone()
+        .and_then(|mut webhook_details| {
+            webhook_details
+                .signing_secret_rotation
+                .take()
+                .map(|rotation| (webhook_details, rotation))
+        })
+        .ok_or(errors::ApiErrorResponse::PreconditionFailed {
+            message: "No signing secret rotation is in progress for the business profile"
+                .to_string(),
+        })?;
+
+    let profile_update = domain::ProfileUpdate::WebhookSigningSecretUpdate {
+        payment_response_hash_key: Some(signing_secret_rotation.staged_secret.expose()),
+        webhook_details: Some(webhook_details),
+    };
+
+    state
+        .store
+        .update_profile_by_profile_id(&state.into(), key_store, business_profile, profile_update)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::ProfileNotFound {
+            id: profile_id.get_string_repr().to_owned(),
+        })
+}
+
+/// Schedules the promotion of the staged signing secret of the business profile for when the grace
+/// period of the signing secret rotation ends
+#[cfg(feature = "v1")]
+async fn add_webhook_signing_secret_promotion_task(
+    state: &SessionState,
+    business_profile: &domain::Profile,
+    signing_secret_rotation: &diesel_models::business_profile::WebhookSigningSecretRotation,
+) -> RouterResult<()> {
+    let runner = storage::ProcessTrackerRunner::WebhookSigningSecretPromotionWorkflow;
+    let task = WEBHOOK_SIGNING_SECRET_PROMOTION_TASK;
+    let tag = [WEBHOOK_SIGNING_SECRET_PROMOTION_TAG];
+
+    let process_tracker_id = format!(
+        "{runner}_{task}_{}_{}",
+        business_profile.get_id().get_string_repr(),
+        signing_secret_rotation
+            .staged_at
+            .assume_utc()
+            .unix_timestamp()
+    );
+    let tracking_data = storage::WebhookSigningSecretPromotionTrackingData {
+        merchant_id: business_profile.merchant_id.clone(),
+        profile_id: business_profile.get_id().to_owned(),
+        staged_at: signing_secret_rotation.staged_at,
+    };
+    let process_tracker_entry = storage::ProcessTrackerNew::new(
+        process_tracker_id,
+        task,
+        runner,
+        tag,
+        tracking_data,
+        None,
+        signing_secret_rotation.grace_period_ends_at,
+        common_types::consts::API_VERSION,
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to construct the webhook signing secret promotion task")?;
+
+    state
+        .store
+        .insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert the webhook signing secret promotion task")?;
+
+    Ok(())
+}
+
+/// Blocks currently applied by the card testing guard velocity rules of the profile
+#[cfg(feature = "v1")]
+pub async fn list_card_testing_guard_blocks(
+    state: SessionState,
+    profile_id: &id_type::ProfileId,
+    key_store: domain::MerchantKeyStore,
+) -> RouterResponse<admin_types::CardTestingGuardBlocksResponse> {
+    let business_profile =
+        find_business_profile_of_merchant(&state, profile_id, &key_store).await?;
+
+    let now = date_time::now_unix_timestamp();
+    let mut blocks = services::card_testing_guard::get_velocity_blocks(&state, profile_id)
+        .await?
+        .into_iter()
+        .filter(|block| block.expires_at > now)
+        .map(|block| {
+            let to_primitive_date_time = |timestamp| {
+                time::OffsetDateTime::from_unix_timestamp(timestamp)
+                    .map(date_time::convert_to_pdt)
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Invalid timestamp in card testing guard block")
+            };
+
+            Ok(admin_types::CardTestingGuardBlock {
+                blocked_at: to_primitive_date_time(block.blocked_at)?,
+                expires_at: to_primitive_date_time(block.expires_at)?,
+                block_id: block.block_id,
+                dimension: block.dimension,
+                value: block.value,
+                strike: block.strike,
+            })
+        })
+        .collect::<RouterResult<Vec<_>>>()?;
+    blocks.sort_by(|block, other| other.blocked_at.cmp(&block.blocked_at));
+
+    Ok(service_api::ApplicationResponse::Json(
+        admin_types::CardTestingGuardBlocksResponse {
+            profile_id: business_profile.get_id().to_owned(),
+            blocks,
+        },
+    ))
+}
+
+/// Clears a block applied by the card testing guard velocity rules of the profile, along with the
+/// failures and strikes recorded for the blocked value
+#[cfg(feature = "v1")]
+pub async fn clear_card_testing_guard_block(
+    state: SessionState,
+    profile_id: &id_type::ProfileId,
+    block_id: &str,
+    key_store: domain::MerchantKeyStore,
+) -> RouterResponse<()> {
+    let business_profile =
+        find_business_profile_of_merchant(&state, profile_id, &key_store).await?;
+
+    let block_not_found = || errors::ApiErrorResponse::GenericNotFoundError {
+        message: format!("Card testing guard block `{block_id}` not found"),
+    };
+
+    let dimension = block_id
+        .rsplit_once('_')
+        .and_then(|(dimension, _)| api_enums::CardTestingGuardDimension::from_str(dimension).ok())
+        .ok_or_else(block_not_found)?;
+
+    let rule_ids = business_profile
+        .card_testing_guard_config
+        .iter()
+        .flat_map(|card_testing_guard_config| card_testing_guard_config.velocity_rules.iter())
+        .filter(|rule| rule.dimension == dimension)
+        .map(|rule| rule.get_rule_id());
+
+    let is_cleared =
+        services::card_testing_guard::clear_velocity_block(&state, profile_id, block_id, rule_ids)
+            .await?;
+
+    fp_utils::when(!is_cleared, || Err(report!(block_not_found())))?;
+
+    Ok(service_api::ApplicationResponse::StatusOk)
+}
+
+#[cfg(feature = "v1")]
+fn get_webhook_endpoints(
+    business_profile: &domain::Profile,
+) -> Vec<diesel_models::business_profile::WebhookEndpoint> {
+    business_profile
+        .webhook_details
+        .as_ref()
+        .and_then(|webhook_details| webhook_details.webhook_endpoints.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "v1")]
+fn webhook_endpoint_not_found(endpoint_id: &str) -> errors::ApiErrorResponse {
+    errors::ApiErrorResponse::GenericNotFoundError {
+        message: format!("Webhook endpoint `{endpoint_id}` does not exist"),
+    }
+}
+
+#[cfg(feature = "v1")]
+async fn update_webhook_endpoints(
+    state: &SessionState,
+    key_store: &domain::MerchantKeyStore,
+    business_profile: domain::Profile,
+    webhook_endpoints: Vec<diesel_models::business_profile::WebhookEndpoint>,
+    webhook_format: Option<api_enums::OutgoingWebhookFormat>,
+) -> RouterResult<domain::Profile> {
+    let profile_id = business_profile.get_id().to_owned();
+    let webhook_details = diesel_models::business_profile::WebhookDetails {
+        webhook_endpoints: Some(webhook_endpoints),
+        ..business_profile.webhook_details.clone().unwrap_or(
+            diesel_models::business_profile::WebhookDetails {
+                webhook_version: None,
+                webhook_username: None,
+                webhook_password: None,
+                webhook_url: None,
+                payment_created_enabled: None,
+                payment_succeeded_enabled: None,
+                payment_failed_enabled: None,
+                webhook_endpoints: None,
+                signing_secret_rotation: None,
+                webhook_format: None,
+            },
+        )
+    };
+    let webhook_details = match webhook_format {
+        Some(webhook_format) => set_webhook_format(webhook_details, webhook_format)?,
+        None => webhook_details,
+    };
+    admin_types::WebhookDetails::foreign_from(webhook_details.clone())
+        .validate()
+        .map_err(|message| errors::ApiErrorResponse::InvalidRequestData { message })?;
+
+    let profile_update = domain::ProfileUpdate::WebhookDetailsUpdate { webhook_details };
+
+    state
+        .store
+        .update_profile_by_profile_id(&state.into(), key_store, business_profile, profile_update)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::ProfileNotFound {
+            id: profile_id.get_string_repr().to_owned(),
+        })
+}
+
+/// Sets the webhook format of the profile to the format the webhook endpoints of the profile are
+/// expected to receive, unless the profile is already configured with a different format, as
+/// switching it would change the webhooks the other endpoints of the profile receive.
+#[cfg(feature = "v1")]
+fn set_webhook_format(
+    webhook_details: diesel_models::business_profile::WebhookDetails,
+    webhook_format: api_enums::OutgoingWebhookFormat,
+) -> Result<diesel_models::business_profile::WebhookDetails, errors::ApiErrorResponse> {
+    match webhook_details.webhook_format {
+        Some(configured_webhook_format) if configured_webhook_format != webhook_format => {
+            Err(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!(
+                    "The business profile is configured with the `{configured_webhook_format}` \
+                    webhook format, while the webhook endpoint requires the `{webhook_format}` \
+                    webhook format"
+                ),
+            })
+        }
+        _ => Ok(diesel_models::business_profile::WebhookDetails {
+            webhook_format: Some(webhook_format),
+            ..webhook_details
+        }),
+    }
+}
+
+/// Applies `modify` to the webhook endpoints of the profile and persists the result, along with
+/// `webhook_format` if specified. The profile is read and updated while holding a lock on it, so
+/// that concurrent changes to the endpoints of the same profile are not lost.
+#[cfg(feature = "v1")]
+async fn modify_webhook_endpoints<T>(
+    state: &SessionState,
+    profile_id: &id_type::ProfileId,
+    key_store: &domain::MerchantKeyStore,
+    webhook_format: Option<api_enums::OutgoingWebhookFormat>,
+    modify: impl FnOnce(
+        &mut Vec<diesel_models::business_profile::WebhookEndpoint>,
+    ) -> Result<T, errors::ApiErrorResponse>,
+) -> RouterResult<T> {
+    let lock_action = crate::core::api_locking::LockAction::Hold {
+        input: crate::core::api_locking::LockingInput {
+            unique_locking_key: format!("webhook_endpoints_{}", profile_id.get_string_repr()),
+            api_identifier: crate::routes::lock_utils::ApiIdentifier::Profile,
+            override_lock_retries: None,
+        },
+    };
+    let _lock_lease = lock_action
+        .clone()
+        .perform_locking_action(state, key_store.merchant_id.clone())
+        .await?;
+
+    let result: RouterResult<T> = async {
+        let business_profile =
+            find_business_profile_of_merchant(state, profile_id, key_store).await?;
+        let mut webhook_endpoints = get_webhook_endpoints(&business_profile);
+        let output = modify(&mut webhook_endpoints)?;
+        update_webhook_endpoints(
+            state,
+            key_store,
+            business_profile,
+            webhook_endpoints,
+            webhook_format,
+        )
+        .await?;
+        Ok(output)
+    }
+    .await;
+
+    lock_action
+        .free_lock_action(state, key_store.merchant_id.clone())
+        .await?;
+
+    result
+}
+
+#[cfg(feature = "v1")]
+pub async fn list_webhook_endpoints(
+    state: SessionState,
+    profile_id: &id_type::ProfileId,
+    key_store: domain::MerchantKeyStore,
+) -> RouterResponse<admin_types::WebhookEndpointListResponse> {
+    let business_profile =
+        find_business_profile_of_merchant(&state, profile_id, &key_store).await?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        admin_types::WebhookEndpointListResponse {
+            profile_id: profile_id.to_owned(),
+            webhook_endpoints: get_webhook_endpoints(&business_profile)
+                .into_iter()
+                .map(ForeignFrom::foreign_from)
+                .collect(),
+        },
+    ))
+}
+
+/// Adds a webhook endpoint to the profile. The signing secret of the endpoint is only usable with
+/// `webhook_format`, which the profile is switched to if specified.
+#[cfg(feature = "v1")]
+pub async fn create_webhook_endpoint(
+    state: SessionState,
+    profile_id: &id_type::ProfileId,
+    key_store: domain::MerchantKeyStore,
+    webhook_endpoint: admin_types::WebhookEndpoint,
+    webhook_format: Option<api_enums::OutgoingWebhookFormat>,
+) -> RouterResponse<admin_types::WebhookEndpointResponse> {
+    modify_webhook_endpoints(
+        &state,
+        profile_id,
+        &key_store,
+        webhook_format,
+        |webhook_endpoints| {
+            fp_utils::when(
+                webhook_endpoints
+                    .iter()
+                    .any(|endpoint| endpoint.endpoint_id == webhook_endpoint.endpoint_id),
+                || {
+                    Err(errors::ApiErrorResponse::GenericDuplicateError {
+                        message: format!(
+                            "Webhook endpoint `{}` already exists",
+                            webhook_endpoint.endpoint_id
+                        ),
+                    })
+                },
+            )?;
+            webhook_endpoints.push(ForeignFrom::foreign_from(webhook_endpoint.clone()));
+            Ok(())
+        },
+    )
+    .await?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        admin_types::WebhookEndpointResponse {
+            profile_id: profile_id.to_owned(),
+            webhook_endpoint,
+        },
+    ))
+}
+
+#[cfg(feature = "v1")]
+pub async fn retrieve_webhook_endpoint(
+    state: SessionState,
+    profile_id: &id_type::ProfileId,
+    key_store: domain::MerchantKeyStore,
+    endpoint_id: String,
+) -> RouterResponse<admin_types::WebhookEndpointResponse> {
+    let business_profile =
+        find_business_profile_of_merchant(&state, profile_id, &key_store).await?;
+
+    let webhook_endpoint = get_webhook_endpoints(&business_profile)
+        .into_iter()
+        .find(|endpoint| endpoint.endpoint_id == endpoint_id)
+        .ok_or_else(|| webhook_endpoint_not_found(&endpoint_id))?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        admin_types::WebhookEndpointResponse {
+            profile_id: profile_id.to_owned(),
+            webhook_endpoint: ForeignFrom::foreign_from(webhook_endpoint),
+        },
+    ))
+}
+
+#[cfg(feature = "v1")]
+pub async fn update_webhook_endpoint(
+    state: SessionState,
+    profile_id: &id_type::ProfileId,
+    key_store: domain::MerchantKeyStore,
+    request: admin_types::WebhookEndpointUpdateRequest,
+) -> RouterResponse<admin_types::WebhookEndpointResponse> {
+    let webhook_endpoint =
+        modify_webhook_endpoints(&state, profile_id, &key_store, None, |webhook_endpoints| {
+            let webhook_endpoint = webhook_endpoints
+                .iter_mut()
+                .find(|endpoint| endpoint.endpoint_id == request.endpoint_id)
+                .ok_or_else(|| webhook_endpoint_not_found(&request.endpoint_id))?;
+            if let Some(url) = request.url {
+                webhook_endpoint.url = url;
+            }
+            if let Some(enabled_events) = request.enabled_events {
+                webhook_endpoint.enabled_events = enabled_events;
+            }
+            if let Some(is_enabled) = request.is_enabled {
+                webhook_endpoint.is_enabled = Some(is_enabled);
+            }
+            Ok(webhook_endpoint.clone())
+        })
+        .await?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        admin_types::WebhookEndpointResponse {
+            profile_id: profile_id.to_owned(),
+            webhook_endpoint: ForeignFrom::foreign_from(webhook_endpoint),
+        },
+    ))
+}
+
+#[cfg(feature = "v1")]
+pub async fn delete_webhook_endpoint(
+    state: SessionState,
+    profile_id: &id_type::ProfileId,
+    key_store: domain::MerchantKeyStore,
+    endpoint_id: String,
+) -> RouterResponse<admin_types::WebhookEndpointDeleteResponse> {
+    modify_webhook_endpoints(&state, profile_id, &key_store, None, |webhook_endpoints| {
+        let endpoint_count = webhook_endpoints.len();
+        webhook_endpoints.retain(|endpoint| endpoint.endpoint_id != endpoint_id);
+        fp_utils::when(webhook_endpoints.len() == endpoint_count, || {
+            Err(webhook_endpoint_not_found(&endpoint_id))
+        })
+    })
+    .await?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        admin_types::WebhookEndpointDeleteResponse {
+            profile_id: profile_id.to_owned(),
+            endpoint_id,
+            deleted: true,
+        },
+    ))
+}
+
 pub async fn transfer_key_store_to_key_manager(
     state: SessionState,
     req: admin_types::MerchantKeyTransferRequest,
@@ -5139,3 +5870,46 @@ pub async fn enable_platform_account(
     .attach_printable("Error while enabling platform merchant account")
     .map(|_| services::ApplicationResponse::StatusOk)
 }
+
+#[cfg(all(test, feature = "v1"))]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use diesel_models::business_profile::WebhookDetails;
+
+    use super::*;
+
+    fn webhook_details(webhook_format: Option<api_enums::OutgoingWebhookFormat>) -> WebhookDetails {
+        WebhookDetails {
+            webhook_version: None,
+            webhook_username: None,
+            webhook_password: None,
+            webhook_url: None,
+            payment_created_enabled: None,
+            payment_succeeded_enabled: None,
+            payment_failed_enabled: None,
+            webhook_endpoints: None,
+            signing_secret_rotation: None,
+            webhook_format,
+        }
+    }
+
+    #[test]
+    fn test_set_webhook_format() {
+        let stripe = api_enums::OutgoingWebhookFormat::Stripe;
+
+        for webhook_format in [None, Some(stripe)] {
+            let webhook_details =
+                set_webhook_format(webhook_details(webhook_format), stripe).unwrap();
+            assert_eq!(webhook_details.webhook_format, Some(stripe));
+        }
+
+        assert!(matches!(
+            set_webhook_format(
+                webhook_details(Some(api_enums::OutgoingWebhookFormat::Hyperswitch)),
+                stripe
+            ),
+            Err(errors::ApiErrorResponse::PreconditionFailed { .. })
+        ));
+    }
+}
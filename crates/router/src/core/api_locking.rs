@@ -1,26 +1,109 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc, time::Instant};
 
 use actix_web::rt::time as actix_time;
 use error_stack::{report, ResultExt};
-use redis_interface as redis;
-use router_env::{instrument, logger, tracing};
+use redis_interface::{self as redis, RedisConnectionPool};
+use router_env::{instrument, logger, tracing, tracing::Instrument};
 
-use super::errors::{self, RouterResult};
+use super::{
+    errors::{self, RouterResult},
+    metrics,
+};
 use crate::routes::{app::SessionStateInfo, lock_utils};
 
 pub const API_LOCK_PREFIX: &str = "API_LOCK";
 
+/// Acquires the lock for the request at the head of the queue of waiters, the waiters which have
+/// stopped polling are dropped from the head of the queue. Returns 0 when the lock is acquired,
+/// otherwise the position of the caller in the queue.
+///
+/// KEYS: lock, queue of waiters, deadlines of waiters
+/// ARGV: request_id, lock expiry in milliseconds, waiter expiry in milliseconds
+const ACQUIRE_LOCK_SCRIPT: &str = r#"
+local owner = redis.call('GET', KEYS[1])
+if owner == ARGV[1] then
+    return 0
+end
+
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+if redis.call('HEXISTS', KEYS[3], ARGV[1]) == 0 then
+    redis.call('RPUSH', KEYS[2], ARGV[1])
+end
+redis.call('HSET', KEYS[3], ARGV[1], now + tonumber(ARGV[3]))
+redis.call('PEXPIRE', KEYS[2], ARGV[3])
+redis.call('PEXPIRE', KEYS[3], ARGV[3])
+
+local head = redis.call('LINDEX', KEYS[2], 0)
+while head do
+    local deadline = tonumber(redis.call('HGET', KEYS[3], head))
+    if deadline and deadline >= now then
+        break
+    end
+    redis.call('LPOP', KEYS[2])
+    redis.call('HDEL', KEYS[3], head)
+    head = redis.call('LINDEX', KEYS[2], 0)
+end
+
+if not owner and head == ARGV[1] then
+    redis.call('SET', KEYS[1], ARGV[1], 'PX', ARGV[2])
+    redis.call('LPOP', KEYS[2])
+    redis.call('HDEL', KEYS[3], ARGV[1])
+    return 0
+end
+
+local waiters = redis.call('LRANGE', KEYS[2], 0, -1)
+for position, waiter in ipairs(waiters) do
+    if waiter == ARGV[1] then
+        return position
+    end
+end
+return #waiters
+"#;
+
+/// Removes a waiter which gave up on acquiring the lock from the queue of waiters
+///
+/// KEYS: queue of waiters, deadlines of waiters
+/// ARGV: request_id
+const ABANDON_LOCK_SCRIPT: &str = r#"
+redis.call('LREM', KEYS[1], 0, ARGV[1])
+return redis.call('HDEL', KEYS[2], ARGV[1])
+"#;
+
+/// Extends the lease of the lock if it is held by the caller
+///
+/// KEYS: lock
+/// ARGV: request_id, lock expiry in milliseconds
+const EXTEND_LOCK_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('PEXPIRE', KEYS[1], ARGV[2])
+end
+return 0
+"#;
+
+/// Releases the lock if it is held by the caller
+///
+/// KEYS: lock
+/// ARGV: request_id
+const RELEASE_LOCK_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum LockStatus {
-    // status when the lock is acquired by the caller
-    Acquired, // [#2129] pick up request_id from AppState and populate here
-    // status when the lock is acquired by some other caller
-    Busy,
+    // status when the lock is acquired by the caller, identified by the request_id of the caller
+    Acquired { request_id: String },
+    // status when the lock is acquired by some other caller, along with the position of the
+    // caller in the queue of waiters
+    Busy { position: u64 },
 }
 
 #[derive(Clone, Debug)]
 pub enum LockAction {
-    // Sleep until the lock is acquired
+    // Wait in the queue of waiters until the lock is acquired
     Hold { input: LockingInput },
     // Queue it but return response as 2xx, could be used for webhooks
     QueueWithOk,
@@ -37,6 +120,34 @@ pub struct LockingInput {
     pub override_lock_retries: Option<u32>,
 }
 
+/// Keeps the lease of a held lock extended until it is dropped, so that the lease is no longer
+/// extended once the request holding the lock completes or is cancelled, even if the lock could
+/// not be freed
+#[derive(Debug, Default)]
+#[must_use = "the lease of the lock is no longer extended once the guard is dropped"]
+pub struct LockLeaseGuard {
+    lease_extension: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl Drop for LockLeaseGuard {
+    fn drop(&mut self) {
+        if let Some(lease_extension) = self.lease_extension.take() {
+            lease_extension.abort();
+        }
+    }
+}
+
+/// The redis keys backing a lock. The lock key is kept in the format used before the queue of
+/// waiters was introduced, so that instances running either version exclude each other during
+/// rolling deployments. The keys of the queue use the lock key as their hash tag so that all the
+/// keys are always stored in the same slot.
+#[derive(Clone, Debug)]
+struct RedisLockKeys {
+    lock: String,
+    waiters_queue: String,
+    waiters_deadlines: String,
+}
+
 impl LockingInput {
     fn get_redis_locking_key(&self, merchant_id: common_utils::id_type::MerchantId) -> String {
         format!(
@@ -47,6 +158,21 @@ impl LockingInput {
             self.unique_locking_key
         )
     }
+
+    fn get_redis_lock_keys(
+        &self,
+        redis_conn: &RedisConnectionPool,
+        merchant_id: common_utils::id_type::MerchantId,
+    ) -> RedisLockKeys {
+        let lock = redis::RedisKey::from(self.get_redis_locking_key(merchant_id).as_str())
+            .tenant_aware_key(redis_conn);
+
+        RedisLockKeys {
+            waiters_queue: format!("{{{lock}}}_WAITERS"),
+            waiters_deadlines: format!("{{{lock}}}_WAITER_DEADLINES"),
+            lock,
+        }
+    }
 }
 
 impl LockAction {
@@ -55,7 +181,7 @@ impl LockAction {
         self,
         state: &A,
         merchant_id: common_utils::id_type::MerchantId,
-    ) -> RouterResult<()>
+    ) -> RouterResult<LockLeaseGuard>
     where
         A: SessionStateInfo,
     {
@@ -65,53 +191,117 @@ impl LockAction {
                     .store()
                     .get_redis_conn()
                     .change_context(errors::ApiErrorResponse::InternalServerError)?;
+                let request_id = get_request_id(state)?;
 
-                let redis_locking_key = input.get_redis_locking_key(merchant_id);
-                let delay_between_retries_in_milliseconds = state
-                    .conf()
-                    .lock_settings
-                    .delay_between_retries_in_milliseconds;
-                let redis_lock_expiry_seconds =
-                    state.conf().lock_settings.redis_lock_expiry_seconds;
+                let redis_lock_keys = input.get_redis_lock_keys(&redis_conn, merchant_id);
+                let lock_settings = state.conf().lock_settings;
+                let delay_between_retries_in_milliseconds =
+                    lock_settings.delay_between_retries_in_milliseconds;
+                let redis_lock_expiry_milliseconds =
+                    u64::from(lock_settings.redis_lock_expiry_seconds).saturating_mul(1000);
+                // Waiters which have not polled for a few retries are considered to have given up
+                let waiter_expiry_milliseconds =
+                    u64::from(delay_between_retries_in_milliseconds.saturating_mul(3)).max(1000);
                 let lock_retries = input
                     .override_lock_retries
-                    .unwrap_or(state.conf().lock_settings.lock_retries);
+                    .unwrap_or(lock_settings.lock_retries);
+                let api_identifier = input.api_identifier.to_string();
+
+                let start_instant = Instant::now();
+                let mut is_contention_recorded = false;
                 for _retry in 0..lock_retries {
-                    let redis_lock_result = redis_conn
-                        .set_key_if_not_exists_with_expiry(
-                            &redis_locking_key.as_str().into(),
-                            state.get_request_id(),
-                            Some(i64::from(redis_lock_expiry_seconds)),
-                        )
-                        .await;
-
-                    match redis_lock_result {
-                        Ok(redis::SetnxReply::KeySet) => {
+                    let lock_status = try_acquire_lock(
+                        &redis_conn,
+                        &redis_lock_keys,
+                        &request_id,
+                        redis_lock_expiry_milliseconds,
+                        waiter_expiry_milliseconds,
+                    )
+                    .await?;
+
+                    if !is_contention_recorded {
+                        let position = match &lock_status {
+                            LockStatus::Acquired { .. } => 0,
+                            LockStatus::Busy { position } => *position,
+                        };
+                        metrics::API_LOCK_CONTENTION.record(
+                            position,
+                            router_env::metric_attributes!((
+                                "api_identifier",
+                                api_identifier.clone()
+                            )),
+                        );
+                        is_contention_recorded = true;
+                    }
+
+                    match lock_status {
+                        LockStatus::Acquired { request_id } => {
                             logger::info!("Lock acquired for locking input {:?}", input);
                             tracing::Span::current()
-                                .record("redis_lock_acquired", redis_locking_key);
-                            return Ok(());
+                                .record("redis_lock_acquired", &redis_lock_keys.lock);
+                            metrics::API_LOCK_WAIT_TIME.record(
+                                start_instant.elapsed().as_secs_f64(),
+                                router_env::metric_attributes!(
+                                    ("api_identifier", api_identifier),
+                                    ("acquired", true)
+                                ),
+                            );
+
+                            let lease_extension = tokio::spawn(
+                                extend_lock_lease(
+                                    redis_conn,
+                                    redis_lock_keys.lock,
+                                    request_id,
+                                    lock_settings.redis_lock_expiry_seconds,
+                                    lock_settings.max_lock_duration_seconds,
+                                    input.api_identifier,
+                                )
+                                .in_current_span(),
+                            );
+                            return Ok(LockLeaseGuard {
+                                lease_extension: Some(lease_extension),
+                            });
                         }
-                        Ok(redis::SetnxReply::KeyNotSet) => {
+                        LockStatus::Busy { position } => {
                             logger::info!(
-                                "Lock busy by other request when tried for locking input {:?}",
-                                input
+                                "Lock busy by other request when tried for locking input {:?}, position in queue: {}",
+                                input,
+                                position
                             );
                             actix_time::sleep(tokio::time::Duration::from_millis(u64::from(
                                 delay_between_retries_in_milliseconds,
                             )))
                             .await;
                         }
-                        Err(err) => {
-                            return Err(err)
-                                .change_context(errors::ApiErrorResponse::InternalServerError)
-                        }
                     }
                 }
 
+                metrics::API_LOCK_WAIT_TIME.record(
+                    start_instant.elapsed().as_secs_f64(),
+                    router_env::metric_attributes!(
+                        ("api_identifier", api_identifier),
+                        ("acquired", false)
+                    ),
+                );
+                // Leave the queue so that the waiters behind are not held up until this waiter
+                // is considered to have given up
+                if let Err(error) = redis_conn
+                    .evaluate_redis_script::<_, i64>(
+                        ABANDON_LOCK_SCRIPT,
+                        vec![
+                            redis_lock_keys.waiters_queue,
+                            redis_lock_keys.waiters_deadlines,
+                        ],
+                        vec![request_id],
+                    )
+                    .await
+                {
+                    logger::error!(?error, "Failed to leave the queue of lock waiters");
+                }
+
                 Err(report!(errors::ApiErrorResponse::ResourceBusy))
             }
-            Self::QueueWithOk | Self::Drop | Self::NotApplicable => Ok(()),
+            Self::QueueWithOk | Self::Drop | Self::NotApplicable => Ok(LockLeaseGuard::default()),
         }
     }
 
@@ -130,39 +320,28 @@ impl LockAction {
                     .store()
                     .get_redis_conn()
                     .change_context(errors::ApiErrorResponse::InternalServerError)?;
+                let request_id = get_request_id(state)?;
 
-                let redis_locking_key = input.get_redis_locking_key(merchant_id);
+                let redis_lock_keys = input.get_redis_lock_keys(&redis_conn, merchant_id);
 
                 match redis_conn
-                    .get_key::<Option<String>>(&redis_locking_key.as_str().into())
+                    .evaluate_redis_script::<_, i64>(
+                        RELEASE_LOCK_SCRIPT,
+                        vec![redis_lock_keys.lock.clone()],
+                        vec![request_id],
+                    )
                     .await
                 {
-                    Ok(val) => {
-                        if val == state.get_request_id() {
-                            match redis_conn
-                                .delete_key(&redis_locking_key.as_str().into())
-                                .await
-                            {
-                                Ok(redis::types::DelReply::KeyDeleted) => {
-                                    logger::info!("Lock freed for locking input {:?}", input);
-                                    tracing::Span::current()
-                                        .record("redis_lock_released", redis_locking_key);
-                                    Ok(())
-                                }
-                                Ok(redis::types::DelReply::KeyNotDeleted) => {
-                                    Err(errors::ApiErrorResponse::InternalServerError)
-                                        .attach_printable(
-                                        "Status release lock called but key is not found in redis",
-                                    )
-                                }
-                                Err(error) => Err(error)
-                                    .change_context(errors::ApiErrorResponse::InternalServerError),
-                            }
-                        } else {
-                            Err(errors::ApiErrorResponse::InternalServerError)
-                                .attach_printable("The request_id which acquired the lock is not equal to the request_id requesting for releasing the lock")
-                        }
+                    Ok(1) => {
+                        logger::info!("Lock freed for locking input {:?}", input);
+                        tracing::Span::current()
+                            .record("redis_lock_released", redis_lock_keys.lock);
+                        Ok(())
                     }
+                    Ok(_) => Err(errors::ApiErrorResponse::InternalServerError)
+                        .attach_printable(
+                            "Release lock called but the lock is not held by the request_id requesting for releasing the lock",
+                        ),
                     Err(error) => {
                         Err(error).change_context(errors::ApiErrorResponse::InternalServerError)
                     }
@@ -173,9 +352,362 @@ impl LockAction {
     }
 }
 
+fn get_request_id<A: SessionStateInfo>(state: &A) -> RouterResult<String> {
+    state
+        .get_request_id()
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("request_id is required to take ownership of the lock")
+}
+
+async fn try_acquire_lock(
+    redis_conn: &RedisConnectionPool,
+    redis_lock_keys: &RedisLockKeys,
+    request_id: &str,
+    redis_lock_expiry_milliseconds: u64,
+    waiter_expiry_milliseconds: u64,
+) -> RouterResult<LockStatus> {
+    let position = redis_conn
+        .evaluate_redis_script::<_, u64>(
+            ACQUIRE_LOCK_SCRIPT,
+            vec![
+                redis_lock_keys.lock.clone(),
+                redis_lock_keys.waiters_queue.clone(),
+                redis_lock_keys.waiters_deadlines.clone(),
+            ],
+            vec![
+                request_id.to_owned(),
+                redis_lock_expiry_milliseconds.to_string(),
+                waiter_expiry_milliseconds.to_string(),
+            ],
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to acquire the lock")?;
+
+    Ok(match position {
+        0 => LockStatus::Acquired {
+            request_id: request_id.to_owned(),
+        },
+        position => LockStatus::Busy { position },
+    })
+}
+
+/// Keeps extending the lease of a held lock, so that the lock does not expire while the request
+/// holding it is waiting on long running calls. Stops once the lock is released by the request,
+/// once the lock has been held for the maximum lock duration, or once the [`LockLeaseGuard`] of the
+/// lock is dropped.
+async fn extend_lock_lease(
+    redis_conn: Arc<RedisConnectionPool>,
+    redis_lock_key: String,
+    request_id: String,
+    redis_lock_expiry_seconds: u32,
+    max_lock_duration_seconds: u32,
+    api_identifier: lock_utils::ApiIdentifier,
+) {
+    let redis_lock_expiry_milliseconds = u64::from(redis_lock_expiry_seconds).saturating_mul(1000);
+    let extension_interval =
+        tokio::time::Duration::from_millis((redis_lock_expiry_milliseconds / 3).max(1000));
+    let max_lock_duration = tokio::time::Duration::from_secs(u64::from(max_lock_duration_seconds));
+    let start_instant = Instant::now();
+
+    loop {
+        actix_time::sleep(extension_interval).await;
+
+        if start_instant.elapsed().saturating_add(extension_interval) > max_lock_duration {
+            logger::warn!(
+                "Lock {} held for the maximum lock duration, the lease is no longer extended",
+                redis_lock_key
+            );
+            break;
+        }
+
+        match redis_conn
+            .evaluate_redis_script::<_, i64>(
+                EXTEND_LOCK_SCRIPT,
+                vec![redis_lock_key.clone()],
+                vec![
+                    request_id.clone(),
+                    redis_lock_expiry_milliseconds.to_string(),
+                ],
+            )
+            .await
+        {
+            Ok(1) => {
+                logger::debug!("Lease of lock {} extended", redis_lock_key);
+                metrics::API_LOCK_LEASE_EXTENSION_COUNT.add(
+                    1,
+                    router_env::metric_attributes!(("api_identifier", api_identifier.to_string())),
+                );
+            }
+            // The lock has been released by the request
+            Ok(_) => break,
+            Err(error) => {
+                logger::error!(
+                    ?error,
+                    "Failed to extend the lease of lock {}",
+                    redis_lock_key
+                );
+            }
+        }
+    }
+}
+
 pub trait GetLockingInput {
     fn get_locking_input<F>(&self, flow: F) -> LockAction
     where
         F: router_env::types::FlowMetric,
         lock_utils::ApiIdentifier: From<F>;
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use redis_interface::RedisSettings;
+
+    use super::*;
+
+    const LOCK_EXPIRY_MILLISECONDS: u64 = 3000;
+    const WAITER_EXPIRY_MILLISECONDS: u64 = 1000;
+
+    async fn get_redis_conn() -> Arc<RedisConnectionPool> {
+        Arc::new(
+            RedisConnectionPool::new(&RedisSettings::default())
+                .await
+                .unwrap(),
+        )
+    }
+
+    fn get_redis_lock_keys() -> RedisLockKeys {
+        let lock = common_utils::generate_id(16, API_LOCK_PREFIX);
+        RedisLockKeys {
+            waiters_queue: format!("{{{lock}}}_WAITERS"),
+            waiters_deadlines: format!("{{{lock}}}_WAITER_DEADLINES"),
+            lock,
+        }
+    }
+
+    async fn acquire(
+        redis_conn: &RedisConnectionPool,
+        redis_lock_keys: &RedisLockKeys,
+        request_id: &str,
+        waiter_expiry_milliseconds: u64,
+    ) -> LockStatus {
+        try_acquire_lock(
+            redis_conn,
+            redis_lock_keys,
+            request_id,
+            LOCK_EXPIRY_MILLISECONDS,
+            waiter_expiry_milliseconds,
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn release(redis_conn: &RedisConnectionPool, redis_lock_key: &str, request_id: &str) {
+        redis_conn
+            .evaluate_redis_script::<_, i64>(
+                RELEASE_LOCK_SCRIPT,
+                vec![redis_lock_key.to_owned()],
+                vec![request_id.to_owned()],
+            )
+            .await
+            .unwrap();
+    }
+
+    async fn get_lock_ttl_milliseconds(
+        redis_conn: &RedisConnectionPool,
+        redis_lock_key: &str,
+    ) -> i64 {
+        redis_conn
+            .evaluate_redis_script::<_, i64>(
+                "return redis.call('PTTL', KEYS[1])",
+                vec![redis_lock_key.to_owned()],
+                Vec::<String>::new(),
+            )
+            .await
+            .unwrap()
+    }
+
+    fn acquired(request_id: &str) -> LockStatus {
+        LockStatus::Acquired {
+            request_id: request_id.to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lock_is_granted_in_arrival_order() {
+        let redis_conn = get_redis_conn().await;
+        let keys = get_redis_lock_keys();
+
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_1", WAITER_EXPIRY_MILLISECONDS).await,
+            acquired("request_1")
+        );
+        // The holder of the lock acquires it again
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_1", WAITER_EXPIRY_MILLISECONDS).await,
+            acquired("request_1")
+        );
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_2", WAITER_EXPIRY_MILLISECONDS).await,
+            LockStatus::Busy { position: 1 }
+        );
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_3", WAITER_EXPIRY_MILLISECONDS).await,
+            LockStatus::Busy { position: 2 }
+        );
+
+        release(&redis_conn, &keys.lock, "request_1").await;
+
+        // Waiters behind the head of the queue keep waiting once the lock is free
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_3", WAITER_EXPIRY_MILLISECONDS).await,
+            LockStatus::Busy { position: 2 }
+        );
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_2", WAITER_EXPIRY_MILLISECONDS).await,
+            acquired("request_2")
+        );
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_3", WAITER_EXPIRY_MILLISECONDS).await,
+            LockStatus::Busy { position: 1 }
+        );
+
+        release(&redis_conn, &keys.lock, "request_2").await;
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_3", WAITER_EXPIRY_MILLISECONDS).await,
+            acquired("request_3")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_waiters_which_stopped_polling_are_skipped() {
+        let redis_conn = get_redis_conn().await;
+        let keys = get_redis_lock_keys();
+
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_1", WAITER_EXPIRY_MILLISECONDS).await,
+            acquired("request_1")
+        );
+        // The waiter is considered to have given up 10 milliseconds after polling
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_2", 10).await,
+            LockStatus::Busy { position: 1 }
+        );
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_3", WAITER_EXPIRY_MILLISECONDS).await,
+            LockStatus::Busy { position: 2 }
+        );
+
+        release(&redis_conn, &keys.lock, "request_1").await;
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_3", WAITER_EXPIRY_MILLISECONDS).await,
+            acquired("request_3")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_waiters_leave_the_queue() {
+        let redis_conn = get_redis_conn().await;
+        let keys = get_redis_lock_keys();
+
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_1", WAITER_EXPIRY_MILLISECONDS).await,
+            acquired("request_1")
+        );
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_2", WAITER_EXPIRY_MILLISECONDS).await,
+            LockStatus::Busy { position: 1 }
+        );
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_3", WAITER_EXPIRY_MILLISECONDS).await,
+            LockStatus::Busy { position: 2 }
+        );
+
+        let removed = redis_conn
+            .evaluate_redis_script::<_, i64>(
+                ABANDON_LOCK_SCRIPT,
+                vec![keys.waiters_queue.clone(), keys.waiters_deadlines.clone()],
+                vec!["request_2".to_owned()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_3", WAITER_EXPIRY_MILLISECONDS).await,
+            LockStatus::Busy { position: 1 }
+        );
+
+        release(&redis_conn, &keys.lock, "request_1").await;
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_3", WAITER_EXPIRY_MILLISECONDS).await,
+            acquired("request_3")
+        );
+    }
+
+    fn spawn_lease_extension(
+        redis_conn: &Arc<RedisConnectionPool>,
+        redis_lock_key: &str,
+        request_id: &str,
+    ) -> LockLeaseGuard {
+        LockLeaseGuard {
+            lease_extension: Some(tokio::spawn(extend_lock_lease(
+                Arc::clone(redis_conn),
+                redis_lock_key.to_owned(),
+                request_id.to_owned(),
+                3,
+                60,
+                lock_utils::ApiIdentifier::Payments,
+            ))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lease_is_extended_until_the_guard_is_dropped() {
+        let redis_conn = get_redis_conn().await;
+        let keys = get_redis_lock_keys();
+
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_1", WAITER_EXPIRY_MILLISECONDS).await,
+            acquired("request_1")
+        );
+        let guard = spawn_lease_extension(&redis_conn, &keys.lock, "request_1");
+
+        // The lease is extended every second, a third of the lock expiry
+        tokio::time::sleep(tokio::time::Duration::from_millis(1500)).await;
+        assert!(get_lock_ttl_milliseconds(&redis_conn, &keys.lock).await > 2000);
+
+        drop(guard);
+        tokio::time::sleep(tokio::time::Duration::from_millis(1500)).await;
+        assert!(get_lock_ttl_milliseconds(&redis_conn, &keys.lock).await < 1600);
+    }
+
+    #[tokio::test]
+    async fn test_lease_extension_stops_once_the_lock_is_released() {
+        let redis_conn = get_redis_conn().await;
+        let keys = get_redis_lock_keys();
+
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_1", WAITER_EXPIRY_MILLISECONDS).await,
+            acquired("request_1")
+        );
+        let guard = spawn_lease_extension(&redis_conn, &keys.lock, "request_1");
+
+        release(&redis_conn, &keys.lock, "request_1").await;
+        // The lease of a lock acquired by another request is not extended
+        assert_eq!(
+            acquire(&redis_conn, &keys, "request_2", WAITER_EXPIRY_MILLISECONDS).await,
+            acquired("request_2")
+        );
+        tokio::time::sleep(tokio::time::Duration::from_millis(1500)).await;
+
+        assert!(guard
+            .lease_extension
+            .as_ref()
+            .is_some_and(|lease_extension| lease_extension.is_finished()));
+        assert!(get_lock_ttl_milliseconds(&redis_conn, &keys.lock).await < 1600);
+    }
+}
@@ -1,6 +1,12 @@
+use common_enums::CardTestingGuardDimension;
+use common_utils::crypto::{GenerateDigest, Sha256};
+use diesel_models::business_profile::{CardTestingGuardAllowlistEntry, CardTestingGuardConfig};
 use error_stack::ResultExt;
 use hyperswitch_domain_models::{
-    card_testing_guard_data::CardTestingGuardData, router_request_types::BrowserInformation,
+    card_testing_guard_data::{
+        CardTestingGuardData, CardTestingGuardValue, CardTestingVelocityCheck,
+    },
+    router_request_types::BrowserInformation,
 };
 use masking::{PeekInterface, Secret};
 use router_env::logger;
@@ -92,6 +98,16 @@ pub async fn validate_card_testing_guard_checks(
                 }
             }
 
+            let velocity_checks = validate_velocity_rules(
+                state,
+                request,
+                payment_method_data,
+                customer_id,
+                business_profile,
+                card_testing_guard_config,
+            )
+            .await?;
+
             Ok(Some(CardTestingGuardData {
                 is_card_ip_blocking_enabled: card_testing_guard_config.is_card_ip_blocking_enabled,
                 card_ip_blocking_cache_key,
@@ -102,12 +118,259 @@ pub async fn validate_card_testing_guard_checks(
                     .is_customer_id_blocking_enabled,
                 customer_id_blocking_cache_key,
                 card_testing_guard_expiry,
+                velocity_checks,
             }))
         }
         None => Ok(None),
     }
 }
 
+/// Fails the payment if any of its values is blocked by the velocity rules of the profile, returns
+/// the velocity rules which track the values of the payment. Payments made using a value present
+/// in the allowlist of the profile are exempt from the velocity rules.
+async fn validate_velocity_rules(
+    state: &SessionState,
+    request: &api::PaymentsRequest,
+    payment_method_data: Option<&api_models::payments::PaymentMethodData>,
+    customer_id: &Option<common_utils::id_type::CustomerId>,
+    business_profile: &domain::Profile,
+    card_testing_guard_config: &CardTestingGuardConfig,
+) -> RouterResult<Vec<CardTestingVelocityCheck>> {
+    if card_testing_guard_config.velocity_rules.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let values = get_card_testing_guard_values(request, payment_method_data, customer_id)?;
+
+    if is_allowlisted(&values, &card_testing_guard_config.allowlist) {
+        logger::info!("Payment exempt from card testing guard velocity rules");
+        return Ok(Vec::new());
+    }
+
+    let velocity_checks = card_testing_guard_config
+        .velocity_rules
+        .iter()
+        .filter_map(|rule| {
+            values
+                .iter()
+                .find(|(value, _)| value.dimension == rule.dimension)
+                .map(|(value, _)| CardTestingVelocityCheck {
+                    profile_id: business_profile.get_id().clone(),
+                    rule: rule.clone(),
+                    value: value.clone(),
+                })
+        })
+        .collect::<Vec<_>>();
+
+    let mut checked_block_ids = Vec::new();
+    for velocity_check in &velocity_checks {
+        let block_id = velocity_check.value.get_block_id();
+
+        if checked_block_ids.contains(&block_id) {
+            continue;
+        }
+
+        if services::card_testing_guard::is_velocity_block_active(
+            state,
+            business_profile.get_id(),
+            &block_id,
+        )
+        .await?
+        {
+            logger::info!(
+                dimension = %velocity_check.value.dimension,
+                "Payment blocked by card testing guard velocity rule"
+            );
+            Err(errors::ApiErrorResponse::PreconditionFailed {
+                message: "Blocked due to suspicious activity".to_string(),
+            })?
+        }
+
+        checked_block_ids.push(block_id);
+    }
+
+    Ok(velocity_checks)
+}
+
+/// Whether any of the values of the payment is present in the allowlist of the profile
+fn is_allowlisted(
+    values: &[(CardTestingGuardValue, String)],
+    allowlist: &[CardTestingGuardAllowlistEntry],
+) -> bool {
+    values.iter().any(|(value, allowlist_value)| {
+        allowlist.iter().any(|entry| {
+            entry.dimension == value.dimension
+                && entry.value.trim().eq_ignore_ascii_case(allowlist_value)
+        })
+    })
+}
+
+/// Values of the payment for each of the card testing guard dimensions, along with the value
+/// which is matched against the allowlist of the profile
+fn get_card_testing_guard_values(
+    request: &api::PaymentsRequest,
+    payment_method_data: Option<&api_models::payments::PaymentMethodData>,
+    customer_id: &Option<common_utils::id_type::CustomerId>,
+) -> RouterResult<Vec<(CardTestingGuardValue, String)>> {
+    let browser_info = get_browser_info(request)?;
+    let mut values = Vec::new();
+
+    if let Some(api_models::payments::PaymentMethodData::Card(card)) = payment_method_data {
+        let card_bin = card.card_number.get_card_isin();
+        values.push((
+            get_card_testing_guard_value(CardTestingGuardDimension::CardBin, &card_bin, &card_bin)?,
+            card_bin,
+        ));
+    }
+
+    if let Some(ip_address) = browser_info
+        .as_ref()
+        .and_then(|browser_info| browser_info.ip_address)
+    {
+        let ip_address = ip_address.to_string();
+        values.push((
+            get_card_testing_guard_value(
+                CardTestingGuardDimension::IpAddress,
+                &ip_address,
+                &ip_address,
+            )?,
+            ip_address,
+        ));
+    }
+
+    if let Some(customer_id) = customer_id {
+        let customer_id = customer_id.get_string_repr().to_owned();
+        values.push((
+            get_card_testing_guard_value(
+                CardTestingGuardDimension::CustomerId,
+                &customer_id,
+                &customer_id,
+            )?,
+            customer_id,
+        ));
+    }
+
+    if let Some(email) = get_email(request) {
+        values.push((
+            get_card_testing_guard_value(
+                CardTestingGuardDimension::Email,
+                &email,
+                &mask_email(&email),
+            )?,
+            email,
+        ));
+    }
+
+    if let Some(device) = browser_info.as_ref().and_then(get_device_identifier) {
+        let fingerprint = generate_value_fingerprint(CardTestingGuardDimension::Device, &device)?;
+        // Devices are identified by a prefix of the fingerprint, both in the blocks and in the
+        // allowlist of the profile
+        let display_value = fingerprint
+            .get(..DEVICE_DISPLAY_VALUE_LENGTH)
+            .unwrap_or(fingerprint.as_str())
+            .to_owned();
+        values.push((
+            CardTestingGuardValue {
+                dimension: CardTestingGuardDimension::Device,
+                fingerprint,
+                display_value: display_value.clone(),
+            },
+            display_value,
+        ));
+    }
+
+    Ok(values)
+}
+
+const DEVICE_DISPLAY_VALUE_LENGTH: usize = 16;
+
+fn get_card_testing_guard_value(
+    dimension: CardTestingGuardDimension,
+    value: &str,
+    display_value: &str,
+) -> RouterResult<CardTestingGuardValue> {
+    Ok(CardTestingGuardValue {
+        dimension,
+        fingerprint: generate_value_fingerprint(dimension, value)?,
+        display_value: display_value.to_owned(),
+    })
+}
+
+fn generate_value_fingerprint(
+    dimension: CardTestingGuardDimension,
+    value: &str,
+) -> RouterResult<String> {
+    Sha256
+        .generate_digest(format!("{dimension}:{value}").as_bytes())
+        .map(hex::encode)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to generate card testing guard fingerprint")
+}
+
+#[cfg(feature = "v1")]
+fn get_browser_info(request: &api::PaymentsRequest) -> RouterResult<Option<BrowserInformation>> {
+    request
+        .browser_info
+        .clone()
+        .map(|browser_info| {
+            serde_json::from_value::<BrowserInformation>(browser_info)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("could not parse browser_info")
+        })
+        .transpose()
+}
+
+#[cfg(feature = "v2")]
+fn get_browser_info(request: &api::PaymentsRequest) -> RouterResult<Option<BrowserInformation>> {
+    Ok(request.browser_info.clone().map(BrowserInformation::from))
+}
+
+#[cfg(feature = "v1")]
+fn get_email(request: &api::PaymentsRequest) -> Option<String> {
+    helpers::get_customer_details_from_request(request)
+        .email
+        .map(|email| email.peek().trim().to_lowercase())
+}
+
+#[cfg(feature = "v2")]
+fn get_email(_request: &api::PaymentsRequest) -> Option<String> {
+    None
+}
+
+fn mask_email(email: &str) -> String {
+    match email.split_once('@') {
+        Some((local_part, domain)) => {
+            let first_character = local_part.chars().next().unwrap_or('*');
+            format!("{first_character}****@{domain}")
+        }
+        None => "****".to_string(),
+    }
+}
+
+/// The attributes of the browser which together identify the device of the customer
+fn get_device_identifier(browser_info: &BrowserInformation) -> Option<String> {
+    let user_agent = browser_info.user_agent.as_ref()?;
+
+    Some(
+        [
+            Some(user_agent.clone()),
+            browser_info.device_model.clone(),
+            browser_info.os_type.clone(),
+            browser_info.os_version.clone(),
+            browser_info.screen_width.map(|width| width.to_string()),
+            browser_info.screen_height.map(|height| height.to_string()),
+            browser_info.language.clone(),
+            browser_info
+                .time_zone
+                .map(|time_zone| time_zone.to_string()),
+        ]
+        .into_iter()
+        .map(Option::unwrap_or_default)
+        .collect::<Vec<_>>()
+        .join("|"),
+    )
+}
+
 pub async fn generate_fingerprint(
     payment_method_data: Option<&api_models::payments::PaymentMethodData>,
     business_profile: &domain::Profile,
@@ -191,6 +454,190 @@ pub async fn increment_blocked_count_in_cache(
             )
             .await;
         }
+
+        for velocity_check in &card_testing_guard_data.velocity_checks {
+            match services::card_testing_guard::record_velocity_failure(
+                state,
+                velocity_check,
+                card_testing_guard_data.card_testing_guard_expiry.into(),
+            )
+            .await
+            {
+                Ok(Some(strike)) => logger::info!(
+                    dimension = %velocity_check.value.dimension,
+                    strike,
+                    "Blocked by card testing guard velocity rule"
+                ),
+                Ok(None) => {}
+                Err(error) => logger::error!(
+                    ?error,
+                    "Failed to record failure for card testing guard velocity rule"
+                ),
+            }
+        }
     }
     Ok(())
 }
+
+#[cfg(all(test, feature = "v1"))]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn payment_method_data() -> api_models::payments::PaymentMethodData {
+        api_models::payments::PaymentMethodData::Card(api_models::payments::Card {
+            card_number: cards::CardNumber::from_str("4242424242424242").unwrap(),
+            ..Default::default()
+        })
+    }
+
+    fn customer_id() -> Option<common_utils::id_type::CustomerId> {
+        Some(
+            common_utils::id_type::CustomerId::try_from(std::borrow::Cow::from("cus_ctg_test"))
+                .unwrap(),
+        )
+    }
+
+    fn payments_request() -> api::PaymentsRequest {
+        api::PaymentsRequest {
+            email: Some(common_utils::pii::Email::from_str("Jane.Doe@Example.com").unwrap()),
+            browser_info: Some(serde_json::json!({
+                "ip_address": "127.0.0.1",
+                "user_agent": "Mozilla/5.0",
+                "screen_width": 1920,
+                "screen_height": 1080,
+                "time_zone": -330
+            })),
+            ..Default::default()
+        }
+    }
+
+    fn allowlist_entry(
+        dimension: CardTestingGuardDimension,
+        value: &str,
+    ) -> CardTestingGuardAllowlistEntry {
+        CardTestingGuardAllowlistEntry {
+            dimension,
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_get_card_testing_guard_values() {
+        let payment_method_data = payment_method_data();
+        let values = get_card_testing_guard_values(
+            &payments_request(),
+            Some(&payment_method_data),
+            &customer_id(),
+        )
+        .unwrap();
+
+        let dimensions = values
+            .iter()
+            .map(|(value, _)| value.dimension)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            dimensions,
+            vec![
+                CardTestingGuardDimension::CardBin,
+                CardTestingGuardDimension::IpAddress,
+                CardTestingGuardDimension::CustomerId,
+                CardTestingGuardDimension::Email,
+                CardTestingGuardDimension::Device,
+            ]
+        );
+
+        let allowlist_values = values
+            .iter()
+            .map(|(_, allowlist_value)| allowlist_value.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            allowlist_values.get(..4).unwrap(),
+            [
+                "424242",
+                "127.0.0.1",
+                "cus_ctg_test",
+                "jane.doe@example.com"
+            ]
+        );
+
+        let (email, _) = values.get(3).unwrap();
+        assert_eq!(email.display_value, "j****@example.com");
+        assert_eq!(
+            email.fingerprint,
+            generate_value_fingerprint(CardTestingGuardDimension::Email, "jane.doe@example.com")
+                .unwrap()
+        );
+
+        let (device, device_allowlist_value) = values.get(4).unwrap();
+        assert_eq!(device.display_value.len(), DEVICE_DISPLAY_VALUE_LENGTH);
+        assert!(device.fingerprint.starts_with(&device.display_value));
+        assert_eq!(&device.display_value, device_allowlist_value);
+
+        // Values are fingerprinted along with their dimension
+        let (card_bin, _) = values.first().unwrap();
+        assert_ne!(
+            card_bin.fingerprint,
+            generate_value_fingerprint(CardTestingGuardDimension::IpAddress, "424242").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_card_testing_guard_values_of_payment_without_details() {
+        let values =
+            get_card_testing_guard_values(&api::PaymentsRequest::default(), None, &None).unwrap();
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn test_mask_email() {
+        assert_eq!(mask_email("jane.doe@example.com"), "j****@example.com");
+        assert_eq!(mask_email("@example.com"), "*****@example.com");
+        assert_eq!(mask_email("invalid"), "****");
+    }
+
+    #[test]
+    fn test_is_allowlisted() {
+        let payment_method_data = payment_method_data();
+        let values = get_card_testing_guard_values(
+            &payments_request(),
+            Some(&payment_method_data),
+            &customer_id(),
+        )
+        .unwrap();
+
+        assert!(!is_allowlisted(&values, &[]));
+        assert!(is_allowlisted(
+            &values,
+            &[allowlist_entry(
+                CardTestingGuardDimension::CardBin,
+                " 424242 "
+            )]
+        ));
+        assert!(is_allowlisted(
+            &values,
+            &[allowlist_entry(
+                CardTestingGuardDimension::Email,
+                "JANE.DOE@example.com"
+            )]
+        ));
+        // Values are matched only against the allowlist entries of their dimension
+        assert!(!is_allowlisted(
+            &values,
+            &[allowlist_entry(
+                CardTestingGuardDimension::IpAddress,
+                "424242"
+            )]
+        ));
+        assert!(!is_allowlisted(
+            &values,
+            &[allowlist_entry(
+                CardTestingGuardDimension::Email,
+                "j****@example.com"
+            )]
+        ));
+    }
+}
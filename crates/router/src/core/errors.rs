@@ -138,6 +138,8 @@ pub enum WebhooksFlowError {
     MerchantWebhookDetailsNotFound,
     #[error("Merchant does not have a webhook URL configured")]
     MerchantWebhookUrlNotConfigured,
+    #[error("Webhook endpoint is not configured or is disabled for the business profile")]
+    WebhookEndpointNotConfigured,
     #[error("Webhook event updation failed")]
     WebhookEventUpdationFailed,
     #[error("Outgoing webhook body signing failed")]
@@ -156,6 +158,10 @@ pub enum WebhooksFlowError {
     OutgoingWebhookRetrySchedulingFailed,
     #[error("Outgoing webhook response encoding failed")]
     OutgoingWebhookResponseEncodingFailed,
+    #[error(
+        "Outgoing webhook delivery short-circuited by the circuit breaker of the webhook endpoint"
+    )]
+    WebhookEndpointCircuitOpen,
 }
 
 impl WebhooksFlowError {
@@ -164,6 +170,7 @@ impl WebhooksFlowError {
             Self::MerchantConfigNotFound
             | Self::MerchantWebhookDetailsNotFound
             | Self::MerchantWebhookUrlNotConfigured
+            | Self::WebhookEndpointNotConfigured
             | Self::OutgoingWebhookResponseEncodingFailed => false,
 
             Self::WebhookEventUpdationFailed
@@ -173,7 +180,8 @@ impl WebhooksFlowError {
             | Self::DisputeWebhookValidationFailed
             | Self::OutgoingWebhookEncodingFailed
             | Self::OutgoingWebhookProcessTrackerTaskUpdateFailed
-            | Self::OutgoingWebhookRetrySchedulingFailed => true,
+            | Self::OutgoingWebhookRetrySchedulingFailed
+            | Self::WebhookEndpointCircuitOpen => true,
         }
     }
 }
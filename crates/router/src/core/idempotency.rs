@@ -0,0 +1,532 @@
+//! Idempotency-Key support for the mutating merchant APIs
+//!
+//! A request carrying an `Idempotency-Key` header holds the key in redis while it is processed,
+//! along with a fingerprint of the request. Once the request is processed, its response (status
+//! code, headers and body) is encrypted using the key of the merchant and stored against the key
+//! for the configured window, and retries using the same key are answered with the stored response
+//! without processing them again. This includes server errors, as the request may have been
+//! partially processed. Requests which are rejected with a client error, or which could not be
+//! taken up by the server, release the key so that they can be retried. Retries using the same key
+//! with a different request are rejected, as are retries made while the original request is still
+//! being processed.
+
+use std::{str::FromStr, sync::Arc};
+
+use actix_web::{
+    http::{Method, StatusCode},
+    HttpRequest, HttpResponse,
+};
+use common_utils::crypto::{DecodeMessage, EncodeMessage, GcmAes256, GenerateDigest, Sha256};
+use error_stack::{report, ResultExt};
+use masking::{PeekInterface, Secret};
+use redis_interface::{errors::RedisError, RedisConnectionPool, SetnxReply};
+use router_env::{instrument, logger, tracing, Flow};
+use serde::Serialize;
+
+use super::errors::{self, RouterResult};
+use crate::{
+    configs::settings::IdempotencySettings,
+    headers,
+    routes::{lock_utils::ApiIdentifier, SessionState},
+};
+
+pub const IDEMPOTENCY_KEY_PREFIX: &str = "IDEMPOTENCY";
+/// Maximum length of an idempotency key, same as Stripe
+const IDEMPOTENCY_KEY_MAX_LENGTH: usize = 255;
+
+/// The state of an idempotency key, as stored in redis
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum IdempotencyRecord {
+    InProgress {
+        fingerprint: String,
+    },
+    Completed {
+        fingerprint: String,
+        /// Hex encoded [`IdempotentResponse`], encrypted using the key of the merchant
+        response: String,
+    },
+}
+
+impl IdempotencyRecord {
+    fn get_fingerprint(&self) -> &str {
+        match self {
+            Self::InProgress { fingerprint } | Self::Completed { fingerprint, .. } => fingerprint,
+        }
+    }
+}
+
+/// The idempotency key of a request along with the fingerprint of the request
+#[derive(Debug)]
+pub struct IdempotencyKey {
+    key: String,
+    fingerprint: String,
+    settings: IdempotencySettings,
+}
+
+pub enum IdempotencyStatus {
+    /// The request does not carry an idempotency key
+    NotApplicable,
+    /// The key is held by the request until its response is stored or the key is released
+    Acquired(IdempotencyGuard),
+    /// A request using the key has already completed, its response is to be replayed
+    Completed(IdempotentResponse),
+}
+
+/// The response of a request which holds an idempotency key, as replayed for retries
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct IdempotentResponse {
+    status_code: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+/// Response headers which are specific to the original response, and are not replayed
+const UNREPLAYED_RESPONSE_HEADERS: [&str; 2] = [headers::CONTENT_LENGTH, headers::X_REQUEST_ID];
+
+impl IdempotentResponse {
+    /// Captures the status code, headers and body of the response, fails if the body is not UTF-8
+    pub fn new(response: &HttpResponse<()>, body: &[u8]) -> RouterResult<Self> {
+        let body = String::from_utf8(body.to_vec())
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Response body is not valid UTF-8")?;
+        let headers = response
+            .headers()
+            .iter()
+            .filter(|(name, _)| {
+                !UNREPLAYED_RESPONSE_HEADERS
+                    .iter()
+                    .any(|unreplayed_header| name.as_str().eq_ignore_ascii_case(unreplayed_header))
+            })
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| (name.to_string(), value.to_owned()))
+            })
+            .collect();
+
+        Ok(Self {
+            status_code: response.status().as_u16(),
+            headers,
+            body,
+        })
+    }
+
+    pub fn into_http_response(self) -> HttpResponse {
+        let mut response = HttpResponse::build(
+            StatusCode::from_u16(self.status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        );
+        for header in self.headers {
+            response.append_header(header);
+        }
+        response.body(self.body)
+    }
+}
+
+/// Holds the idempotency key of a request which is being processed, the guard is kept in the
+/// extensions of the request until the final response is built
+pub struct IdempotencyGuard {
+    redis_conn: Arc<RedisConnectionPool>,
+    redis_key: String,
+    fingerprint: String,
+    key_expiry_seconds: i64,
+    merchant_key: Secret<Vec<u8>>,
+}
+
+impl IdempotencyKey {
+    /// Reads the idempotency key from the headers of the request, requests of APIs which do not
+    /// support idempotency keys and requests which do not modify any resources are ignored
+    pub fn from_request<T: Serialize>(
+        settings: &IdempotencySettings,
+        flow: &impl router_env::types::FlowMetric,
+        request: &HttpRequest,
+        payload: &T,
+    ) -> RouterResult<Option<Self>> {
+        let Some(key) = request.headers().get(headers::IDEMPOTENCY_KEY) else {
+            return Ok(None);
+        };
+
+        if !settings.enabled
+            || !is_mutating_method(request.method())
+            || !is_idempotency_supported(flow)
+        {
+            return Ok(None);
+        }
+
+        let key = key
+            .to_str()
+            .ok()
+            .map(str::trim)
+            .filter(|key| !key.is_empty() && key.len() <= IDEMPOTENCY_KEY_MAX_LENGTH)
+            .ok_or(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "`{}` header must be a non-empty string of at most {} characters",
+                    headers::IDEMPOTENCY_KEY,
+                    IDEMPOTENCY_KEY_MAX_LENGTH
+                ),
+            })?
+            .to_owned();
+
+        Ok(Some(Self {
+            key,
+            fingerprint: get_request_fingerprint(request, payload)?,
+            settings: settings.clone(),
+        }))
+    }
+
+    fn get_redis_key(&self, merchant_id: &common_utils::id_type::MerchantId) -> String {
+        format!(
+            "{}_{}_{}",
+            IDEMPOTENCY_KEY_PREFIX,
+            merchant_id.get_string_repr(),
+            self.key
+        )
+    }
+
+    /// Holds the idempotency key for the request, unless a request using the same key has been
+    /// made already
+    #[instrument(skip_all)]
+    pub async fn acquire(
+        self,
+        state: &SessionState,
+        merchant_id: &common_utils::id_type::MerchantId,
+    ) -> RouterResult<IdempotencyStatus> {
+        let redis_conn = state
+            .store
+            .get_redis_conn()
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+        let redis_key = self.get_redis_key(merchant_id);
+        // The responses are encrypted, since they may carry personal information of customers
+        let merchant_key = Secret::new(
+            state
+                .store
+                .get_merchant_key_store_by_merchant_id(
+                    &state.into(),
+                    merchant_id,
+                    &state.store.get_master_key().to_vec().into(),
+                )
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to fetch the merchant key store")?
+                .key
+                .get_inner()
+                .peek()
+                .clone(),
+        );
+
+        self.acquire_redis_key(redis_conn, redis_key, merchant_key)
+            .await
+    }
+
+    async fn acquire_redis_key(
+        self,
+        redis_conn: Arc<RedisConnectionPool>,
+        redis_key: String,
+        merchant_key: Secret<Vec<u8>>,
+    ) -> RouterResult<IdempotencyStatus> {
+        let in_progress_record = serde_json::to_string(&IdempotencyRecord::InProgress {
+            fingerprint: self.fingerprint.clone(),
+        })
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize idempotency record")?;
+
+        let setnx_reply = redis_conn
+            .set_key_if_not_exists_with_expiry(
+                &redis_key.as_str().into(),
+                in_progress_record,
+                Some(i64::from(self.settings.in_progress_expiry_seconds)),
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to acquire idempotency key")?;
+
+        if setnx_reply == SetnxReply::KeySet {
+            logger::info!("Idempotency key acquired");
+            return Ok(IdempotencyStatus::Acquired(IdempotencyGuard {
+                redis_conn,
+                redis_key,
+                fingerprint: self.fingerprint,
+                key_expiry_seconds: i64::from(self.settings.key_expiry_seconds),
+                merchant_key,
+            }));
+        }
+
+        let existing_record = redis_conn
+            .get_and_deserialize_key::<IdempotencyRecord>(
+                &redis_key.as_str().into(),
+                "IdempotencyRecord",
+            )
+            .await
+            .map_err(|error| {
+                // The key held by the other request expired in the meantime
+                if matches!(error.current_context(), RedisError::NotFound) {
+                    error.change_context(errors::ApiErrorResponse::IdempotentRequestInProgress)
+                } else {
+                    error.change_context(errors::ApiErrorResponse::InternalServerError)
+                }
+            })
+            .attach_printable("Failed to fetch idempotency record")?;
+
+        if existing_record.get_fingerprint() != self.fingerprint {
+            return Err(report!(errors::ApiErrorResponse::IdempotencyKeyReused));
+        }
+
+        match existing_record {
+            IdempotencyRecord::InProgress { .. } => Err(report!(
+                errors::ApiErrorResponse::IdempotentRequestInProgress
+            )),
+            IdempotencyRecord::Completed { response, .. } => {
+                logger::info!("Replaying the response stored against the idempotency key");
+                let response = hex::decode(response)
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Failed to decode the stored idempotent response")?;
+                let response = GcmAes256
+                    .decode_message(merchant_key.peek(), response.into())
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Failed to decrypt the stored idempotent response")?;
+                serde_json::from_slice(&response)
+                    .map(IdempotencyStatus::Completed)
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Failed to deserialize the stored idempotent response")
+            }
+        }
+    }
+}
+
+impl IdempotencyGuard {
+    /// Stores the response of the request against the idempotency key, so that it is replayed
+    /// for retries using the same key
+    #[instrument(skip_all)]
+    pub async fn store_response(&self, response: &IdempotentResponse) -> RouterResult<()> {
+        let response = serde_json::to_vec(response)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to serialize the idempotent response")?;
+        let response = GcmAes256
+            .encode_message(self.merchant_key.peek(), &response)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to encrypt the idempotent response")
+            .map(hex::encode)?;
+
+        self.redis_conn
+            .serialize_and_set_key_with_expiry(
+                &self.redis_key.as_str().into(),
+                IdempotencyRecord::Completed {
+                    fingerprint: self.fingerprint.clone(),
+                    response,
+                },
+                self.key_expiry_seconds,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to store the response against the idempotency key")
+    }
+
+    /// Releases the idempotency key, so that the request can be retried using the same key
+    #[instrument(skip_all)]
+    pub async fn release(&self) -> RouterResult<()> {
+        self.redis_conn
+            .delete_key(&self.redis_key.as_str().into())
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to release the idempotency key")
+            .map(|_| ())
+    }
+}
+
+fn is_mutating_method(method: &Method) -> bool {
+    [Method::POST, Method::PUT, Method::PATCH, Method::DELETE].contains(method)
+}
+
+fn is_idempotency_supported(flow: &impl router_env::types::FlowMetric) -> bool {
+    Flow::from_str(&flow.to_string())
+        .map(ApiIdentifier::from)
+        .is_ok_and(|api_identifier| {
+            matches!(
+                api_identifier,
+                ApiIdentifier::Payments
+                    | ApiIdentifier::Refunds
+                    | ApiIdentifier::Payouts
+                    | ApiIdentifier::Customers
+            )
+        })
+}
+
+/// Hash of the method, path, query and body of the request, which is used to detect whether an
+/// idempotency key is reused for a different request
+fn get_request_fingerprint<T: Serialize>(
+    request: &HttpRequest,
+    payload: &T,
+) -> RouterResult<String> {
+    let body = serde_json::to_vec(payload)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize request for idempotency fingerprint")?;
+
+    let request_bytes = [
+        request.method().as_str().as_bytes(),
+        request.path().as_bytes(),
+        request.query_string().as_bytes(),
+        body.as_slice(),
+    ]
+    .join(&b'\n');
+
+    Sha256
+        .generate_digest(&request_bytes)
+        .map(hex::encode)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to generate idempotency fingerprint")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use actix_web::{body, test::TestRequest};
+    use redis_interface::RedisSettings;
+
+    use super::*;
+    use crate::services::api::complete_idempotent_request;
+
+    async fn get_redis_conn() -> Arc<RedisConnectionPool> {
+        Arc::new(
+            RedisConnectionPool::new(&RedisSettings::default())
+                .await
+                .unwrap(),
+        )
+    }
+
+    fn get_redis_key() -> String {
+        common_utils::generate_id(16, IDEMPOTENCY_KEY_PREFIX)
+    }
+
+    async fn acquire(
+        redis_conn: &Arc<RedisConnectionPool>,
+        redis_key: &str,
+        fingerprint: &str,
+    ) -> RouterResult<IdempotencyStatus> {
+        IdempotencyKey {
+            key: redis_key.to_owned(),
+            fingerprint: fingerprint.to_owned(),
+            settings: IdempotencySettings::default(),
+        }
+        .acquire_redis_key(
+            Arc::clone(redis_conn),
+            redis_key.to_owned(),
+            Secret::new(vec![7; 32]),
+        )
+        .await
+    }
+
+    fn get_guard(status: RouterResult<IdempotencyStatus>) -> Option<IdempotencyGuard> {
+        match status {
+            Ok(IdempotencyStatus::Acquired(guard)) => Some(guard),
+            _ => None,
+        }
+    }
+
+    fn get_stored_response(status: RouterResult<IdempotencyStatus>) -> Option<IdempotentResponse> {
+        match status {
+            Ok(IdempotencyStatus::Completed(response)) => Some(response),
+            _ => None,
+        }
+    }
+
+    fn get_status_code(status: RouterResult<IdempotencyStatus>) -> Option<StatusCode> {
+        status
+            .err()
+            .map(|error| actix_web::ResponseError::status_code(error.current_context()))
+    }
+
+    /// Completes a request holding the idempotency key with the specified response
+    async fn complete(guard: IdempotencyGuard, response: HttpResponse) -> HttpResponse {
+        let request = TestRequest::post().to_http_request();
+        request.extensions_mut().insert(guard);
+        complete_idempotent_request(&request, response).await
+    }
+
+    #[tokio::test]
+    async fn test_replay_stored_response() {
+        let redis_conn = get_redis_conn().await;
+        let redis_key = get_redis_key();
+
+        let guard = get_guard(acquire(&redis_conn, &redis_key, "fingerprint").await).unwrap();
+        let response = HttpResponse::Created()
+            .insert_header(("X-Custom-Header", "value"))
+            .insert_header((headers::X_REQUEST_ID, "request_1"))
+            .body(r#"{"payment_id":"pay_1"}"#);
+        let response = complete(guard, response).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let stored_response =
+            get_stored_response(acquire(&redis_conn, &redis_key, "fingerprint").await).unwrap();
+        let request = TestRequest::post().to_http_request();
+        request.extensions_mut().insert(stored_response);
+        // The response built for the retry is replaced by the stored response
+        let response = complete_idempotent_request(&request, HttpResponse::Ok().finish()).await;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            response
+                .headers()
+                .get("X-Custom-Header")
+                .and_then(|value| value.to_str().ok()),
+            Some("value")
+        );
+        assert!(response.headers().get(headers::X_REQUEST_ID).is_none());
+        let body = body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(body.as_ref(), br#"{"payment_id":"pay_1"}"#);
+    }
+
+    #[tokio::test]
+    async fn test_request_in_progress() {
+        let redis_conn = get_redis_conn().await;
+        let redis_key = get_redis_key();
+
+        let _guard = get_guard(acquire(&redis_conn, &redis_key, "fingerprint").await).unwrap();
+
+        assert_eq!(
+            get_status_code(acquire(&redis_conn, &redis_key, "fingerprint").await),
+            Some(StatusCode::CONFLICT)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_key_reused_with_different_request() {
+        let redis_conn = get_redis_conn().await;
+        let redis_key = get_redis_key();
+
+        let guard = get_guard(acquire(&redis_conn, &redis_key, "fingerprint").await).unwrap();
+        assert_eq!(
+            get_status_code(acquire(&redis_conn, &redis_key, "other_fingerprint").await),
+            Some(StatusCode::UNPROCESSABLE_ENTITY)
+        );
+
+        complete(guard, HttpResponse::Ok().body("{}")).await;
+        assert_eq!(
+            get_status_code(acquire(&redis_conn, &redis_key, "other_fingerprint").await),
+            Some(StatusCode::UNPROCESSABLE_ENTITY)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_key_released_after_failed_request() {
+        let redis_conn = get_redis_conn().await;
+        let redis_key = get_redis_key();
+
+        // Requests rejected with a client error were not processed, and can be retried
+        let guard = get_guard(acquire(&redis_conn, &redis_key, "fingerprint").await).unwrap();
+        complete(guard, HttpResponse::BadRequest().body("{}")).await;
+        let guard = get_guard(acquire(&redis_conn, &redis_key, "fingerprint").await).unwrap();
+        complete(guard, HttpResponse::ServiceUnavailable().body("{}")).await;
+
+        // Server errors are replayed, as the request may have been partially processed
+        let guard = get_guard(acquire(&redis_conn, &redis_key, "fingerprint").await).unwrap();
+        complete(guard, HttpResponse::InternalServerError().body("{}")).await;
+        let stored_response =
+            get_stored_response(acquire(&redis_conn, &redis_key, "fingerprint").await).unwrap();
+        assert_eq!(
+            stored_response.into_http_response().status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
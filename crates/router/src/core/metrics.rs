@@ -1,4 +1,4 @@
-use router_env::{counter_metric, global_meter};
+use router_env::{counter_metric, global_meter, histogram_metric_f64, histogram_metric_u64};
 
 global_meter!(GLOBAL_METER, "ROUTER_API");
 
@@ -43,6 +43,10 @@ counter_metric!(WEBHOOK_SOURCE_VERIFIED_COUNT, GLOBAL_METER);
 counter_metric!(WEBHOOK_OUTGOING_COUNT, GLOBAL_METER);
 counter_metric!(WEBHOOK_OUTGOING_RECEIVED_COUNT, GLOBAL_METER);
 counter_metric!(WEBHOOK_OUTGOING_NOT_RECEIVED_COUNT, GLOBAL_METER);
+counter_metric!(WEBHOOK_OUTGOING_DEAD_LETTERED_COUNT, GLOBAL_METER);
+counter_metric!(WEBHOOK_OUTGOING_REDELIVERED_COUNT, GLOBAL_METER);
+counter_metric!(WEBHOOK_OUTGOING_SHORT_CIRCUITED_COUNT, GLOBAL_METER);
+counter_metric!(WEBHOOK_OUTGOING_CIRCUIT_BREAKER_OPENED_COUNT, GLOBAL_METER);
 counter_metric!(WEBHOOK_PAYMENT_NOT_FOUND, GLOBAL_METER);
 counter_metric!(
     WEBHOOK_EVENT_TYPE_IDENTIFICATION_FAILURE_COUNT,
@@ -89,3 +93,7 @@ counter_metric!(PARTIAL_AUTH_FAILURE, GLOBAL_METER);
 
 counter_metric!(API_KEY_REQUEST_INITIATED, GLOBAL_METER);
 counter_metric!(API_KEY_REQUEST_COMPLETED, GLOBAL_METER);
+
+histogram_metric_f64!(API_LOCK_WAIT_TIME, GLOBAL_METER); // Time spent by requests waiting for API locks, in seconds
+histogram_metric_u64!(API_LOCK_CONTENTION, GLOBAL_METER); // No. of requests ahead of a request when it starts waiting for an API lock
+counter_metric!(API_LOCK_LEASE_EXTENSION_COUNT, GLOBAL_METER);
@@ -2781,6 +2781,8 @@ pub async fn list_payment_methods(
     logger::debug!(mca_before_filtering=?filtered_mcas);
 
     let mut response: Vec<ResponsePaymentMethodIntermediate> = vec![];
+    let debug = req.debug.unwrap_or(false);
+    let mut eligibility_failures: Vec<api::PaymentMethodEligibilityFailure> = vec![];
     // Key creation for storing PM_FILTER_CGRAPH
     let key = {
         format!(
@@ -2806,6 +2808,7 @@ pub async fn list_payment_methods(
                 payment_methods,
                 &mut req,
                 &mut response,
+                &mut eligibility_failures,
                 payment_intent.as_ref(),
                 payment_attempt.as_ref(),
                 billing_address.as_ref(),
@@ -2861,6 +2864,7 @@ pub async fn list_payment_methods(
                 payment_methods,
                 &mut req,
                 &mut response,
+                &mut eligibility_failures,
                 payment_intent.as_ref(),
                 payment_attempt.as_ref(),
                 billing_address.as_ref(),
@@ -2968,6 +2972,8 @@ pub async fn list_payment_methods(
             .unwrap_or(storage::PaymentRoutingInfo {
                 algorithm: None,
                 pre_routing_results: None,
+                evaluation_summary: None,
+                evaluation_trace: None,
             });
 
         let mut pre_routing_results: HashMap<
@@ -3602,6 +3608,7 @@ pub async fn list_payment_methods(
             collect_shipping_details_from_wallets,
             collect_billing_details_from_wallets,
             is_tax_calculation_enabled: is_tax_connector_enabled && !skip_external_tax_calculation,
+            eligibility_failures: debug.then_some(eligibility_failures),
         },
     ))
 }
@@ -3816,6 +3823,7 @@ pub async fn filter_payment_methods(
     payment_methods: &[Secret<serde_json::Value>],
     req: &mut api::PaymentMethodListRequest,
     resp: &mut Vec<ResponsePaymentMethodIntermediate>,
+    eligibility_failures: &mut Vec<api::PaymentMethodEligibilityFailure>,
     payment_intent: Option<&storage::PaymentIntent>,
     payment_attempt: Option<&storage::PaymentAttempt>,
     address: Option<&domain::Address>,
@@ -4011,10 +4019,12 @@ pub async fn filter_payment_methods(
                     logger::info!("Context created for List Payment method is {:?}", context);
 
                     let domain_ident: &[String] = &[mca_id.clone().get_string_repr().to_string()];
+                    // The analysis trace of a failure only lives as long as the memoization
+                    let mut memo = cgraph::Memoization::new();
                     let result = graph.key_value_analysis(
                         pm_dir_value.clone(),
                         &context,
-                        &mut cgraph::Memoization::new(),
+                        &mut memo,
                         &mut cgraph::CycleCheck::new(),
                         Some(domain_ident),
                     );
@@ -4024,6 +4034,20 @@ pub async fn filter_payment_methods(
                             for list payment methods {:?}",
                             e
                         );
+                        if req.debug.unwrap_or(false) {
+                            eligibility_failures.push(api::PaymentMethodEligibilityFailure {
+                                payment_method,
+                                payment_method_type: payment_method_object.payment_method_type,
+                                connector: connector.clone(),
+                                merchant_connector_id: mca_id.clone(),
+                                reason: kgraph_utils::explain::explain_analysis_error(
+                                    &pm_dir_value,
+                                    &connector,
+                                    e,
+                                    &context,
+                                ),
+                            });
+                        }
                     } else if filter_pm_based_on_allowed_types
                         && filter_pm_card_network_based
                         && saved_payment_methods_filter
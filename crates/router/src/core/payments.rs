@@ -6330,6 +6330,8 @@ where
             .unwrap_or(storage::PaymentRoutingInfo {
                 algorithm: None,
                 pre_routing_results: None,
+                evaluation_summary: None,
+                evaluation_trace: None,
             }),
     };
 
@@ -7198,7 +7200,7 @@ where
         algorithm_ref.algorithm_id
     };
 
-    let mut connectors = routing::perform_static_routing_v1(
+    let (mut connectors, evaluation_trace) = routing::perform_static_routing_v1_with_trace(
         state,
         merchant_context.get_merchant_account().get_id(),
         routing_algorithm_id.as_ref(),
@@ -7208,6 +7210,24 @@ where
     .await
     .change_context(errors::ApiErrorResponse::InternalServerError)?;
 
+    // persisted on the payment attempt along with the rest of the routing info, to explain why
+    // the payment was routed to the chosen connector. The full trace grows with the size of the
+    // algorithm, so it is only stored when enabled for the merchant. A trace stored by an earlier
+    // routing of the payment is cleared, as it does not describe this routing.
+    routing_data.routing_info.evaluation_summary =
+        evaluation_trace.as_ref().map(|trace| trace.summary());
+    routing_data.routing_info.evaluation_trace = if evaluation_trace.is_some()
+        && is_routing_evaluation_trace_enabled(
+            state,
+            merchant_context.get_merchant_account().get_id(),
+        )
+        .await
+    {
+        evaluation_trace
+    } else {
+        None
+    };
+
     // adds straight through connectors to the list of connectors in the active routing algorithm
     // and perform eligibility analysis on the combined set of connectors
 
@@ -7346,6 +7366,31 @@ where
     .await
 }
 
+/// Whether the full routing evaluation trace is stored on the payment attempts of the merchant,
+/// controlled through the `routing_evaluation_trace_{merchant_id}` config and disabled by default
+#[cfg(feature = "v1")]
+async fn is_routing_evaluation_trace_enabled(
+    state: &SessionState,
+    merchant_id: &id_type::MerchantId,
+) -> bool {
+    match state
+        .store
+        .find_config_by_key_unwrap_or(
+            &merchant_id.get_routing_evaluation_trace_key(),
+            Some("false".to_string()),
+        )
+        .await
+    {
+        Ok(config) => serde_json::from_str(&config.config).unwrap_or(false),
+        Err(error) => {
+            if !error.current_context().is_db_not_found() {
+                logger::error!(?error, "Error fetching routing evaluation trace config");
+            }
+            false
+        }
+    }
+}
+
 #[cfg(feature = "payouts")]
 #[cfg(feature = "v2")]
 #[allow(clippy::too_many_arguments)]
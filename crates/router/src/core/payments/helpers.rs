@@ -2998,7 +2998,7 @@ pub(super) async fn filter_by_constraints(
 }
 
 #[cfg(feature = "olap")]
-pub(super) fn validate_payment_list_request(
+pub fn validate_payment_list_request(
     req: &api::PaymentListConstraints,
 ) -> CustomResult<(), errors::ApiErrorResponse> {
     use common_utils::consts::PAYMENTS_LIST_MAX_LIMIT_V1;
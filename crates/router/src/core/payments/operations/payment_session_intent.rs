@@ -363,6 +363,8 @@ impl<F: Clone + Send + Sync> Domain<F, PaymentsSessionRequest, payments::Payment
                 }
                 Ok::<_, error_stack::Report<errors::ApiErrorResponse>>(pre_routing_results)
             })()?),
+            evaluation_summary: None,
+            evaluation_trace: None,
         };
 
         // Store the routing results in payment intent
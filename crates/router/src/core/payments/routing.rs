@@ -191,6 +191,9 @@ pub fn make_dsl_input_for_payouts(
         metadata,
         payment,
         payment_method,
+        time: Some(dsl_inputs::TimeInput::from_timestamp(
+            common_utils::date_time::now(),
+        )),
     })
 }
 
@@ -303,6 +306,9 @@ pub fn make_dsl_input(
         payment: payment_input,
         payment_method: payment_method_input,
         mandate: mandate_data,
+        time: Some(dsl_inputs::TimeInput::from_timestamp(
+            common_utils::date_time::now(),
+        )),
     })
 }
 
@@ -414,6 +420,9 @@ pub fn make_dsl_input(
         payment: payment_input,
         payment_method: payment_method_input,
         mandate: mandate_data,
+        time: Some(dsl_inputs::TimeInput::from_timestamp(
+            common_utils::date_time::now(),
+        )),
     })
 }
 
@@ -424,6 +433,30 @@ pub async fn perform_static_routing_v1(
     business_profile: &domain::Profile,
     transaction_data: &routing::TransactionData<'_>,
 ) -> RoutingResult<Vec<routing_types::RoutableConnectorChoice>> {
+    perform_static_routing_v1_with_trace(
+        state,
+        merchant_id,
+        algorithm_id,
+        business_profile,
+        transaction_data,
+    )
+    .await
+    .map(|(connectors, _)| connectors)
+}
+
+/// Performs static routing, additionally returning the evaluation trace when the active algorithm
+/// is an advanced (rule based) one. The trace records every rule visited, the outcome of each
+/// comparison against the transaction and whether the default selection was used.
+pub async fn perform_static_routing_v1_with_trace(
+    state: &SessionState,
+    merchant_id: &common_utils::id_type::MerchantId,
+    algorithm_id: Option<&common_utils::id_type::RoutingId>,
+    business_profile: &domain::Profile,
+    transaction_data: &routing::TransactionData<'_>,
+) -> RoutingResult<(
+    Vec<routing_types::RoutableConnectorChoice>,
+    Option<routing_types::EvaluationTrace>,
+)> {
     let algorithm_id = if let Some(id) = algorithm_id {
         id
     } else {
@@ -440,7 +473,7 @@ pub async fn perform_static_routing_v1(
             .get_default_fallback_list_of_connector_under_profile()
             .change_context(errors::RoutingError::FallbackConfigFetchFailed)?;
 
-        return Ok(fallback_config);
+        return Ok((fallback_config, None));
     };
     let cached_algorithm = ensure_algorithm_cached_v1(
         state,
@@ -452,12 +485,15 @@ pub async fn perform_static_routing_v1(
     .await?;
 
     Ok(match cached_algorithm.as_ref() {
-        CachedAlgorithm::Single(conn) => vec![(**conn).clone()],
+        CachedAlgorithm::Single(conn) => (vec![(**conn).clone()], None),
 
-        CachedAlgorithm::Priority(plist) => plist.clone(),
+        CachedAlgorithm::Priority(plist) => (plist.clone(), None),
 
-        CachedAlgorithm::VolumeSplit(splits) => perform_volume_split(splits.to_vec())
-            .change_context(errors::RoutingError::ConnectorSelectionFailed)?,
+        CachedAlgorithm::VolumeSplit(splits) => (
+            perform_volume_split(splits.to_vec())
+                .change_context(errors::RoutingError::ConnectorSelectionFailed)?,
+            None,
+        ),
 
         CachedAlgorithm::Advanced(interpreter) => {
             let backend_input = match transaction_data {
@@ -478,7 +514,8 @@ pub async fn perform_static_routing_v1(
                 // errors are ignored as this is just for diff checking as of now (optional flow).
                 logger::error!(decision_engine_euclid_evaluate_error=?e, "decision_engine_euclid: error in evaluation of rule")
             ).unwrap_or_default();
-            let routable_connectors = execute_dsl_and_get_connector_v1(backend_input, interpreter)?;
+            let (routable_connectors, evaluation_trace) =
+                execute_dsl_with_trace_and_get_connector_v1(backend_input, interpreter)?;
             let connectors = routable_connectors
                 .iter()
                 .map(|c| c.connector.to_string())
@@ -488,7 +525,7 @@ pub async fn perform_static_routing_v1(
                 connectors,
                 "evaluate_routing".to_string(),
             );
-            routable_connectors
+            (routable_connectors, Some(evaluation_trace))
         }
     })
 }
@@ -581,6 +618,35 @@ fn execute_dsl_and_get_connector_v1(
         .map(|out| out.connector_selection.foreign_into())
         .change_context(errors::RoutingError::DslExecutionError)?;
 
+    get_connectors_from_dsl_output(routing_output)
+}
+
+fn execute_dsl_with_trace_and_get_connector_v1(
+    backend_input: dsl_inputs::BackendInput,
+    interpreter: &backend::VirInterpreterBackend<ConnectorSelection>,
+) -> RoutingResult<(
+    Vec<routing_types::RoutableConnectorChoice>,
+    routing_types::EvaluationTrace,
+)> {
+    let (output, evaluation_trace) = interpreter.execute_with_trace(backend_input);
+
+    logger::debug!(
+        routing_rule = ?output.rule_name,
+        default_selection_used = evaluation_trace.default_selection_used,
+        "static routing evaluation trace recorded"
+    );
+
+    let routing_output: routing_types::RoutingAlgorithm = output.connector_selection.foreign_into();
+
+    Ok((
+        get_connectors_from_dsl_output(routing_output)?,
+        evaluation_trace,
+    ))
+}
+
+fn get_connectors_from_dsl_output(
+    routing_output: routing_types::RoutingAlgorithm,
+) -> RoutingResult<Vec<routing_types::RoutableConnectorChoice>> {
     Ok(match routing_output {
         routing_types::RoutingAlgorithm::Priority(plist) => plist,
 
@@ -1062,6 +1128,9 @@ pub async fn perform_session_flow_routing<'a>(
             mandate_type: None,
             payment_type: None,
         },
+        time: Some(dsl_inputs::TimeInput::from_timestamp(
+            common_utils::date_time::now(),
+        )),
     };
 
     for connector_data in session_input.chosen.iter() {
@@ -1206,6 +1275,9 @@ pub async fn perform_session_flow_routing(
             mandate_type: None,
             payment_type: None,
         },
+        time: Some(dsl_inputs::TimeInput::from_timestamp(
+            common_utils::date_time::now(),
+        )),
     };
 
     for connector_data in session_input.chosen.iter() {
@@ -1508,6 +1580,9 @@ pub fn make_dsl_input_for_surcharge(
         payment: payment_input,
         payment_method: payment_method_input,
         mandate: mandate_data,
+        time: Some(dsl_inputs::TimeInput::from_timestamp(
+            common_utils::date_time::now(),
+        )),
     };
     Ok(backend_input)
 }
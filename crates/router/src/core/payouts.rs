@@ -126,6 +126,8 @@ pub async fn get_connector_choice(
                 routing_info: PaymentRoutingInfo {
                     algorithm: None,
                     pre_routing_results: None,
+                    evaluation_summary: None,
+                    evaluation_trace: None,
                 },
             };
             helpers::decide_payout_connector(
@@ -147,6 +149,8 @@ pub async fn get_connector_choice(
                 routing_info: PaymentRoutingInfo {
                     algorithm: None,
                     pre_routing_results: None,
+                    evaluation_summary: None,
+                    evaluation_trace: None,
                 },
             };
             helpers::decide_payout_connector(
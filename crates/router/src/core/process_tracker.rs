@@ -0,0 +1,837 @@
+//! Admin APIs to inspect and operate the tasks of the process tracker
+//!
+//! Tasks can be listed, retried and cancelled, and the scheduling of the tasks of a runner can be
+//! paused and resumed. Tasks which have been picked up by the scheduler already can neither be
+//! retried nor cancelled, as the consumer executing them would overwrite the change. Retrying a
+//! failed task restores the tasks which were not executed due to its failure, the tasks which were
+//! not executed as their parent failed are retried through their parent.
+
+use std::str::FromStr;
+
+use api_models::{enums, process_tracker as process_tracker_api};
+use common_utils::{ext_traits::OptionExt, fp_utils};
+use diesel_models::process_tracker::business_status;
+use error_stack::ResultExt;
+use router_env::{instrument, logger, tracing};
+use scheduler::db::queue::PausedRunner;
+
+use crate::{
+    core::errors::{self, RouterResponse, RouterResult},
+    db::StorageInterface,
+    events::audit_events::{AuditEvent, AuditEventType},
+    routes::{
+        app::{ReqState, SessionStateInfo},
+        SessionState,
+    },
+    services::ApplicationResponse,
+    types::{storage, transformers::ForeignFrom},
+};
+
+const TASK_LIST_DEFAULT_LIMIT: u32 = 10;
+const TASK_LIST_MAX_LIMIT: u32 = 100;
+/// Maximum number of tasks which can be cancelled in a single request
+const TASK_CANCEL_MAX_COUNT: usize = 100;
+
+#[instrument(skip_all)]
+pub async fn list_tasks(
+    state: SessionState,
+    constraints: process_tracker_api::ProcessTrackerTaskListConstraints,
+) -> RouterResponse<process_tracker_api::ProcessTrackerTaskListResponse> {
+    let limit = constraints.limit.unwrap_or(TASK_LIST_DEFAULT_LIMIT);
+    fp_utils::when(limit == 0 || limit > TASK_LIST_MAX_LIMIT, || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("limit should be between 1 and {TASK_LIST_MAX_LIMIT}"),
+        })
+    })?;
+
+    let tasks = state
+        .store
+        .filter_processes(
+            constraints.runner.map(|runner| runner.to_string()),
+            constraints.status,
+            constraints
+                .merchant_id
+                .map(|merchant_id| merchant_id.get_string_repr().to_owned()),
+            i64::from(limit),
+            i64::from(constraints.offset.unwrap_or_default()),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list process tracker tasks")?;
+
+    let data = tasks
+        .into_iter()
+        .map(process_tracker_api::ProcessTrackerTaskResponse::foreign_from)
+        .collect::<Vec<_>>();
+
+    Ok(ApplicationResponse::Json(
+        process_tracker_api::ProcessTrackerTaskListResponse {
+            count: data.len(),
+            data,
+        },
+    ))
+}
+
+#[instrument(skip_all)]
+pub async fn retrieve_task(
+    state: SessionState,
+    request: process_tracker_api::ProcessTrackerTaskId,
+) -> RouterResponse<process_tracker_api::ProcessTrackerTaskResponse> {
+    let task = find_task(state.store.as_ref(), &request.task_id).await?;
+
+    Ok(ApplicationResponse::Json(
+        process_tracker_api::ProcessTrackerTaskResponse::foreign_from(task),
+    ))
+}
+
+/// Schedules the task for execution right away, without consuming a retry of its workflow
+#[instrument(skip_all)]
+pub async fn retry_task(
+    state: SessionState,
+    req_state: ReqState,
+    request: process_tracker_api::ProcessTrackerTaskRetryRequest,
+) -> RouterResponse<process_tracker_api::ProcessTrackerTaskResponse> {
+    validate_operator(&request.operator)?;
+    let db = state.store.as_ref();
+    let task = find_task(db, &request.task_id).await?;
+
+    fp_utils::when(
+        matches!(
+            task.status,
+            enums::ProcessTrackerStatus::Processing | enums::ProcessTrackerStatus::ProcessStarted
+        ),
+        || {
+            Err(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!(
+                    "Task is being processed by the scheduler with status `{}`, it cannot be retried",
+                    task.status
+                ),
+            })
+        },
+    )?;
+    fp_utils::when(task.status == enums::ProcessTrackerStatus::Waiting, || {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "Task is waiting for its parent tasks, it cannot be retried".to_string(),
+        })
+    })?;
+    fp_utils::when(
+        task.business_status == business_status::PARENT_FAILED,
+        || {
+            Err(errors::ApiErrorResponse::PreconditionFailed {
+                message: "Task was not executed as one of its parent tasks failed, the parent task is to be retried instead".to_string(),
+            })
+        },
+    )?;
+
+    let is_failed = task.status == enums::ProcessTrackerStatus::Finish
+        && business_status::is_failure(&task.business_status);
+    let schedule_time = common_utils::date_time::now();
+    let retry_count = task.retry_count;
+    let retry_history = task.get_retry_history_with(retry_count, schedule_time, true);
+    // Finished tasks are picked up again by their workflow as if they were new
+    let business_status = (task.status == enums::ProcessTrackerStatus::Finish)
+        .then(|| String::from(business_status::PENDING));
+
+    // The task is only updated if it is still in the status it was validated in, so that a task
+    // picked up by the scheduler in the meantime is not retried
+    let task = db
+        .update_processes_by_ids_and_statuses(
+            vec![task.id],
+            vec![task.status],
+            storage::ProcessTrackerUpdate::RetryUpdate {
+                retry_count,
+                schedule_time,
+                business_status,
+                retry_history,
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retry process tracker task")?
+        .into_iter()
+        .next()
+        .ok_or(errors::ApiErrorResponse::PreconditionFailed {
+            message: "Task was updated while it was being retried, it cannot be retried"
+                .to_string(),
+        })?;
+
+    // The tasks waiting for the task are restored so that they are resolved once the task
+    // finishes. Should the task finish before they are restored, the waiting tasks are resolved by
+    // the reconciliation of the scheduler.
+    if is_failed {
+        scheduler::dependencies::restore_dependent_tasks(state.store.as_scheduler(), &task.id)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to restore tasks waiting for the retried task")?;
+    }
+
+    logger::info!(task_id = %task.id, operator = %request.operator, "Forced a retry of the process tracker task");
+    req_state
+        .event_context
+        .event(AuditEvent::new(AuditEventType::ProcessTrackerTaskRetried {
+            task_id: task.id.clone(),
+            operator: request.operator,
+            request_id: state.get_request_id(),
+        }))
+        .emit();
+
+    Ok(ApplicationResponse::Json(
+        process_tracker_api::ProcessTrackerTaskResponse::foreign_from(task),
+    ))
+}
+
+/// Finishes the tasks which have not been picked up by the scheduler yet, the other tasks are
+/// left as is. Tasks waiting for the cancelled tasks are finished as well.
+#[instrument(skip_all)]
+pub async fn cancel_tasks(
+    state: SessionState,
+    req_state: ReqState,
+    request: process_tracker_api::ProcessTrackerTasksCancelRequest,
+) -> RouterResponse<process_tracker_api::ProcessTrackerTasksCancelResponse> {
+    validate_operator(&request.operator)?;
+    fp_utils::when(
+        request.task_ids.is_empty() || request.task_ids.len() > TASK_CANCEL_MAX_COUNT,
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "task_ids should contain between 1 and {TASK_CANCEL_MAX_COUNT} tasks"
+                ),
+            })
+        },
+    )?;
+
+    let cancelled_task_ids = state
+        .store
+        .update_processes_by_ids_and_statuses(
+            request.task_ids,
+            vec![
+                enums::ProcessTrackerStatus::New,
+                enums::ProcessTrackerStatus::Pending,
+                enums::ProcessTrackerStatus::Waiting,
+            ],
+            storage::ProcessTrackerUpdate::StatusUpdate {
+                status: enums::ProcessTrackerStatus::Finish,
+                business_status: Some(String::from(business_status::CANCELLED_BY_OPERATOR)),
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to cancel process tracker tasks")?
+        .into_iter()
+        .map(|task| task.id)
+        .collect::<Vec<_>>();
+
+    for task_id in &cancelled_task_ids {
+        scheduler::dependencies::fail_dependent_tasks(state.store.as_scheduler(), task_id)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to finish tasks waiting for the cancelled task")?;
+    }
+
+    logger::info!(
+        ?cancelled_task_ids,
+        reason = ?request.reason,
+        operator = %request.operator,
+        "Cancelled process tracker tasks"
+    );
+    req_state
+        .event_context
+        .event(AuditEvent::new(
+            AuditEventType::ProcessTrackerTasksCancelled {
+                task_ids: cancelled_task_ids.clone(),
+                reason: request.reason,
+                operator: request.operator,
+                request_id: state.get_request_id(),
+            },
+        ))
+        .emit();
+
+    Ok(ApplicationResponse::Json(
+        process_tracker_api::ProcessTrackerTasksCancelResponse { cancelled_task_ids },
+    ))
+}
+
+/// Stops the producer from scheduling the tasks of the runner, the tasks which have been
+/// scheduled already are still executed by the consumers
+#[instrument(skip_all)]
+pub async fn pause_runner(
+    state: SessionState,
+    req_state: ReqState,
+    request: process_tracker_api::ProcessTrackerRunnerPauseRequest,
+) -> RouterResponse<process_tracker_api::ProcessTrackerPausedRunner> {
+    validate_operator(&request.operator)?;
+    let paused_runner = PausedRunner {
+        paused_at: common_utils::date_time::now(),
+        reason: request.reason,
+    };
+
+    let is_paused = state
+        .store
+        .pause_runner(&request.runner.to_string(), paused_runner.clone())
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to pause process tracker runner")?;
+
+    fp_utils::when(!is_paused, || {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!("Runner `{}` is paused already", request.runner),
+        })
+    })?;
+
+    logger::info!(
+        runner = %request.runner,
+        reason = ?paused_runner.reason,
+        operator = %request.operator,
+        "Paused process tracker runner"
+    );
+    req_state
+        .event_context
+        .event(AuditEvent::new(
+            AuditEventType::ProcessTrackerRunnerPaused {
+                runner: request.runner,
+                reason: paused_runner.reason.clone(),
+                operator: request.operator,
+                request_id: state.get_request_id(),
+            },
+        ))
+        .emit();
+
+    Ok(ApplicationResponse::Json(
+        process_tracker_api::ProcessTrackerPausedRunner {
+            runner: request.runner,
+            paused_at: paused_runner.paused_at,
+            reason: paused_runner.reason,
+        },
+    ))
+}
+
+/// Resumes the scheduling of the tasks of the runner. The tasks which became due while the runner
+/// was paused are rescheduled to now, as the producer only fetches the tasks which became due
+/// recently.
+#[instrument(skip_all)]
+pub async fn resume_runner(
+    state: SessionState,
+    req_state: ReqState,
+    request: process_tracker_api::ProcessTrackerRunnerResumeRequest,
+) -> RouterResponse<()> {
+    validate_operator(&request.operator)?;
+    let is_resumed = state
+        .store
+        .resume_runner(&request.runner.to_string())
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to resume process tracker runner")?;
+
+    fp_utils::when(!is_resumed, || {
+        Err(errors::ApiErrorResponse::GenericNotFoundError {
+            message: format!("Runner `{}` is not paused", request.runner),
+        })
+    })?;
+
+    let rescheduled_task_count = state
+        .store
+        .reschedule_overdue_processes_by_runner(
+            request.runner.to_string(),
+            common_utils::date_time::now(),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to reschedule the tasks of the resumed process tracker runner")?;
+
+    logger::info!(
+        runner = %request.runner,
+        rescheduled_task_count,
+        operator = %request.operator,
+        "Resumed process tracker runner"
+    );
+    req_state
+        .event_context
+        .event(AuditEvent::new(
+            AuditEventType::ProcessTrackerRunnerResumed {
+                runner: request.runner,
+                rescheduled_task_count,
+                operator: request.operator,
+                request_id: state.get_request_id(),
+            },
+        ))
+        .emit();
+
+    Ok(ApplicationResponse::StatusOk)
+}
+
+#[instrument(skip_all)]
+pub async fn list_paused_runners(
+    state: SessionState,
+) -> RouterResponse<process_tracker_api::ProcessTrackerPausedRunnersResponse> {
+    let paused_runners = state
+        .store
+        .get_paused_runners()
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch paused process tracker runners")?;
+
+    let mut data = paused_runners
+        .into_iter()
+        .filter_map(|(runner, paused_runner)| {
+            enums::ProcessTrackerRunner::from_str(&runner)
+                .inspect_err(|error| {
+                    logger::warn!(?error, %runner, "Unknown paused process tracker runner");
+                })
+                .ok()
+                .map(|runner| process_tracker_api::ProcessTrackerPausedRunner {
+                    runner,
+                    paused_at: paused_runner.paused_at,
+                    reason: paused_runner.reason,
+                })
+        })
+        .collect::<Vec<_>>();
+    data.sort_by_key(|paused_runner| paused_runner.paused_at);
+
+    Ok(ApplicationResponse::Json(
+        process_tracker_api::ProcessTrackerPausedRunnersResponse {
+            count: data.len(),
+            data,
+        },
+    ))
+}
+
+fn validate_operator(operator: &str) -> RouterResult<()> {
+    fp_utils::when(operator.trim().is_empty(), || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "operator must not be empty".to_string(),
+        })
+    })?;
+
+    Ok(())
+}
+
+async fn find_task(
+    db: &dyn StorageInterface,
+    task_id: &str,
+) -> RouterResult<storage::ProcessTracker> {
+    db.find_process_by_id(task_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch process tracker task")?
+        .get_required_value("process_tracker")
+        .change_context(errors::ApiErrorResponse::GenericNotFoundError {
+            message: format!("Process tracker task `{task_id}` does not exist"),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        routes::{
+            self,
+            app::{settings::Settings, StorageImpl},
+        },
+        services,
+    };
+
+    async fn get_session_state() -> SessionState {
+        let (tx, _) = tokio::sync::oneshot::channel();
+        let app_state = Box::pin(routes::AppState::with_storage(
+            Settings::default(),
+            StorageImpl::Mock,
+            tx,
+            Box::new(services::MockApiClient),
+        ))
+        .await;
+
+        Arc::new(app_state)
+            .get_session_state(
+                &common_utils::id_type::TenantId::try_from_string("public".to_string()).unwrap(),
+                None,
+                || {},
+            )
+            .unwrap()
+    }
+
+    async fn insert_task(
+        state: &SessionState,
+        task_id: &str,
+        status: enums::ProcessTrackerStatus,
+        business_status: &str,
+        parent_ids: Vec<&str>,
+    ) {
+        let mut task = storage::ProcessTrackerNew::new(
+            task_id,
+            "PAYMENTS_SYNC",
+            enums::ProcessTrackerRunner::PaymentsSyncWorkflow,
+            ["PAYMENT"],
+            serde_json::json!({}),
+            None,
+            common_utils::date_time::now(),
+            common_enums::ApiVersion::V1,
+        )
+        .unwrap();
+        task.status = status;
+        task.business_status = business_status.to_string();
+        task.parent_ids =
+            (!parent_ids.is_empty()).then(|| parent_ids.into_iter().map(String::from).collect());
+
+        state.store.insert_process(task).await.unwrap();
+    }
+
+    async fn get_task(state: &SessionState, task_id: &str) -> storage::ProcessTracker {
+        find_task(state.store.as_ref(), task_id).await.unwrap()
+    }
+
+    async fn retry(
+        state: &SessionState,
+        task_id: &str,
+    ) -> RouterResponse<process_tracker_api::ProcessTrackerTaskResponse> {
+        retry_task(
+            state.clone(),
+            state.get_req_state(),
+            process_tracker_api::ProcessTrackerTaskRetryRequest {
+                task_id: task_id.to_string(),
+                operator: "operator@example.com".to_string(),
+            },
+        )
+        .await
+    }
+
+    fn is_precondition_failed<T>(response: RouterResponse<T>) -> bool {
+        response.is_err_and(|error| {
+            matches!(
+                error.current_context(),
+                errors::ApiErrorResponse::PreconditionFailed { .. }
+            )
+        })
+    }
+
+    #[tokio::test]
+    async fn test_retry_task_status_preconditions() {
+        let state = get_session_state().await;
+
+        for (task_id, status, task_business_status) in [
+            (
+                "pt_processing",
+                enums::ProcessTrackerStatus::Processing,
+                business_status::PENDING,
+            ),
+            (
+                "pt_process_started",
+                enums::ProcessTrackerStatus::ProcessStarted,
+                business_status::PENDING,
+            ),
+            (
+                "pt_waiting",
+                enums::ProcessTrackerStatus::Waiting,
+                business_status::PENDING,
+            ),
+            (
+                "pt_parent_failed",
+                enums::ProcessTrackerStatus::Finish,
+                business_status::PARENT_FAILED,
+            ),
+        ] {
+            insert_task(&state, task_id, status, task_business_status, vec![]).await;
+
+            assert!(is_precondition_failed(retry(&state, task_id).await));
+            assert_eq!(get_task(&state, task_id).await.status, status);
+        }
+
+        insert_task(
+            &state,
+            "pt_finished",
+            enums::ProcessTrackerStatus::Finish,
+            business_status::COMPLETED_BY_PT,
+            vec![],
+        )
+        .await;
+        retry(&state, "pt_finished").await.unwrap();
+        let task = get_task(&state, "pt_finished").await;
+        assert_eq!(task.status, enums::ProcessTrackerStatus::Pending);
+        assert_eq!(task.business_status, business_status::PENDING);
+        assert_eq!(task.retry_history.unwrap().0.len(), 1);
+
+        insert_task(
+            &state,
+            "pt_pending",
+            enums::ProcessTrackerStatus::Pending,
+            business_status::PENDING,
+            vec![],
+        )
+        .await;
+        retry(&state, "pt_pending").await.unwrap();
+        assert_eq!(
+            get_task(&state, "pt_pending").await.status,
+            enums::ProcessTrackerStatus::Pending
+        );
+
+        assert!(retry(&state, "pt_missing")
+            .await
+            .is_err_and(|error| matches!(
+                error.current_context(),
+                errors::ApiErrorResponse::GenericNotFoundError { .. }
+            )));
+    }
+
+    #[tokio::test]
+    async fn test_retry_failed_task_restores_dependent_tasks() {
+        let state = get_session_state().await;
+        let finish = enums::ProcessTrackerStatus::Finish;
+
+        insert_task(
+            &state,
+            "pt_parent",
+            finish,
+            business_status::FAILURE,
+            vec![],
+        )
+        .await;
+        insert_task(&state, "pt_other", finish, business_status::FAILURE, vec![]).await;
+        insert_task(
+            &state,
+            "pt_child",
+            finish,
+            business_status::PARENT_FAILED,
+            vec!["pt_parent"],
+        )
+        .await;
+        insert_task(
+            &state,
+            "pt_grandchild",
+            finish,
+            business_status::PARENT_FAILED,
+            vec!["pt_child"],
+        )
+        .await;
+        insert_task(
+            &state,
+            "pt_child_of_both",
+            finish,
+            business_status::PARENT_FAILED,
+            vec!["pt_parent", "pt_other"],
+        )
+        .await;
+
+        retry(&state, "pt_parent").await.unwrap();
+
+        assert_eq!(
+            get_task(&state, "pt_parent").await.status,
+            enums::ProcessTrackerStatus::Pending
+        );
+        for task_id in ["pt_child", "pt_grandchild"] {
+            let task = get_task(&state, task_id).await;
+            assert_eq!(task.status, enums::ProcessTrackerStatus::Waiting);
+            assert_eq!(task.business_status, business_status::PENDING);
+        }
+        // The other parent of the task has failed as well
+        let task = get_task(&state, "pt_child_of_both").await;
+        assert_eq!(task.status, finish);
+        assert_eq!(task.business_status, business_status::PARENT_FAILED);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_tasks_status_preconditions() {
+        let state = get_session_state().await;
+
+        let statuses = [
+            ("pt_cancel_new", enums::ProcessTrackerStatus::New),
+            ("pt_cancel_pending", enums::ProcessTrackerStatus::Pending),
+            ("pt_cancel_waiting", enums::ProcessTrackerStatus::Waiting),
+            (
+                "pt_cancel_processing",
+                enums::ProcessTrackerStatus::Processing,
+            ),
+            ("pt_cancel_finished", enums::ProcessTrackerStatus::Finish),
+        ];
+        for (task_id, status) in statuses {
+            insert_task(&state, task_id, status, business_status::PENDING, vec![]).await;
+        }
+        insert_task(
+            &state,
+            "pt_cancel_child",
+            enums::ProcessTrackerStatus::Waiting,
+            business_status::PENDING,
+            vec!["pt_cancel_new"],
+        )
+        .await;
+
+        let response = cancel_tasks(
+            state.clone(),
+            state.get_req_state(),
+            process_tracker_api::ProcessTrackerTasksCancelRequest {
+                task_ids: statuses
+                    .iter()
+                    .map(|(task_id, _)| task_id.to_string())
+                    .collect(),
+                reason: None,
+                operator: "operator@example.com".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let mut cancelled_task_ids = match response {
+            ApplicationResponse::Json(response) => response.cancelled_task_ids,
+            _ => Vec::new(),
+        };
+        cancelled_task_ids.sort();
+        assert_eq!(
+            cancelled_task_ids,
+            vec!["pt_cancel_new", "pt_cancel_pending", "pt_cancel_waiting"]
+        );
+        for task_id in &cancelled_task_ids {
+            assert_eq!(
+                get_task(&state, task_id).await.business_status,
+                business_status::CANCELLED_BY_OPERATOR
+            );
+        }
+        assert_eq!(
+            get_task(&state, "pt_cancel_processing").await.status,
+            enums::ProcessTrackerStatus::Processing
+        );
+        assert_eq!(
+            get_task(&state, "pt_cancel_finished").await.business_status,
+            business_status::PENDING
+        );
+
+        let child = get_task(&state, "pt_cancel_child").await;
+        assert_eq!(child.status, enums::ProcessTrackerStatus::Finish);
+        assert_eq!(child.business_status, business_status::PARENT_FAILED);
+
+        let response = cancel_tasks(
+            state.clone(),
+            state.get_req_state(),
+            process_tracker_api::ProcessTrackerTasksCancelRequest {
+                task_ids: vec![],
+                reason: None,
+                operator: "operator@example.com".to_string(),
+            },
+        )
+        .await;
+        assert!(response.is_err_and(|error| matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::InvalidRequestData { .. }
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume_runner_preconditions() {
+        let state = get_session_state().await;
+        let runner = enums::ProcessTrackerRunner::PassiveRecoveryWorkflow;
+        let pause = || {
+            pause_runner(
+                state.clone(),
+                state.get_req_state(),
+                process_tracker_api::ProcessTrackerRunnerPauseRequest {
+                    runner,
+                    reason: Some("maintenance".to_string()),
+                    operator: "operator@example.com".to_string(),
+                },
+            )
+        };
+        let resume = || {
+            resume_runner(
+                state.clone(),
+                state.get_req_state(),
+                process_tracker_api::ProcessTrackerRunnerResumeRequest {
+                    runner,
+                    operator: "operator@example.com".to_string(),
+                },
+            )
+        };
+
+        pause().await.unwrap();
+        assert!(is_precondition_failed(pause().await));
+
+        resume().await.unwrap();
+        assert!(resume().await.is_err_and(|error| matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::GenericNotFoundError { .. }
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_resume_runner_reschedules_overdue_tasks() {
+        let state = get_session_state().await;
+        let runner = enums::ProcessTrackerRunner::PaymentsSyncWorkflow;
+        let operator = "operator@example.com".to_string();
+
+        insert_task(
+            &state,
+            "pt_overdue",
+            enums::ProcessTrackerStatus::New,
+            business_status::PENDING,
+            vec![],
+        )
+        .await;
+        let overdue_schedule_time =
+            common_utils::date_time::now().saturating_sub(time::Duration::hours(1));
+        state
+            .store
+            .update_process(
+                get_task(&state, "pt_overdue").await,
+                storage::ProcessTrackerUpdate::ScheduleUpdate {
+                    schedule_time: overdue_schedule_time,
+                    retry_history: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        pause_runner(
+            state.clone(),
+            state.get_req_state(),
+            process_tracker_api::ProcessTrackerRunnerPauseRequest {
+                runner,
+                reason: None,
+                operator: operator.clone(),
+            },
+        )
+        .await
+        .unwrap();
+        resume_runner(
+            state.clone(),
+            state.get_req_state(),
+            process_tracker_api::ProcessTrackerRunnerResumeRequest { runner, operator },
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            get_task(&state, "pt_overdue").await.schedule_time.unwrap() > overdue_schedule_time
+        );
+    }
+
+    #[tokio::test]
+    async fn test_operator_is_required() {
+        let state = get_session_state().await;
+
+        insert_task(
+            &state,
+            "pt_no_operator",
+            enums::ProcessTrackerStatus::Finish,
+            business_status::FAILURE,
+            vec![],
+        )
+        .await;
+        let response = retry_task(
+            state.clone(),
+            state.get_req_state(),
+            process_tracker_api::ProcessTrackerTaskRetryRequest {
+                task_id: "pt_no_operator".to_string(),
+                operator: " ".to_string(),
+            },
+        )
+        .await;
+
+        assert!(response.is_err_and(|error| matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::InvalidRequestData { .. }
+        )));
+        assert_eq!(
+            get_task(&state, "pt_no_operator").await.status,
+            enums::ProcessTrackerStatus::Finish
+        );
+    }
+}
@@ -1258,6 +1258,7 @@ impl ForeignFrom<storage::Refund> for api::RefundResponse {
 
         Self {
             payment_id: refund.payment_id,
+            attempt_id: Some(refund.attempt_id),
             refund_id: refund.refund_id,
             amount: refund.refund_amount,
             currency: refund.currency.to_string(),
@@ -127,6 +127,7 @@ pub async fn perform_execute_payment(
                     insert_psync_pcr_task_to_pt(
                         revenue_recovery_payment_data.billing_mca.get_id().clone(),
                         db,
+                        &execute_task_process.id,
                         revenue_recovery_payment_data
                             .merchant_account
                             .get_id()
@@ -196,9 +197,13 @@ pub async fn perform_execute_payment(
     Ok(())
 }
 
+/// Creates the psync task of the payment attempt, executed once the execute task which made the
+/// attempt finishes
+#[allow(clippy::too_many_arguments)]
 async fn insert_psync_pcr_task_to_pt(
     billing_mca_id: id_type::MerchantConnectorAccountId,
     db: &dyn StorageInterface,
+    execute_task_id: &str,
     merchant_id: id_type::MerchantId,
     payment_id: id_type::GlobalPaymentId,
     profile_id: id_type::ProfileId,
@@ -229,11 +234,14 @@ async fn insert_psync_pcr_task_to_pt(
     .change_context(errors::ApiErrorResponse::InternalServerError)
     .attach_printable("Failed to construct delete tokenized data process tracker task")?;
 
-    let response = db
-        .insert_process(process_tracker_entry)
-        .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Failed to construct delete tokenized data process tracker task")?;
+    let response = scheduler::dependencies::create_dependent_task(
+        db.as_scheduler(),
+        process_tracker_entry,
+        vec![execute_task_id.to_owned()],
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to insert revenue recovery psync process tracker task")?;
     metrics::TASKS_ADDED_COUNT.add(
         1,
         router_env::metric_attributes!(("flow", "RevenueRecoveryPsync")),
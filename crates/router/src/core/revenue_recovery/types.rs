@@ -346,6 +346,7 @@ impl Action {
                 revenue_recovery_core::insert_psync_pcr_task_to_pt(
                     revenue_recovery_payment_data.billing_mca.get_id().clone(),
                     db,
+                    &execute_task_process.id,
                     revenue_recovery_payment_data
                         .merchant_account
                         .get_id()
@@ -1,4 +1,8 @@
+#[cfg(all(feature = "olap", feature = "v1"))]
+pub mod fixtures;
 pub mod helpers;
+#[cfg(all(feature = "olap", feature = "v1"))]
+pub mod simulation;
 pub mod transformers;
 use std::collections::HashSet;
 
@@ -259,7 +263,9 @@ pub async fn create_routing_algorithm_under_profile(
         .await
         .to_not_found_response(errors::ApiErrorResponse::ResourceIdNotFound)?;
 
-    let new_record = record.foreign_into();
+    let mut new_record: routing_types::RoutingDictionaryRecord = record.foreign_into();
+    new_record.analysis_warnings =
+        helpers::get_routing_algorithm_analysis_warnings(&request.algorithm);
 
     metrics::ROUTING_CREATE_SUCCESS_RESPONSE.add(1, &[]);
     Ok(service_api::ApplicationResponse::Json(new_record))
@@ -385,7 +391,8 @@ pub async fn create_routing_algorithm_under_profile(
         .await
         .to_not_found_response(errors::ApiErrorResponse::ResourceIdNotFound)?;
 
-    let new_record = record.foreign_into();
+    let mut new_record: routing_types::RoutingDictionaryRecord = record.foreign_into();
+    new_record.analysis_warnings = helpers::get_routing_algorithm_analysis_warnings(&algorithm);
 
     metrics::ROUTING_CREATE_SUCCESS_RESPONSE.add(1, &[]);
     Ok(service_api::ApplicationResponse::Json(new_record))
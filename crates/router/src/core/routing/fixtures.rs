@@ -0,0 +1,73 @@
+use api_models::routing as routing_types;
+use error_stack::{report, ResultExt};
+use euclid::backend::fixtures::FixtureRunner;
+
+use crate::{
+    core::{
+        errors::{self, RouterResponse, StorageErrorExt},
+        utils as core_utils,
+    },
+    routes::SessionState,
+    services::api as service_api,
+    types::domain,
+    utils::{OptionExt, ValueExt},
+};
+
+/// Runs a suite of regression fixtures against a routing algorithm of the merchant, which need
+/// not be active, so that the algorithm can be verified before activating it
+pub async fn run_routing_fixtures(
+    state: SessionState,
+    merchant_context: domain::MerchantContext,
+    authentication_profile_id: Option<common_utils::id_type::ProfileId>,
+    algorithm_id: common_utils::id_type::RoutingId,
+    request: routing_types::RoutingFixturesRequest,
+) -> RouterResponse<routing_types::RoutingFixturesResponse> {
+    let db = state.store.as_ref();
+    let key_manager_state = &(&state).into();
+
+    let routing_algorithm = db
+        .find_routing_algorithm_by_algorithm_id_merchant_id(
+            &algorithm_id,
+            merchant_context.get_merchant_account().get_id(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::ResourceIdNotFound)?;
+
+    let business_profile = core_utils::validate_and_get_business_profile(
+        db,
+        key_manager_state,
+        merchant_context.get_merchant_key_store(),
+        Some(&routing_algorithm.profile_id),
+        merchant_context.get_merchant_account().get_id(),
+    )
+    .await?
+    .get_required_value("Profile")
+    .change_context(errors::ApiErrorResponse::ResourceIdNotFound)?;
+
+    core_utils::validate_profile_id_from_auth_layer(authentication_profile_id, &business_profile)?;
+
+    let algorithm: routing_types::RoutingAlgorithm = routing_algorithm
+        .algorithm_data
+        .parse_value("RoutingAlgorithm")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("unable to deserialize the routing algorithm")?;
+
+    let routing_types::RoutingAlgorithm::Advanced(program) = algorithm else {
+        return Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "fixtures can only be run against advanced routing algorithms".to_string(),
+        }
+        .into());
+    };
+
+    let runner = FixtureRunner::new(program).map_err(|error| {
+        report!(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable(format!("error initializing the routing algorithm: {error}"))
+    })?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        routing_types::RoutingFixturesResponse {
+            algorithm_id: routing_algorithm.algorithm_id,
+            report: runner.run_suite(&request.fixtures),
+        },
+    ))
+}
@@ -503,6 +503,25 @@ pub async fn validate_connectors_in_routing_config(
     Ok(())
 }
 
+/// Runs static analysis over an advanced routing algorithm, returning warnings about unreachable
+/// or overlapping rules and number comparisons which can never be satisfied.
+///
+/// Analysis failures are only logged, so that they do not prevent the algorithm from being saved.
+pub fn get_routing_algorithm_analysis_warnings(
+    algorithm: &routing_types::RoutingAlgorithm,
+) -> Option<Vec<routing_types::AnalysisWarning>> {
+    let routing_types::RoutingAlgorithm::Advanced(program) = algorithm else {
+        return None;
+    };
+
+    euclid::dssa::analyzer::analyze_with_warnings(program.clone(), None)
+        .map(|(_, warnings)| warnings)
+        .map_err(|error| {
+            router_env::logger::warn!(?error, "static analysis of the routing algorithm failed")
+        })
+        .ok()
+}
+
 /// Provides the identifier for the specific merchant's routing_dictionary_key
 #[inline(always)]
 pub fn get_routing_dictionary_key(merchant_id: &str) -> String {
@@ -0,0 +1,551 @@
+use std::collections::HashMap;
+
+use analytics::payments::routing_inputs::PaymentRoutingInputRow;
+use api_models::routing::{
+    self as routing_types, ConnectorSelection, ConnectorSimulationVolume, RoutingSimulationOutcome,
+};
+use common_utils::types::{authentication::AuthInfo, MinorUnit, TimeRange};
+use diesel_models::enums as storage_enums;
+use error_stack::{report, ResultExt};
+use euclid::backend::{self, inputs as dsl_inputs, EuclidBackend};
+
+use crate::{
+    core::{
+        errors::{self, RouterResponse},
+        utils as core_utils,
+    },
+    routes::SessionState,
+    services::api as service_api,
+    types::domain,
+    utils::{OptionExt, ValueExt},
+};
+
+const DEFAULT_LOOKBACK_DAYS: u16 = 7;
+const MAX_LOOKBACK_DAYS: u16 = 90;
+/// Maximum number of groups of payment attempts replayed in a simulation, which keeps the query
+/// and the replay bounded for profiles with many distinct payment attempts
+const MAX_SIMULATED_GROUPS: u64 = 10_000;
+
+/// A routing algorithm prepared for replaying payment attempts through it
+enum AlgorithmSimulator {
+    Static(Vec<(String, f64)>),
+    Advanced(backend::VirInterpreterBackend<ConnectorSelection>),
+}
+
+impl AlgorithmSimulator {
+    fn new(algorithm: routing_types::RoutingAlgorithm) -> Result<Self, String> {
+        Ok(match algorithm {
+            routing_types::RoutingAlgorithm::Single(connector) => {
+                Self::Static(vec![(connector.connector.to_string(), 1.0)])
+            }
+            routing_types::RoutingAlgorithm::Priority(connectors) => {
+                Self::Static(get_priority_weights(&connectors))
+            }
+            routing_types::RoutingAlgorithm::VolumeSplit(splits) => {
+                Self::Static(get_volume_split_weights(&splits))
+            }
+            routing_types::RoutingAlgorithm::Advanced(program) => Self::Advanced(
+                backend::VirInterpreterBackend::with_program(program)
+                    .map_err(|error| error.to_string())?,
+            ),
+        })
+    }
+
+    /// Returns the share of the payment routed to each connector. Payments routed through a
+    /// volume split are distributed across its connectors instead of being sampled, to keep the
+    /// simulation deterministic.
+    fn get_connector_weights(&self, input: dsl_inputs::BackendInput) -> Vec<(String, f64)> {
+        match self {
+            Self::Static(weights) => weights.clone(),
+            Self::Advanced(interpreter) => match interpreter.execute(input) {
+                Ok(output) => match output.connector_selection {
+                    ConnectorSelection::Priority(connectors) => get_priority_weights(&connectors),
+                    ConnectorSelection::VolumeSplit(splits) => get_volume_split_weights(&splits),
+                },
+                Err(_) => Vec::new(),
+            },
+        }
+    }
+}
+
+fn get_priority_weights(
+    connectors: &[routing_types::RoutableConnectorChoice],
+) -> Vec<(String, f64)> {
+    connectors
+        .first()
+        .map(|connector| vec![(connector.connector.to_string(), 1.0)])
+        .unwrap_or_default()
+}
+
+fn get_volume_split_weights(splits: &[routing_types::ConnectorVolumeSplit]) -> Vec<(String, f64)> {
+    splits
+        .iter()
+        .map(|split| {
+            (
+                split.connector.connector.to_string(),
+                f64::from(split.split) / 100.0,
+            )
+        })
+        .collect()
+}
+
+fn get_backend_input(row: &PaymentRoutingInputRow) -> Option<dsl_inputs::BackendInput> {
+    Some(dsl_inputs::BackendInput {
+        metadata: None,
+        payment: dsl_inputs::PaymentInput {
+            amount: MinorUnit::new(row.amount?),
+            currency: row.currency.as_ref()?.0,
+            authentication_type: row
+                .authentication_type
+                .as_ref()
+                .map(|authentication_type| authentication_type.0),
+            card_bin: None,
+            capture_method: row
+                .capture_method
+                .as_ref()
+                .map(|capture_method| capture_method.0),
+            business_country: None,
+            billing_country: None,
+            business_label: None,
+            setup_future_usage: None,
+        },
+        payment_method: dsl_inputs::PaymentMethodInput {
+            payment_method: row
+                .payment_method
+                .as_ref()
+                .and_then(|payment_method| payment_method.parse().ok()),
+            payment_method_type: row
+                .payment_method_type
+                .as_ref()
+                .and_then(|payment_method_type| payment_method_type.parse().ok()),
+            card_network: row
+                .card_network
+                .as_ref()
+                .and_then(|card_network| card_network.parse().ok()),
+        },
+        mandate: dsl_inputs::MandateData {
+            mandate_acceptance_type: None,
+            mandate_type: None,
+            payment_type: None,
+        },
+        time: row.start_bucket.map(dsl_inputs::TimeInput::from_timestamp),
+    })
+}
+
+#[derive(Default)]
+struct AttemptOutcomes {
+    attempts: f64,
+    successes: f64,
+}
+
+impl AttemptOutcomes {
+    fn record(&mut self, attempts: f64, succeeded: bool) {
+        self.attempts += attempts;
+        if succeeded {
+            self.successes += attempts;
+        }
+    }
+
+    fn success_rate(&self) -> Option<f64> {
+        (self.attempts > 0.0).then(|| self.successes / self.attempts)
+    }
+}
+
+/// The historical success rate of each connector, for each payment method and overall
+#[derive(Default)]
+struct SuccessRates {
+    by_payment_method: HashMap<(String, Option<String>), AttemptOutcomes>,
+    by_connector: HashMap<String, AttemptOutcomes>,
+}
+
+impl SuccessRates {
+    fn record(
+        &mut self,
+        connector: &str,
+        payment_method: Option<&String>,
+        attempts: f64,
+        succeeded: bool,
+    ) {
+        self.by_payment_method
+            .entry((connector.to_owned(), payment_method.cloned()))
+            .or_default()
+            .record(attempts, succeeded);
+        self.by_connector
+            .entry(connector.to_owned())
+            .or_default()
+            .record(attempts, succeeded);
+    }
+
+    fn get(&self, connector: &str, payment_method: Option<&String>) -> Option<f64> {
+        self.by_payment_method
+            .get(&(connector.to_owned(), payment_method.cloned()))
+            .and_then(AttemptOutcomes::success_rate)
+            .or_else(|| {
+                self.by_connector
+                    .get(connector)
+                    .and_then(AttemptOutcomes::success_rate)
+            })
+    }
+}
+
+#[derive(Default)]
+struct OutcomeBuilder {
+    volumes: HashMap<String, f64>,
+    total_attempts: f64,
+    expected_successes: f64,
+    attempts_with_known_rate: f64,
+}
+
+impl OutcomeBuilder {
+    fn record(
+        &mut self,
+        weights: Vec<(String, f64)>,
+        attempts: f64,
+        payment_method: Option<&String>,
+        success_rates: &SuccessRates,
+    ) {
+        self.total_attempts += attempts;
+        for (connector, weight) in weights {
+            let routed_attempts = attempts * weight;
+            if let Some(success_rate) = success_rates.get(&connector, payment_method) {
+                self.expected_successes += routed_attempts * success_rate;
+                self.attempts_with_known_rate += routed_attempts;
+            }
+            *self.volumes.entry(connector).or_default() += routed_attempts;
+        }
+    }
+
+    fn build(self) -> RoutingSimulationOutcome {
+        let total_attempts = self.total_attempts;
+        let mut connectors = self
+            .volumes
+            .into_iter()
+            .map(|(connector, attempts)| ConnectorSimulationVolume {
+                connector,
+                attempts,
+                share: if total_attempts > 0.0 {
+                    attempts * 100.0 / total_attempts
+                } else {
+                    0.0
+                },
+            })
+            .collect::<Vec<_>>();
+        connectors.sort_by(|a, b| {
+            b.attempts
+                .total_cmp(&a.attempts)
+                .then_with(|| a.connector.cmp(&b.connector))
+        });
+
+        RoutingSimulationOutcome {
+            connectors,
+            expected_success_rate: (self.attempts_with_known_rate > 0.0)
+                .then(|| self.expected_successes * 100.0 / self.attempts_with_known_rate),
+        }
+    }
+}
+
+struct SimulationResult {
+    simulated_attempts: u64,
+    skipped_attempts: u64,
+    historical: RoutingSimulationOutcome,
+    active: Option<RoutingSimulationOutcome>,
+    candidate: RoutingSimulationOutcome,
+}
+
+/// Drops the groups loaded beyond the limit, returns whether there were any
+fn is_truncated(rows: &mut Vec<PaymentRoutingInputRow>, limit: u64) -> bool {
+    let limit = usize::try_from(limit).unwrap_or(usize::MAX);
+    let truncated = rows.len() > limit;
+    rows.truncate(limit);
+    truncated
+}
+
+fn simulate(
+    rows: &[PaymentRoutingInputRow],
+    active: Option<&AlgorithmSimulator>,
+    candidate: &AlgorithmSimulator,
+) -> SimulationResult {
+    let mut success_rates = SuccessRates::default();
+    for row in rows {
+        if let Some(connector) = row.connector.as_ref() {
+            let succeeded = row
+                .status
+                .as_ref()
+                .is_some_and(|status| status.0 == storage_enums::AttemptStatus::Charged);
+            success_rates.record(
+                connector,
+                row.payment_method.as_ref(),
+                f64::from(get_attempt_count(row)),
+                succeeded,
+            );
+        }
+    }
+
+    let mut simulated_attempts = 0;
+    let mut skipped_attempts = 0;
+    let mut historical = OutcomeBuilder::default();
+    let mut active_outcome = active.map(|_| OutcomeBuilder::default());
+    let mut candidate_outcome = OutcomeBuilder::default();
+
+    for row in rows {
+        let count = get_attempt_count(row);
+        let Some(input) = get_backend_input(row) else {
+            skipped_attempts += u64::from(count);
+            continue;
+        };
+        simulated_attempts += u64::from(count);
+
+        let attempts = f64::from(count);
+        let payment_method = row.payment_method.as_ref();
+
+        if let Some(connector) = row.connector.as_ref() {
+            historical.record(
+                vec![(connector.clone(), 1.0)],
+                attempts,
+                payment_method,
+                &success_rates,
+            );
+        }
+        if let Some((simulator, outcome)) = active.zip(active_outcome.as_mut()) {
+            outcome.record(
+                simulator.get_connector_weights(input.clone()),
+                attempts,
+                payment_method,
+                &success_rates,
+            );
+        }
+        candidate_outcome.record(
+            candidate.get_connector_weights(input),
+            attempts,
+            payment_method,
+            &success_rates,
+        );
+    }
+
+    SimulationResult {
+        simulated_attempts,
+        skipped_attempts,
+        historical: historical.build(),
+        active: active_outcome.map(OutcomeBuilder::build),
+        candidate: candidate_outcome.build(),
+    }
+}
+
+fn get_attempt_count(row: &PaymentRoutingInputRow) -> u32 {
+    row.count
+        .and_then(|count| u32::try_from(count).ok())
+        .unwrap_or(0)
+}
+
+pub async fn simulate_routing_algorithm(
+    state: SessionState,
+    merchant_context: domain::MerchantContext,
+    authentication_profile_id: Option<common_utils::id_type::ProfileId>,
+    request: routing_types::RoutingSimulationRequest,
+) -> RouterResponse<routing_types::RoutingSimulationResponse> {
+    let db = state.store.as_ref();
+    let key_manager_state = &(&state).into();
+
+    let lookback_days = request.lookback_days.unwrap_or(DEFAULT_LOOKBACK_DAYS);
+    if lookback_days == 0 || lookback_days > MAX_LOOKBACK_DAYS {
+        return Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("lookback_days must be between 1 and {MAX_LOOKBACK_DAYS}"),
+        }
+        .into());
+    }
+
+    let business_profile = core_utils::validate_and_get_business_profile(
+        db,
+        key_manager_state,
+        merchant_context.get_merchant_key_store(),
+        Some(&request.profile_id),
+        merchant_context.get_merchant_account().get_id(),
+    )
+    .await?
+    .get_required_value("Profile")?;
+    core_utils::validate_profile_id_from_auth_layer(authentication_profile_id, &business_profile)?;
+
+    let candidate = AlgorithmSimulator::new(request.algorithm).map_err(|message| {
+        errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("invalid routing algorithm: {message}"),
+        }
+    })?;
+
+    let routing_ref: routing_types::RoutingAlgorithmRef = business_profile
+        .routing_algorithm
+        .clone()
+        .map(|val| val.parse_value("RoutingAlgorithmRef"))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("unable to deserialize routing algorithm ref from business profile")?
+        .unwrap_or_default();
+
+    let active = match routing_ref.algorithm_id.as_ref() {
+        Some(algorithm_id) => {
+            let algorithm: routing_types::RoutingAlgorithm = db
+                .find_routing_algorithm_by_profile_id_algorithm_id(
+                    business_profile.get_id(),
+                    algorithm_id,
+                )
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("unable to find the active routing algorithm")?
+                .algorithm_data
+                .parse_value("RoutingAlgorithm")
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("unable to deserialize the active routing algorithm")?;
+
+            Some(AlgorithmSimulator::new(algorithm).map_err(|message| {
+                report!(errors::ApiErrorResponse::InternalServerError).attach_printable(format!(
+                    "error initializing the active routing algorithm: {message}"
+                ))
+            })?)
+        }
+        None => None,
+    };
+
+    let auth = AuthInfo::ProfileLevel {
+        org_id: merchant_context.get_merchant_account().get_org_id().clone(),
+        merchant_id: merchant_context.get_merchant_account().get_id().clone(),
+        profile_ids: vec![business_profile.get_id().clone()],
+    };
+    let end_time = common_utils::date_time::now();
+    let time_range = TimeRange {
+        start_time: end_time.saturating_sub(time::Duration::days(i64::from(lookback_days))),
+        end_time: Some(end_time),
+    };
+
+    // One group more than the limit is loaded, to know whether any groups were left out
+    let mut rows = analytics::payments::get_routing_inputs(
+        &state.pool,
+        &auth,
+        &time_range,
+        MAX_SIMULATED_GROUPS.saturating_add(1),
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("error loading the payment attempts to simulate")?;
+    let truncated = is_truncated(&mut rows, MAX_SIMULATED_GROUPS);
+
+    let result = simulate(&rows, active.as_ref(), &candidate);
+
+    Ok(service_api::ApplicationResponse::Json(
+        routing_types::RoutingSimulationResponse {
+            profile_id: business_profile.get_id().clone(),
+            simulated_attempts: result.simulated_attempts,
+            skipped_attempts: result.skipped_attempts,
+            truncated,
+            historical: result.historical,
+            active_algorithm_id: routing_ref.algorithm_id,
+            active: result.active,
+            candidate: result.candidate,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use api_models::enums::RoutableConnectors;
+
+    use super::*;
+
+    fn split(connector: RoutableConnectors, split: u8) -> routing_types::ConnectorVolumeSplit {
+        routing_types::ConnectorVolumeSplit {
+            connector: routing_types::RoutableConnectorChoice {
+                choice_kind: routing_types::RoutableChoiceKind::FullStruct,
+                connector,
+                merchant_connector_id: None,
+            },
+            split,
+        }
+    }
+
+    #[test]
+    fn test_volume_split_outcome() {
+        let card = "card".to_string();
+        let mut success_rates = SuccessRates::default();
+        success_rates.record("stripe", Some(&card), 8.0, true);
+        success_rates.record("stripe", Some(&card), 2.0, false);
+        success_rates.record("adyen", None, 1.0, true);
+        success_rates.record("adyen", None, 1.0, false);
+
+        let weights = get_volume_split_weights(&[
+            split(RoutableConnectors::Stripe, 75),
+            split(RoutableConnectors::Adyen, 25),
+        ]);
+        let mut outcome = OutcomeBuilder::default();
+        outcome.record(weights, 100.0, Some(&card), &success_rates);
+        let outcome = outcome.build();
+
+        let volumes = outcome
+            .connectors
+            .iter()
+            .map(|volume| (volume.connector.as_str(), volume.attempts, volume.share))
+            .collect::<Vec<_>>();
+        assert_eq!(volumes, vec![("stripe", 75.0, 75.0), ("adyen", 25.0, 25.0)]);
+
+        // Stripe has a success rate of 80% for cards, while adyen falls back to its overall
+        // success rate of 50%
+        assert_eq!(outcome.expected_success_rate, Some(72.5));
+    }
+
+    #[test]
+    fn test_backend_input_from_row() {
+        let row = PaymentRoutingInputRow {
+            currency: Some(analytics::DBEnumWrapper(storage_enums::Currency::USD)),
+            amount: Some(1000),
+            payment_method: Some("card".to_string()),
+            payment_method_type: None,
+            authentication_type: None,
+            capture_method: Some(analytics::DBEnumWrapper(
+                storage_enums::CaptureMethod::Manual,
+            )),
+            card_network: None,
+            connector: Some("stripe".to_string()),
+            status: None,
+            count: Some(3),
+            start_bucket: Some(time::macros::datetime!(2025-01-31 18:30)),
+        };
+
+        let input = get_backend_input(&row).expect("Backend input");
+        assert!(matches!(
+            input.payment.capture_method,
+            Some(common_enums::CaptureMethod::Manual)
+        ));
+
+        let time = input.time.expect("Time input");
+        assert_eq!(time.time_of_day, 1830);
+        assert_eq!(time.date, 20250131);
+        assert_eq!(get_attempt_count(&row), 3);
+    }
+
+    #[test]
+    fn test_groups_beyond_limit_are_dropped() {
+        let row = |count| PaymentRoutingInputRow {
+            currency: None,
+            amount: None,
+            payment_method: None,
+            payment_method_type: None,
+            authentication_type: None,
+            capture_method: None,
+            card_network: None,
+            connector: None,
+            status: None,
+            count: Some(count),
+            start_bucket: None,
+        };
+
+        let mut rows = vec![row(5), row(3), row(1)];
+        assert!(is_truncated(&mut rows, 2));
+        assert_eq!(
+            rows.iter().map(get_attempt_count).collect::<Vec<_>>(),
+            vec![5, 3]
+        );
+
+        let mut rows = vec![row(5), row(3)];
+        assert!(!is_truncated(&mut rows, 2));
+        assert_eq!(rows.len(), 2);
+    }
+}
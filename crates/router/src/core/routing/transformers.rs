@@ -33,6 +33,7 @@ impl ForeignFrom<RoutingProfileMetadata> for RoutingDictionaryRecord {
             modified_at: value.modified_at.assume_utc().unix_timestamp(),
             algorithm_for: Some(value.algorithm_for),
             decision_engine_routing_id: None,
+            analysis_warnings: None,
         }
     }
 }
@@ -50,6 +51,7 @@ impl ForeignFrom<RoutingAlgorithm> for RoutingDictionaryRecord {
             modified_at: value.modified_at.assume_utc().unix_timestamp(),
             algorithm_for: Some(value.algorithm_for),
             decision_engine_routing_id: value.decision_engine_routing_id,
+            analysis_warnings: None,
         }
     }
 }
@@ -1,4 +1,6 @@
 #[cfg(feature = "v1")]
+mod circuit_breaker;
+#[cfg(feature = "v1")]
 mod incoming;
 #[cfg(feature = "v2")]
 mod incoming_v2;
@@ -26,4 +28,4 @@ pub(crate) use self::{
     incoming_v2::incoming_webhooks_wrapper, outgoing_v2::create_event_and_trigger_outgoing_webhook,
 };
 
-const MERCHANT_ID: &str = "merchant_id";
+pub(crate) const MERCHANT_ID: &str = "merchant_id";
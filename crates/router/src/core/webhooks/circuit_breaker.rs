@@ -0,0 +1,646 @@
+//! Circuit breakers guarding the deliveries of outgoing webhooks to webhook endpoints
+//!
+//! The circuit breaker of a webhook endpoint is opened once the number of consecutive failed
+//! deliveries to the endpoint reaches the configured threshold. While open, deliveries to the
+//! endpoint are short-circuited without making any HTTP calls, and their retry tasks are queued.
+//! Once the configured duration has elapsed, the circuit breaker is half-open and a single probe
+//! delivery is allowed through: if it succeeds, the circuit breaker is closed and the queued retry
+//! tasks are scheduled immediately, otherwise the circuit breaker is opened again.
+//!
+//! The state of the circuit breakers is stored in redis, and failed deliveries are recorded
+//! atomically so that concurrent deliveries to an endpoint are all counted. Failing to read or
+//! update the state never blocks deliveries, in which case the deliveries are attempted as if the
+//! circuit breaker were closed.
+
+use std::{collections::HashMap, sync::Arc};
+
+use api_models::webhook_events::{WebhookCircuitBreakerResponse, WebhookCircuitBreakerState};
+use common_utils::errors::CustomResult;
+use error_stack::ResultExt;
+use redis_interface::{RedisConnectionPool, RedisKey, SetnxReply};
+use time::PrimitiveDateTime;
+
+use super::MERCHANT_ID;
+use crate::{
+    configs::settings::WebhookCircuitBreakerSettings,
+    core::{errors, metrics},
+    logger,
+    routes::SessionState,
+    types::{domain, storage},
+};
+
+const WEBHOOK_CIRCUIT_BREAKER_PREFIX: &str = "WEBHOOK_CIRCUIT_BREAKER";
+/// Expiry of the probe key, so that another probe delivery is allowed if the outcome of a probe
+/// delivery is never recorded
+const PROBE_EXPIRY_SECONDS: i64 = 30;
+const CONSECUTIVE_FAILURES_FIELD: &str = "consecutive_failures";
+const OPENED_AT_FIELD: &str = "opened_at";
+
+/// Records a failed delivery, opening the circuit breaker if the failure threshold is reached
+/// while closed, or if the failed delivery was a probe delivery while half-open. Returns the
+/// number of consecutive failures if the circuit breaker was opened, 0 otherwise.
+///
+/// KEYS: circuit breaker state, probe
+/// ARGV: now (unix timestamp), failure_threshold, open_duration_seconds, state_expiry_seconds
+const RECORD_FAILURE_SCRIPT: &str = r#"
+local now = tonumber(ARGV[1])
+local consecutive_failures = redis.call('HINCRBY', KEYS[1], 'consecutive_failures', 1)
+local opened_at = tonumber(redis.call('HGET', KEYS[1], 'opened_at'))
+local opened = false
+
+if opened_at then
+    if now >= opened_at + tonumber(ARGV[3]) then
+        redis.call('DEL', KEYS[2])
+        opened = true
+    end
+elseif consecutive_failures >= tonumber(ARGV[2]) then
+    opened = true
+end
+
+if opened then
+    redis.call('HSET', KEYS[1], 'opened_at', now)
+end
+redis.call('EXPIRE', KEYS[1], tonumber(ARGV[4]))
+
+if opened then
+    return consecutive_failures
+end
+return 0
+"#;
+
+/// The state of the circuit breaker of a webhook endpoint, as stored in a redis hash
+#[derive(Clone, Debug, Default)]
+struct CircuitBreakerRecord {
+    consecutive_failures: u32,
+    opened_at: Option<PrimitiveDateTime>,
+}
+
+impl CircuitBreakerRecord {
+    fn from_hash_fields(fields: &HashMap<String, String>) -> Self {
+        Self {
+            consecutive_failures: fields
+                .get(CONSECUTIVE_FAILURES_FIELD)
+                .and_then(|consecutive_failures| consecutive_failures.parse().ok())
+                .unwrap_or_default(),
+            opened_at: fields
+                .get(OPENED_AT_FIELD)
+                .and_then(|opened_at| opened_at.parse().ok())
+                .and_then(|opened_at| time::OffsetDateTime::from_unix_timestamp(opened_at).ok())
+                .map(|opened_at| PrimitiveDateTime::new(opened_at.date(), opened_at.time())),
+        }
+    }
+
+    fn get_next_probe_at(&self, open_duration: time::Duration) -> Option<PrimitiveDateTime> {
+        self.opened_at.map(|opened_at| opened_at + open_duration)
+    }
+
+    fn get_state(
+        &self,
+        now: PrimitiveDateTime,
+        open_duration: time::Duration,
+    ) -> WebhookCircuitBreakerState {
+        match self.get_next_probe_at(open_duration) {
+            None => WebhookCircuitBreakerState::Closed,
+            Some(next_probe_at) if now < next_probe_at => WebhookCircuitBreakerState::Open,
+            Some(_) => WebhookCircuitBreakerState::HalfOpen,
+        }
+    }
+}
+
+/// Whether a delivery to a webhook endpoint may be attempted
+pub(super) enum CircuitBreakerAdmission {
+    Allowed,
+    Probe,
+    ShortCircuited { retry_after: PrimitiveDateTime },
+}
+
+pub(super) struct WebhookEndpointCircuitBreaker {
+    merchant_id: common_utils::id_type::MerchantId,
+    webhook_endpoint_id: Option<String>,
+    redis_key: String,
+}
+
+impl WebhookEndpointCircuitBreaker {
+    /// Obtains the circuit breaker of the specified webhook endpoint of the business profile, where
+    /// no endpoint stands for the primary webhook URL of the business profile
+    pub(super) fn new(
+        business_profile: &domain::Profile,
+        webhook_endpoint_id: Option<&str>,
+    ) -> Self {
+        Self::for_webhook_endpoint(
+            &business_profile.merchant_id,
+            business_profile.get_id(),
+            webhook_endpoint_id,
+        )
+    }
+
+    fn for_webhook_endpoint(
+        merchant_id: &common_utils::id_type::MerchantId,
+        profile_id: &common_utils::id_type::ProfileId,
+        webhook_endpoint_id: Option<&str>,
+    ) -> Self {
+        let redis_key = format!(
+            "{}_{}_{}_{}",
+            WEBHOOK_CIRCUIT_BREAKER_PREFIX,
+            merchant_id.get_string_repr(),
+            profile_id.get_string_repr(),
+            webhook_endpoint_id.unwrap_or("default"),
+        );
+
+        Self {
+            merchant_id: merchant_id.clone(),
+            webhook_endpoint_id: webhook_endpoint_id.map(ToOwned::to_owned),
+            redis_key,
+        }
+    }
+
+    fn get_probe_key(&self) -> String {
+        format!("{}_PROBE", self.redis_key)
+    }
+
+    fn get_queue_key(&self) -> String {
+        format!("{}_QUEUE", self.redis_key)
+    }
+
+    /// Checks whether a delivery to the webhook endpoint may be attempted
+    pub(super) async fn admit(&self, state: &SessionState) -> CircuitBreakerAdmission {
+        if !state.conf.webhooks.circuit_breaker.enabled {
+            return CircuitBreakerAdmission::Allowed;
+        }
+
+        self.try_admit(state).await.unwrap_or_else(|error| {
+            logger::error!(
+                ?error,
+                webhook_endpoint_id = ?self.webhook_endpoint_id,
+                "Failed to check webhook endpoint circuit breaker, attempting delivery"
+            );
+            CircuitBreakerAdmission::Allowed
+        })
+    }
+
+    async fn try_admit(
+        &self,
+        state: &SessionState,
+    ) -> CustomResult<CircuitBreakerAdmission, errors::RedisError> {
+        let redis_conn = get_redis_conn(state)?;
+        let open_duration = get_open_duration(state);
+        let record = self.get_record(&redis_conn).await?;
+        let now = common_utils::date_time::now();
+
+        match record.get_state(now, open_duration) {
+            WebhookCircuitBreakerState::Closed => Ok(CircuitBreakerAdmission::Allowed),
+            WebhookCircuitBreakerState::Open => Ok(CircuitBreakerAdmission::ShortCircuited {
+                retry_after: record.get_next_probe_at(open_duration).unwrap_or(now),
+            }),
+            // Only a single probe delivery is allowed at a time
+            WebhookCircuitBreakerState::HalfOpen => match redis_conn
+                .set_key_if_not_exists_with_expiry(
+                    &self.get_probe_key().as_str().into(),
+                    self.merchant_id.get_string_repr().to_owned(),
+                    Some(PROBE_EXPIRY_SECONDS),
+                )
+                .await?
+            {
+                SetnxReply::KeySet => Ok(CircuitBreakerAdmission::Probe),
+                SetnxReply::KeyNotSet => Ok(CircuitBreakerAdmission::ShortCircuited {
+                    retry_after: now + open_duration,
+                }),
+            },
+        }
+    }
+
+    /// Updates the state of the circuit breaker with the outcome of a delivery to the webhook
+    /// endpoint
+    pub(super) async fn record_delivery_outcome(
+        &self,
+        state: &SessionState,
+        is_delivery_successful: bool,
+    ) {
+        if !state.conf.webhooks.circuit_breaker.enabled {
+            return;
+        }
+
+        let result = if is_delivery_successful {
+            self.record_success(state).await
+        } else {
+            self.record_failure(state).await
+        };
+
+        if let Err(error) = result {
+            logger::error!(
+                ?error,
+                webhook_endpoint_id = ?self.webhook_endpoint_id,
+                is_delivery_successful,
+                "Failed to update webhook endpoint circuit breaker"
+            );
+        }
+    }
+
+    async fn record_success(&self, state: &SessionState) -> CustomResult<(), errors::RedisError> {
+        let redis_conn = get_redis_conn(state)?;
+        let record = self.get_record(&redis_conn).await?;
+        if record.consecutive_failures == 0 && record.opened_at.is_none() {
+            return Ok(());
+        }
+
+        redis_conn
+            .delete_key(&self.redis_key.as_str().into())
+            .await?;
+
+        if record.opened_at.is_some() {
+            logger::info!(
+                webhook_endpoint_id = ?self.webhook_endpoint_id,
+                "Closing webhook endpoint circuit breaker after successful delivery"
+            );
+            redis_conn
+                .delete_key(&self.get_probe_key().as_str().into())
+                .await?;
+            self.release_queued_deliveries(state, &redis_conn).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn record_failure(&self, state: &SessionState) -> CustomResult<(), errors::RedisError> {
+        let redis_conn = get_redis_conn(state)?;
+        let opened_with_failures = self
+            .record_failure_at(
+                &redis_conn,
+                &state.conf.webhooks.circuit_breaker,
+                common_utils::date_time::now(),
+            )
+            .await?;
+
+        if let Some(consecutive_failures) = opened_with_failures {
+            logger::info!(
+                webhook_endpoint_id = ?self.webhook_endpoint_id,
+                consecutive_failures,
+                "Opening webhook endpoint circuit breaker"
+            );
+            metrics::WEBHOOK_OUTGOING_CIRCUIT_BREAKER_OPENED_COUNT.add(
+                1,
+                router_env::metric_attributes!((MERCHANT_ID, self.merchant_id.clone())),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Records a failed delivery made at `now`, returning the number of consecutive failures if
+    /// the circuit breaker was opened by it
+    async fn record_failure_at(
+        &self,
+        redis_conn: &RedisConnectionPool,
+        settings: &WebhookCircuitBreakerSettings,
+        now: PrimitiveDateTime,
+    ) -> CustomResult<Option<u32>, errors::RedisError> {
+        let keys = vec![
+            RedisKey::from(self.redis_key.as_str()).tenant_aware_key(redis_conn),
+            RedisKey::from(self.get_probe_key().as_str()).tenant_aware_key(redis_conn),
+        ];
+        let args = vec![
+            now.assume_utc().unix_timestamp().to_string(),
+            settings.failure_threshold.to_string(),
+            settings.open_duration_seconds.to_string(),
+            settings.state_expiry_seconds.to_string(),
+        ];
+
+        let consecutive_failures = redis_conn
+            .evaluate_redis_script::<_, u32>(RECORD_FAILURE_SCRIPT, keys, args)
+            .await?;
+
+        Ok((consecutive_failures > 0).then_some(consecutive_failures))
+    }
+
+    /// Postpones the retry task of a short-circuited delivery without counting the delivery as a
+    /// retry attempt, and queues the task to be scheduled as soon as a probe delivery succeeds
+    pub(super) async fn queue_delivery(
+        &self,
+        state: &SessionState,
+        process_tracker: storage::ProcessTracker,
+        retry_after: PrimitiveDateTime,
+    ) -> CustomResult<(), errors::WebhooksFlowError> {
+        let process_tracker_id = process_tracker.id.clone();
+        let retry_count = process_tracker.retry_count;
+        state
+            .store
+            .as_scheduler()
+            .update_process(
+                process_tracker,
+                storage::ProcessTrackerUpdate::StatusRetryUpdate {
+                    status: storage::enums::ProcessTrackerStatus::Pending,
+                    retry_count,
+                    schedule_time: retry_after,
+                },
+            )
+            .await
+            .change_context(errors::WebhooksFlowError::OutgoingWebhookRetrySchedulingFailed)
+            .attach_printable("Failed to postpone short-circuited outgoing webhook retry task")?;
+
+        // The postponed task is retried on its schedule even if it could not be queued
+        let queue_result = async {
+            let redis_conn = get_redis_conn(state)?;
+            let queue_key = self.get_queue_key();
+            redis_conn
+                .append_elements_to_list(&queue_key.as_str().into(), process_tracker_id)
+                .await?;
+            redis_conn
+                .set_expiry(
+                    &queue_key.as_str().into(),
+                    i64::from(state.conf.webhooks.circuit_breaker.state_expiry_seconds),
+                )
+                .await
+        }
+        .await;
+
+        if let Err(error) = queue_result {
+            logger::error!(
+                ?error,
+                webhook_endpoint_id = ?self.webhook_endpoint_id,
+                "Failed to queue short-circuited outgoing webhook retry task"
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn release_queued_deliveries(
+        &self,
+        state: &SessionState,
+        redis_conn: &RedisConnectionPool,
+    ) -> CustomResult<(), errors::RedisError> {
+        let queue_key = self.get_queue_key();
+        let process_tracker_ids = redis_conn
+            .get_list_elements(&queue_key.as_str().into(), 0, -1)
+            .await?;
+        redis_conn.delete_key(&queue_key.as_str().into()).await?;
+
+        let now = common_utils::date_time::now();
+        let scheduler = state.store.as_scheduler();
+        for process_tracker_id in process_tracker_ids {
+            // A task may have been queued more than once, or may have completed since it was
+            // queued, only tasks still waiting to be retried are scheduled
+            let process_tracker = match scheduler.find_process_by_id(&process_tracker_id).await {
+                Ok(Some(process_tracker))
+                    if process_tracker.status == storage::enums::ProcessTrackerStatus::Pending
+                        && process_tracker.schedule_time.is_some_and(|time| time > now) =>
+                {
+                    process_tracker
+                }
+                Ok(_) => continue,
+                Err(error) => {
+                    logger::error!(
+                        ?error,
+                        %process_tracker_id,
+                        "Failed to find queued outgoing webhook retry task"
+                    );
+                    continue;
+                }
+            };
+
+            let retry_count = process_tracker.retry_count;
+            scheduler
+                .update_process(
+                    process_tracker,
+                    storage::ProcessTrackerUpdate::StatusRetryUpdate {
+                        status: storage::enums::ProcessTrackerStatus::Pending,
+                        retry_count,
+                        schedule_time: now,
+                    },
+                )
+                .await
+                .map_err(|error| {
+                    logger::error!(
+                        ?error,
+                        %process_tracker_id,
+                        "Failed to schedule queued outgoing webhook retry task"
+                    )
+                })
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    /// Obtains the current state of the circuit breaker
+    pub(super) async fn get_state(
+        &self,
+        state: &SessionState,
+    ) -> CustomResult<WebhookCircuitBreakerResponse, errors::RedisError> {
+        let redis_conn = get_redis_conn(state)?;
+        let open_duration = get_open_duration(state);
+        let record = self.get_record(&redis_conn).await?;
+        let queued_deliveries = redis_conn
+            .get_list_length(&self.get_queue_key().as_str().into())
+            .await?;
+
+        Ok(WebhookCircuitBreakerResponse {
+            webhook_endpoint_id: self.webhook_endpoint_id.clone(),
+            state: record.get_state(common_utils::date_time::now(), open_duration),
+            consecutive_failures: record.consecutive_failures,
+            opened_at: record.opened_at,
+            next_probe_at: record.get_next_probe_at(open_duration),
+            queued_deliveries,
+        })
+    }
+
+    async fn get_record(
+        &self,
+        redis_conn: &RedisConnectionPool,
+    ) -> CustomResult<CircuitBreakerRecord, errors::RedisError> {
+        redis_conn
+            .get_hash_fields::<HashMap<String, String>>(&self.redis_key.as_str().into())
+            .await
+            .map(|fields| CircuitBreakerRecord::from_hash_fields(&fields))
+    }
+}
+
+fn get_redis_conn(
+    state: &SessionState,
+) -> CustomResult<Arc<RedisConnectionPool>, errors::RedisError> {
+    state
+        .store
+        .get_redis_conn()
+        .attach_printable("Failed to get redis connection")
+}
+
+fn get_open_duration(state: &SessionState) -> time::Duration {
+    time::Duration::seconds(i64::from(
+        state.conf.webhooks.circuit_breaker.open_duration_seconds,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use common_utils::id_type::{self, GenerateId};
+    use redis_interface::RedisSettings;
+
+    use super::*;
+
+    const FAILURE_THRESHOLD: u32 = 3;
+    const OPEN_DURATION_SECONDS: u32 = 60;
+
+    fn settings() -> WebhookCircuitBreakerSettings {
+        WebhookCircuitBreakerSettings {
+            enabled: true,
+            failure_threshold: FAILURE_THRESHOLD,
+            open_duration_seconds: OPEN_DURATION_SECONDS,
+            state_expiry_seconds: 600,
+        }
+    }
+
+    fn circuit_breaker() -> WebhookEndpointCircuitBreaker {
+        let merchant_id = id_type::MerchantId::try_from(std::borrow::Cow::from("merchant_cb_test"))
+            .expect("Merchant ID");
+        WebhookEndpointCircuitBreaker::for_webhook_endpoint(
+            &merchant_id,
+            &id_type::ProfileId::generate(),
+            Some("whe_test"),
+        )
+    }
+
+    async fn redis_conn() -> RedisConnectionPool {
+        RedisConnectionPool::new(&RedisSettings::default())
+            .await
+            .expect("failed to create redis connection pool")
+    }
+
+    fn open_duration() -> time::Duration {
+        time::Duration::seconds(i64::from(OPEN_DURATION_SECONDS))
+    }
+
+    #[test]
+    fn test_circuit_breaker_state() {
+        let now = time::macros::datetime!(2025-01-01 12:00);
+
+        assert_eq!(
+            CircuitBreakerRecord::default().get_state(now, open_duration()),
+            WebhookCircuitBreakerState::Closed
+        );
+
+        let record = CircuitBreakerRecord {
+            consecutive_failures: FAILURE_THRESHOLD,
+            opened_at: Some(now),
+        };
+        assert_eq!(
+            record.get_state(now + time::Duration::seconds(59), open_duration()),
+            WebhookCircuitBreakerState::Open
+        );
+        assert_eq!(
+            record.get_state(now + open_duration(), open_duration()),
+            WebhookCircuitBreakerState::HalfOpen
+        );
+    }
+
+    #[test]
+    fn test_record_from_hash_fields() {
+        let fields = HashMap::from([
+            (CONSECUTIVE_FAILURES_FIELD.to_string(), "4".to_string()),
+            (OPENED_AT_FIELD.to_string(), "1735732800".to_string()),
+        ]);
+        let record = CircuitBreakerRecord::from_hash_fields(&fields);
+
+        assert_eq!(record.consecutive_failures, 4);
+        assert_eq!(
+            record.opened_at,
+            Some(time::macros::datetime!(2025-01-01 12:00))
+        );
+
+        let record = CircuitBreakerRecord::from_hash_fields(&HashMap::new());
+        assert_eq!(record.consecutive_failures, 0);
+        assert!(record.opened_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failure_threshold_opens_circuit_breaker() {
+        let redis_conn = redis_conn().await;
+        let circuit_breaker = circuit_breaker();
+        let now = time::macros::datetime!(2025-01-01 12:00);
+
+        for _ in 1..FAILURE_THRESHOLD {
+            let opened = circuit_breaker
+                .record_failure_at(&redis_conn, &settings(), now)
+                .await
+                .unwrap();
+            assert!(opened.is_none());
+        }
+
+        let opened = circuit_breaker
+            .record_failure_at(&redis_conn, &settings(), now)
+            .await
+            .unwrap();
+        assert_eq!(opened, Some(FAILURE_THRESHOLD));
+
+        let record = circuit_breaker.get_record(&redis_conn).await.unwrap();
+        assert_eq!(record.opened_at, Some(now));
+        assert_eq!(
+            record.get_state(now, open_duration()),
+            WebhookCircuitBreakerState::Open
+        );
+
+        // Failures while open do not extend the open duration
+        let later = now + time::Duration::seconds(30);
+        let opened = circuit_breaker
+            .record_failure_at(&redis_conn, &settings(), later)
+            .await
+            .unwrap();
+        assert!(opened.is_none());
+        let record = circuit_breaker.get_record(&redis_conn).await.unwrap();
+        assert_eq!(record.opened_at, Some(now));
+
+        // A failed probe delivery while half-open opens the circuit breaker again
+        let probe_time = now + open_duration();
+        let opened = circuit_breaker
+            .record_failure_at(&redis_conn, &settings(), probe_time)
+            .await
+            .unwrap();
+        assert_eq!(opened, Some(FAILURE_THRESHOLD + 2));
+        let record = circuit_breaker.get_record(&redis_conn).await.unwrap();
+        assert_eq!(record.opened_at, Some(probe_time));
+
+        redis_conn
+            .delete_key(&circuit_breaker.redis_key.as_str().into())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_failures_are_all_counted() {
+        let redis_conn = Arc::new(redis_conn().await);
+        let circuit_breaker = Arc::new(circuit_breaker());
+        let now = time::macros::datetime!(2025-01-01 12:00);
+        let failures = 20;
+
+        let handles = (0..failures)
+            .map(|_| {
+                let redis_conn = Arc::clone(&redis_conn);
+                let circuit_breaker = Arc::clone(&circuit_breaker);
+                tokio::spawn(async move {
+                    circuit_breaker
+                        .record_failure_at(&redis_conn, &settings(), now)
+                        .await
+                        .unwrap()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut opened_count = 0;
+        for handle in handles {
+            if handle.await.unwrap().is_some() {
+                opened_count += 1;
+            }
+        }
+
+        let record = circuit_breaker.get_record(&redis_conn).await.unwrap();
+        assert_eq!(record.consecutive_failures, failures);
+        // Only the delivery reaching the threshold opens the circuit breaker
+        assert_eq!(opened_count, 1);
+
+        redis_conn
+            .delete_key(&circuit_breaker.redis_key.as_str().into())
+            .await
+            .unwrap();
+    }
+}
@@ -445,7 +445,7 @@ async fn payments_incoming_webhook_flow(
                 },
             };
 
-            lock_action
+            let _lock_lease = lock_action
                 .clone()
                 .perform_locking_action(
                     &state,
@@ -5,6 +5,7 @@ use api_models::{
     webhooks,
 };
 use common_utils::{
+    crypto::SignMessage,
     ext_traits::{Encode, StringExt},
     request::RequestContent,
     type_name,
@@ -20,7 +21,7 @@ use router_env::{
     tracing::{self, Instrument},
 };
 
-use super::{types, utils, MERCHANT_ID};
+use super::{circuit_breaker, types, utils, MERCHANT_ID};
 #[cfg(feature = "stripe")]
 use crate::compatibility::stripe::webhooks as stripe_webhooks;
 use crate::{
@@ -32,7 +33,7 @@ use crate::{
     events::outgoing_webhook_logs::{
         OutgoingWebhookEvent, OutgoingWebhookEventContent, OutgoingWebhookEventMetric,
     },
-    logger,
+    headers, logger,
     routes::{app::SessionStateInfo, SessionState},
     services,
     types::{
@@ -60,24 +61,72 @@ pub(crate) async fn create_event_and_trigger_outgoing_webhook(
     content: api::OutgoingWebhookContent,
     primary_object_created_at: Option<time::PrimitiveDateTime>,
 ) -> CustomResult<(), errors::ApiErrorResponse> {
-    let delivery_attempt = enums::WebhookDeliveryAttempt::InitialAttempt;
-    let idempotent_event_id =
-        utils::get_idempotent_event_id(&primary_object_id, event_type, delivery_attempt);
-    let webhook_url_result = get_webhook_url_from_business_profile(&business_profile);
+    let webhook_endpoint_ids = get_webhook_endpoint_ids_for_event(&business_profile, event_type);
 
-    if !state.conf.webhooks.outgoing_enabled
-        || webhook_url_result.is_err()
-        || webhook_url_result.as_ref().is_ok_and(String::is_empty)
-    {
+    if !state.conf.webhooks.outgoing_enabled || webhook_endpoint_ids.is_empty() {
         logger::debug!(
             business_profile_id=?business_profile.get_id(),
-            %idempotent_event_id,
-            "Outgoing webhooks are disabled in application configuration, or merchant webhook URL \
-             could not be obtained; skipping outgoing webhooks for event"
+            %primary_object_id,
+            ?event_type,
+            "Outgoing webhooks are disabled in application configuration, or no merchant webhook \
+             URL is subscribed to the event; skipping outgoing webhooks for event"
         );
         return Ok(());
     }
 
+    let mut result = Ok(());
+    // Each endpoint gets its own event, so that deliveries and retries are tracked independently
+    for webhook_endpoint_id in webhook_endpoint_ids {
+        if let Err(error) = Box::pin(create_event_and_trigger_outgoing_webhook_for_endpoint(
+            state.clone(),
+            merchant_context.clone(),
+            business_profile.clone(),
+            event_type,
+            event_class,
+            primary_object_id.clone(),
+            primary_object_type,
+            content.clone(),
+            primary_object_created_at,
+            webhook_endpoint_id.clone(),
+        ))
+        .await
+        {
+            logger::error!(
+                ?error,
+                ?webhook_endpoint_id,
+                "Failed to trigger outgoing webhook for endpoint"
+            );
+            result = Err(error);
+        }
+    }
+
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all)]
+async fn create_event_and_trigger_outgoing_webhook_for_endpoint(
+    state: SessionState,
+    merchant_context: domain::MerchantContext,
+    business_profile: domain::Profile,
+    event_type: enums::EventType,
+    event_class: enums::EventClass,
+    primary_object_id: String,
+    primary_object_type: enums::EventObjectType,
+    content: api::OutgoingWebhookContent,
+    primary_object_created_at: Option<time::PrimitiveDateTime>,
+    webhook_endpoint_id: Option<String>,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    let delivery_attempt = enums::WebhookDeliveryAttempt::InitialAttempt;
+    let idempotent_event_id =
+        utils::get_idempotent_event_id(&primary_object_id, event_type, delivery_attempt);
+    let idempotent_event_id = match &webhook_endpoint_id {
+        Some(webhook_endpoint_id) => {
+            utils::get_idempotent_event_id_for_endpoint(&idempotent_event_id, webhook_endpoint_id)?
+        }
+        None => idempotent_event_id,
+    };
+
     let event_id = utils::generate_event_id();
     let merchant_id = business_profile.merchant_id.clone();
     let now = common_utils::date_time::now();
@@ -90,10 +139,14 @@ pub(crate) async fn create_event_and_trigger_outgoing_webhook(
         timestamp: now,
     };
 
-    let request_content =
-        get_outgoing_webhook_request(&merchant_context, outgoing_webhook, &business_profile)
-            .change_context(errors::ApiErrorResponse::WebhookProcessingFailure)
-            .attach_printable("Failed to construct outgoing webhook request content")?;
+    let request_content = get_outgoing_webhook_request(
+        &merchant_context,
+        outgoing_webhook,
+        &business_profile,
+        webhook_endpoint_id.as_deref(),
+    )
+    .change_context(errors::ApiErrorResponse::WebhookProcessingFailure)
+    .attach_printable("Failed to construct outgoing webhook request content")?;
 
     let event_metadata = storage::EventMetadata::foreign_from(&content);
     let key_manager_state = &(&state).into();
@@ -142,6 +195,8 @@ pub(crate) async fn create_event_and_trigger_outgoing_webhook(
         delivery_attempt: Some(delivery_attempt),
         metadata: Some(event_metadata),
         is_overall_delivery_successful: Some(false),
+        webhook_endpoint_id,
+        dead_lettered_at: None,
     };
 
     let lock_value = utils::perform_redis_lock(
@@ -234,6 +289,7 @@ pub(crate) async fn create_event_and_trigger_outgoing_webhook(
                 delivery_attempt,
                 Some(content),
                 process_tracker,
+                None,
             ))
             .await;
         }
@@ -254,6 +310,7 @@ pub(crate) async fn trigger_webhook_and_raise_event(
     delivery_attempt: enums::WebhookDeliveryAttempt,
     content: Option<api::OutgoingWebhookContent>,
     process_tracker: Option<storage::ProcessTracker>,
+    webhook_url_override: Option<String>,
 ) {
     logger::debug!(
         event_id=%event.event_id,
@@ -271,6 +328,7 @@ pub(crate) async fn trigger_webhook_and_raise_event(
         request_content,
         delivery_attempt,
         process_tracker,
+        webhook_url_override,
     )
     .await;
 
@@ -285,19 +343,34 @@ pub(crate) async fn trigger_webhook_and_raise_event(
     .await;
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn trigger_webhook_to_merchant(
     state: SessionState,
     business_profile: domain::Profile,
     merchant_key_store: &domain::MerchantKeyStore,
     event: domain::Event,
-    request_content: OutgoingWebhookRequestContent,
+    mut request_content: OutgoingWebhookRequestContent,
     delivery_attempt: enums::WebhookDeliveryAttempt,
     process_tracker: Option<storage::ProcessTracker>,
+    webhook_url_override: Option<String>,
 ) -> CustomResult<(), errors::WebhooksFlowError> {
-    let webhook_url = match (
-        get_webhook_url_from_business_profile(&business_profile),
-        process_tracker.clone(),
-    ) {
+    // Deliveries to an override URL are not guarded by the circuit breaker of the webhook endpoint
+    let circuit_breaker = webhook_url_override.is_none().then(|| {
+        circuit_breaker::WebhookEndpointCircuitBreaker::new(
+            &business_profile,
+            event.webhook_endpoint_id.as_deref(),
+        )
+    });
+
+    // An override URL takes precedence over the URL configured in the business profile, it is only
+    // used when redelivering events from the dead-letter queue
+    let webhook_url_result = webhook_url_override.map(Ok).unwrap_or_else(|| {
+        get_webhook_url_from_business_profile(
+            &business_profile,
+            event.webhook_endpoint_id.as_deref(),
+        )
+    });
+    let webhook_url = match (webhook_url_result, process_tracker.clone()) {
         (Ok(webhook_url), _) => Ok(webhook_url),
         (Err(error), Some(process_tracker)) => {
             if !error
@@ -319,6 +392,40 @@ async fn trigger_webhook_to_merchant(
         (Err(error), None) => Err(error),
     }?;
 
+    // Manual retries are always attempted, so that the webhook endpoint can be probed on demand
+    if let Some(circuit_breaker) = circuit_breaker
+        .as_ref()
+        .filter(|_| !matches!(delivery_attempt, enums::WebhookDeliveryAttempt::ManualRetry))
+    {
+        if let circuit_breaker::CircuitBreakerAdmission::ShortCircuited { retry_after } =
+            circuit_breaker.admit(&state).await
+        {
+            logger::info!(
+                event_id = %event.event_id,
+                %retry_after,
+                "Short-circuiting webhook delivery as the circuit breaker of the webhook endpoint is open"
+            );
+            metrics::WEBHOOK_OUTGOING_SHORT_CIRCUITED_COUNT.add(
+                1,
+                router_env::metric_attributes!((MERCHANT_ID, business_profile.merchant_id.clone())),
+            );
+            if let Some(process_tracker) = process_tracker {
+                circuit_breaker
+                    .queue_delivery(&state, process_tracker, retry_after)
+                    .await?;
+            }
+            return Err(report!(
+                errors::WebhooksFlowError::WebhookEndpointCircuitOpen
+            ));
+        }
+    }
+
+    sign_outgoing_webhook_request(
+        &business_profile,
+        event.webhook_endpoint_id.as_deref(),
+        &mut request_content,
+    )?;
+
     let event_id = event.event_id;
 
     let headers = request_content
@@ -347,6 +454,15 @@ async fn trigger_webhook_to_merchant(
     );
     logger::debug!(outgoing_webhook_response=?response);
 
+    if let Some(circuit_breaker) = &circuit_breaker {
+        let is_delivery_successful = response
+            .as_ref()
+            .is_ok_and(|response| response.status().is_success());
+        circuit_breaker
+            .record_delivery_outcome(&state, is_delivery_successful)
+            .await;
+    }
+
     match delivery_attempt {
         enums::WebhookDeliveryAttempt::InitialAttempt => match response {
             Err(client_error) => {
@@ -391,6 +507,7 @@ async fn trigger_webhook_to_merchant(
                 } else {
                     error_response_handler(
                         state.clone(),
+                        merchant_key_store.clone(),
                         &business_profile.merchant_id,
                         delivery_attempt,
                         status_code.as_u16(),
@@ -449,6 +566,7 @@ async fn trigger_webhook_to_merchant(
                     } else {
                         error_response_handler(
                             state.clone(),
+                            merchant_key_store.clone(),
                             &business_profile.merchant_id,
                             delivery_attempt,
                             status_code.as_u16(),
@@ -489,6 +607,7 @@ async fn trigger_webhook_to_merchant(
                 } else {
                     error_response_handler(
                         state,
+                        merchant_key_store.clone(),
                         &business_profile.merchant_id,
                         delivery_attempt,
                         status_code.as_u16(),
@@ -639,8 +758,11 @@ pub(crate) async fn add_outgoing_webhook_retry_task_to_process_tracker(
     }
 }
 
+/// Obtains the URL of the specified webhook endpoint of the business profile, or the primary
+/// webhook URL of the business profile if no endpoint is specified
 fn get_webhook_url_from_business_profile(
     business_profile: &domain::Profile,
+    webhook_endpoint_id: Option<&str>,
 ) -> CustomResult<String, errors::WebhooksFlowError> {
     let webhook_details = business_profile
         .webhook_details
@@ -648,22 +770,257 @@ fn get_webhook_url_from_business_profile(
         .get_required_value("webhook_details")
         .change_context(errors::WebhooksFlowError::MerchantWebhookDetailsNotFound)?;
 
-    webhook_details
-        .webhook_url
-        .get_required_value("webhook_url")
-        .change_context(errors::WebhooksFlowError::MerchantWebhookUrlNotConfigured)
-        .map(ExposeInterface::expose)
+    match webhook_endpoint_id {
+        Some(webhook_endpoint_id) => get_webhook_endpoint(business_profile, webhook_endpoint_id)
+            .filter(|webhook_endpoint| webhook_endpoint.is_enabled.unwrap_or(true))
+            .map(|webhook_endpoint| webhook_endpoint.url.clone().expose())
+            .ok_or_else(|| report!(errors::WebhooksFlowError::WebhookEndpointNotConfigured))
+            .attach_printable_lazy(|| {
+                format!("Webhook endpoint `{webhook_endpoint_id}` is not configured or is disabled")
+            }),
+        None => webhook_details
+            .webhook_url
+            .get_required_value("webhook_url")
+            .change_context(errors::WebhooksFlowError::MerchantWebhookUrlNotConfigured)
+            .map(ExposeInterface::expose),
+    }
+}
+
+fn get_webhook_endpoint<'a>(
+    business_profile: &'a domain::Profile,
+    webhook_endpoint_id: &str,
+) -> Option<&'a diesel_models::business_profile::WebhookEndpoint> {
+    business_profile
+        .webhook_details
+        .as_ref()?
+        .webhook_endpoints
+        .as_ref()?
+        .iter()
+        .find(|webhook_endpoint| webhook_endpoint.endpoint_id == webhook_endpoint_id)
+}
+
+/// Lists the endpoints the event must be delivered to, where `None` stands for the primary
+/// webhook URL of the business profile
+fn get_webhook_endpoint_ids_for_event(
+    business_profile: &domain::Profile,
+    event_type: enums::EventType,
+) -> Vec<Option<String>> {
+    get_webhook_endpoint_ids_matching(business_profile, |webhook_endpoint| {
+        webhook_endpoint.is_subscribed_to(event_type)
+    })
+}
+
+/// Lists all the endpoints configured for the business profile, where `None` stands for the
+/// primary webhook URL of the business profile
+pub(super) fn get_webhook_endpoint_ids(business_profile: &domain::Profile) -> Vec<Option<String>> {
+    get_webhook_endpoint_ids_matching(business_profile, |_| true)
+}
+
+fn get_webhook_endpoint_ids_matching(
+    business_profile: &domain::Profile,
+    predicate: impl Fn(&diesel_models::business_profile::WebhookEndpoint) -> bool,
+) -> Vec<Option<String>> {
+    get_webhook_endpoint_ids_from_details(business_profile.webhook_details.as_ref(), predicate)
+}
+
+fn get_webhook_endpoint_ids_from_details(
+    webhook_details: Option<&diesel_models::business_profile::WebhookDetails>,
+    predicate: impl Fn(&diesel_models::business_profile::WebhookEndpoint) -> bool,
+) -> Vec<Option<String>> {
+    let primary_webhook_url = webhook_details
+        .and_then(|webhook_details| webhook_details.webhook_url.as_ref())
+        .filter(|webhook_url| !webhook_url.peek().is_empty())
+        .map(|_| None);
+
+    let webhook_endpoint_ids = webhook_details
+        .into_iter()
+        .flat_map(|webhook_details| webhook_details.webhook_endpoints.iter().flatten())
+        .filter(|webhook_endpoint| predicate(webhook_endpoint))
+        .map(|webhook_endpoint| Some(webhook_endpoint.endpoint_id.clone()));
+
+    primary_webhook_url
+        .into_iter()
+        .chain(webhook_endpoint_ids)
+        .collect()
+}
+
+/// The signing secrets outgoing webhooks to the webhook endpoint must currently be signed with
+fn get_webhook_signing_keys(
+    business_profile: &domain::Profile,
+    webhook_endpoint_id: Option<&str>,
+) -> Vec<String> {
+    get_webhook_signing_keys_from_details(
+        business_profile.payment_response_hash_key.as_ref(),
+        business_profile.webhook_details.as_ref(),
+        webhook_endpoint_id,
+        common_utils::date_time::now(),
+    )
+}
+
+/// This is the signing secret of the webhook endpoint if one is configured. Otherwise, it is the
+/// `payment_response_hash_key` of the business profile, along with the staged secret while a
+/// signing secret rotation is in its grace period, or the staged secret alone once the grace period
+/// has ended and until the staged secret is promoted to the `payment_response_hash_key`.
+fn get_webhook_signing_keys_from_details(
+    payment_response_hash_key: Option<&String>,
+    webhook_details: Option<&diesel_models::business_profile::WebhookDetails>,
+    webhook_endpoint_id: Option<&str>,
+    now: time::PrimitiveDateTime,
+) -> Vec<String> {
+    if let Some(signing_secret) = webhook_endpoint_id
+        .and_then(|webhook_endpoint_id| {
+            webhook_details?
+                .webhook_endpoints
+                .as_ref()?
+                .iter()
+                .find(|webhook_endpoint| webhook_endpoint.endpoint_id == webhook_endpoint_id)
+        })
+        .and_then(|webhook_endpoint| webhook_endpoint.signing_secret.clone())
+    {
+        return vec![signing_secret.expose()];
+    }
+
+    let signing_secret_rotation = webhook_details
+        .and_then(|webhook_details| webhook_details.signing_secret_rotation.as_ref());
+
+    match signing_secret_rotation {
+        Some(rotation) if rotation.is_in_grace_period(now) => payment_response_hash_key
+            .cloned()
+            .into_iter()
+            .chain(std::iter::once(rotation.staged_secret.clone().expose()))
+            .collect(),
+        Some(rotation) => vec![rotation.staged_secret.clone().expose()],
+        None => payment_response_hash_key.cloned().into_iter().collect(),
+    }
+}
+
+/// The format of the outgoing webhooks configured for the business profile, if any
+fn get_profile_webhook_format(
+    business_profile: &domain::Profile,
+) -> Option<enums::OutgoingWebhookFormat> {
+    business_profile
+        .webhook_details
+        .as_ref()
+        .and_then(|webhook_details| webhook_details.webhook_format)
+}
+
+/// The signing secret used for the `X-Webhook-Signature-512` header of outgoing webhooks
+fn get_webhook_signing_key(
+    business_profile: &domain::Profile,
+    webhook_endpoint_id: Option<&str>,
+) -> Option<String> {
+    get_webhook_signing_keys(business_profile, webhook_endpoint_id)
+        .into_iter()
+        .next()
+}
+
+/// Signs the outgoing webhook request with every signing secret currently active for the webhook
+/// endpoint, right before it is sent
+///
+/// The `X-Webhook-Signature-512` header is computed with the first secret, as before. In addition,
+/// an `X-Webhook-Signature-V2` header of the form `keyid={key_id},t={timestamp},v1={signature}` is
+/// added per secret, where the signature is the HMAC-SHA512 of `{timestamp}.{body}`, so that
+/// receivers can verify deliveries with either secret during a rotation.
+///
+/// Stripe compatible webhooks carry their own `Stripe-Signature` header instead. When the profile
+/// has opted into the Stripe webhook format, the header carries one HMAC-SHA256 signature per
+/// secret, timestamped with the time of delivery.
+fn sign_outgoing_webhook_request(
+    business_profile: &domain::Profile,
+    webhook_endpoint_id: Option<&str>,
+    request_content: &mut OutgoingWebhookRequestContent,
+) -> CustomResult<(), errors::WebhooksFlowError> {
+    let signing_keys = get_webhook_signing_keys(business_profile, webhook_endpoint_id);
+    if signing_keys.is_empty() {
+        return Ok(());
+    }
+
+    let timestamp = common_utils::date_time::now_unix_timestamp();
+
+    let is_stripe_compatible_request = request_content
+        .headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case(headers::STRIPE_COMPATIBLE_WEBHOOK_SIGNATURE));
+    if is_stripe_compatible_request {
+        #[cfg(feature = "stripe")]
+        if get_profile_webhook_format(business_profile)
+            == Some(enums::OutgoingWebhookFormat::Stripe)
+        {
+            let signature = stripe_webhooks::get_stripe_signature_header(
+                timestamp,
+                request_content.body.peek(),
+                signing_keys.as_slice(),
+            )?;
+            request_content.headers.retain(|(name, _)| {
+                !name.eq_ignore_ascii_case(headers::STRIPE_COMPATIBLE_WEBHOOK_SIGNATURE)
+            });
+            request_content.headers.push((
+                headers::STRIPE_COMPATIBLE_WEBHOOK_SIGNATURE.to_string(),
+                signature.into(),
+            ));
+        }
+        return Ok(());
+    }
+
+    add_webhook_signature_headers(signing_keys.as_slice(), timestamp, request_content)
+}
+
+/// Replaces the `X-Webhook-Signature-512` and `X-Webhook-Signature-V2` headers of the request with
+/// the signatures computed with the signing secrets
+fn add_webhook_signature_headers(
+    signing_keys: &[String],
+    timestamp: i64,
+    request_content: &mut OutgoingWebhookRequestContent,
+) -> CustomResult<(), errors::WebhooksFlowError> {
+    let body = request_content.body.peek();
+
+    request_content.headers.retain(|(name, _)| {
+        !name.eq_ignore_ascii_case(headers::X_WEBHOOK_SIGNATURE)
+            && !name.eq_ignore_ascii_case(headers::X_WEBHOOK_SIGNATURE_V2)
+    });
+
+    if let Some(signing_key) = signing_keys.first() {
+        let signature = sign_outgoing_webhook_payload(signing_key, body.as_bytes())?;
+        request_content
+            .headers
+            .push((headers::X_WEBHOOK_SIGNATURE.to_string(), signature.into()));
+    }
+
+    for signing_key in signing_keys {
+        let key_id = utils::get_webhook_signing_key_id(signing_key)?;
+        let signature =
+            sign_outgoing_webhook_payload(signing_key, format!("{timestamp}.{body}").as_bytes())?;
+        request_content.headers.push((
+            headers::X_WEBHOOK_SIGNATURE_V2.to_string(),
+            format!("keyid={key_id},t={timestamp},v1={signature}").into(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn sign_outgoing_webhook_payload(
+    signing_key: &str,
+    payload: &[u8],
+) -> CustomResult<String, errors::WebhooksFlowError> {
+    common_utils::crypto::HmacSha512
+        .sign_message(signing_key.as_bytes(), payload)
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookSigningFailed)
+        .attach_printable("Failed to sign the outgoing webhook payload")
+        .map(hex::encode)
 }
 
 pub(crate) fn get_outgoing_webhook_request(
     merchant_context: &domain::MerchantContext,
     outgoing_webhook: api::OutgoingWebhook,
     business_profile: &domain::Profile,
+    webhook_endpoint_id: Option<&str>,
 ) -> CustomResult<OutgoingWebhookRequestContent, errors::WebhooksFlowError> {
     #[inline]
     fn get_outgoing_webhook_request_inner<WebhookType: types::OutgoingWebhookType>(
         outgoing_webhook: api::OutgoingWebhook,
         business_profile: &domain::Profile,
+        webhook_endpoint_id: Option<&str>,
     ) -> CustomResult<OutgoingWebhookRequestContent, errors::WebhooksFlowError> {
         let mut headers = vec![
             (
@@ -677,7 +1034,8 @@ pub(crate) fn get_outgoing_webhook_request(
         ];
 
         let transformed_outgoing_webhook = WebhookType::from(outgoing_webhook);
-        let payment_response_hash_key = business_profile.payment_response_hash_key.clone();
+        let payment_response_hash_key =
+            get_webhook_signing_key(business_profile, webhook_endpoint_id);
         let custom_headers = business_profile
             .outgoing_webhook_custom_http_headers
             .clone()
@@ -712,17 +1070,35 @@ pub(crate) fn get_outgoing_webhook_request(
         })
     }
 
-    match merchant_context
-        .get_merchant_account()
-        .get_compatible_connector()
-    {
+    // Stripe `event` objects are only sent to profiles which have opted into the Stripe webhook
+    // format, merchants with Stripe as the compatible connector otherwise keep receiving the
+    // Stripe compatible webhooks they have been receiving
+    match (
+        get_profile_webhook_format(business_profile),
+        merchant_context
+            .get_merchant_account()
+            .get_compatible_connector(),
+    ) {
         #[cfg(feature = "stripe")]
-        Some(api_models::enums::Connector::Stripe) => get_outgoing_webhook_request_inner::<
-            stripe_webhooks::StripeOutgoingWebhook,
-        >(outgoing_webhook, business_profile),
+        (Some(enums::OutgoingWebhookFormat::Stripe), _) => {
+            get_outgoing_webhook_request_inner::<stripe_webhooks::StripeEventOutgoingWebhook>(
+                outgoing_webhook,
+                business_profile,
+                webhook_endpoint_id,
+            )
+        }
+        #[cfg(feature = "stripe")]
+        (None, Some(api_models::enums::Connector::Stripe)) => {
+            get_outgoing_webhook_request_inner::<stripe_webhooks::StripeOutgoingWebhook>(
+                outgoing_webhook,
+                business_profile,
+                webhook_endpoint_id,
+            )
+        }
         _ => get_outgoing_webhook_request_inner::<webhooks::OutgoingWebhook>(
             outgoing_webhook,
             business_profile,
+            webhook_endpoint_id,
         ),
     }
 }
@@ -799,7 +1175,7 @@ async fn api_client_error_handler(
     // much of diagnostic information to be exposed to the merchant.
     update_event_if_client_error(
         state.clone(),
-        merchant_key_store,
+        merchant_key_store.clone(),
         merchant_id,
         event_id,
         "Unable to send request to merchant server".to_string(),
@@ -816,7 +1192,8 @@ async fn api_client_error_handler(
     if let ScheduleWebhookRetry::WithProcessTracker(process_tracker) = schedule_webhook_retry {
         // Schedule a retry attempt for webhook delivery
         outgoing_webhook_retry::retry_webhook_delivery_task(
-            &*state.store,
+            &state,
+            &merchant_key_store,
             merchant_id,
             *process_tracker,
         )
@@ -975,6 +1352,7 @@ async fn success_response_handler(
 
 async fn error_response_handler(
     state: SessionState,
+    merchant_key_store: domain::MerchantKeyStore,
     merchant_id: &common_utils::id_type::MerchantId,
     delivery_attempt: enums::WebhookDeliveryAttempt,
     status_code: u16,
@@ -992,7 +1370,8 @@ async fn error_response_handler(
     if let ScheduleWebhookRetry::WithProcessTracker(process_tracker) = schedule_webhook_retry {
         // Schedule a retry attempt for webhook delivery
         outgoing_webhook_retry::retry_webhook_delivery_task(
-            &*state.store,
+            &state,
+            &merchant_key_store,
             merchant_id,
             *process_tracker,
         )
@@ -1072,3 +1451,246 @@ fn get_outgoing_webhook_event_content_from_event_metadata(
         },
     })
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use diesel_models::business_profile::{
+        WebhookDetails, WebhookEndpoint, WebhookSigningSecretRotation,
+    };
+
+    use super::*;
+
+    fn webhook_endpoint(
+        endpoint_id: &str,
+        enabled_events: Vec<enums::EventType>,
+        is_enabled: Option<bool>,
+    ) -> WebhookEndpoint {
+        WebhookEndpoint {
+            endpoint_id: endpoint_id.to_string(),
+            url: Secret::new(format!("https://example.com/{endpoint_id}")),
+            enabled_events,
+            signing_secret: None,
+            is_enabled,
+        }
+    }
+
+    fn webhook_details(
+        webhook_url: Option<&str>,
+        webhook_endpoints: Vec<WebhookEndpoint>,
+    ) -> WebhookDetails {
+        WebhookDetails {
+            webhook_version: None,
+            webhook_username: None,
+            webhook_password: None,
+            webhook_url: webhook_url.map(|webhook_url| Secret::new(webhook_url.to_string())),
+            payment_created_enabled: None,
+            payment_succeeded_enabled: None,
+            payment_failed_enabled: None,
+            webhook_endpoints: Some(webhook_endpoints),
+            signing_secret_rotation: None,
+            webhook_format: None,
+        }
+    }
+
+    fn get_endpoint_ids_for_event(
+        webhook_details: &WebhookDetails,
+        event_type: enums::EventType,
+    ) -> Vec<Option<String>> {
+        get_webhook_endpoint_ids_from_details(Some(webhook_details), |webhook_endpoint| {
+            webhook_endpoint.is_subscribed_to(event_type)
+        })
+    }
+
+    #[test]
+    fn test_webhook_fan_out_to_subscribed_endpoints() {
+        let webhook_details = webhook_details(
+            Some("https://example.com/primary"),
+            vec![
+                webhook_endpoint("whe_all", vec![], None),
+                webhook_endpoint("whe_refunds", vec![enums::EventType::RefundSucceeded], None),
+                webhook_endpoint(
+                    "whe_payments",
+                    vec![
+                        enums::EventType::PaymentSucceeded,
+                        enums::EventType::PaymentFailed,
+                    ],
+                    Some(true),
+                ),
+                webhook_endpoint("whe_disabled", vec![], Some(false)),
+            ],
+        );
+
+        assert_eq!(
+            get_endpoint_ids_for_event(&webhook_details, enums::EventType::PaymentSucceeded),
+            vec![
+                None,
+                Some("whe_all".to_string()),
+                Some("whe_payments".to_string())
+            ]
+        );
+        assert_eq!(
+            get_endpoint_ids_for_event(&webhook_details, enums::EventType::RefundSucceeded),
+            vec![
+                None,
+                Some("whe_all".to_string()),
+                Some("whe_refunds".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_webhook_fan_out_without_primary_url() {
+        let endpoints = vec![webhook_endpoint("whe_all", vec![], None)];
+
+        assert_eq!(
+            get_endpoint_ids_for_event(
+                &webhook_details(None, endpoints.clone()),
+                enums::EventType::PaymentSucceeded
+            ),
+            vec![Some("whe_all".to_string())]
+        );
+        assert_eq!(
+            get_endpoint_ids_for_event(
+                &webhook_details(Some(""), endpoints),
+                enums::EventType::PaymentSucceeded
+            ),
+            vec![Some("whe_all".to_string())]
+        );
+        assert!(
+            get_webhook_endpoint_ids_from_details(None, |_| true).is_empty(),
+            "no endpoints are expected without webhook details"
+        );
+    }
+
+    fn webhook_details_with_rotation(
+        staged_secret: &str,
+        grace_period_ends_at: time::PrimitiveDateTime,
+    ) -> WebhookDetails {
+        WebhookDetails {
+            signing_secret_rotation: Some(WebhookSigningSecretRotation {
+                staged_secret: Secret::new(staged_secret.to_string()),
+                staged_at: grace_period_ends_at - time::Duration::hours(1),
+                grace_period_ends_at,
+            }),
+            ..webhook_details(Some("https://example.com/primary"), vec![])
+        }
+    }
+
+    fn get_signature_headers(signing_keys: &[String]) -> (Vec<String>, Vec<String>) {
+        let mut request_content = OutgoingWebhookRequestContent {
+            body: Secret::new(r#"{"event_id":"evt_123"}"#.to_string()),
+            headers: vec![(headers::X_WEBHOOK_SIGNATURE.to_string(), "stale".into())],
+        };
+        add_webhook_signature_headers(signing_keys, 1_700_000_000, &mut request_content).unwrap();
+
+        let get_headers = |header_name: &str| {
+            request_content
+                .headers
+                .iter()
+                .filter(|(name, _)| name == header_name)
+                .map(|(_, value)| value.peek().clone())
+                .collect::<Vec<_>>()
+        };
+        (
+            get_headers(headers::X_WEBHOOK_SIGNATURE),
+            get_headers(headers::X_WEBHOOK_SIGNATURE_V2),
+        )
+    }
+
+    #[test]
+    fn test_webhook_signed_with_both_secrets_during_grace_period() {
+        let now = common_utils::date_time::now();
+        let current_secret = "current_secret".to_string();
+        let webhook_details =
+            webhook_details_with_rotation("staged_secret", now + time::Duration::hours(1));
+
+        let signing_keys = get_webhook_signing_keys_from_details(
+            Some(&current_secret),
+            Some(&webhook_details),
+            None,
+            now,
+        );
+        assert_eq!(
+            signing_keys,
+            vec!["current_secret".to_string(), "staged_secret".to_string()]
+        );
+
+        let (signatures, signatures_v2) = get_signature_headers(&signing_keys);
+        assert_eq!(
+            signatures,
+            vec![
+                sign_outgoing_webhook_payload("current_secret", br#"{"event_id":"evt_123"}"#)
+                    .unwrap()
+            ]
+        );
+        assert_eq!(signatures_v2.len(), 2);
+        for (signature, signing_key) in signatures_v2.iter().zip(&signing_keys) {
+            let key_id = utils::get_webhook_signing_key_id(signing_key).unwrap();
+            let expected =
+                sign_outgoing_webhook_payload(signing_key, br#"1700000000.{"event_id":"evt_123"}"#)
+                    .unwrap();
+            assert_eq!(
+                signature,
+                &format!("keyid={key_id},t=1700000000,v1={expected}")
+            );
+        }
+    }
+
+    #[test]
+    fn test_webhook_signed_with_staged_secret_after_grace_period() {
+        let now = common_utils::date_time::now();
+        let current_secret = "current_secret".to_string();
+        let webhook_details =
+            webhook_details_with_rotation("staged_secret", now - time::Duration::seconds(1));
+
+        let signing_keys = get_webhook_signing_keys_from_details(
+            Some(&current_secret),
+            Some(&webhook_details),
+            None,
+            now,
+        );
+        assert_eq!(signing_keys, vec!["staged_secret".to_string()]);
+
+        let (signatures, signatures_v2) = get_signature_headers(&signing_keys);
+        assert_eq!(signatures.len(), 1);
+        assert_eq!(signatures_v2.len(), 1);
+
+        // Once the staged secret is promoted, it remains the only signing secret
+        let promoted_secret = "staged_secret".to_string();
+        let promoted_details = webhook_details(Some("https://example.com/primary"), vec![]);
+        assert_eq!(
+            get_webhook_signing_keys_from_details(
+                Some(&promoted_secret),
+                Some(&promoted_details),
+                None,
+                now,
+            ),
+            vec!["staged_secret".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_webhook_endpoint_secret_takes_precedence_over_rotation() {
+        let now = common_utils::date_time::now();
+        let current_secret = "current_secret".to_string();
+        let webhook_details = WebhookDetails {
+            webhook_endpoints: Some(vec![WebhookEndpoint {
+                signing_secret: Some(Secret::new("endpoint_secret".to_string())),
+                ..webhook_endpoint("whe_all", vec![], None)
+            }]),
+            ..webhook_details_with_rotation("staged_secret", now + time::Duration::hours(1))
+        };
+
+        assert_eq!(
+            get_webhook_signing_keys_from_details(
+                Some(&current_secret),
+                Some(&webhook_details),
+                Some("whe_all"),
+                now,
+            ),
+            vec!["endpoint_secret".to_string()]
+        );
+    }
+}
@@ -115,6 +115,8 @@ pub(crate) async fn create_event_and_trigger_outgoing_webhook(
         delivery_attempt: Some(delivery_attempt),
         metadata: Some(event_metadata),
         is_overall_delivery_successful: Some(false),
+        webhook_endpoint_id: None,
+        dead_lettered_at: None,
     };
 
     let event_insert_result = state
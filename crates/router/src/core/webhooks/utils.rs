@@ -1,6 +1,10 @@
 use std::marker::PhantomData;
 
-use common_utils::{errors::CustomResult, ext_traits::ValueExt};
+use common_utils::{
+    crypto::{self, GenerateDigest},
+    errors::CustomResult,
+    ext_traits::ValueExt,
+};
 use error_stack::{Report, ResultExt};
 use redis_interface as redis;
 use router_env::tracing;
@@ -24,6 +28,8 @@ const IRRELEVANT_ATTEMPT_ID_IN_SOURCE_VERIFICATION_FLOW: &str =
     "irrelevant_attempt_id_in_source_verification_flow";
 const IRRELEVANT_CONNECTOR_REQUEST_REFERENCE_ID_IN_SOURCE_VERIFICATION_FLOW: &str =
     "irrelevant_connector_request_reference_id_in_source_verification_flow";
+const WEBHOOK_SIGNING_KEY_ID_LENGTH_IN_BYTES: usize = 8;
+const WEBHOOK_ENDPOINT_DIGEST_LENGTH_IN_BYTES: usize = 4;
 
 /// Check whether the merchant has configured to disable the webhook `event` for the `connector`
 /// First check for the key "whconf_{merchant_id}_{connector_id}" in redis,
@@ -156,11 +162,52 @@ pub(crate) fn get_idempotent_event_id(
     }
 }
 
+/// The idempotent event ID of an event delivered to one of the additional webhook endpoints of a
+/// business profile. The endpoint is identified by a fixed length digest of its ID, so that the
+/// idempotent event ID stays within the length of the column irrespective of the endpoint ID.
+pub(crate) fn get_idempotent_event_id_for_endpoint(
+    idempotent_event_id: &str,
+    webhook_endpoint_id: &str,
+) -> CustomResult<String, errors::ApiErrorResponse> {
+    let digest = crypto::Sha256
+        .generate_digest(webhook_endpoint_id.as_bytes())
+        .change_context(errors::ApiErrorResponse::WebhookProcessingFailure)
+        .attach_printable("Failed to generate the webhook endpoint digest")?;
+
+    Ok(format!(
+        "{idempotent_event_id}_{}",
+        hex::encode(
+            digest
+                .into_iter()
+                .take(WEBHOOK_ENDPOINT_DIGEST_LENGTH_IN_BYTES)
+                .collect::<Vec<_>>(),
+        )
+    ))
+}
+
 #[inline]
 pub(crate) fn generate_event_id() -> String {
     common_utils::generate_time_ordered_id("evt")
 }
 
+/// Derives the identifier of a webhook signing key, which is sent along with webhook signatures so
+/// that merchants can pick the right secret for verification without the secret being disclosed
+pub(crate) fn get_webhook_signing_key_id(
+    signing_key: &str,
+) -> CustomResult<String, errors::WebhooksFlowError> {
+    let digest = crypto::Sha256
+        .generate_digest(signing_key.as_bytes())
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookSigningFailed)
+        .attach_printable("Failed to generate the webhook signing key ID")?;
+
+    Ok(hex::encode(
+        digest
+            .into_iter()
+            .take(WEBHOOK_SIGNING_KEY_ID_LENGTH_IN_BYTES)
+            .collect::<Vec<_>>(),
+    ))
+}
+
 pub fn increment_webhook_outgoing_received_count(merchant_id: &common_utils::id_type::MerchantId) {
     metrics::WEBHOOK_OUTGOING_RECEIVED_COUNT.add(
         1,
@@ -205,6 +252,25 @@ pub(super) async fn perform_redis_lock<A>(
     unique_locking_key: &str,
     merchant_id: common_utils::id_type::MerchantId,
 ) -> RouterResult<Option<String>>
+where
+    A: SessionStateInfo,
+{
+    let redis_lock_expiry_seconds = i64::from(state.conf().webhooks.redis_lock_expiry_seconds);
+    perform_redis_lock_with_expiry(
+        state,
+        unique_locking_key,
+        merchant_id,
+        redis_lock_expiry_seconds,
+    )
+    .await
+}
+
+pub(super) async fn perform_redis_lock_with_expiry<A>(
+    state: &A,
+    unique_locking_key: &str,
+    merchant_id: common_utils::id_type::MerchantId,
+    redis_lock_expiry_seconds: i64,
+) -> RouterResult<Option<String>>
 where
     A: SessionStateInfo,
 {
@@ -221,13 +287,11 @@ where
         merchant_id.get_string_repr(),
         unique_locking_key
     );
-    let redis_lock_expiry_seconds = state.conf().webhooks.redis_lock_expiry_seconds;
-
     let redis_lock_result = redis_conn
         .set_key_if_not_exists_with_expiry(
             &redis_locking_key.as_str().into(),
             lock_value.clone(),
-            Some(i64::from(redis_lock_expiry_seconds)),
+            Some(redis_lock_expiry_seconds),
         )
         .await;
 
@@ -300,3 +364,35 @@ where
             .attach_printable("Error while deleting redis key"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_idempotent_event_id_for_endpoint() {
+        let idempotent_event_id = get_idempotent_event_id(
+            "pay_abcdefghijklmnopqrst",
+            types::storage::enums::EventType::PaymentSucceeded,
+            types::storage::enums::WebhookDeliveryAttempt::InitialAttempt,
+        );
+        let long_endpoint_id = "whe_".to_string() + &"a".repeat(60);
+
+        let first =
+            get_idempotent_event_id_for_endpoint(&idempotent_event_id, &long_endpoint_id).unwrap();
+        let second =
+            get_idempotent_event_id_for_endpoint(&idempotent_event_id, "whe_second").unwrap();
+
+        assert_ne!(first, second);
+        assert!(first.starts_with(&idempotent_event_id));
+        assert_eq!(first.len(), second.len());
+        assert_eq!(first.len(), idempotent_event_id.len() + 9);
+        assert!(first.len() <= 64);
+        assert_eq!(
+            first,
+            get_idempotent_event_id_for_endpoint(&idempotent_event_id, &long_endpoint_id).unwrap()
+        );
+    }
+}
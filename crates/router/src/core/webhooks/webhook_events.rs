@@ -3,8 +3,12 @@ use std::collections::HashSet;
 use common_utils::{self, errors::CustomResult, fp_utils};
 use error_stack::ResultExt;
 use masking::PeekInterface;
+#[cfg(feature = "v1")]
+use router_env::logger;
 use router_env::{instrument, tracing};
 
+#[cfg(feature = "v1")]
+use crate::core::metrics;
 use crate::{
     core::errors::{self, RouterResponse, StorageErrorExt},
     routes::SessionState,
@@ -15,6 +19,14 @@ use crate::{
 
 const INITIAL_DELIVERY_ATTEMPTS_LIST_MAX_LIMIT: i64 = 100;
 const INITIAL_DELIVERY_ATTEMPTS_LIST_MAX_DAYS: i64 = 90;
+#[cfg(feature = "v1")]
+const DEAD_LETTER_REDELIVERIES_PER_MINUTE_DEFAULT: u16 = 60;
+#[cfg(feature = "v1")]
+const DEAD_LETTER_REDELIVERIES_PER_MINUTE_MAX: u16 = 600;
+#[cfg(feature = "v1")]
+const DEAD_LETTER_REDELIVERY_TASK: &str = "DEAD_LETTER_REDELIVERY";
+#[cfg(feature = "v1")]
+const DEAD_LETTER_REDELIVERY_TAG: &str = "WEBHOOK";
 
 #[derive(Debug)]
 enum MerchantAccountOrProfile {
@@ -28,6 +40,85 @@ pub async fn list_initial_delivery_attempts(
     merchant_id: common_utils::id_type::MerchantId,
     api_constraints: api::webhook_events::EventListConstraints,
 ) -> RouterResponse<api::webhook_events::TotalEventsResponse> {
+    let (events, total_count) =
+        find_initial_delivery_attempts(state, merchant_id, api_constraints).await?;
+
+    let events = events
+        .into_iter()
+        .map(api::webhook_events::EventListItemResponse::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ApplicationResponse::Json(
+        api::webhook_events::TotalEventsResponse::new(total_count, events),
+    ))
+}
+
+/// Lists the initial delivery attempts along with the request sent in each webhook, including
+/// the events whose webhooks are yet to be delivered
+#[instrument(skip(state))]
+pub async fn list_initial_delivery_attempts_with_content(
+    state: SessionState,
+    merchant_id: common_utils::id_type::MerchantId,
+    api_constraints: api::webhook_events::EventListConstraints,
+) -> RouterResponse<api::webhook_events::TotalEventContentsResponse> {
+    let (events, total_count) =
+        find_initial_delivery_attempts(state, merchant_id, api_constraints).await?;
+
+    let events = events
+        .into_iter()
+        .map(api::webhook_events::EventContentResponse::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ApplicationResponse::Json(
+        api::webhook_events::TotalEventContentsResponse {
+            events,
+            total_count,
+        },
+    ))
+}
+
+/// Retrieves an event along with the request sent in the webhook
+#[instrument(skip(state))]
+pub async fn retrieve_event_content(
+    state: SessionState,
+    merchant_id: common_utils::id_type::MerchantId,
+    profile_id: Option<common_utils::id_type::ProfileId>,
+    event_id: String,
+) -> RouterResponse<api::webhook_events::EventContentResponse> {
+    let store = state.store.as_ref();
+    let key_manager_state = &(&state).into();
+
+    let key_store = store
+        .get_merchant_key_store_by_merchant_id(
+            key_manager_state,
+            &merchant_id,
+            &store.get_master_key().to_vec().into(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let event = store
+        .find_event_by_merchant_id_event_id(key_manager_state, &merchant_id, &event_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::EventNotFound)?;
+
+    // Events of other business profiles are not visible to profile level callers
+    fp_utils::when(
+        profile_id
+            .is_some_and(|profile_id| event.business_profile_id.as_ref() != Some(&profile_id)),
+        || Err(errors::ApiErrorResponse::EventNotFound),
+    )?;
+
+    Ok(ApplicationResponse::Json(
+        api::webhook_events::EventContentResponse::try_from(event)?,
+    ))
+}
+
+async fn find_initial_delivery_attempts(
+    state: SessionState,
+    merchant_id: common_utils::id_type::MerchantId,
+    api_constraints: api::webhook_events::EventListConstraints,
+) -> errors::RouterResult<(Vec<domain::Event>, i64)> {
     let profile_id = api_constraints.profile_id.clone();
     let constraints = api::webhook_events::EventListConstraintsInternal::foreign_try_from(
         api_constraints.clone(),
@@ -70,7 +161,8 @@ pub async fn list_initial_delivery_attempts(
             offset,
             event_classes,
             event_types,
-            is_delivered
+            is_delivered,
+            webhook_endpoint_id,
         } => {
             let limit = match limit {
                 Some(limit) if  limit <= INITIAL_DELIVERY_ATTEMPTS_LIST_MAX_LIMIT => Ok(Some(limit)),
@@ -129,6 +221,7 @@ pub async fn list_initial_delivery_attempts(
                     offset,
                     updated_event_types.clone(),
                     is_delivered,
+                    webhook_endpoint_id.clone(),
                     &key_store,
                 )
                 .await,
@@ -141,6 +234,7 @@ pub async fn list_initial_delivery_attempts(
                     offset,
                     updated_event_types.clone(),
                     is_delivered,
+                    webhook_endpoint_id.clone(),
                     &key_store,
                 )
                 .await,
@@ -150,17 +244,13 @@ pub async fn list_initial_delivery_attempts(
     .change_context(errors::ApiErrorResponse::InternalServerError)
     .attach_printable("Failed to list events with specified constraints")?;
 
-    let events = events
-        .into_iter()
-        .map(api::webhook_events::EventListItemResponse::try_from)
-        .collect::<Result<Vec<_>, _>>()?;
-
     let created_after = api_constraints
         .created_after
         .unwrap_or(events_list_begin_time);
     let created_before = api_constraints.created_before.unwrap_or(now);
 
     let is_delivered = api_constraints.is_delivered;
+    let webhook_endpoint_id = api_constraints.webhook_endpoint_id;
 
     let total_count = store
         .count_initial_events_by_constraints(
@@ -170,14 +260,13 @@ pub async fn list_initial_delivery_attempts(
             created_before,
             updated_event_types,
             is_delivered,
+            webhook_endpoint_id,
         )
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Failed to get total events count")?;
 
-    Ok(ApplicationResponse::Json(
-        api::webhook_events::TotalEventsResponse::new(total_count, events),
-    ))
+    Ok((events, total_count))
 }
 
 #[instrument(skip(state))]
@@ -264,6 +353,58 @@ pub async fn retry_delivery_attempt(
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Failed to find business profile")?;
 
+    let initial_attempt_id = event_to_retry
+        .initial_attempt_id
+        .clone()
+        .unwrap_or_else(|| event_to_retry.event_id.clone());
+    let is_initial_attempt_dead_lettered = if initial_attempt_id == event_to_retry.event_id {
+        event_to_retry.dead_lettered_at.is_some()
+    } else {
+        store
+            .find_event_by_merchant_id_event_id(
+                key_manager_state,
+                &key_store.merchant_id,
+                &initial_attempt_id,
+                &key_store,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find initial delivery attempt of event to retry")?
+            .dead_lettered_at
+            .is_some()
+    };
+
+    let updated_event =
+        retry_delivery(&state, business_profile, &key_store, event_to_retry, None).await?;
+
+    if is_initial_attempt_dead_lettered && updated_event.is_webhook_notified {
+        remove_event_from_dead_letter_queue(
+            &state,
+            &key_store,
+            key_store.merchant_id.clone(),
+            &initial_attempt_id,
+        )
+        .await;
+    }
+
+    Ok(ApplicationResponse::Json(
+        api::webhook_events::EventRetrieveResponse::try_from(updated_event)?,
+    ))
+}
+
+/// Attempts delivering the event again, optionally to `webhook_url_override` instead of the
+/// webhook URL configured in the business profile, and returns the new delivery attempt
+#[cfg(feature = "v1")]
+async fn retry_delivery(
+    state: &SessionState,
+    business_profile: domain::Profile,
+    key_store: &domain::MerchantKeyStore,
+    event_to_retry: domain::Event,
+    webhook_url_override: Option<String>,
+) -> errors::RouterResult<domain::Event> {
+    let store = state.store.as_ref();
+    let key_manager_state = &state.into();
+
     let delivery_attempt = storage::enums::WebhookDeliveryAttempt::ManualRetry;
     let new_event_id = super::utils::generate_event_id();
     let idempotent_event_id = super::utils::get_idempotent_event_id(
@@ -291,10 +432,12 @@ pub async fn retry_delivery_attempt(
         delivery_attempt: Some(delivery_attempt),
         metadata: event_to_retry.metadata,
         is_overall_delivery_successful: Some(false),
+        webhook_endpoint_id: event_to_retry.webhook_endpoint_id,
+        dead_lettered_at: None,
     };
 
     let event = store
-        .insert_event(key_manager_state, new_event, &key_store)
+        .insert_event(key_manager_state, new_event, key_store)
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Failed to insert event")?;
@@ -313,30 +456,458 @@ pub async fn retry_delivery_attempt(
     Box::pin(super::outgoing::trigger_webhook_and_raise_event(
         state.clone(),
         business_profile,
-        &key_store,
+        key_store,
         event,
         request_content,
         delivery_attempt,
         None,
         None,
+        webhook_url_override,
     ))
     .await;
 
-    let updated_event = store
+    store
         .find_event_by_merchant_id_event_id(
             key_manager_state,
             &key_store.merchant_id,
             &new_event_id,
-            &key_store,
+            key_store,
         )
         .await
-        .to_not_found_response(errors::ApiErrorResponse::EventNotFound)?;
+        .to_not_found_response(errors::ApiErrorResponse::EventNotFound)
+}
+
+#[instrument(skip(state))]
+pub async fn list_dead_lettered_events(
+    state: SessionState,
+    merchant_id: common_utils::id_type::MerchantId,
+    constraints: api::webhook_events::DeadLetterEventListConstraints,
+) -> RouterResponse<Vec<api::webhook_events::EventListItemResponse>> {
+    let (_, _, events) = find_dead_lettered_events(&state, merchant_id, constraints).await?;
 
     Ok(ApplicationResponse::Json(
-        api::webhook_events::EventRetrieveResponse::try_from(updated_event)?,
+        events
+            .into_iter()
+            .map(api::webhook_events::EventListItemResponse::try_from)
+            .collect::<Result<Vec<_>, _>>()?,
+    ))
+}
+
+#[instrument(skip(state))]
+#[cfg(feature = "v1")]
+pub async fn redeliver_dead_lettered_events(
+    state: SessionState,
+    merchant_id: common_utils::id_type::MerchantId,
+    request: api::webhook_events::DeadLetterRedeliveryRequest,
+) -> RouterResponse<api::webhook_events::DeadLetterRedeliveryResponse> {
+    let deliveries_per_minute = request
+        .deliveries_per_minute
+        .unwrap_or(DEAD_LETTER_REDELIVERIES_PER_MINUTE_DEFAULT);
+    fp_utils::when(
+        deliveries_per_minute == 0
+            || deliveries_per_minute > DEAD_LETTER_REDELIVERIES_PER_MINUTE_MAX,
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "`deliveries_per_minute` must be a number between 1 and {DEAD_LETTER_REDELIVERIES_PER_MINUTE_MAX}"
+                ),
+            })
+        },
+    )?;
+
+    let webhook_url_override = request
+        .webhook_url
+        .map(|webhook_url| {
+            url::Url::parse(webhook_url.peek())
+                .map(|_| webhook_url.peek().to_owned())
+                .map_err(|_| errors::ApiErrorResponse::InvalidRequestData {
+                    message: "`webhook_url` must be a valid URL".to_string(),
+                })
+        })
+        .transpose()?;
+
+    let (business_profile, _, events) =
+        find_dead_lettered_events(&state, merchant_id.clone(), request.constraints).await?;
+
+    let now = common_utils::date_time::now();
+    let event_ids = events
+        .iter()
+        .map(|event| event.event_id.clone())
+        .collect::<Vec<_>>();
+    if events.is_empty() {
+        return Ok(ApplicationResponse::Json(
+            api::webhook_events::DeadLetterRedeliveryResponse {
+                event_ids,
+                deliveries_per_minute,
+                estimated_completion_at: now,
+            },
+        ));
+    }
+
+    let delivery_interval = get_dead_letter_redelivery_interval(deliveries_per_minute);
+    let events_count = u32::try_from(events.len())
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Number of events to redeliver exceeds `u32::MAX`")?;
+    let redelivery_duration = delivery_interval * events_count;
+
+    // The lock is held for the worst case duration of the redelivery, where every delivery
+    // attempt times out, so that concurrent bulk redeliveries for the same profile are rejected.
+    let lock_expiry = (redelivery_duration
+        + std::time::Duration::from_secs(super::types::OUTGOING_WEBHOOK_TIMEOUT_SECS)
+            * events_count)
+        .as_secs()
+        .saturating_add(1);
+    let lock_key = get_dead_letter_redelivery_lock_key(business_profile.get_id());
+    let lock_value = super::utils::perform_redis_lock_with_expiry(
+        &state,
+        &lock_key,
+        merchant_id.clone(),
+        i64::try_from(lock_expiry).unwrap_or(i64::MAX),
+    )
+    .await?
+    .ok_or(errors::ApiErrorResponse::PreconditionFailed {
+        message: "A redelivery of dead-lettered events is already in progress for this profile"
+            .to_string(),
+    })?;
+
+    let tracking_data = storage::DeadLetterRedeliveryTrackingData {
+        merchant_id,
+        profile_id: business_profile.get_id().to_owned(),
+        event_ids: event_ids.clone(),
+        deliveries_per_minute,
+        webhook_url_override,
+        lock_value,
+    };
+    if let Err(error) = add_dead_letter_redelivery_task(&state, &tracking_data, now).await {
+        release_dead_letter_redelivery_lock(&state, &tracking_data)
+            .await
+            .map_err(|error| {
+                logger::error!(?error, "Failed to release dead-letter redelivery lock")
+            })
+            .ok();
+        return Err(error);
+    }
+
+    let estimated_completion_at = now + redelivery_duration;
+    Ok(ApplicationResponse::Json(
+        api::webhook_events::DeadLetterRedeliveryResponse {
+            event_ids,
+            deliveries_per_minute,
+            estimated_completion_at,
+        },
     ))
 }
 
+#[cfg(feature = "v1")]
+fn get_dead_letter_redelivery_interval(deliveries_per_minute: u16) -> std::time::Duration {
+    std::time::Duration::from_millis(60_000 / u64::from(deliveries_per_minute))
+}
+
+#[cfg(feature = "v1")]
+fn get_dead_letter_redelivery_lock_key(profile_id: &common_utils::id_type::ProfileId) -> String {
+    format!("dead_letter_redelivery_{}", profile_id.get_string_repr())
+}
+
+/// Schedules a process tracker task which redelivers the dead-lettered events in `tracking_data`
+#[cfg(feature = "v1")]
+async fn add_dead_letter_redelivery_task(
+    state: &SessionState,
+    tracking_data: &storage::DeadLetterRedeliveryTrackingData,
+    schedule_time: time::PrimitiveDateTime,
+) -> errors::RouterResult<()> {
+    let runner = storage::ProcessTrackerRunner::DeadLetterRedeliveryWorkflow;
+    let task = DEAD_LETTER_REDELIVERY_TASK;
+    let tag = [DEAD_LETTER_REDELIVERY_TAG];
+
+    let process_tracker_id = format!(
+        "{runner}_{task}_{}_{}",
+        tracking_data.profile_id.get_string_repr(),
+        schedule_time.assume_utc().unix_timestamp()
+    );
+    let process_tracker_entry = storage::ProcessTrackerNew::new(
+        process_tracker_id,
+        task,
+        runner,
+        tag,
+        tracking_data,
+        None,
+        schedule_time,
+        common_types::consts::API_VERSION,
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to construct the dead-letter redelivery task")?;
+
+    state
+        .store
+        .insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert the dead-letter redelivery task")?;
+
+    Ok(())
+}
+
+/// Removes the next batch of events to be redelivered from `tracking_data`, which contains at most
+/// the number of events that are delivered in a minute.
+#[cfg(feature = "v1")]
+fn take_dead_letter_redelivery_batch(
+    tracking_data: &mut storage::DeadLetterRedeliveryTrackingData,
+) -> Vec<String> {
+    let batch_size =
+        usize::from(tracking_data.deliveries_per_minute).min(tracking_data.event_ids.len());
+    tracking_data.event_ids.drain(..batch_size).collect()
+}
+
+/// Redelivers the next batch of events of a bulk redelivery, paced at the requested number of
+/// deliveries per minute, and removes them from `tracking_data`. Events which are no longer
+/// dead-lettered, such as the ones redelivered by an earlier run of the task which failed before
+/// updating its tracking data, are skipped.
+#[cfg(feature = "v1")]
+pub(crate) async fn redeliver_dead_lettered_events_batch(
+    state: &SessionState,
+    tracking_data: &mut storage::DeadLetterRedeliveryTrackingData,
+) -> errors::RouterResult<()> {
+    let store = state.store.as_ref();
+    let key_manager_state = &state.into();
+
+    let (account, key_store) = get_account_and_key_store(
+        state.clone(),
+        tracking_data.merchant_id.clone(),
+        Some(tracking_data.profile_id.clone()),
+    )
+    .await?;
+    let MerchantAccountOrProfile::Profile(business_profile) = account else {
+        return Err(errors::ApiErrorResponse::InternalServerError).attach_printable(
+            "Expected business profile to be returned when profile ID is specified",
+        );
+    };
+
+    let mut interval = tokio::time::interval(get_dead_letter_redelivery_interval(
+        tracking_data.deliveries_per_minute,
+    ));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    for event_id in take_dead_letter_redelivery_batch(tracking_data) {
+        interval.tick().await;
+
+        let event = match store
+            .find_event_by_merchant_id_event_id(
+                key_manager_state,
+                &tracking_data.merchant_id,
+                &event_id,
+                &key_store,
+            )
+            .await
+        {
+            Ok(event) if event.dead_lettered_at.is_some() => event,
+            Ok(_) => {
+                logger::info!(%event_id, "Event is no longer dead-lettered, skipping redelivery");
+                continue;
+            }
+            Err(error) => {
+                logger::error!(?error, %event_id, "Failed to find dead-lettered event");
+                continue;
+            }
+        };
+
+        redeliver_dead_lettered_event(
+            state,
+            (*business_profile).clone(),
+            &key_store,
+            event,
+            tracking_data.webhook_url_override.clone(),
+        )
+        .await;
+    }
+
+    Ok(())
+}
+
+/// Releases the lock which rejects concurrent bulk redeliveries for the profile
+#[cfg(feature = "v1")]
+pub(crate) async fn release_dead_letter_redelivery_lock(
+    state: &SessionState,
+    tracking_data: &storage::DeadLetterRedeliveryTrackingData,
+) -> errors::RouterResult<()> {
+    super::utils::free_redis_lock(
+        state,
+        &get_dead_letter_redelivery_lock_key(&tracking_data.profile_id),
+        tracking_data.merchant_id.clone(),
+        Some(tracking_data.lock_value.clone()),
+    )
+    .await
+}
+
+/// Redelivers a single dead-lettered event, and removes the event from the dead-letter queue if
+/// the redelivery succeeds.
+#[cfg(feature = "v1")]
+async fn redeliver_dead_lettered_event(
+    state: &SessionState,
+    business_profile: domain::Profile,
+    key_store: &domain::MerchantKeyStore,
+    event: domain::Event,
+    webhook_url_override: Option<String>,
+) {
+    let merchant_id = business_profile.merchant_id.clone();
+    let initial_attempt_id = event.event_id.clone();
+
+    let redelivered_event = match retry_delivery(
+        state,
+        business_profile,
+        key_store,
+        event,
+        webhook_url_override,
+    )
+    .await
+    {
+        Ok(redelivered_event) => redelivered_event,
+        Err(error) => {
+            logger::error!(
+                ?error,
+                %initial_attempt_id,
+                "Failed to redeliver dead-lettered event"
+            );
+            return;
+        }
+    };
+
+    if !redelivered_event.is_webhook_notified {
+        logger::warn!(%initial_attempt_id, "Redelivery of dead-lettered event was unsuccessful");
+        return;
+    }
+
+    remove_event_from_dead_letter_queue(state, key_store, merchant_id, &initial_attempt_id).await;
+}
+
+/// Removes the dead-lettered initial delivery attempt of an event from the dead-letter queue once
+/// the event has been delivered successfully
+#[cfg(feature = "v1")]
+async fn remove_event_from_dead_letter_queue(
+    state: &SessionState,
+    key_store: &domain::MerchantKeyStore,
+    merchant_id: common_utils::id_type::MerchantId,
+    initial_attempt_id: &str,
+) {
+    match state
+        .store
+        .update_event_by_merchant_id_event_id(
+            &state.into(),
+            &merchant_id,
+            initial_attempt_id,
+            domain::EventUpdate::DeadLetterRedeliveryUpdate,
+            key_store,
+        )
+        .await
+    {
+        Ok(_) => metrics::WEBHOOK_OUTGOING_REDELIVERED_COUNT.add(
+            1,
+            router_env::metric_attributes!((super::MERCHANT_ID, merchant_id)),
+        ),
+        Err(error) => logger::error!(
+            ?error,
+            %initial_attempt_id,
+            "Failed to remove redelivered event from the dead-letter queue"
+        ),
+    }
+}
+
+#[instrument(skip(state))]
+#[cfg(feature = "v1")]
+pub async fn list_webhook_circuit_breakers(
+    state: SessionState,
+    merchant_id: common_utils::id_type::MerchantId,
+    profile_id: common_utils::id_type::ProfileId,
+) -> RouterResponse<Vec<api::webhook_events::WebhookCircuitBreakerResponse>> {
+    let (account, _) =
+        get_account_and_key_store(state.clone(), merchant_id, Some(profile_id)).await?;
+    let MerchantAccountOrProfile::Profile(business_profile) = account else {
+        return Err(errors::ApiErrorResponse::InternalServerError).attach_printable(
+            "Expected business profile to be returned when profile ID is specified",
+        );
+    };
+
+    let mut circuit_breakers = Vec::new();
+    for webhook_endpoint_id in super::outgoing::get_webhook_endpoint_ids(&business_profile) {
+        let circuit_breaker = super::circuit_breaker::WebhookEndpointCircuitBreaker::new(
+            &business_profile,
+            webhook_endpoint_id.as_deref(),
+        )
+        .get_state(&state)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to obtain webhook endpoint circuit breaker state")?;
+        circuit_breakers.push(circuit_breaker);
+    }
+
+    Ok(ApplicationResponse::Json(circuit_breakers))
+}
+
+async fn find_dead_lettered_events(
+    state: &SessionState,
+    merchant_id: common_utils::id_type::MerchantId,
+    constraints: api::webhook_events::DeadLetterEventListConstraints,
+) -> errors::RouterResult<(
+    domain::Profile,
+    domain::MerchantKeyStore,
+    Vec<domain::Event>,
+)> {
+    let now = common_utils::date_time::now();
+    let events_list_begin_time =
+        (now.date() - time::Duration::days(INITIAL_DELIVERY_ATTEMPTS_LIST_MAX_DAYS)).midnight();
+
+    let limit = match constraints.limit.map(i64::from) {
+        Some(limit) if limit > INITIAL_DELIVERY_ATTEMPTS_LIST_MAX_LIMIT => {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "`limit` must be a number less than {INITIAL_DELIVERY_ATTEMPTS_LIST_MAX_LIMIT}"
+                ),
+            })
+        }
+        Some(limit) => Ok(limit),
+        None => Ok(INITIAL_DELIVERY_ATTEMPTS_LIST_MAX_LIMIT),
+    }?;
+
+    let created_after = constraints.created_after.unwrap_or(events_list_begin_time);
+    let created_before = constraints.created_before.unwrap_or(now);
+    fp_utils::when(created_after < events_list_begin_time, || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "`created_after` must be a timestamp within the past {INITIAL_DELIVERY_ATTEMPTS_LIST_MAX_DAYS} days."
+            ),
+        })
+    })?;
+    fp_utils::when(created_after > created_before, || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "The `created_after` timestamp must be an earlier timestamp compared to the `created_before` timestamp".to_string(),
+        })
+    })?;
+
+    let (account, key_store) =
+        get_account_and_key_store(state.clone(), merchant_id, Some(constraints.profile_id)).await?;
+    let MerchantAccountOrProfile::Profile(business_profile) = account else {
+        return Err(errors::ApiErrorResponse::InternalServerError).attach_printable(
+            "Expected business profile to be returned when profile ID is specified",
+        );
+    };
+
+    let events = state
+        .store
+        .list_dead_lettered_initial_events_by_profile_id(
+            &state.into(),
+            business_profile.get_id(),
+            created_after,
+            created_before,
+            constraints.event_types.unwrap_or_default(),
+            limit,
+            &key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list dead-lettered events")?;
+
+    Ok((*business_profile, key_store, events))
+}
+
 async fn get_account_and_key_store(
     state: SessionState,
     merchant_id: common_utils::id_type::MerchantId,
@@ -444,3 +1015,55 @@ async fn finalize_event_types(
 
     Ok(event_types.clone())
 }
+
+#[cfg(all(test, feature = "v1"))]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_dead_letter_redelivery_batches() {
+        let mut tracking_data = storage::DeadLetterRedeliveryTrackingData {
+            merchant_id: common_utils::id_type::MerchantId::try_from(std::borrow::Cow::from(
+                "merchant_1",
+            ))
+            .unwrap(),
+            profile_id: common_utils::id_type::ProfileId::try_from(std::borrow::Cow::from(
+                "profile_1",
+            ))
+            .unwrap(),
+            event_ids: (1..=5).map(|index| format!("evt_{index}")).collect(),
+            deliveries_per_minute: 2,
+            webhook_url_override: None,
+            lock_value: "lock_value".to_string(),
+        };
+
+        assert_eq!(
+            take_dead_letter_redelivery_batch(&mut tracking_data),
+            vec!["evt_1", "evt_2"]
+        );
+        assert_eq!(
+            take_dead_letter_redelivery_batch(&mut tracking_data),
+            vec!["evt_3", "evt_4"]
+        );
+        assert_eq!(
+            take_dead_letter_redelivery_batch(&mut tracking_data),
+            vec!["evt_5"]
+        );
+        assert!(tracking_data.event_ids.is_empty());
+        assert!(take_dead_letter_redelivery_batch(&mut tracking_data).is_empty());
+    }
+
+    #[test]
+    fn test_dead_letter_redelivery_interval() {
+        assert_eq!(
+            get_dead_letter_redelivery_interval(DEAD_LETTER_REDELIVERIES_PER_MINUTE_DEFAULT),
+            std::time::Duration::from_secs(1)
+        );
+        assert_eq!(
+            get_dead_letter_redelivery_interval(DEAD_LETTER_REDELIVERIES_PER_MINUTE_MAX),
+            std::time::Duration::from_millis(100)
+        );
+    }
+}
@@ -57,6 +57,7 @@ where
         offset: Option<i64>,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
         merchant_key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<Vec<domain::Event>, errors::StorageError>;
 
@@ -87,6 +88,19 @@ where
         offset: Option<i64>,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
+        merchant_key_store: &domain::MerchantKeyStore,
+    ) -> CustomResult<Vec<domain::Event>, errors::StorageError>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn list_dead_lettered_initial_events_by_profile_id(
+        &self,
+        state: &KeyManagerState,
+        profile_id: &common_utils::id_type::ProfileId,
+        created_after: time::PrimitiveDateTime,
+        created_before: time::PrimitiveDateTime,
+        event_types: HashSet<common_enums::EventType>,
+        limit: i64,
         merchant_key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<Vec<domain::Event>, errors::StorageError>;
 
@@ -99,6 +113,7 @@ where
         merchant_key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<domain::Event, errors::StorageError>;
 
+    #[allow(clippy::too_many_arguments)]
     async fn count_initial_events_by_constraints(
         &self,
         merchant_id: &common_utils::id_type::MerchantId,
@@ -107,6 +122,7 @@ where
         created_before: time::PrimitiveDateTime,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
     ) -> CustomResult<i64, errors::StorageError>;
 }
 
@@ -203,6 +219,7 @@ impl EventInterface for Store {
         offset: Option<i64>,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
         merchant_key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<Vec<domain::Event>, errors::StorageError> {
         let conn = connection::pg_connection_read(self).await?;
@@ -215,6 +232,7 @@ impl EventInterface for Store {
             offset,
             event_types,
             is_delivered,
+            webhook_endpoint_id,
         )
         .await
         .map_err(|error| report!(errors::StorageError::from(error)))
@@ -318,6 +336,7 @@ impl EventInterface for Store {
         offset: Option<i64>,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
         merchant_key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<Vec<domain::Event>, errors::StorageError> {
         let conn = connection::pg_connection_read(self).await?;
@@ -330,6 +349,50 @@ impl EventInterface for Store {
             offset,
             event_types,
             is_delivered,
+            webhook_endpoint_id,
+        )
+        .await
+        .map_err(|error| report!(errors::StorageError::from(error)))
+        .async_and_then(|events| async {
+            let mut domain_events = Vec::with_capacity(events.len());
+            for event in events.into_iter() {
+                domain_events.push(
+                    event
+                        .convert(
+                            state,
+                            merchant_key_store.key.get_inner(),
+                            common_utils::types::keymanager::Identifier::Merchant(
+                                merchant_key_store.merchant_id.clone(),
+                            ),
+                        )
+                        .await
+                        .change_context(errors::StorageError::DecryptionError)?,
+                );
+            }
+            Ok(domain_events)
+        })
+        .await
+    }
+
+    #[instrument(skip_all)]
+    async fn list_dead_lettered_initial_events_by_profile_id(
+        &self,
+        state: &KeyManagerState,
+        profile_id: &common_utils::id_type::ProfileId,
+        created_after: time::PrimitiveDateTime,
+        created_before: time::PrimitiveDateTime,
+        event_types: HashSet<common_enums::EventType>,
+        limit: i64,
+        merchant_key_store: &domain::MerchantKeyStore,
+    ) -> CustomResult<Vec<domain::Event>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::Event::list_dead_lettered_initial_attempts_by_profile_id(
+            &conn,
+            profile_id,
+            created_after,
+            created_before,
+            event_types,
+            limit,
         )
         .await
         .map_err(|error| report!(errors::StorageError::from(error)))
@@ -384,6 +447,7 @@ impl EventInterface for Store {
         created_before: time::PrimitiveDateTime,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
     ) -> CustomResult<i64, errors::StorageError> {
         let conn = connection::pg_connection_read(self).await?;
         storage::Event::count_initial_attempts_by_constraints(
@@ -394,6 +458,7 @@ impl EventInterface for Store {
             created_before,
             event_types,
             is_delivered,
+            webhook_endpoint_id,
         )
         .await
         .map_err(|error| report!(errors::StorageError::from(error)))
@@ -505,6 +570,7 @@ impl EventInterface for MockDb {
         offset: Option<i64>,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
         merchant_key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<Vec<domain::Event>, errors::StorageError> {
         let locked_events = self.events.lock().await;
@@ -514,7 +580,9 @@ impl EventInterface for MockDb {
                 && (event.created_at >= created_after)
                 && (event.created_at <= created_before)
                 && (event_types.is_empty() || event_types.contains(&event.event_type))
-                && (event.is_overall_delivery_successful == is_delivered);
+                && (event.is_overall_delivery_successful == is_delivered)
+                && (webhook_endpoint_id.is_none()
+                    || event.webhook_endpoint_id == webhook_endpoint_id);
 
             check
         });
@@ -641,6 +709,7 @@ impl EventInterface for MockDb {
         offset: Option<i64>,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
         merchant_key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<Vec<domain::Event>, errors::StorageError> {
         let locked_events = self.events.lock().await;
@@ -650,7 +719,9 @@ impl EventInterface for MockDb {
                 && (event.created_at >= created_after)
                 && (event.created_at <= created_before)
                 && (event_types.is_empty() || event_types.contains(&event.event_type))
-                && (event.is_overall_delivery_successful == is_delivered);
+                && (event.is_overall_delivery_successful == is_delivered)
+                && (webhook_endpoint_id.is_none()
+                    || event.webhook_endpoint_id == webhook_endpoint_id);
 
             check
         });
@@ -699,6 +770,51 @@ impl EventInterface for MockDb {
         Ok(domain_events)
     }
 
+    async fn list_dead_lettered_initial_events_by_profile_id(
+        &self,
+        state: &KeyManagerState,
+        profile_id: &common_utils::id_type::ProfileId,
+        created_after: time::PrimitiveDateTime,
+        created_before: time::PrimitiveDateTime,
+        event_types: HashSet<common_enums::EventType>,
+        limit: i64,
+        merchant_key_store: &domain::MerchantKeyStore,
+    ) -> CustomResult<Vec<domain::Event>, errors::StorageError> {
+        let locked_events = self.events.lock().await;
+        let mut events = locked_events
+            .iter()
+            .filter(|event| {
+                event.business_profile_id == Some(profile_id.to_owned())
+                    && event.initial_attempt_id.as_ref() == Some(&event.event_id)
+                    && event.dead_lettered_at.is_some()
+                    && (event.created_at >= created_after)
+                    && (event.created_at <= created_before)
+                    && (event_types.is_empty() || event_types.contains(&event.event_type))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        events.sort_by_key(|event| event.created_at);
+
+        let limit: usize = limit
+            .try_into()
+            .map_err(|_| errors::StorageError::MockDbError)?;
+        let mut domain_events = Vec::with_capacity(events.len());
+
+        for event in events.into_iter().take(limit) {
+            let domain_event = event
+                .convert(
+                    state,
+                    merchant_key_store.key.get_inner(),
+                    merchant_key_store.merchant_id.clone().into(),
+                )
+                .await
+                .change_context(errors::StorageError::DecryptionError)?;
+            domain_events.push(domain_event);
+        }
+
+        Ok(domain_events)
+    }
+
     async fn update_event_by_merchant_id_event_id(
         &self,
         state: &KeyManagerState,
@@ -729,6 +845,13 @@ impl EventInterface for MockDb {
                 event_to_update.is_overall_delivery_successful =
                     Some(is_overall_delivery_successful)
             }
+            domain::EventUpdate::DeadLetterUpdate { dead_lettered_at } => {
+                event_to_update.dead_lettered_at = Some(dead_lettered_at)
+            }
+            domain::EventUpdate::DeadLetterRedeliveryUpdate => {
+                event_to_update.is_overall_delivery_successful = Some(true);
+                event_to_update.dead_lettered_at = None;
+            }
         }
 
         event_to_update
@@ -750,6 +873,7 @@ impl EventInterface for MockDb {
         created_before: time::PrimitiveDateTime,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
     ) -> CustomResult<i64, errors::StorageError> {
         let locked_events = self.events.lock().await;
 
@@ -760,7 +884,9 @@ impl EventInterface for MockDb {
                 && (event.created_at >= created_after)
                 && (event.created_at <= created_before)
                 && (event_types.is_empty() || event_types.contains(&event.event_type))
-                && (event.is_overall_delivery_successful == is_delivered);
+                && (event.is_overall_delivery_successful == is_delivered)
+                && (webhook_endpoint_id.is_none()
+                    || event.webhook_endpoint_id == webhook_endpoint_id);
 
             check
         });
@@ -884,6 +1010,8 @@ mod tests {
                         .unwrap(),
                     }),
                     is_overall_delivery_successful: Some(false),
+                    webhook_endpoint_id: None,
+                    dead_lettered_at: None,
                 },
                 &merchant_key_store,
             )
@@ -911,6 +1039,165 @@ mod tests {
         assert_eq!(updated_event.event_id, event_id);
     }
 
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    #[cfg(feature = "v1")]
+    async fn test_mockdb_dead_letter_queue() {
+        #[allow(clippy::expect_used)]
+        let mockdb = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+        let (tx, _) = tokio::sync::oneshot::channel();
+        let app_state = Box::pin(routes::AppState::with_storage(
+            Settings::default(),
+            StorageImpl::PostgresqlTest,
+            tx,
+            Box::new(services::MockApiClient),
+        ))
+        .await;
+        let state = &Arc::new(app_state)
+            .get_session_state(
+                &common_utils::id_type::TenantId::try_from_string("public".to_string()).unwrap(),
+                None,
+                || {},
+            )
+            .unwrap();
+        let merchant_id =
+            common_utils::id_type::MerchantId::try_from(std::borrow::Cow::from("merchant_1"))
+                .unwrap();
+        let business_profile_id =
+            common_utils::id_type::ProfileId::try_from(std::borrow::Cow::from("profile1")).unwrap();
+        let key_manager_state = &state.into();
+        let master_key = mockdb.get_master_key();
+        mockdb
+            .insert_merchant_key_store(
+                key_manager_state,
+                domain::MerchantKeyStore {
+                    merchant_id: merchant_id.clone(),
+                    key: domain::types::crypto_operation(
+                        key_manager_state,
+                        type_name!(domain::MerchantKeyStore),
+                        domain::types::CryptoOperation::Encrypt(
+                            services::generate_aes256_key().unwrap().to_vec().into(),
+                        ),
+                        Identifier::Merchant(merchant_id.to_owned()),
+                        master_key,
+                    )
+                    .await
+                    .and_then(|val| val.try_into_operation())
+                    .unwrap(),
+                    created_at: datetime!(2023-02-01 0:00),
+                },
+                &master_key.to_vec().into(),
+            )
+            .await
+            .unwrap();
+        let merchant_key_store = mockdb
+            .get_merchant_key_store_by_merchant_id(
+                key_manager_state,
+                &merchant_id,
+                &master_key.to_vec().into(),
+            )
+            .await
+            .unwrap();
+
+        let created_at = common_utils::date_time::now();
+        let event =
+            |event_id: &str,
+             initial_attempt_id: &str,
+             delivery_attempt: enums::WebhookDeliveryAttempt| domain::Event {
+                event_id: event_id.into(),
+                event_type: enums::EventType::PaymentSucceeded,
+                event_class: enums::EventClass::Payments,
+                is_webhook_notified: false,
+                primary_object_id: "test_payment_id".into(),
+                primary_object_type: enums::EventObjectType::PaymentDetails,
+                created_at,
+                merchant_id: Some(merchant_id.to_owned()),
+                business_profile_id: Some(business_profile_id.to_owned()),
+                primary_object_created_at: Some(created_at),
+                idempotent_event_id: Some(event_id.into()),
+                initial_attempt_id: Some(initial_attempt_id.into()),
+                request: None,
+                response: None,
+                delivery_attempt: Some(delivery_attempt),
+                metadata: None,
+                is_overall_delivery_successful: Some(false),
+                webhook_endpoint_id: None,
+                dead_lettered_at: None,
+            };
+        for event in [
+            event(
+                "evt_initial",
+                "evt_initial",
+                enums::WebhookDeliveryAttempt::InitialAttempt,
+            ),
+            event(
+                "evt_retry",
+                "evt_initial",
+                enums::WebhookDeliveryAttempt::AutomaticRetry,
+            ),
+            event(
+                "evt_delivered",
+                "evt_delivered",
+                enums::WebhookDeliveryAttempt::InitialAttempt,
+            ),
+        ] {
+            mockdb
+                .insert_event(key_manager_state, event, &merchant_key_store)
+                .await
+                .unwrap();
+        }
+
+        let list_dead_lettered_event_ids = || async {
+            mockdb
+                .list_dead_lettered_initial_events_by_profile_id(
+                    key_manager_state,
+                    &business_profile_id,
+                    created_at - time::Duration::minutes(1),
+                    created_at + time::Duration::minutes(1),
+                    std::collections::HashSet::new(),
+                    100,
+                    &merchant_key_store,
+                )
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|event| event.event_id)
+                .collect::<Vec<_>>()
+        };
+        assert!(list_dead_lettered_event_ids().await.is_empty());
+
+        let dead_lettered_event = mockdb
+            .update_event_by_merchant_id_event_id(
+                key_manager_state,
+                &merchant_id,
+                "evt_initial",
+                domain::EventUpdate::DeadLetterUpdate {
+                    dead_lettered_at: created_at,
+                },
+                &merchant_key_store,
+            )
+            .await
+            .unwrap();
+        assert_eq!(dead_lettered_event.dead_lettered_at, Some(created_at));
+        assert_eq!(list_dead_lettered_event_ids().await, vec!["evt_initial"]);
+
+        let redelivered_event = mockdb
+            .update_event_by_merchant_id_event_id(
+                key_manager_state,
+                &merchant_id,
+                "evt_initial",
+                domain::EventUpdate::DeadLetterRedeliveryUpdate,
+                &merchant_key_store,
+            )
+            .await
+            .unwrap();
+        assert_eq!(redelivered_event.dead_lettered_at, None);
+        assert_eq!(redelivered_event.is_overall_delivery_successful, Some(true));
+        assert!(list_dead_lettered_event_ids().await.is_empty());
+    }
+
     #[allow(clippy::unwrap_used)]
     #[tokio::test]
     #[cfg(feature = "v2")]
@@ -1001,6 +1288,8 @@ mod tests {
                         .unwrap(),
                     }),
                     is_overall_delivery_successful: Some(false),
+                    webhook_endpoint_id: None,
+                    dead_lettered_at: None,
                 },
                 &merchant_key_store,
             )
@@ -1,4 +1,7 @@
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use ::payment_methods::state::PaymentMethodsStorageInterface;
 use common_enums::enums::MerchantStorageScheme;
@@ -33,7 +36,10 @@ use masking::Secret;
 use redis_interface::{errors::RedisError, RedisConnectionPool, RedisEntryId};
 use router_env::{instrument, logger, tracing};
 use scheduler::{
-    db::{process_tracker::ProcessTrackerInterface, queue::QueueInterface},
+    db::{
+        process_tracker::ProcessTrackerInterface,
+        queue::{PausedRunner, QueueInterface},
+    },
     SchedulerInterface,
 };
 use serde::Serialize;
@@ -776,6 +782,7 @@ impl EventInterface for KafkaStore {
         offset: Option<i64>,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
         merchant_key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<Vec<domain::Event>, errors::StorageError> {
         self.diesel_store
@@ -788,6 +795,7 @@ impl EventInterface for KafkaStore {
                 offset,
                 event_types,
                 is_delivered,
+                webhook_endpoint_id,
                 merchant_key_store,
             )
             .await
@@ -837,6 +845,7 @@ impl EventInterface for KafkaStore {
         offset: Option<i64>,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
         merchant_key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<Vec<domain::Event>, errors::StorageError> {
         self.diesel_store
@@ -849,6 +858,30 @@ impl EventInterface for KafkaStore {
                 offset,
                 event_types,
                 is_delivered,
+                webhook_endpoint_id,
+                merchant_key_store,
+            )
+            .await
+    }
+
+    async fn list_dead_lettered_initial_events_by_profile_id(
+        &self,
+        state: &KeyManagerState,
+        profile_id: &id_type::ProfileId,
+        created_after: PrimitiveDateTime,
+        created_before: PrimitiveDateTime,
+        event_types: HashSet<common_enums::EventType>,
+        limit: i64,
+        merchant_key_store: &domain::MerchantKeyStore,
+    ) -> CustomResult<Vec<domain::Event>, errors::StorageError> {
+        self.diesel_store
+            .list_dead_lettered_initial_events_by_profile_id(
+                state,
+                profile_id,
+                created_after,
+                created_before,
+                event_types,
+                limit,
                 merchant_key_store,
             )
             .await
@@ -881,6 +914,7 @@ impl EventInterface for KafkaStore {
         created_before: PrimitiveDateTime,
         event_types: HashSet<common_enums::EventType>,
         is_delivered: Option<bool>,
+        webhook_endpoint_id: Option<String>,
     ) -> CustomResult<i64, errors::StorageError> {
         self.diesel_store
             .count_initial_events_by_constraints(
@@ -890,6 +924,7 @@ impl EventInterface for KafkaStore {
                 created_before,
                 event_types,
                 is_delivered,
+                webhook_endpoint_id,
             )
             .await
     }
@@ -1460,6 +1495,44 @@ impl QueueInterface for KafkaStore {
     async fn get_key(&self, key: &str) -> CustomResult<Vec<u8>, RedisError> {
         self.diesel_store.get_key(key).await
     }
+
+    async fn reserve_in_flight_slots(
+        &self,
+        key: &str,
+        task_ids: Vec<String>,
+        max_in_flight: u64,
+        expiry: i64,
+    ) -> CustomResult<Vec<String>, RedisError> {
+        self.diesel_store
+            .reserve_in_flight_slots(key, task_ids, max_in_flight, expiry)
+            .await
+    }
+
+    async fn release_in_flight_slots(
+        &self,
+        key: &str,
+        task_ids: Vec<String>,
+    ) -> CustomResult<(), RedisError> {
+        self.diesel_store
+            .release_in_flight_slots(key, task_ids)
+            .await
+    }
+
+    async fn pause_runner(
+        &self,
+        runner: &str,
+        paused_runner: PausedRunner,
+    ) -> CustomResult<bool, RedisError> {
+        self.diesel_store.pause_runner(runner, paused_runner).await
+    }
+
+    async fn resume_runner(&self, runner: &str) -> CustomResult<bool, RedisError> {
+        self.diesel_store.resume_runner(runner).await
+    }
+
+    async fn get_paused_runners(&self) -> CustomResult<HashMap<String, PausedRunner>, RedisError> {
+        self.diesel_store.get_paused_runners().await
+    }
 }
 
 #[async_trait::async_trait]
@@ -2593,6 +2666,13 @@ impl ProcessTrackerInterface for KafkaStore {
         self.diesel_store.find_process_by_id(id).await
     }
 
+    async fn find_processes_by_ids(
+        &self,
+        ids: Vec<String>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store.find_processes_by_ids(ids).await
+    }
+
     async fn update_process(
         &self,
         this: storage::ProcessTracker,
@@ -2654,6 +2734,69 @@ impl ProcessTrackerInterface for KafkaStore {
             .find_processes_by_time_status(time_lower_limit, time_upper_limit, status, limit)
             .await
     }
+
+    async fn filter_processes(
+        &self,
+        runner: Option<String>,
+        status: Option<ProcessTrackerStatus>,
+        merchant_id: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store
+            .filter_processes(runner, status, merchant_id, limit, offset)
+            .await
+    }
+
+    async fn update_processes_by_ids_and_statuses(
+        &self,
+        task_ids: Vec<String>,
+        statuses: Vec<ProcessTrackerStatus>,
+        task_update: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store
+            .update_processes_by_ids_and_statuses(task_ids, statuses, task_update)
+            .await
+    }
+
+    async fn reschedule_overdue_processes_by_runner(
+        &self,
+        runner: String,
+        schedule_time: PrimitiveDateTime,
+    ) -> CustomResult<usize, errors::StorageError> {
+        self.diesel_store
+            .reschedule_overdue_processes_by_runner(runner, schedule_time)
+            .await
+    }
+
+    async fn remove_parent_from_waiting_processes(
+        &self,
+        parent_id: &str,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store
+            .remove_parent_from_waiting_processes(parent_id)
+            .await
+    }
+
+    async fn update_waiting_processes_by_parent_id(
+        &self,
+        parent_id: &str,
+        task_update: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store
+            .update_waiting_processes_by_parent_id(parent_id, task_update)
+            .await
+    }
+
+    async fn update_parent_failed_processes_by_parent_id(
+        &self,
+        parent_id: &str,
+        task_update: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store
+            .update_parent_failed_processes_by_parent_id(parent_id, task_update)
+            .await
+    }
 }
 
 #[async_trait::async_trait]
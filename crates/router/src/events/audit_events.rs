@@ -39,6 +39,29 @@ pub enum AuditEventType {
         error_code: Option<String>,
         error_message: Option<String>,
     },
+    ProcessTrackerTaskRetried {
+        task_id: String,
+        operator: String,
+        request_id: Option<String>,
+    },
+    ProcessTrackerTasksCancelled {
+        task_ids: Vec<String>,
+        reason: Option<String>,
+        operator: String,
+        request_id: Option<String>,
+    },
+    ProcessTrackerRunnerPaused {
+        runner: common_enums::ProcessTrackerRunner,
+        reason: Option<String>,
+        operator: String,
+        request_id: Option<String>,
+    },
+    ProcessTrackerRunnerResumed {
+        runner: common_enums::ProcessTrackerRunner,
+        rescheduled_task_count: usize,
+        operator: String,
+        request_id: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -83,6 +106,12 @@ impl Event for AuditEvent {
             AuditEventType::PaymentStatus => "payment_status",
             AuditEventType::PaymentCompleteAuthorize => "payment_complete_authorize",
             AuditEventType::PaymentReject { .. } => "payment_rejected",
+            AuditEventType::ProcessTrackerTaskRetried { .. } => "process_tracker_task_retried",
+            AuditEventType::ProcessTrackerTasksCancelled { .. } => {
+                "process_tracker_tasks_cancelled"
+            }
+            AuditEventType::ProcessTrackerRunnerPaused { .. } => "process_tracker_runner_paused",
+            AuditEventType::ProcessTrackerRunnerResumed { .. } => "process_tracker_runner_resumed",
         };
         format!(
             "{event_type}-{}",
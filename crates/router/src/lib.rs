@@ -75,6 +75,7 @@ pub mod headers {
     pub const X_ACCEPT_VERSION: &str = "X-Accept-Version";
     pub const X_DATE: &str = "X-Date";
     pub const X_WEBHOOK_SIGNATURE: &str = "X-Webhook-Signature-512";
+    pub const X_WEBHOOK_SIGNATURE_V2: &str = "X-Webhook-Signature-V2";
     pub const X_REQUEST_ID: &str = "X-Request-Id";
     pub const X_PROFILE_ID: &str = "X-Profile-Id";
     pub const STRIPE_COMPATIBLE_WEBHOOK_SIGNATURE: &str = "Stripe-Signature";
@@ -202,7 +203,8 @@ pub fn mk_app(
                 .service(routes::Verify::server(state.clone()))
                 .service(routes::Analytics::server(state.clone()))
                 .service(routes::WebhookEvents::server(state.clone()))
-                .service(routes::FeatureMatrix::server(state.clone()));
+                .service(routes::FeatureMatrix::server(state.clone()))
+                .service(routes::ProcessTracker::server(state.clone()));
         }
 
         #[cfg(feature = "v2")]
@@ -871,6 +871,7 @@ impl Routing {
                         )
                     })),
             )
+            .service(web::resource("/simulate").route(web::post().to(routing::routing_simulate)))
             .service(web::resource("/list/profile").route(web::get().to(
                 |state, req, query: web::Query<RoutingRetrieveQuery>| {
                     routing::list_routing_configs_for_profile(
@@ -1035,6 +1036,10 @@ impl Routing {
                 web::resource("/{algorithm_id}")
                     .route(web::get().to(routing::routing_retrieve_config)),
             )
+            .service(
+                web::resource("/{algorithm_id}/fixtures")
+                    .route(web::post().to(routing::routing_run_fixtures)),
+            )
             .service(
                 web::resource("/{algorithm_id}/activate").route(web::post().to(
                     |state, req, path| {
@@ -2097,6 +2102,22 @@ impl Profile {
                 .service(
                     web::resource("/toggle_connector_agnostic_mit")
                         .route(web::post().to(profiles::toggle_connector_agnostic_mit)),
+                )
+                .service(
+                    web::resource("/webhook_signing_secret/rotate")
+                        .route(web::post().to(profiles::rotate_webhook_signing_secret)),
+                )
+                .service(
+                    web::resource("/webhook_signing_secret/retire")
+                        .route(web::post().to(profiles::retire_webhook_signing_secret)),
+                )
+                .service(
+                    web::resource("/card_testing_guard/blocks")
+                        .route(web::get().to(profiles::list_card_testing_guard_blocks)),
+                )
+                .service(
+                    web::resource("/card_testing_guard/blocks/{block_id}")
+                        .route(web::delete().to(profiles::clear_card_testing_guard_block)),
                 ),
         );
 
@@ -2542,6 +2563,22 @@ impl WebhookEvents {
                     .service(web::resource("").route(
                         web::post().to(webhook_events::list_initial_webhook_delivery_attempts),
                     ))
+                    .service(
+                        web::scope("/dead_letter")
+                            .service(web::resource("").route(
+                                web::post().to(webhook_events::list_dead_lettered_webhook_events),
+                            ))
+                            .service(
+                                web::resource("redeliver")
+                                    .route(web::post().to(
+                                        webhook_events::redeliver_dead_lettered_webhook_events,
+                                    )),
+                            ),
+                    )
+                    .service(
+                        web::resource("circuit_breakers/{profile_id}")
+                            .route(web::get().to(webhook_events::list_webhook_circuit_breakers)),
+                    )
                     .service(
                         web::scope("/{event_id}")
                             .service(web::resource("attempts").route(
@@ -2570,6 +2607,22 @@ impl FeatureMatrix {
 #[cfg(feature = "olap")]
 pub struct ProcessTracker;
 
+#[cfg(all(feature = "olap", feature = "v1"))]
+impl ProcessTracker {
+    pub fn server(state: AppState) -> Scope {
+        use super::process_tracker::*;
+        web::scope("/process_tracker")
+            .app_data(web::Data::new(state))
+            .service(web::resource("/tasks").route(web::get().to(list_tasks)))
+            .service(web::resource("/tasks/cancel").route(web::post().to(cancel_tasks)))
+            .service(web::resource("/tasks/{task_id}").route(web::get().to(retrieve_task)))
+            .service(web::resource("/tasks/{task_id}/retry").route(web::post().to(retry_task)))
+            .service(web::resource("/runners/paused").route(web::get().to(list_paused_runners)))
+            .service(web::resource("/runners/{runner}/pause").route(web::post().to(pause_runner)))
+            .service(web::resource("/runners/{runner}/resume").route(web::post().to(resume_runner)))
+    }
+}
+
 #[cfg(all(feature = "olap", feature = "v2"))]
 impl ProcessTracker {
     pub fn server(state: AppState) -> Scope {
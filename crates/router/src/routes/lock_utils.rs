@@ -61,6 +61,8 @@ impl From<Flow> for ApiIdentifier {
             }
 
             Flow::RoutingCreateConfig
+            | Flow::RoutingSimulate
+            | Flow::RoutingRunFixtures
             | Flow::RoutingLinkConfig
             | Flow::RoutingUnlinkConfig
             | Flow::RoutingRetrieveConfig
@@ -185,6 +187,10 @@ impl From<Flow> for ApiIdentifier {
             | Flow::WebhookEventInitialDeliveryAttemptList
             | Flow::WebhookEventDeliveryAttemptList
             | Flow::WebhookEventDeliveryRetry
+            | Flow::WebhookEventDeadLetterList
+            | Flow::WebhookEventDeadLetterRedelivery
+            | Flow::WebhookCircuitBreakerList
+            | Flow::WebhookEventRetrieve
             | Flow::RecoveryIncomingWebhookReceive => Self::Webhooks,
 
             Flow::ApiKeyCreate
@@ -217,7 +223,16 @@ impl From<Flow> for ApiIdentifier {
             | Flow::ProfileDelete
             | Flow::ProfileList
             | Flow::ToggleExtendedCardInfo
-            | Flow::ToggleConnectorAgnosticMit => Self::Profile,
+            | Flow::ToggleConnectorAgnosticMit
+            | Flow::WebhookSigningSecretRotate
+            | Flow::WebhookSigningSecretRetire
+            | Flow::CardTestingGuardBlocksList
+            | Flow::CardTestingGuardBlockClear
+            | Flow::WebhookEndpointCreate
+            | Flow::WebhookEndpointRetrieve
+            | Flow::WebhookEndpointUpdate
+            | Flow::WebhookEndpointDelete
+            | Flow::WebhookEndpointList => Self::Profile,
 
             Flow::PaymentLinkRetrieve
             | Flow::PaymentLinkInitiate
@@ -339,7 +354,14 @@ impl From<Flow> for ApiIdentifier {
             | Flow::PaymentMethodSessionDeleteSavedPaymentMethod
             | Flow::PaymentMethodSessionUpdate => Self::PaymentMethodSession,
 
-            Flow::RevenueRecoveryRetrieve => Self::ProcessTracker,
+            Flow::RevenueRecoveryRetrieve
+            | Flow::ProcessTrackerTasksList
+            | Flow::ProcessTrackerTaskRetrieve
+            | Flow::ProcessTrackerTaskRetry
+            | Flow::ProcessTrackerTasksCancel
+            | Flow::ProcessTrackerRunnerPause
+            | Flow::ProcessTrackerRunnerResume
+            | Flow::ProcessTrackerPausedRunnersList => Self::ProcessTracker,
         }
     }
 }
@@ -450,14 +450,20 @@ pub async fn list_payment_method_api(
     json_payload: web::Query<payment_methods::PaymentMethodListRequest>,
 ) -> HttpResponse {
     let flow = Flow::PaymentMethodsList;
-    let payload = json_payload.into_inner();
+    let mut payload = json_payload.into_inner();
     let api_auth = auth::ApiKeyAuth::default();
 
-    let (auth, _) = match auth::check_client_secret_and_get_auth(req.headers(), &payload, api_auth)
-    {
-        Ok((auth, _auth_flow)) => (auth, _auth_flow),
-        Err(e) => return api::log_and_return_error_response(e),
-    };
+    let (auth, auth_flow) =
+        match auth::check_client_secret_and_get_auth(req.headers(), &payload, api_auth) {
+            Ok((auth, auth_flow)) => (auth, auth_flow),
+            Err(e) => return api::log_and_return_error_response(e),
+        };
+
+    // The eligibility failures expose the merchant connector accounts of the merchant, and are
+    // therefore only returned to the merchant and never to the client
+    if matches!(auth_flow, api::AuthFlow::Client) {
+        payload.debug = None;
+    }
 
     Box::pin(api::server_wrap(
         flow,
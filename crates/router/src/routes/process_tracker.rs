@@ -1,2 +1,179 @@
 #[cfg(feature = "v2")]
 pub mod revenue_recovery;
+
+#[cfg(feature = "v1")]
+use actix_web::{web, HttpRequest, HttpResponse};
+#[cfg(feature = "v1")]
+use api_models::{enums, process_tracker as process_tracker_api};
+#[cfg(feature = "v1")]
+use router_env::{instrument, tracing, Flow};
+
+#[cfg(feature = "v1")]
+use crate::{
+    core::{api_locking, process_tracker},
+    routes::AppState,
+    services::{api, authentication as auth},
+};
+
+#[cfg(feature = "v1")]
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerTasksList))]
+pub async fn list_tasks(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<process_tracker_api::ProcessTrackerTaskListConstraints>,
+) -> HttpResponse {
+    let flow = Flow::ProcessTrackerTasksList;
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        query.into_inner(),
+        |state, _, constraints, _| process_tracker::list_tasks(state, constraints),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[cfg(feature = "v1")]
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerTaskRetrieve))]
+pub async fn retrieve_task(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::ProcessTrackerTaskRetrieve;
+    let payload = process_tracker_api::ProcessTrackerTaskId {
+        task_id: path.into_inner(),
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, request, _| process_tracker::retrieve_task(state, request),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[cfg(feature = "v1")]
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerTaskRetry))]
+pub async fn retry_task(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<process_tracker_api::ProcessTrackerOperatorBody>,
+) -> HttpResponse {
+    let flow = Flow::ProcessTrackerTaskRetry;
+    let payload = process_tracker_api::ProcessTrackerTaskRetryRequest {
+        task_id: path.into_inner(),
+        operator: json_payload.into_inner().operator,
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, request, req_state| process_tracker::retry_task(state, req_state, request),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[cfg(feature = "v1")]
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerTasksCancel))]
+pub async fn cancel_tasks(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<process_tracker_api::ProcessTrackerTasksCancelRequest>,
+) -> HttpResponse {
+    let flow = Flow::ProcessTrackerTasksCancel;
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, request, req_state| process_tracker::cancel_tasks(state, req_state, request),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[cfg(feature = "v1")]
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerRunnerPause))]
+pub async fn pause_runner(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<enums::ProcessTrackerRunner>,
+    json_payload: web::Json<process_tracker_api::ProcessTrackerRunnerPauseBody>,
+) -> HttpResponse {
+    let flow = Flow::ProcessTrackerRunnerPause;
+    let body = json_payload.into_inner();
+    let payload = process_tracker_api::ProcessTrackerRunnerPauseRequest {
+        runner: path.into_inner(),
+        reason: body.reason,
+        operator: body.operator,
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, request, req_state| process_tracker::pause_runner(state, req_state, request),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[cfg(feature = "v1")]
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerRunnerResume))]
+pub async fn resume_runner(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<enums::ProcessTrackerRunner>,
+    json_payload: web::Json<process_tracker_api::ProcessTrackerOperatorBody>,
+) -> HttpResponse {
+    let flow = Flow::ProcessTrackerRunnerResume;
+    let payload = process_tracker_api::ProcessTrackerRunnerResumeRequest {
+        runner: path.into_inner(),
+        operator: json_payload.into_inner().operator,
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, request, req_state| process_tracker::resume_runner(state, req_state, request),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[cfg(feature = "v1")]
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerPausedRunnersList))]
+pub async fn list_paused_runners(state: web::Data<AppState>, req: HttpRequest) -> HttpResponse {
+    let flow = Flow::ProcessTrackerPausedRunnersList;
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        (),
+        |state, _, _, _| process_tracker::list_paused_runners(state),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
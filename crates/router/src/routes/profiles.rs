@@ -365,6 +365,180 @@ pub async fn toggle_connector_agnostic_mit(
     .await
 }
 
+#[cfg(all(feature = "olap", feature = "v1"))]
+#[instrument(skip_all, fields(flow = ?Flow::WebhookSigningSecretRotate))]
+pub async fn rotate_webhook_signing_secret(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(
+        common_utils::id_type::MerchantId,
+        common_utils::id_type::ProfileId,
+    )>,
+    json_payload: web::Json<api_models::admin::WebhookSigningSecretRotateRequest>,
+) -> HttpResponse {
+    let flow = Flow::WebhookSigningSecretRotate;
+    let (merchant_id, profile_id) = path.into_inner();
+    let locking_action = api_locking::LockAction::Hold {
+        input: api_locking::LockingInput {
+            unique_locking_key: profile_id.get_string_repr().to_owned(),
+            api_identifier: super::lock_utils::ApiIdentifier::from(flow.clone()),
+            override_lock_retries: None,
+        },
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, auth_data, req, _| {
+            crate::core::admin::rotate_webhook_signing_secret(
+                state,
+                &profile_id,
+                auth_data.key_store,
+                req,
+            )
+        },
+        auth::auth_type(
+            &auth::HeaderAuth(auth::ApiKeyAuthWithMerchantIdFromRoute(merchant_id.clone())),
+            &auth::JWTAuthMerchantAndProfileFromRoute {
+                merchant_id: merchant_id.clone(),
+                profile_id: profile_id.clone(),
+                required_permission: permissions::Permission::ProfileAccountWrite,
+            },
+            req.headers(),
+        ),
+        locking_action,
+    ))
+    .await
+}
+
+#[cfg(all(feature = "olap", feature = "v1"))]
+#[instrument(skip_all, fields(flow = ?Flow::WebhookSigningSecretRetire))]
+pub async fn retire_webhook_signing_secret(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(
+        common_utils::id_type::MerchantId,
+        common_utils::id_type::ProfileId,
+    )>,
+) -> HttpResponse {
+    let flow = Flow::WebhookSigningSecretRetire;
+    let (merchant_id, profile_id) = path.into_inner();
+    let locking_action = api_locking::LockAction::Hold {
+        input: api_locking::LockingInput {
+            unique_locking_key: profile_id.get_string_repr().to_owned(),
+            api_identifier: super::lock_utils::ApiIdentifier::from(flow.clone()),
+            override_lock_retries: None,
+        },
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        (),
+        |state, auth_data, _, _| {
+            crate::core::admin::retire_webhook_signing_secret(
+                state,
+                &profile_id,
+                auth_data.key_store,
+            )
+        },
+        auth::auth_type(
+            &auth::HeaderAuth(auth::ApiKeyAuthWithMerchantIdFromRoute(merchant_id.clone())),
+            &auth::JWTAuthMerchantAndProfileFromRoute {
+                merchant_id: merchant_id.clone(),
+                profile_id: profile_id.clone(),
+                required_permission: permissions::Permission::ProfileAccountWrite,
+            },
+            req.headers(),
+        ),
+        locking_action,
+    ))
+    .await
+}
+
+#[cfg(all(feature = "olap", feature = "v1"))]
+#[instrument(skip_all, fields(flow = ?Flow::CardTestingGuardBlocksList))]
+pub async fn list_card_testing_guard_blocks(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(
+        common_utils::id_type::MerchantId,
+        common_utils::id_type::ProfileId,
+    )>,
+) -> HttpResponse {
+    let flow = Flow::CardTestingGuardBlocksList;
+    let (merchant_id, profile_id) = path.into_inner();
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        (),
+        |state, auth_data, _, _| {
+            crate::core::admin::list_card_testing_guard_blocks(
+                state,
+                &profile_id,
+                auth_data.key_store,
+            )
+        },
+        auth::auth_type(
+            &auth::HeaderAuth(auth::ApiKeyAuthWithMerchantIdFromRoute(merchant_id.clone())),
+            &auth::JWTAuthMerchantAndProfileFromRoute {
+                merchant_id: merchant_id.clone(),
+                profile_id: profile_id.clone(),
+                required_permission: permissions::Permission::ProfileAccountRead,
+            },
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[cfg(all(feature = "olap", feature = "v1"))]
+#[instrument(skip_all, fields(flow = ?Flow::CardTestingGuardBlockClear))]
+pub async fn clear_card_testing_guard_block(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(
+        common_utils::id_type::MerchantId,
+        common_utils::id_type::ProfileId,
+        String,
+    )>,
+) -> HttpResponse {
+    let flow = Flow::CardTestingGuardBlockClear;
+    let (merchant_id, profile_id, block_id) = path.into_inner();
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        (),
+        |state, auth_data, _, _| {
+            crate::core::admin::clear_card_testing_guard_block(
+                state,
+                &profile_id,
+                &block_id,
+                auth_data.key_store,
+            )
+        },
+        auth::auth_type(
+            &auth::HeaderAuth(auth::ApiKeyAuthWithMerchantIdFromRoute(merchant_id.clone())),
+            &auth::JWTAuthMerchantAndProfileFromRoute {
+                merchant_id: merchant_id.clone(),
+                profile_id: profile_id.clone(),
+                required_permission: permissions::Permission::ProfileAccountWrite,
+            },
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
 #[instrument(skip_all, fields(flow = ?Flow::ToggleExtendedCardInfo))]
 pub async fn toggle_extended_card_info(
     state: web::Data<AppState>,
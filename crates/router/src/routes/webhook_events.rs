@@ -10,8 +10,10 @@ use crate::{
         authorization::permissions::Permission,
     },
     types::api::webhook_events::{
-        EventListConstraints, EventListRequestInternal, WebhookDeliveryAttemptListRequestInternal,
-        WebhookDeliveryRetryRequestInternal,
+        DeadLetterEventListConstraints, DeadLetterEventListRequestInternal,
+        DeadLetterRedeliveryRequest, DeadLetterRedeliveryRequestInternal, EventListConstraints,
+        EventListRequestInternal, WebhookCircuitBreakerListRequestInternal,
+        WebhookDeliveryAttemptListRequestInternal, WebhookDeliveryRetryRequestInternal,
     },
 };
 
@@ -174,3 +176,127 @@ pub async fn retry_webhook_delivery_attempt(
     ))
     .await
 }
+
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEventDeadLetterList))]
+pub async fn list_dead_lettered_webhook_events(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<common_utils::id_type::MerchantId>,
+    json_payload: web::Json<DeadLetterEventListConstraints>,
+) -> impl Responder {
+    let flow = Flow::WebhookEventDeadLetterList;
+    let merchant_id = path.into_inner();
+
+    let request_internal = DeadLetterEventListRequestInternal {
+        merchant_id: merchant_id.clone(),
+        constraints: json_payload.into_inner(),
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        request_internal,
+        |state, _, request_internal, _| {
+            webhook_events::list_dead_lettered_events(
+                state,
+                request_internal.merchant_id,
+                request_internal.constraints,
+            )
+        },
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id,
+                required_permission: Permission::MerchantWebhookEventRead,
+            },
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[cfg(feature = "v1")]
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEventDeadLetterRedelivery))]
+pub async fn redeliver_dead_lettered_webhook_events(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<common_utils::id_type::MerchantId>,
+    json_payload: web::Json<DeadLetterRedeliveryRequest>,
+) -> impl Responder {
+    let flow = Flow::WebhookEventDeadLetterRedelivery;
+    let merchant_id = path.into_inner();
+
+    let request_internal = DeadLetterRedeliveryRequestInternal {
+        merchant_id: merchant_id.clone(),
+        request: json_payload.into_inner(),
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        request_internal,
+        |state, _, request_internal, _| {
+            webhook_events::redeliver_dead_lettered_events(
+                state,
+                request_internal.merchant_id,
+                request_internal.request,
+            )
+        },
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id,
+                required_permission: Permission::MerchantWebhookEventWrite,
+            },
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[cfg(feature = "v1")]
+#[instrument(skip_all, fields(flow = ?Flow::WebhookCircuitBreakerList))]
+pub async fn list_webhook_circuit_breakers(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(
+        common_utils::id_type::MerchantId,
+        common_utils::id_type::ProfileId,
+    )>,
+) -> impl Responder {
+    let flow = Flow::WebhookCircuitBreakerList;
+    let (merchant_id, profile_id) = path.into_inner();
+
+    let request_internal = WebhookCircuitBreakerListRequestInternal {
+        merchant_id: merchant_id.clone(),
+        profile_id,
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        request_internal,
+        |state, _, request_internal, _| {
+            webhook_events::list_webhook_circuit_breakers(
+                state,
+                request_internal.merchant_id,
+                request_internal.profile_id,
+            )
+        },
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id,
+                required_permission: Permission::MerchantWebhookEventRead,
+            },
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
@@ -61,7 +61,7 @@ use crate::{
     core::{
         api_locking,
         errors::{self, CustomResult},
-        payments,
+        idempotency, payments,
     },
     events::{
         api_logs::{ApiEvent, ApiEventMetric, ApiEventsType},
@@ -556,6 +556,10 @@ where
         .attach_printable("Failed to serialize json request")
         .change_context(errors::ApiErrorResponse::InternalServerError.switch())?;
 
+    let idempotency_key =
+        idempotency::IdempotencyKey::from_request(&state.conf.idempotency, flow, request, &payload)
+            .switch()?;
+
     let mut event_type = payload.get_api_event_type();
     let tenant_id = if !state.conf.multitenancy.enabled {
         common_utils::id_type::TenantId::try_from_string(DEFAULT_TENANT.to_owned())
@@ -628,20 +632,41 @@ where
 
     tracing::Span::current().record("merchant_id", merchant_id.get_string_repr().to_owned());
 
-    let output = {
-        lock_action
-            .clone()
-            .perform_locking_action(&session_state, merchant_id.to_owned())
-            .await
-            .switch()?;
-        let res = func(session_state.clone(), auth_out, payload, request_state)
-            .await
-            .switch();
-        lock_action
-            .free_lock_action(&session_state, merchant_id.to_owned())
+    let idempotency_status = match idempotency_key {
+        Some(idempotency_key) => idempotency_key
+            .acquire(&session_state, &merchant_id)
             .await
-            .switch()?;
-        res
+            .switch()?,
+        None => idempotency::IdempotencyStatus::NotApplicable,
+    };
+
+    let output = match idempotency_status {
+        // The stored response replaces the final response once it is built, along with its
+        // status code and headers
+        idempotency::IdempotencyStatus::Completed(stored_response) => {
+            request.extensions_mut().insert(stored_response);
+            Ok(ApplicationResponse::StatusOk)
+        }
+        idempotency_status => {
+            // The guard is picked up once the final response is built, to store the response or
+            // release the key
+            if let idempotency::IdempotencyStatus::Acquired(guard) = idempotency_status {
+                request.extensions_mut().insert(guard);
+            }
+            let _lock_lease = lock_action
+                .clone()
+                .perform_locking_action(&session_state, merchant_id.to_owned())
+                .await
+                .switch()?;
+            let res = func(session_state.clone(), auth_out, payload, request_state)
+                .await
+                .switch();
+            lock_action
+                .free_lock_action(&session_state, merchant_id.to_owned())
+                .await
+                .switch()?;
+            res
+        }
     };
     let request_duration = Instant::now()
         .saturating_duration_since(start_instant)
@@ -892,6 +917,7 @@ where
         }
         Err(error) => log_and_return_error_response(error),
     };
+    let res = complete_idempotent_request(request, res).await;
 
     let response_code = res.status().as_u16();
     tracing::Span::current().record("status_code", response_code);
@@ -905,6 +931,71 @@ where
     res
 }
 
+/// Stores the final response of a request which holds an idempotency key, so that the response
+/// is replayed for retries using the same key, and replays the stored response for such retries.
+///
+/// The key is released if the request was rejected with a client error or could not be taken up by
+/// the server, as the request was not processed. Responses of other failed requests are stored as
+/// well, since the request may have been partially processed. If the response cannot be stored,
+/// the key is left to expire rather than released.
+pub async fn complete_idempotent_request(
+    request: &HttpRequest,
+    response: HttpResponse,
+) -> HttpResponse {
+    if let Some(stored_response) = request
+        .extensions_mut()
+        .remove::<idempotency::IdempotentResponse>()
+    {
+        return stored_response.into_http_response();
+    }
+
+    let Some(guard) = request
+        .extensions_mut()
+        .remove::<idempotency::IdempotencyGuard>()
+    else {
+        return response;
+    };
+
+    if response.status().is_client_error()
+        || response.status() == actix_web::http::StatusCode::SERVICE_UNAVAILABLE
+    {
+        release_idempotency_key(&guard).await;
+        return response;
+    }
+
+    let (response, response_body) = response.into_parts();
+    match body::to_bytes(response_body).await {
+        Ok(response_body) => {
+            let stored_response = idempotency::IdempotentResponse::new(&response, &response_body);
+            match stored_response {
+                Ok(stored_response) => {
+                    if let Err(error) = guard.store_response(&stored_response).await {
+                        logger::error!(?error);
+                    }
+                }
+                Err(error) => logger::error!(?error),
+            }
+            response.set_body(response_body).map_into_boxed_body()
+        }
+        Err(error) => {
+            logger::error!(%error, "Failed to read the response body of an idempotent request");
+            http_server_error_json_response(
+                r#"{
+                    "error": {
+                        "message": "Something went wrong"
+                    }
+                }"#,
+            )
+        }
+    }
+}
+
+async fn release_idempotency_key(guard: &idempotency::IdempotencyGuard) {
+    if let Err(error) = guard.release().await {
+        logger::error!(?error);
+    }
+}
+
 pub fn log_and_return_error_response<T>(error: Report<T>) -> HttpResponse
 where
     T: error_stack::Context + Clone + ResponseError,
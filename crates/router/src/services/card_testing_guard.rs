@@ -1,13 +1,92 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
+use common_utils::id_type;
 use error_stack::ResultExt;
-use redis_interface::RedisConnectionPool;
+use hyperswitch_domain_models::card_testing_guard_data::CardTestingVelocityCheck;
+use redis_interface::{RedisConnectionPool, RedisKey};
 
 use crate::{
+    consts,
     core::errors::{ApiErrorResponse, RouterResult},
     routes::app::SessionStateInfo,
 };
 
+/// Records a failed payment in the sliding window of a velocity rule. Once the failures within the
+/// window reach the threshold, the value is blocked for the duration of its next strike and the
+/// block is added to the blocks of the profile. Returns the strike of the block, or 0 when the
+/// value is not blocked by this failure.
+///
+/// KEYS: window, strikes, block, blocks of the profile
+/// ARGV: failure threshold, window in milliseconds, unique id of the failure, strike expiry in
+/// seconds, block_id, dimension, display value, followed by the block durations in seconds
+const RECORD_VELOCITY_FAILURE_SCRIPT: &str = r#"
+if redis.call('EXISTS', KEYS[3]) == 1 then
+    return 0
+end
+
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+local window = tonumber(ARGV[2])
+redis.call('ZADD', KEYS[1], now, ARGV[3])
+redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', now - window)
+redis.call('PEXPIRE', KEYS[1], window)
+
+if redis.call('ZCARD', KEYS[1]) < tonumber(ARGV[1]) then
+    return 0
+end
+
+redis.call('DEL', KEYS[1])
+local block_durations = {}
+for index = 8, #ARGV do
+    block_durations[#block_durations + 1] = tonumber(ARGV[index])
+end
+local strike = redis.call('INCR', KEYS[2])
+local block_duration = block_durations[math.min(strike, #block_durations)]
+redis.call('EXPIRE', KEYS[2], block_duration + tonumber(ARGV[4]))
+
+local blocked_at = tonumber(time[1])
+local block = cjson.encode({
+    block_id = ARGV[5],
+    dimension = ARGV[6],
+    value = ARGV[7],
+    strike = strike,
+    blocked_at = blocked_at,
+    expires_at = blocked_at + block_duration
+})
+redis.call('SET', KEYS[3], block, 'EX', block_duration)
+redis.call('HSET', KEYS[4], ARGV[5], block)
+if redis.call('TTL', KEYS[4]) < block_duration then
+    redis.call('EXPIRE', KEYS[4], block_duration)
+end
+return strike
+"#;
+
+/// Clears a block along with its strikes and the failures recorded in the sliding windows.
+/// Returns 0 when the block does not exist.
+///
+/// KEYS: block, strikes, blocks of the profile, followed by the windows
+/// ARGV: block_id
+const CLEAR_VELOCITY_BLOCK_SCRIPT: &str = r#"
+local cleared = redis.call('DEL', KEYS[1])
+cleared = cleared + redis.call('HDEL', KEYS[3], ARGV[1])
+redis.call('DEL', KEYS[2])
+for index = 4, #KEYS do
+    redis.call('DEL', KEYS[index])
+end
+return cleared
+"#;
+
+/// A block applied by the velocity rules of a profile, as stored in redis
+#[derive(Debug, serde::Deserialize)]
+pub struct VelocityBlock {
+    pub block_id: String,
+    pub dimension: common_enums::CardTestingGuardDimension,
+    pub value: String,
+    pub strike: u32,
+    pub blocked_at: i64,
+    pub expires_at: i64,
+}
+
 fn get_redis_connection<A: SessionStateInfo>(state: &A) -> RouterResult<Arc<RedisConnectionPool>> {
     state
         .store()
@@ -76,3 +155,379 @@ where
         .await
         .change_context(ApiErrorResponse::InternalServerError)
 }
+
+/// The keys of the velocity rules of a profile share a hash tag, so that the scripts operating on
+/// them can be run on redis clusters
+fn get_velocity_key_prefix(profile_id: &id_type::ProfileId) -> String {
+    format!(
+        "{{{}_{}}}",
+        consts::CARD_TESTING_GUARD_VELOCITY_PREFIX,
+        profile_id.get_string_repr()
+    )
+}
+
+fn get_velocity_block_key(profile_id: &id_type::ProfileId, block_id: &str) -> String {
+    format!("{}_BLOCK_{block_id}", get_velocity_key_prefix(profile_id))
+}
+
+fn get_velocity_strikes_key(profile_id: &id_type::ProfileId, block_id: &str) -> String {
+    format!("{}_STRIKES_{block_id}", get_velocity_key_prefix(profile_id))
+}
+
+fn get_velocity_window_key(
+    profile_id: &id_type::ProfileId,
+    rule_id: &str,
+    block_id: &str,
+) -> String {
+    format!(
+        "{}_WINDOW_{rule_id}_{block_id}",
+        get_velocity_key_prefix(profile_id)
+    )
+}
+
+fn get_velocity_blocks_key(profile_id: &id_type::ProfileId) -> String {
+    format!("{}_BLOCKS", get_velocity_key_prefix(profile_id))
+}
+
+pub async fn is_velocity_block_active<A>(
+    state: &A,
+    profile_id: &id_type::ProfileId,
+    block_id: &str,
+) -> RouterResult<bool>
+where
+    A: SessionStateInfo + Sync,
+{
+    let redis_conn = get_redis_connection(state)?;
+
+    redis_conn
+        .exists::<()>(&get_velocity_block_key(profile_id, block_id).into())
+        .await
+        .change_context(ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to check card testing guard block")
+}
+
+/// Records a failed payment against a velocity rule, returns the strike of the block if the value
+/// got blocked
+pub async fn record_velocity_failure<A>(
+    state: &A,
+    velocity_check: &CardTestingVelocityCheck,
+    strike_expiry_in_secs: i64,
+) -> RouterResult<Option<u32>>
+where
+    A: SessionStateInfo + Sync,
+{
+    let redis_conn = get_redis_connection(state)?;
+
+    record_failure_in_window(&redis_conn, velocity_check, strike_expiry_in_secs).await
+}
+
+async fn record_failure_in_window(
+    redis_conn: &RedisConnectionPool,
+    velocity_check: &CardTestingVelocityCheck,
+    strike_expiry_in_secs: i64,
+) -> RouterResult<Option<u32>> {
+    let profile_id = &velocity_check.profile_id;
+    let rule = &velocity_check.rule;
+    let block_id = velocity_check.value.get_block_id();
+    let tenant_aware_key = |key: String| RedisKey::from(key).tenant_aware_key(redis_conn);
+
+    let keys = vec![
+        tenant_aware_key(get_velocity_window_key(
+            profile_id,
+            &rule.get_rule_id(),
+            &block_id,
+        )),
+        tenant_aware_key(get_velocity_strikes_key(profile_id, &block_id)),
+        tenant_aware_key(get_velocity_block_key(profile_id, &block_id)),
+        tenant_aware_key(get_velocity_blocks_key(profile_id)),
+    ];
+
+    let mut args = vec![
+        rule.failure_threshold.to_string(),
+        (u64::from(rule.window_in_minutes) * 60 * 1000).to_string(),
+        common_utils::generate_id_with_default_len("ctg"),
+        strike_expiry_in_secs.to_string(),
+        block_id,
+        velocity_check.value.dimension.to_string(),
+        velocity_check.value.display_value.clone(),
+    ];
+    args.extend(
+        rule.block_durations_in_minutes
+            .iter()
+            .map(|block_duration| (u64::from(*block_duration) * 60).to_string()),
+    );
+
+    let strike = redis_conn
+        .evaluate_redis_script::<_, u32>(RECORD_VELOCITY_FAILURE_SCRIPT, keys, args)
+        .await
+        .change_context(ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to record failure for card testing guard velocity rule")?;
+
+    Ok((strike > 0).then_some(strike))
+}
+
+/// Blocks of the profile, including the ones which have expired since they were applied
+pub async fn get_velocity_blocks<A>(
+    state: &A,
+    profile_id: &id_type::ProfileId,
+) -> RouterResult<Vec<VelocityBlock>>
+where
+    A: SessionStateInfo + Sync,
+{
+    let redis_conn = get_redis_connection(state)?;
+
+    get_blocks(&redis_conn, profile_id).await
+}
+
+async fn get_blocks(
+    redis_conn: &RedisConnectionPool,
+    profile_id: &id_type::ProfileId,
+) -> RouterResult<Vec<VelocityBlock>> {
+    let blocks = redis_conn
+        .get_hash_fields::<HashMap<String, String>>(&get_velocity_blocks_key(profile_id).into())
+        .await
+        .change_context(ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch card testing guard blocks")?;
+
+    blocks
+        .into_values()
+        .map(|block| {
+            serde_json::from_str::<VelocityBlock>(&block)
+                .change_context(ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to deserialize card testing guard block")
+        })
+        .collect()
+}
+
+/// Clears the block along with the failures recorded for the blocked value by the given rules,
+/// returns false if the block does not exist
+pub async fn clear_velocity_block<A>(
+    state: &A,
+    profile_id: &id_type::ProfileId,
+    block_id: &str,
+    rule_ids: impl Iterator<Item = String>,
+) -> RouterResult<bool>
+where
+    A: SessionStateInfo + Sync,
+{
+    let redis_conn = get_redis_connection(state)?;
+
+    clear_block(&redis_conn, profile_id, block_id, rule_ids).await
+}
+
+async fn clear_block(
+    redis_conn: &RedisConnectionPool,
+    profile_id: &id_type::ProfileId,
+    block_id: &str,
+    rule_ids: impl Iterator<Item = String>,
+) -> RouterResult<bool> {
+    let tenant_aware_key = |key: String| RedisKey::from(key).tenant_aware_key(redis_conn);
+
+    let keys = [
+        get_velocity_block_key(profile_id, block_id),
+        get_velocity_strikes_key(profile_id, block_id),
+        get_velocity_blocks_key(profile_id),
+    ]
+    .into_iter()
+    .chain(rule_ids.map(|rule_id| get_velocity_window_key(profile_id, &rule_id, block_id)))
+    .map(tenant_aware_key)
+    .collect();
+
+    let cleared = redis_conn
+        .evaluate_redis_script::<_, u32>(
+            CLEAR_VELOCITY_BLOCK_SCRIPT,
+            keys,
+            vec![block_id.to_owned()],
+        )
+        .await
+        .change_context(ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to clear card testing guard block")?;
+
+    Ok(cleared > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use common_enums::CardTestingGuardDimension;
+    use common_utils::id_type::GenerateId;
+    use diesel_models::business_profile::CardTestingVelocityRule;
+    use hyperswitch_domain_models::card_testing_guard_data::CardTestingGuardValue;
+    use redis_interface::RedisSettings;
+
+    use super::*;
+
+    const FAILURE_THRESHOLD: u32 = 3;
+    const STRIKE_EXPIRY_IN_SECS: i64 = 3600;
+
+    async fn redis_conn() -> RedisConnectionPool {
+        RedisConnectionPool::new(&RedisSettings::default())
+            .await
+            .expect("failed to create redis connection pool")
+    }
+
+    fn velocity_check(profile_id: &id_type::ProfileId) -> CardTestingVelocityCheck {
+        CardTestingVelocityCheck {
+            profile_id: profile_id.clone(),
+            rule: CardTestingVelocityRule {
+                dimension: CardTestingGuardDimension::CardBin,
+                failure_threshold: FAILURE_THRESHOLD,
+                window_in_minutes: 10,
+                block_durations_in_minutes: vec![1, 5],
+            },
+            value: CardTestingGuardValue {
+                dimension: CardTestingGuardDimension::CardBin,
+                fingerprint: "fingerprint".to_string(),
+                display_value: "424242".to_string(),
+            },
+        }
+    }
+
+    async fn record_failures(
+        redis_conn: &RedisConnectionPool,
+        velocity_check: &CardTestingVelocityCheck,
+        failures: u32,
+    ) -> Vec<Option<u32>> {
+        let mut strikes = Vec::new();
+        for _ in 0..failures {
+            strikes.push(
+                record_failure_in_window(redis_conn, velocity_check, STRIKE_EXPIRY_IN_SECS)
+                    .await
+                    .unwrap(),
+            );
+        }
+        strikes
+    }
+
+    async fn is_block_active(
+        redis_conn: &RedisConnectionPool,
+        profile_id: &id_type::ProfileId,
+        block_id: &str,
+    ) -> bool {
+        redis_conn
+            .exists::<()>(&get_velocity_block_key(profile_id, block_id).into())
+            .await
+            .unwrap()
+    }
+
+    /// Expires the block ahead of its duration, the strikes of the value are retained
+    async fn expire_block(
+        redis_conn: &RedisConnectionPool,
+        profile_id: &id_type::ProfileId,
+        block_id: &str,
+    ) {
+        redis_conn
+            .delete_key(&get_velocity_block_key(profile_id, block_id).into())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failure_threshold_blocks_value() {
+        let redis_conn = redis_conn().await;
+        let profile_id = id_type::ProfileId::generate();
+        let velocity_check = velocity_check(&profile_id);
+        let block_id = velocity_check.value.get_block_id();
+
+        let strikes = record_failures(&redis_conn, &velocity_check, FAILURE_THRESHOLD).await;
+        assert_eq!(strikes, vec![None, None, Some(1)]);
+        assert!(is_block_active(&redis_conn, &profile_id, &block_id).await);
+
+        let blocks = get_blocks(&redis_conn, &profile_id).await.unwrap();
+        assert_eq!(blocks.len(), 1);
+        let block = blocks.first().unwrap();
+        assert_eq!(block.block_id, block_id);
+        assert_eq!(block.value, "424242");
+        assert_eq!(block.strike, 1);
+        assert_eq!(block.expires_at - block.blocked_at, 60);
+
+        // Failures of a blocked value are not counted
+        let strikes = record_failures(&redis_conn, &velocity_check, FAILURE_THRESHOLD).await;
+        assert_eq!(strikes, vec![None, None, None]);
+
+        let rule_ids = std::iter::once(velocity_check.rule.get_rule_id());
+        assert!(clear_block(&redis_conn, &profile_id, &block_id, rule_ids)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_repeated_blocks_escalate_strikes() {
+        let redis_conn = redis_conn().await;
+        let profile_id = id_type::ProfileId::generate();
+        let velocity_check = velocity_check(&profile_id);
+        let block_id = velocity_check.value.get_block_id();
+
+        let expected_block_durations = [60, 300, 300];
+        for (strike, expected_block_duration) in (1..).zip(expected_block_durations) {
+            let strikes = record_failures(&redis_conn, &velocity_check, FAILURE_THRESHOLD).await;
+            assert_eq!(strikes.last(), Some(&Some(strike)));
+
+            let blocks = get_blocks(&redis_conn, &profile_id).await.unwrap();
+            let block = blocks.first().unwrap();
+            assert_eq!(block.strike, strike);
+            assert_eq!(block.expires_at - block.blocked_at, expected_block_duration);
+
+            expire_block(&redis_conn, &profile_id, &block_id).await;
+        }
+
+        let rule_ids = std::iter::once(velocity_check.rule.get_rule_id());
+        clear_block(&redis_conn, &profile_id, &block_id, rule_ids)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_clear_velocity_block_resets_strikes_and_window() {
+        let redis_conn = redis_conn().await;
+        let profile_id = id_type::ProfileId::generate();
+        let velocity_check = velocity_check(&profile_id);
+        let block_id = velocity_check.value.get_block_id();
+        let rule_id = velocity_check.rule.get_rule_id();
+
+        record_failures(&redis_conn, &velocity_check, FAILURE_THRESHOLD).await;
+        expire_block(&redis_conn, &profile_id, &block_id).await;
+        record_failures(&redis_conn, &velocity_check, FAILURE_THRESHOLD).await;
+        expire_block(&redis_conn, &profile_id, &block_id).await;
+        // Failures recorded in the window after the block expired
+        record_failures(&redis_conn, &velocity_check, FAILURE_THRESHOLD - 1).await;
+
+        let is_cleared = clear_block(
+            &redis_conn,
+            &profile_id,
+            &block_id,
+            std::iter::once(rule_id.clone()),
+        )
+        .await
+        .unwrap();
+        assert!(is_cleared);
+        assert!(!is_block_active(&redis_conn, &profile_id, &block_id).await);
+        assert!(get_blocks(&redis_conn, &profile_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // The window and strikes start afresh once the block is cleared
+        let strikes = record_failures(&redis_conn, &velocity_check, FAILURE_THRESHOLD).await;
+        assert_eq!(strikes, vec![None, None, Some(1)]);
+
+        assert!(clear_block(
+            &redis_conn,
+            &profile_id,
+            &block_id,
+            std::iter::once(rule_id.clone())
+        )
+        .await
+        .unwrap());
+        // Blocks which do not exist are not cleared
+        assert!(!clear_block(
+            &redis_conn,
+            &profile_id,
+            &block_id,
+            std::iter::once(rule_id)
+        )
+        .await
+        .unwrap());
+    }
+}
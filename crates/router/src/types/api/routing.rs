@@ -1,9 +1,9 @@
 pub use api_models::{
     enums as api_enums,
     routing::{
-        ConnectorVolumeSplit, RoutableChoiceKind, RoutableConnectorChoice, RoutingAlgorithm,
-        RoutingAlgorithmKind, RoutingAlgorithmRef, RoutingConfigRequest, RoutingDictionary,
-        RoutingDictionaryRecord, StraightThroughAlgorithm,
+        ConnectorVolumeSplit, EvaluationSummary, EvaluationTrace, RoutableChoiceKind,
+        RoutableConnectorChoice, RoutingAlgorithm, RoutingAlgorithmKind, RoutingAlgorithmRef,
+        RoutingConfigRequest, RoutingDictionary, RoutingDictionaryRecord, StraightThroughAlgorithm,
     },
 };
 
@@ -1,6 +1,9 @@
 pub use api_models::webhook_events::{
-    EventListConstraints, EventListConstraintsInternal, EventListItemResponse,
-    EventListRequestInternal, EventRetrieveResponse, OutgoingWebhookRequestContent,
-    OutgoingWebhookResponseContent, TotalEventsResponse, WebhookDeliveryAttemptListRequestInternal,
+    DeadLetterEventListConstraints, DeadLetterEventListRequestInternal,
+    DeadLetterRedeliveryRequest, DeadLetterRedeliveryRequestInternal, DeadLetterRedeliveryResponse,
+    EventContentResponse, EventListConstraints, EventListConstraintsInternal,
+    EventListItemResponse, EventListRequestInternal, EventRetrieveResponse,
+    OutgoingWebhookRequestContent, OutgoingWebhookResponseContent, TotalEventContentsResponse,
+    TotalEventsResponse, WebhookDeliveryAttemptListRequestInternal,
     WebhookDeliveryRetryRequestInternal,
 };
@@ -71,6 +71,14 @@ pub struct Event {
 
     /// Indicates whether the event was ultimately delivered.
     pub is_overall_delivery_successful: Option<bool>,
+
+    /// Identifier of the profile webhook endpoint the event is delivered to, if it is not
+    /// delivered to the profile's primary webhook URL.
+    pub webhook_endpoint_id: Option<String>,
+
+    /// The timestamp when the event was moved to the dead-letter queue, after its automatic
+    /// delivery retries were exhausted.
+    pub dead_lettered_at: Option<time::PrimitiveDateTime>,
 }
 
 #[derive(Debug)]
@@ -82,6 +90,10 @@ pub enum EventUpdate {
     OverallDeliveryStatusUpdate {
         is_overall_delivery_successful: bool,
     },
+    DeadLetterUpdate {
+        dead_lettered_at: time::PrimitiveDateTime,
+    },
+    DeadLetterRedeliveryUpdate,
 }
 
 impl From<EventUpdate> for EventUpdateInternal {
@@ -94,6 +106,7 @@ impl From<EventUpdate> for EventUpdateInternal {
                 is_webhook_notified: Some(is_webhook_notified),
                 response: response.map(Into::into),
                 is_overall_delivery_successful: None,
+                dead_lettered_at: None,
             },
             EventUpdate::OverallDeliveryStatusUpdate {
                 is_overall_delivery_successful,
@@ -101,6 +114,20 @@ impl From<EventUpdate> for EventUpdateInternal {
                 is_webhook_notified: None,
                 response: None,
                 is_overall_delivery_successful: Some(is_overall_delivery_successful),
+                dead_lettered_at: None,
+            },
+            EventUpdate::DeadLetterUpdate { dead_lettered_at } => Self {
+                is_webhook_notified: None,
+                response: None,
+                is_overall_delivery_successful: None,
+                dead_lettered_at: Some(Some(dead_lettered_at)),
+            },
+            // A successful redelivery removes the event from the dead-letter queue
+            EventUpdate::DeadLetterRedeliveryUpdate => Self {
+                is_webhook_notified: None,
+                response: None,
+                is_overall_delivery_successful: Some(true),
+                dead_lettered_at: Some(None),
             },
         }
     }
@@ -130,6 +157,8 @@ impl super::behaviour::Conversion for Event {
             delivery_attempt: self.delivery_attempt,
             metadata: self.metadata,
             is_overall_delivery_successful: self.is_overall_delivery_successful,
+            webhook_endpoint_id: self.webhook_endpoint_id,
+            dead_lettered_at: self.dead_lettered_at,
         })
     }
 
@@ -180,6 +209,8 @@ impl super::behaviour::Conversion for Event {
             delivery_attempt: item.delivery_attempt,
             metadata: item.metadata,
             is_overall_delivery_successful: item.is_overall_delivery_successful,
+            webhook_endpoint_id: item.webhook_endpoint_id,
+            dead_lettered_at: item.dead_lettered_at,
         })
     }
 
@@ -202,6 +233,8 @@ impl super::behaviour::Conversion for Event {
             delivery_attempt: self.delivery_attempt,
             metadata: self.metadata,
             is_overall_delivery_successful: self.is_overall_delivery_successful,
+            webhook_endpoint_id: self.webhook_endpoint_id,
+            dead_lettered_at: self.dead_lettered_at,
         })
     }
 }
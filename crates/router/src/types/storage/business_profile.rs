@@ -1 +1,11 @@
 pub use diesel_models::business_profile::{Profile, ProfileNew, ProfileUpdateInternal};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct WebhookSigningSecretPromotionTrackingData {
+    pub merchant_id: common_utils::id_type::MerchantId,
+    pub profile_id: common_utils::id_type::ProfileId,
+    /// When the secret to be promoted was staged, so that a secret staged by a later rotation is
+    /// not promoted by this task
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub staged_at: time::PrimitiveDateTime,
+}
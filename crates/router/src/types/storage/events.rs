@@ -1 +1,13 @@
 pub use diesel_models::events::{Event, EventMetadata, EventNew};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DeadLetterRedeliveryTrackingData {
+    pub merchant_id: common_utils::id_type::MerchantId,
+    pub profile_id: common_utils::id_type::ProfileId,
+    /// The dead-lettered initial delivery attempts which remain to be redelivered
+    pub event_ids: Vec<String>,
+    pub deliveries_per_minute: u16,
+    pub webhook_url_override: Option<String>,
+    /// The value of the lock held for the profile until the redelivery completes
+    pub lock_value: String,
+}
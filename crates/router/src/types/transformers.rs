@@ -1952,6 +1952,7 @@ impl ForeignTryFrom<api_types::webhook_events::EventListConstraints>
                 event_classes: item.event_classes,
                 event_types: item.event_types,
                 is_delivered: item.is_delivered,
+                webhook_endpoint_id: item.webhook_endpoint_id,
             }),
         }
     }
@@ -1989,6 +1990,8 @@ impl TryFrom<domain::Event> for api_models::webhook_events::EventListItemRespons
             event_class: item.event_class,
             is_delivery_successful: item.is_overall_delivery_successful,
             initial_attempt_id,
+            webhook_endpoint_id: item.webhook_endpoint_id,
+            dead_lettered_at: item.dead_lettered_at,
             created: item.created_at,
         })
     }
@@ -2033,6 +2036,34 @@ impl TryFrom<domain::Event> for api_models::webhook_events::EventRetrieveRespons
     }
 }
 
+#[cfg(feature = "olap")]
+impl TryFrom<domain::Event> for api_models::webhook_events::EventContentResponse {
+    type Error = error_stack::Report<errors::ApiErrorResponse>;
+
+    fn try_from(item: domain::Event) -> Result<Self, Self::Error> {
+        use crate::utils::OptionExt;
+
+        // The request is populated when the event is created, unlike the response which is only
+        // populated once a delivery attempt is made.
+        let event_information =
+            api_models::webhook_events::EventListItemResponse::try_from(item.clone())?;
+
+        let request = item
+            .request
+            .get_required_value("request")
+            .change_context(errors::ApiErrorResponse::InternalServerError)?
+            .peek()
+            .parse_struct("OutgoingWebhookRequestContent")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to parse webhook event request information")?;
+
+        Ok(Self {
+            event_information,
+            request,
+        })
+    }
+}
+
 impl ForeignFrom<api_models::admin::AuthenticationConnectorDetails>
     for diesel_models::business_profile::AuthenticationConnectorDetails
 {
@@ -2098,6 +2129,16 @@ impl ForeignFrom<api_models::admin::CardTestingGuardConfig>
             },
             customer_id_blocking_threshold: item.customer_id_blocking_threshold,
             card_testing_guard_expiry: item.card_testing_guard_expiry,
+            velocity_rules: item
+                .velocity_rules
+                .into_iter()
+                .map(ForeignFrom::foreign_from)
+                .collect(),
+            allowlist: item
+                .allowlist
+                .into_iter()
+                .map(ForeignFrom::foreign_from)
+                .collect(),
         }
     }
 }
@@ -2123,6 +2164,64 @@ impl ForeignFrom<diesel_models::business_profile::CardTestingGuardConfig>
             },
             customer_id_blocking_threshold: item.customer_id_blocking_threshold,
             card_testing_guard_expiry: item.card_testing_guard_expiry,
+            velocity_rules: item
+                .velocity_rules
+                .into_iter()
+                .map(ForeignFrom::foreign_from)
+                .collect(),
+            allowlist: item
+                .allowlist
+                .into_iter()
+                .map(ForeignFrom::foreign_from)
+                .collect(),
+        }
+    }
+}
+
+impl ForeignFrom<api_models::admin::CardTestingVelocityRule>
+    for diesel_models::business_profile::CardTestingVelocityRule
+{
+    fn foreign_from(item: api_models::admin::CardTestingVelocityRule) -> Self {
+        Self {
+            dimension: item.dimension,
+            failure_threshold: item.failure_threshold,
+            window_in_minutes: item.window_in_minutes,
+            block_durations_in_minutes: item.block_durations_in_minutes,
+        }
+    }
+}
+
+impl ForeignFrom<diesel_models::business_profile::CardTestingVelocityRule>
+    for api_models::admin::CardTestingVelocityRule
+{
+    fn foreign_from(item: diesel_models::business_profile::CardTestingVelocityRule) -> Self {
+        Self {
+            dimension: item.dimension,
+            failure_threshold: item.failure_threshold,
+            window_in_minutes: item.window_in_minutes,
+            block_durations_in_minutes: item.block_durations_in_minutes,
+        }
+    }
+}
+
+impl ForeignFrom<api_models::admin::CardTestingGuardAllowlistEntry>
+    for diesel_models::business_profile::CardTestingGuardAllowlistEntry
+{
+    fn foreign_from(item: api_models::admin::CardTestingGuardAllowlistEntry) -> Self {
+        Self {
+            dimension: item.dimension,
+            value: item.value,
+        }
+    }
+}
+
+impl ForeignFrom<diesel_models::business_profile::CardTestingGuardAllowlistEntry>
+    for api_models::admin::CardTestingGuardAllowlistEntry
+{
+    fn foreign_from(item: diesel_models::business_profile::CardTestingGuardAllowlistEntry) -> Self {
+        Self {
+            dimension: item.dimension,
+            value: item.value,
         }
     }
 }
@@ -2139,6 +2238,15 @@ impl ForeignFrom<api_models::admin::WebhookDetails>
             payment_created_enabled: item.payment_created_enabled,
             payment_succeeded_enabled: item.payment_succeeded_enabled,
             payment_failed_enabled: item.payment_failed_enabled,
+            webhook_endpoints: item.webhook_endpoints.map(|endpoints| {
+                endpoints
+                    .into_iter()
+                    .map(ForeignFrom::foreign_from)
+                    .collect()
+            }),
+            // Rotations are staged through the signing secret rotation APIs only
+            signing_secret_rotation: None,
+            webhook_format: item.webhook_format,
         }
     }
 }
@@ -2155,6 +2263,41 @@ impl ForeignFrom<diesel_models::business_profile::WebhookDetails>
             payment_created_enabled: item.payment_created_enabled,
             payment_succeeded_enabled: item.payment_succeeded_enabled,
             payment_failed_enabled: item.payment_failed_enabled,
+            webhook_endpoints: item.webhook_endpoints.map(|endpoints| {
+                endpoints
+                    .into_iter()
+                    .map(ForeignFrom::foreign_from)
+                    .collect()
+            }),
+            webhook_format: item.webhook_format,
+        }
+    }
+}
+
+impl ForeignFrom<api_models::admin::WebhookEndpoint>
+    for diesel_models::business_profile::WebhookEndpoint
+{
+    fn foreign_from(item: api_models::admin::WebhookEndpoint) -> Self {
+        Self {
+            endpoint_id: item.endpoint_id,
+            url: item.url,
+            enabled_events: item.enabled_events,
+            signing_secret: item.signing_secret,
+            is_enabled: item.is_enabled,
+        }
+    }
+}
+
+impl ForeignFrom<diesel_models::business_profile::WebhookEndpoint>
+    for api_models::admin::WebhookEndpoint
+{
+    fn foreign_from(item: diesel_models::business_profile::WebhookEndpoint) -> Self {
+        Self {
+            endpoint_id: item.endpoint_id,
+            url: item.url,
+            enabled_events: item.enabled_events,
+            signing_secret: item.signing_secret,
+            is_enabled: item.is_enabled,
         }
     }
 }
@@ -2375,3 +2518,42 @@ impl ForeignFrom<card_info_types::CardInfoUpdateRequest> for storage::CardInfo {
         }
     }
 }
+
+impl ForeignFrom<storage::ProcessTracker>
+    for api_models::process_tracker::ProcessTrackerTaskResponse
+{
+    fn foreign_from(task: storage::ProcessTracker) -> Self {
+        Self {
+            id: task.id,
+            name: task.name,
+            tag: task.tag,
+            runner: task.runner,
+            retry_count: task.retry_count,
+            schedule_time: task.schedule_time,
+            tracking_data: task.tracking_data,
+            business_status: task.business_status,
+            status: task.status,
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            retry_history: task
+                .retry_history
+                .map(|retry_history| {
+                    retry_history
+                        .0
+                        .into_iter()
+                        .map(
+                            |retry| api_models::process_tracker::ProcessTrackerRetryResponse {
+                                retry_count: retry.retry_count,
+                                business_status: retry.business_status,
+                                retried_at: retry.retried_at,
+                                schedule_time: retry.schedule_time,
+                                is_forced: retry.is_forced,
+                            },
+                        )
+                        .collect()
+                })
+                .unwrap_or_default(),
+            parent_ids: task.parent_ids.unwrap_or_default(),
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::{collections::HashMap, ops::Deref, str::FromStr, sync::Arc};
 
 use api_models::enums;
 use common_utils::{date_time, errors::CustomResult, events::ApiEventMetric, ext_traits::AsyncExt};
-use currency_conversion::types::{CurrencyFactors, ExchangeRates};
+use currency_conversion::types::{ConversionResult, CurrencyFactors, ExchangeRates, RateSource};
 use error_stack::ResultExt;
 use masking::PeekInterface;
 use once_cell::sync::Lazy;
@@ -25,11 +25,17 @@ const FOREX_BASE_URL: &str = "https://openexchangerates.org/api/latest.json?app_
 const FOREX_BASE_CURRENCY: &str = "&base=USD";
 const FALLBACK_FOREX_BASE_URL: &str = "http://apilayer.net/api/live?access_key=";
 const FALLBACK_FOREX_API_CURRENCY_PREFIX: &str = "USD";
+const PRIMARY_FOREX_SOURCE: &str = "openexchangerates";
+const FALLBACK_FOREX_SOURCE: &str = "apilayer";
+const STATIC_FOREX_SOURCE: &str = "static_rates";
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct FxExchangeRatesCacheEntry {
     pub data: Arc<ExchangeRates>,
     timestamp: i64,
+    /// Provider the rates were fetched from
+    #[serde(default)]
+    source: Option<String>,
 }
 
 static FX_EXCHANGE_RATES_CACHE: Lazy<RwLock<Option<FxExchangeRatesCacheEntry>>> =
@@ -101,12 +107,19 @@ impl Deref for FloatDecimal {
 }
 
 impl FxExchangeRatesCacheEntry {
-    fn new(exchange_rate: ExchangeRates) -> Self {
+    fn new(exchange_rate: ExchangeRates, source: Option<String>) -> Self {
         Self {
             data: Arc::new(exchange_rate),
             timestamp: date_time::now_unix_timestamp(),
+            source,
         }
     }
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
     fn is_expired(&self, data_expiration_delay: u32) -> bool {
         self.timestamp + i64::from(data_expiration_delay) < date_time::now_unix_timestamp()
     }
@@ -175,6 +188,25 @@ pub async fn get_forex_rates(
     }
 }
 
+/// Fetches the forex rates from the forex APIs and stores them in redis, unless the rates are
+/// being fetched by another instance already. Used to refresh the rates ahead of their expiry,
+/// rather than when the expired rates are requested.
+#[instrument(skip_all)]
+pub async fn refresh_forex_rates(state: &SessionState) -> CustomResult<(), ForexError> {
+    let forex_api_key = state.conf.forex_api.get_inner().api_key.peek();
+    if forex_api_key.is_empty() {
+        return Err(ForexError::ConfigurationError("api_keys not provided".into()).into());
+    }
+
+    match acquire_redis_lock_and_call_forex_api(state).await {
+        Err(error) if matches!(error.current_context(), ForexError::CouldNotAcquireLock) => {
+            logger::debug!("forex_log: Forex rates are being refreshed by another instance");
+            Ok(())
+        }
+        result => result,
+    }
+}
+
 async fn call_api_if_redis_forex_data_expired(
     state: &SessionState,
     data_expiration_delay: u32,
@@ -269,7 +301,10 @@ async fn call_forex_api_if_redis_data_expired(
     match is_redis_expired(Some(redis_data.clone()).as_ref(), data_expiration_delay).await {
         Some(redis_forex) => {
             // Valid data present in redis
-            let exchange_rates = FxExchangeRatesCacheEntry::new(redis_forex.as_ref().clone());
+            let exchange_rates = FxExchangeRatesCacheEntry::new(
+                redis_forex.as_ref().clone(),
+                redis_data.source.clone(),
+            );
             logger::debug!("forex_log: forex response found in redis");
             save_forex_data_to_local_cache(exchange_rates.clone()).await?;
             Ok(exchange_rates)
@@ -341,10 +376,10 @@ async fn fetch_forex_rates_from_primary_api(
         };
     }
 
-    Ok(FxExchangeRatesCacheEntry::new(ExchangeRates::new(
-        enums::Currency::USD,
-        conversions,
-    )))
+    Ok(FxExchangeRatesCacheEntry::new(
+        ExchangeRates::new(enums::Currency::USD, conversions),
+        Some(PRIMARY_FOREX_SOURCE.to_string()),
+    ))
 }
 
 pub async fn fetch_forex_rates_from_fallback_api(
@@ -421,8 +456,10 @@ pub async fn fetch_forex_rates_from_fallback_api(
         };
     }
 
-    let rates =
-        FxExchangeRatesCacheEntry::new(ExchangeRates::new(enums::Currency::USD, conversions));
+    let rates = FxExchangeRatesCacheEntry::new(
+        ExchangeRates::new(enums::Currency::USD, conversions),
+        Some(FALLBACK_FOREX_SOURCE.to_string()),
+    );
     match acquire_redis_lock(state).await {
         Ok(_) => {
             save_forex_data_to_cache_and_redis(state, rates.clone()).await?;
@@ -519,11 +556,6 @@ pub async fn convert_currency(
     to_currency: String,
     from_currency: String,
 ) -> CustomResult<api_models::currency::CurrencyConversionResponse, ForexError> {
-    let forex_api = state.conf.forex_api.get_inner();
-    let rates = get_forex_rates(&state, forex_api.data_expiration_delay_in_seconds)
-        .await
-        .change_context(ForexError::ApiError)?;
-
     let to_currency = enums::Currency::from_str(to_currency.as_str())
         .change_context(ForexError::CurrencyNotAcceptable)
         .attach_printable("The provided currency is not acceptable")?;
@@ -532,13 +564,71 @@ pub async fn convert_currency(
         .change_context(ForexError::CurrencyNotAcceptable)
         .attach_printable("The provided currency is not acceptable")?;
 
-    let converted_amount =
-        currency_conversion::conversion::convert(&rates.data, from_currency, to_currency, amount)
-            .change_context(ForexError::ConversionError)
-            .attach_printable("Unable to perform currency conversion")?;
+    let conversion =
+        convert_currency_with_provenance(&state, amount, from_currency, to_currency).await?;
 
     Ok(api_models::currency::CurrencyConversionResponse {
-        converted_amount: converted_amount.to_string(),
+        converted_amount: conversion.amount.to_string(),
         currency: to_currency.to_string(),
+        rate: conversion.rate.to_string(),
+        rate_sources: conversion
+            .legs
+            .into_iter()
+            .map(|leg| api_models::currency::CurrencyConversionRateSource {
+                from_currency: leg.from_currency.to_string(),
+                to_currency: leg.to_currency.to_string(),
+                rate: leg.rate.to_string(),
+                source: leg.source,
+                fetched_at: leg.fetched_at,
+            })
+            .collect(),
     })
 }
+
+/// Converts `amount` using the live forex rates, falling back to the statically configured rates
+/// when the live rates are unavailable or don't cover the currencies. The result records every
+/// rate applied along with its source and fetch time.
+pub async fn convert_currency_with_provenance(
+    state: &SessionState,
+    amount: i64,
+    from_currency: enums::Currency,
+    to_currency: enums::Currency,
+) -> CustomResult<ConversionResult, ForexError> {
+    let forex_api = state.conf.forex_api.get_inner();
+    let mut sources = Vec::new();
+
+    match get_forex_rates(state, forex_api.data_expiration_delay_in_seconds).await {
+        Ok(rates) => sources.push(RateSource::new(
+            rates.source().unwrap_or(PRIMARY_FOREX_SOURCE),
+            0,
+            rates.timestamp(),
+            rates.data.as_ref().clone(),
+        )),
+        Err(error) if forex_api.static_rates.is_none() => {
+            return Err(error.change_context(ForexError::ApiError));
+        }
+        Err(error) => {
+            logger::warn!(forex_error=?error, "forex_log: live forex rates unavailable, using static rates");
+        }
+    }
+
+    if let Some(static_rates) = forex_api.static_rates.clone() {
+        let fetched_at = static_rates.timestamp;
+        sources.push(RateSource::new(
+            STATIC_FOREX_SOURCE,
+            1,
+            fetched_at,
+            ExchangeRates::try_from(static_rates)?,
+        ));
+    }
+
+    currency_conversion::conversion::convert_with_sources(
+        &sources,
+        &forex_api.rounding,
+        from_currency,
+        to_currency,
+        amount,
+    )
+    .change_context(ForexError::ConversionError)
+    .attach_printable("Unable to perform currency conversion")
+}
@@ -11,3 +11,9 @@ pub mod refund_router;
 pub mod tokenized_data;
 
 pub mod revenue_recovery;
+
+pub mod webhook_signing_secret_promotion;
+
+pub mod dead_letter_redelivery;
+
+pub mod forex_rates_refresh;
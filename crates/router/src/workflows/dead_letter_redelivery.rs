@@ -0,0 +1,82 @@
+#[cfg(feature = "v1")]
+use common_utils::ext_traits::{Encode, ValueExt};
+#[cfg(feature = "v1")]
+use diesel_models::process_tracker::business_status;
+use scheduler::{consumer, workflows::ProcessTrackerWorkflow};
+
+#[cfg(feature = "v1")]
+use crate::{
+    core::webhooks::webhook_events, logger, types::storage::DeadLetterRedeliveryTrackingData,
+};
+use crate::{errors, routes::SessionState, types::storage};
+
+/// Redelivers the dead-lettered events of a bulk redelivery request, one batch of the requested
+/// deliveries per minute at a time
+pub struct DeadLetterRedeliveryWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<SessionState> for DeadLetterRedeliveryWorkflow {
+    #[cfg(feature = "v1")]
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db = &*state.store;
+        let mut tracking_data: DeadLetterRedeliveryTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("DeadLetterRedeliveryTrackingData")?;
+
+        webhook_events::redeliver_dead_lettered_events_batch(state, &mut tracking_data).await?;
+
+        if !tracking_data.event_ids.is_empty() {
+            // Persist the remaining events, so that the events of this batch are not redelivered
+            // again, and pick the task up again for the next batch right away
+            let process_tracker_update = storage::ProcessTrackerUpdate::Update {
+                name: None,
+                retry_count: None,
+                schedule_time: Some(common_utils::date_time::now()),
+                tracking_data: Some(tracking_data.encode_to_value()?),
+                business_status: None,
+                status: Some(storage::enums::ProcessTrackerStatus::New),
+                updated_at: Some(common_utils::date_time::now()),
+            };
+            db.as_scheduler()
+                .update_process(process, process_tracker_update)
+                .await?;
+
+            return Ok(());
+        }
+
+        webhook_events::release_dead_letter_redelivery_lock(state, &tracking_data)
+            .await
+            .map_err(|error| {
+                logger::error!(?error, "Failed to release dead-letter redelivery lock")
+            })
+            .ok();
+        db.as_scheduler()
+            .finish_process_with_business_status(process, business_status::COMPLETED_BY_PT)
+            .await?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "v2")]
+    async fn execute_workflow<'a>(
+        &'a self,
+        _state: &'a SessionState,
+        _process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
@@ -0,0 +1,86 @@
+use diesel_models::process_tracker::business_status;
+use error_stack::ResultExt;
+use masking::PeekInterface;
+use scheduler::{
+    consumer,
+    recurrence::{self, ProcessTrackerRecurrence, ProcessTrackerSchedule},
+    workflows::ProcessTrackerWorkflow,
+};
+
+use crate::{
+    core::errors::RouterResult, errors, logger, routes::SessionState, types::storage,
+    utils::currency,
+};
+
+const FOREX_RATES_REFRESH_TASK_ID: &str = "FOREX_RATES_REFRESH";
+const FOREX_RATES_REFRESH_TASK: &str = "FOREX_RATES_REFRESH";
+const FOREX_RATES_REFRESH_TAG: &str = "FOREX";
+
+/// Refreshes the forex rates stored in redis on a recurring schedule, so that the rates are
+/// fetched ahead of their expiry instead of by the requests which find them expired
+pub struct ForexRatesRefreshWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<SessionState> for ForexRatesRefreshWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        currency::refresh_forex_rates(state)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to refresh forex rates")?;
+
+        // The task is rescheduled for its next occurrence
+        state
+            .store
+            .as_scheduler()
+            .finish_process_with_business_status(process, business_status::COMPLETED_BY_PT)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
+
+/// Registers the recurring task refreshing the forex rates, once every expiration delay of the
+/// rates. The task is not registered when the forex APIs are not configured.
+pub async fn register_forex_rates_refresh_task(state: &SessionState) -> RouterResult<()> {
+    let forex_api = state.conf.forex_api.get_inner();
+    if forex_api.api_key.peek().is_empty() || forex_api.data_expiration_delay_in_seconds == 0 {
+        logger::info!("Forex APIs are not configured, not registering the forex rates refresh");
+        return Ok(());
+    }
+
+    let schedule = ProcessTrackerSchedule {
+        recurrence: ProcessTrackerRecurrence::Interval {
+            interval_seconds: forex_api.data_expiration_delay_in_seconds,
+        },
+        jitter_seconds: 0,
+    };
+    let process_tracker_entry = recurrence::new_recurring_task(
+        FOREX_RATES_REFRESH_TASK_ID,
+        FOREX_RATES_REFRESH_TASK,
+        storage::ProcessTrackerRunner::ForexRatesRefreshWorkflow,
+        [FOREX_RATES_REFRESH_TAG],
+        serde_json::json!({}),
+        schedule,
+        common_types::consts::API_VERSION,
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to construct forex rates refresh task")?;
+
+    recurrence::register_recurring_task(state.store.as_scheduler(), process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to register forex rates refresh task")
+}
@@ -23,7 +23,7 @@ use scheduler::{
 use crate::core::payouts;
 use crate::{
     core::{
-        payments,
+        metrics, payments,
         webhooks::{self as webhooks_core, types::OutgoingWebhookTrackingData},
     },
     db::StorageInterface,
@@ -119,6 +119,8 @@ impl ProcessTrackerWorkflow<SessionState> for OutgoingWebhookRetryWorkflow {
             delivery_attempt: Some(delivery_attempt),
             metadata: initial_event.metadata,
             is_overall_delivery_successful: Some(false),
+            webhook_endpoint_id: initial_event.webhook_endpoint_id,
+            dead_lettered_at: None,
         };
 
         let event = db
@@ -144,6 +146,7 @@ impl ProcessTrackerWorkflow<SessionState> for OutgoingWebhookRetryWorkflow {
                     delivery_attempt,
                     None,
                     Some(process),
+                    None,
                 ))
                 .await;
             }
@@ -187,6 +190,7 @@ impl ProcessTrackerWorkflow<SessionState> for OutgoingWebhookRetryWorkflow {
                             &merchant_context,
                             outgoing_webhook,
                             &business_profile,
+                            event.webhook_endpoint_id.as_deref(),
                         )
                         .map_err(|error| {
                             logger::error!(
@@ -205,6 +209,7 @@ impl ProcessTrackerWorkflow<SessionState> for OutgoingWebhookRetryWorkflow {
                             delivery_attempt,
                             Some(content),
                             Some(process),
+                            None,
                         ))
                         .await;
                     }
@@ -324,14 +329,17 @@ pub(crate) async fn get_webhook_delivery_retry_schedule_time(
     scheduler_utils::get_time_from_delta(time_delta)
 }
 
-/// Schedule the webhook delivery task for retry
+/// Schedule the webhook delivery task for retry, or move the event to the dead-letter queue if the
+/// delivery retries have been exhausted
 #[cfg(feature = "v1")]
 #[instrument(skip_all)]
 pub(crate) async fn retry_webhook_delivery_task(
-    db: &dyn StorageInterface,
+    state: &SessionState,
+    merchant_key_store: &domain::MerchantKeyStore,
     merchant_id: &common_utils::id_type::MerchantId,
     process: storage::ProcessTracker,
 ) -> errors::CustomResult<(), errors::StorageError> {
+    let db = &*state.store;
     let schedule_time =
         get_webhook_delivery_retry_schedule_time(db, merchant_id, process.retry_count + 1).await;
 
@@ -342,13 +350,67 @@ pub(crate) async fn retry_webhook_delivery_task(
                 .await
         }
         None => {
+            let tracking_data: OutgoingWebhookTrackingData = process
+                .tracking_data
+                .clone()
+                .parse_value("OutgoingWebhookTrackingData")
+                .change_context(errors::StorageError::DeserializationFailed)?;
+
             db.as_scheduler()
                 .finish_process_with_business_status(process, business_status::RETRIES_EXCEEDED)
-                .await
+                .await?;
+
+            dead_letter_webhook_event(
+                state,
+                merchant_key_store,
+                merchant_id,
+                tracking_data.initial_attempt_id,
+            )
+            .await
         }
     }
 }
 
+/// Marks the initial attempt of the event as dead-lettered, so that the event can be listed and
+/// redelivered once the merchant's webhook endpoint has recovered
+#[cfg(feature = "v1")]
+#[instrument(skip_all)]
+async fn dead_letter_webhook_event(
+    state: &SessionState,
+    merchant_key_store: &domain::MerchantKeyStore,
+    merchant_id: &common_utils::id_type::MerchantId,
+    initial_attempt_id: Option<String>,
+) -> errors::CustomResult<(), errors::StorageError> {
+    // Tracking data inserted by old version of application does not reference the initial attempt
+    let Some(initial_attempt_id) = initial_attempt_id else {
+        logger::warn!(
+            "Initial attempt of event is unknown, skipping moving it to dead-letter queue"
+        );
+        return Ok(());
+    };
+
+    state
+        .store
+        .update_event_by_merchant_id_event_id(
+            &state.into(),
+            merchant_id,
+            &initial_attempt_id,
+            domain::EventUpdate::DeadLetterUpdate {
+                dead_lettered_at: common_utils::date_time::now(),
+            },
+            merchant_key_store,
+        )
+        .await?;
+
+    logger::info!(%initial_attempt_id, "Moved event to dead-letter queue");
+    metrics::WEBHOOK_OUTGOING_DEAD_LETTERED_COUNT.add(
+        1,
+        router_env::metric_attributes!((webhooks_core::MERCHANT_ID, merchant_id.clone())),
+    );
+
+    Ok(())
+}
+
 #[cfg(feature = "v1")]
 #[instrument(skip_all)]
 async fn get_outgoing_webhook_content_and_event_type(
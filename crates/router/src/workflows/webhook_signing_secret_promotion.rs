@@ -0,0 +1,97 @@
+#[cfg(feature = "v1")]
+use common_utils::ext_traits::ValueExt;
+#[cfg(feature = "v1")]
+use diesel_models::process_tracker::business_status;
+use scheduler::{consumer, workflows::ProcessTrackerWorkflow};
+
+#[cfg(feature = "v1")]
+use crate::{core::admin, logger, types::storage::WebhookSigningSecretPromotionTrackingData};
+use crate::{errors, routes::SessionState, types::storage};
+
+/// Promotes the staged signing secret of a business profile to its `payment_response_hash_key`
+/// once the grace period of the signing secret rotation has ended
+pub struct WebhookSigningSecretPromotionWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<SessionState> for WebhookSigningSecretPromotionWorkflow {
+    #[cfg(feature = "v1")]
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db = &*state.store;
+        let tracking_data: WebhookSigningSecretPromotionTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("WebhookSigningSecretPromotionTrackingData")?;
+
+        let key_manager_state = &state.into();
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                key_manager_state,
+                &tracking_data.merchant_id,
+                &db.get_master_key().to_vec().into(),
+            )
+            .await?;
+        let business_profile = db
+            .find_business_profile_by_profile_id(
+                key_manager_state,
+                &key_store,
+                &tracking_data.profile_id,
+            )
+            .await?;
+
+        let signing_secret_rotation = business_profile
+            .webhook_details
+            .as_ref()
+            .and_then(|webhook_details| webhook_details.signing_secret_rotation.as_ref())
+            .filter(|rotation| rotation.staged_at == tracking_data.staged_at);
+
+        match signing_secret_rotation {
+            // The staged secret was retired manually, possibly followed by another rotation
+            None => {
+                logger::info!(
+                    "Signing secret rotation of profile {:?} is no longer in progress",
+                    tracking_data.profile_id
+                );
+                db.as_scheduler()
+                    .finish_process_with_business_status(process, "PROCESS_ALREADY_COMPLETED")
+                    .await?;
+            }
+            Some(rotation) if rotation.is_in_grace_period(common_utils::date_time::now()) => {
+                let grace_period_ends_at = rotation.grace_period_ends_at;
+                db.as_scheduler()
+                    .retry_process(process, grace_period_ends_at)
+                    .await?;
+            }
+            Some(_) => {
+                admin::promote_staged_webhook_signing_secret(state, &key_store, business_profile)
+                    .await?;
+                db.as_scheduler()
+                    .finish_process_with_business_status(process, business_status::COMPLETED_BY_PT)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "v2")]
+    async fn execute_workflow<'a>(
+        &'a self,
+        _state: &'a SessionState,
+        _process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a SessionState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
@@ -50,7 +50,7 @@ pub enum Tag {
 }
 
 /// API Flow
-#[derive(Debug, Display, Clone, PartialEq, Eq)]
+#[derive(Debug, Display, Clone, PartialEq, Eq, EnumString)]
 pub enum Flow {
     /// Health check
     HealthCheck,
@@ -237,6 +237,10 @@ pub enum Flow {
     ReconVerifyToken,
     /// Routing create flow,
     RoutingCreateConfig,
+    /// Routing simulation flow
+    RoutingSimulate,
+    /// Routing fixtures run flow
+    RoutingRunFixtures,
     /// Routing link config
     RoutingLinkConfig,
     /// Routing link config
@@ -523,12 +527,38 @@ pub enum Flow {
     WebhookEventDeliveryAttemptList,
     /// Manually retry the delivery for a webhook event
     WebhookEventDeliveryRetry,
+    /// List the webhook events in the dead-letter queue
+    WebhookEventDeadLetterList,
+    /// Redeliver the webhook events in the dead-letter queue
+    WebhookEventDeadLetterRedelivery,
+    /// List the circuit breakers of the webhook endpoints of a business profile
+    WebhookCircuitBreakerList,
+    /// Retrieve a webhook event along with the request sent in the webhook
+    WebhookEventRetrieve,
     /// Retrieve status of the Poll
     RetrievePollStatus,
     /// Toggles the extended card info feature in profile level
     ToggleExtendedCardInfo,
     /// Toggles the extended card info feature in profile level
     ToggleConnectorAgnosticMit,
+    /// Stage a new signing secret for the outgoing webhooks of a profile
+    WebhookSigningSecretRotate,
+    /// Retire the current signing secret for the outgoing webhooks of a profile
+    WebhookSigningSecretRetire,
+    /// List the blocks applied by the card testing guard velocity rules of a profile
+    CardTestingGuardBlocksList,
+    /// Clear a block applied by the card testing guard velocity rules of a profile
+    CardTestingGuardBlockClear,
+    /// Create a webhook endpoint for a profile
+    WebhookEndpointCreate,
+    /// Retrieve a webhook endpoint of a profile
+    WebhookEndpointRetrieve,
+    /// Update a webhook endpoint of a profile
+    WebhookEndpointUpdate,
+    /// Delete a webhook endpoint of a profile
+    WebhookEndpointDelete,
+    /// List the webhook endpoints of a profile
+    WebhookEndpointList,
     /// Get the extended card info associated to a payment_id
     GetExtendedCardInfo,
     /// Manually update the refund details like status, error code, error message etc.
@@ -586,6 +616,20 @@ pub enum Flow {
     TotalPaymentMethodCount,
     /// Process Tracker Revenue Recovery Workflow Retrieve
     RevenueRecoveryRetrieve,
+    /// List process tracker tasks
+    ProcessTrackerTasksList,
+    /// Retrieve a process tracker task
+    ProcessTrackerTaskRetrieve,
+    /// Force a retry of a process tracker task
+    ProcessTrackerTaskRetry,
+    /// Cancel process tracker tasks
+    ProcessTrackerTasksCancel,
+    /// Pause the scheduling of the tasks of a process tracker runner
+    ProcessTrackerRunnerPause,
+    /// Resume the scheduling of the tasks of a process tracker runner
+    ProcessTrackerRunnerResume,
+    /// List the paused process tracker runners
+    ProcessTrackerPausedRunnersList,
     /// Clone Connector flow
     CloneConnector,
 }
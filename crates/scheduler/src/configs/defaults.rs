@@ -19,6 +19,7 @@ impl Default for super::settings::ProducerSettings {
             lock_key: "PRODUCER_LOCKING_KEY".into(),
             lock_ttl: 160,
             batch_size: 200,
+            dependency_reconciliation_interval: 300,
         }
     }
 }
@@ -28,6 +29,8 @@ impl Default for super::settings::ConsumerSettings {
         Self {
             disabled: false,
             consumer_group: "SCHEDULER_GROUP".into(),
+            in_flight_expiry: 900,
+            runners: std::collections::HashMap::new(),
         }
     }
 }
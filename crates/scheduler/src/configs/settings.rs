@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 pub use router_env::config::{Log, LogConsole, LogFile, LogTelemetry};
 use serde::Deserialize;
 
@@ -29,6 +31,9 @@ pub struct ProducerSettings {
     pub lock_key: String,
     pub lock_ttl: i64,
     pub batch_size: usize,
+    /// Interval at which the tasks waiting for parents which have finished are reconciled, in
+    /// seconds
+    pub dependency_reconciliation_interval: u64,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -36,4 +41,43 @@ pub struct ProducerSettings {
 pub struct ConsumerSettings {
     pub disabled: bool,
     pub consumer_group: String,
+    /// Duration after which a task stops counting towards the in flight tasks of its runner, in
+    /// seconds. Ensures that tasks lost by a consumer which went down do not hold up the runner.
+    pub in_flight_expiry: i64,
+    /// Priority class and concurrency limit of the runners, keyed by the runner name
+    pub runners: HashMap<String, RunnerSettings>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct RunnerSettings {
+    pub priority: TaskPriority,
+    /// Maximum number of tasks of the runner which are batched or executed at once, across all
+    /// the consumers
+    pub max_in_flight: Option<u64>,
+}
+
+/// Tasks of higher priority runners are batched and dispatched ahead of the others
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskPriority {
+    High,
+    #[default]
+    Normal,
+    Low,
+}
+
+impl ConsumerSettings {
+    /// Settings of the runner, runner names are matched case insensitively since configuration
+    /// keys may be lowercased
+    pub fn get_runner_settings(&self, runner: Option<&str>) -> RunnerSettings {
+        runner
+            .and_then(|runner| {
+                self.runners
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(runner))
+            })
+            .map(|(_, runner_settings)| runner_settings.clone())
+            .unwrap_or_default()
+    }
 }
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use common_utils::ext_traits::ConfigExt;
 use storage_impl::errors::ApplicationError;
 
@@ -19,6 +21,8 @@ impl super::settings::SchedulerSettings {
 
         self.producer.validate()?;
 
+        self.consumer.validate()?;
+
         self.server.validate()?;
 
         Ok(())
@@ -35,6 +39,40 @@ impl super::settings::ProducerSettings {
     }
 }
 
+impl super::settings::ConsumerSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.in_flight_expiry <= 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "consumer in flight expiry must be greater than 0".into(),
+            ))
+        })?;
+
+        self.runners
+            .iter()
+            .try_for_each(|(runner, runner_settings)| {
+                when(
+                    diesel_models::process_tracker::ProcessTrackerRunner::from_str(
+                        &runner.to_uppercase(),
+                    )
+                    .is_err(),
+                    || {
+                        Err(ApplicationError::InvalidConfigurationValueError(format!(
+                            "unknown process tracker runner {runner} in consumer runners"
+                        )))
+                    },
+                )?;
+
+                when(runner_settings.max_in_flight == Some(0), || {
+                    Err(ApplicationError::InvalidConfigurationValueError(format!(
+                        "max in flight tasks of the {runner} runner must be greater than 0"
+                    )))
+                })
+            })
+    }
+}
+
 impl super::settings::Server {
     pub fn validate(&self) -> Result<(), ApplicationError> {
         common_utils::fp_utils::when(self.host.is_default_or_empty(), || {
@@ -1,6 +1,7 @@
 // TODO: Figure out what to log
 
 use std::{
+    collections::HashMap,
     sync::{self, atomic},
     time as std_time,
 };
@@ -18,7 +19,7 @@ use router_env::{
     tracing::{self, Instrument},
 };
 use time::PrimitiveDateTime;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, Semaphore};
 use uuid::Uuid;
 
 use super::env::logger;
@@ -160,6 +161,12 @@ pub async fn consumer_operations<T: SchedulerSessionState + 'static>(
     }
     let mut handler = vec![];
 
+    // Tasks are dispatched in the order of priority, and the tasks of runners with a limit of in
+    // flight tasks wait for one of the running tasks of the runner to complete
+    pt_utils::sort_by_priority(&mut tasks, &settings.consumer);
+    let mut runner_semaphores = HashMap::new();
+    let settings = sync::Arc::new(settings.clone());
+
     for task in tasks.iter_mut() {
         let pickup_time = common_utils::date_time::now();
 
@@ -167,12 +174,41 @@ pub async fn consumer_operations<T: SchedulerSessionState + 'static>(
 
         metrics::TASK_CONSUMED.add(1, &[]);
 
-        handler.push(tokio::task::spawn(start_workflow(
-            state.clone(),
-            task.clone(),
-            pickup_time,
-            workflow_selector,
-        )))
+        let runner_semaphore = task.runner.as_ref().and_then(|runner| {
+            settings
+                .consumer
+                .get_runner_settings(Some(runner))
+                .max_in_flight
+                .map(|max_in_flight| {
+                    runner_semaphores
+                        .entry(runner.clone())
+                        .or_insert_with(|| {
+                            sync::Arc::new(Semaphore::new(
+                                usize::try_from(max_in_flight).unwrap_or(Semaphore::MAX_PERMITS),
+                            ))
+                        })
+                        .clone()
+                })
+        });
+
+        let state = state.clone();
+        let task = task.clone();
+        let settings = settings.clone();
+        handler.push(tokio::task::spawn(
+            async move {
+                let _permit = match runner_semaphore {
+                    Some(semaphore) => semaphore.acquire_owned().await.ok(),
+                    None => None,
+                };
+                let result =
+                    start_workflow(state.clone(), task.clone(), pickup_time, workflow_selector)
+                        .await;
+                pt_utils::release_in_flight_slot(state.get_db().as_scheduler(), &task, &settings)
+                    .await;
+                result
+            }
+            .in_current_span(),
+        ))
     }
     future::join_all(handler).await;
 
@@ -256,6 +292,14 @@ pub async fn consumer_error_handler(
 ) -> CustomResult<(), errors::ProcessTrackerError> {
     logger::error!(pt.name=?process.name, pt.id=%process.id, ?error, "Failed to execute workflow");
 
+    // A failed occurrence of a recurring task does not stop its later occurrences
+    if process.schedule.is_some() {
+        return state
+            .finish_process_with_business_status(process, storage::business_status::GLOBAL_ERROR)
+            .await
+            .change_context(errors::ProcessTrackerError::ProcessUpdateFailed);
+    }
+
     state
         .process_tracker_update_process_status_by_ids(
             vec![process.id],
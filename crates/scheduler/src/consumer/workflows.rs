@@ -4,7 +4,7 @@ pub use diesel_models::process_tracker as storage;
 use diesel_models::process_tracker::business_status;
 use router_env::logger;
 
-use crate::{errors, SchedulerSessionState};
+use crate::{dependencies, errors, SchedulerSessionState};
 
 pub type WorkflowSelectorFn =
     fn(&storage::ProcessTracker) -> Result<(), errors::ProcessTrackerError>;
@@ -30,6 +30,7 @@ pub trait ProcessTrackerWorkflows<T>: Send + Sync {
         T: SchedulerSessionState,
     {
         let app_state = &state.clone();
+        let process_id = process.id.clone();
         let output = operation.execute_workflow(app_state, process.clone()).await;
         match output {
             Ok(_) => operation.success_handler(app_state, process).await,
@@ -57,6 +58,14 @@ pub trait ProcessTrackerWorkflows<T>: Send + Sync {
                 }
             },
         };
+
+        if let Err(error) =
+            dependencies::resolve_dependent_tasks(app_state.get_db().as_scheduler(), &process_id)
+                .await
+        {
+            // The tasks are resolved once the producer reconciles the tasks waiting for the process
+            logger::error!(?error, "Failed to resolve tasks depending on the process");
+        }
         Ok(())
     }
 }
@@ -2,10 +2,11 @@ use common_utils::errors::CustomResult;
 pub use diesel_models as storage;
 use diesel_models::enums as storage_enums;
 use error_stack::{report, ResultExt};
+use router_env::logger;
 use storage_impl::{connection, errors, mock_db::MockDb};
 use time::PrimitiveDateTime;
 
-use crate::{metrics, scheduler::Store};
+use crate::{metrics, recurrence, scheduler::Store};
 
 #[async_trait::async_trait]
 pub trait ProcessTrackerInterface: Send + Sync + 'static {
@@ -20,6 +21,11 @@ pub trait ProcessTrackerInterface: Send + Sync + 'static {
         id: &str,
     ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError>;
 
+    async fn find_processes_by_ids(
+        &self,
+        ids: Vec<String>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+
     async fn update_process(
         &self,
         this: storage::ProcessTracker,
@@ -62,6 +68,47 @@ pub trait ProcessTrackerInterface: Send + Sync + 'static {
         status: storage_enums::ProcessTrackerStatus,
         limit: Option<i64>,
     ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+
+    async fn filter_processes(
+        &self,
+        runner: Option<String>,
+        status: Option<storage_enums::ProcessTrackerStatus>,
+        merchant_id: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+
+    async fn update_processes_by_ids_and_statuses(
+        &self,
+        task_ids: Vec<String>,
+        statuses: Vec<storage_enums::ProcessTrackerStatus>,
+        task_update: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+
+    /// Moves the tasks of the runner which are yet to be scheduled, and were due before the given
+    /// schedule time, to the given schedule time
+    async fn reschedule_overdue_processes_by_runner(
+        &self,
+        runner: String,
+        schedule_time: PrimitiveDateTime,
+    ) -> CustomResult<usize, errors::StorageError>;
+
+    async fn remove_parent_from_waiting_processes(
+        &self,
+        parent_id: &str,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+
+    async fn update_waiting_processes_by_parent_id(
+        &self,
+        parent_id: &str,
+        task_update: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+
+    async fn update_parent_failed_processes_by_parent_id(
+        &self,
+        parent_id: &str,
+        task_update: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -76,6 +123,16 @@ impl ProcessTrackerInterface for Store {
             .map_err(|error| report!(errors::StorageError::from(error)))
     }
 
+    async fn find_processes_by_ids(
+        &self,
+        ids: Vec<String>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ProcessTracker::find_processes_by_ids(&conn, ids)
+            .await
+            .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
     async fn reinitialize_limbo_processes(
         &self,
         ids: Vec<String>,
@@ -152,12 +209,14 @@ impl ProcessTrackerInterface for Store {
     ) -> CustomResult<(), errors::StorageError> {
         metrics::TASK_RETRIED.add(1, &[]);
         let retry_count = this.retry_count + 1;
+        let retry_history = this.get_retry_history_with(retry_count, schedule_time, false);
         self.update_process(
             this,
-            storage::ProcessTrackerUpdate::StatusRetryUpdate {
-                status: storage_enums::ProcessTrackerStatus::Pending,
+            storage::ProcessTrackerUpdate::RetryUpdate {
                 retry_count,
                 schedule_time,
+                business_status: None,
+                retry_history,
             },
         )
         .await?;
@@ -169,6 +228,37 @@ impl ProcessTrackerInterface for Store {
         this: storage::ProcessTracker,
         business_status: &'static str,
     ) -> CustomResult<(), errors::StorageError> {
+        // Recurring tasks are rescheduled for their next occurrence instead
+        if let Some(schedule) = this.schedule.as_ref() {
+            match recurrence::get_next_schedule_time(schedule, common_utils::date_time::now()) {
+                Ok(schedule_time) => {
+                    logger::info!(
+                        pt.id = %this.id,
+                        %business_status,
+                        %schedule_time,
+                        "Rescheduling recurring process for its next occurrence"
+                    );
+                    let process_tracker_update = recurrence::get_next_occurrence_update(
+                        &this,
+                        business_status,
+                        schedule_time,
+                    );
+                    self.update_process(this, process_tracker_update)
+                        .await
+                        .attach_printable("Failed to reschedule recurring process")?;
+                    metrics::TASK_RESCHEDULED.add(1, &[]);
+                    return Ok(());
+                }
+                Err(error) => {
+                    logger::error!(
+                        pt.id = %this.id,
+                        ?error,
+                        "Failed to get next occurrence of recurring process, finishing it"
+                    );
+                }
+            }
+        }
+
         self.update_process(
             this,
             storage::ProcessTrackerUpdate::StatusUpdate {
@@ -192,6 +282,92 @@ impl ProcessTrackerInterface for Store {
             .await
             .map_err(|error| report!(errors::StorageError::from(error)))
     }
+
+    async fn filter_processes(
+        &self,
+        runner: Option<String>,
+        status: Option<storage_enums::ProcessTrackerStatus>,
+        merchant_id: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ProcessTracker::filter_processes(&conn, runner, status, merchant_id, limit, offset)
+            .await
+            .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
+    async fn update_processes_by_ids_and_statuses(
+        &self,
+        task_ids: Vec<String>,
+        statuses: Vec<storage_enums::ProcessTrackerStatus>,
+        task_update: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::ProcessTracker::update_processes_by_ids_and_statuses(
+            &conn,
+            task_ids,
+            statuses,
+            task_update,
+        )
+        .await
+        .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
+    async fn reschedule_overdue_processes_by_runner(
+        &self,
+        runner: String,
+        schedule_time: PrimitiveDateTime,
+    ) -> CustomResult<usize, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::ProcessTracker::reschedule_overdue_processes_by_runner(
+            &conn,
+            runner,
+            schedule_time,
+        )
+        .await
+        .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
+    async fn remove_parent_from_waiting_processes(
+        &self,
+        parent_id: &str,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::ProcessTracker::remove_parent_from_waiting_processes(&conn, parent_id)
+            .await
+            .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
+    async fn update_waiting_processes_by_parent_id(
+        &self,
+        parent_id: &str,
+        task_update: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::ProcessTracker::update_waiting_processes_by_parent_id(
+            &conn,
+            parent_id,
+            task_update,
+        )
+        .await
+        .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
+    async fn update_parent_failed_processes_by_parent_id(
+        &self,
+        parent_id: &str,
+        task_update: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::ProcessTracker::update_parent_failed_processes_by_parent_id(
+            &conn,
+            parent_id,
+            task_update,
+        )
+        .await
+        .map_err(|error| report!(errors::StorageError::from(error)))
+    }
 }
 
 #[async_trait::async_trait]
@@ -211,6 +387,20 @@ impl ProcessTrackerInterface for MockDb {
         Ok(optional)
     }
 
+    async fn find_processes_by_ids(
+        &self,
+        ids: Vec<String>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        Ok(self
+            .processes
+            .lock()
+            .await
+            .iter()
+            .filter(|process| ids.contains(&process.id))
+            .cloned()
+            .collect())
+    }
+
     async fn reinitialize_limbo_processes(
         &self,
         _ids: Vec<String>,
@@ -250,6 +440,9 @@ impl ProcessTrackerInterface for MockDb {
             created_at: new.created_at,
             updated_at: new.updated_at,
             version: new.version,
+            retry_history: new.retry_history,
+            schedule: new.schedule,
+            parent_ids: new.parent_ids,
         };
         processes.push(process.clone());
         Ok(process)
@@ -257,11 +450,21 @@ impl ProcessTrackerInterface for MockDb {
 
     async fn update_process(
         &self,
-        _this: storage::ProcessTracker,
-        _process: storage::ProcessTrackerUpdate,
+        this: storage::ProcessTracker,
+        process: storage::ProcessTrackerUpdate,
     ) -> CustomResult<storage::ProcessTracker, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let mut processes = self.processes.lock().await;
+        let stored_process = processes
+            .iter_mut()
+            .find(|stored_process| stored_process.id == this.id)
+            .ok_or(errors::StorageError::ValueNotFound(format!(
+                "No process tracker task found for id = {}",
+                this.id
+            )))?;
+        *stored_process = storage::ProcessTrackerUpdateInternal::from(process)
+            .apply_changeset(stored_process.clone());
+
+        Ok(stored_process.clone())
     }
 
     async fn reset_process(
@@ -299,4 +502,159 @@ impl ProcessTrackerInterface for MockDb {
         // [#172]: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
     }
+
+    async fn filter_processes(
+        &self,
+        runner: Option<String>,
+        status: Option<storage_enums::ProcessTrackerStatus>,
+        merchant_id: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let processes = self.processes.lock().await;
+        let mut filtered_processes = processes
+            .iter()
+            .filter(|process| {
+                runner
+                    .as_ref()
+                    .map_or(true, |runner| process.runner.as_ref() == Some(runner))
+                    && status.map_or(true, |status| process.status == status)
+                    && merchant_id.as_ref().map_or(true, |merchant_id| {
+                        process
+                            .tracking_data
+                            .get("merchant_id")
+                            .and_then(|value| value.as_str())
+                            == Some(merchant_id.as_str())
+                    })
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        filtered_processes.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        Ok(filtered_processes
+            .into_iter()
+            .skip(usize::try_from(offset).unwrap_or_default())
+            .take(usize::try_from(limit).unwrap_or_default())
+            .collect())
+    }
+
+    async fn update_processes_by_ids_and_statuses(
+        &self,
+        task_ids: Vec<String>,
+        statuses: Vec<storage_enums::ProcessTrackerStatus>,
+        task_update: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let mut processes = self.processes.lock().await;
+        let task_update = storage::ProcessTrackerUpdateInternal::from(task_update);
+
+        Ok(processes
+            .iter_mut()
+            .filter(|process| task_ids.contains(&process.id) && statuses.contains(&process.status))
+            .map(|process| {
+                *process = task_update.clone().apply_changeset(process.clone());
+                process.clone()
+            })
+            .collect())
+    }
+
+    async fn reschedule_overdue_processes_by_runner(
+        &self,
+        runner: String,
+        schedule_time: PrimitiveDateTime,
+    ) -> CustomResult<usize, errors::StorageError> {
+        let mut processes = self.processes.lock().await;
+        let now = common_utils::date_time::now();
+
+        Ok(processes
+            .iter_mut()
+            .filter(|process| {
+                process.runner.as_ref() == Some(&runner)
+                    && matches!(
+                        process.status,
+                        storage_enums::ProcessTrackerStatus::New
+                            | storage_enums::ProcessTrackerStatus::Pending
+                    )
+                    && process
+                        .schedule_time
+                        .is_some_and(|process_schedule_time| process_schedule_time < schedule_time)
+            })
+            .map(|process| {
+                process.schedule_time = Some(schedule_time);
+                process.updated_at = now;
+            })
+            .count())
+    }
+
+    async fn remove_parent_from_waiting_processes(
+        &self,
+        parent_id: &str,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let mut processes = self.processes.lock().await;
+        let now = common_utils::date_time::now();
+
+        Ok(processes
+            .iter_mut()
+            .filter(|process| {
+                process.status == storage_enums::ProcessTrackerStatus::Waiting
+                    && has_parent(process, parent_id)
+            })
+            .map(|process| {
+                if let Some(parent_ids) = process.parent_ids.as_mut() {
+                    parent_ids.retain(|id| id != parent_id);
+                }
+                process.updated_at = now;
+                process.clone()
+            })
+            .collect())
+    }
+
+    async fn update_waiting_processes_by_parent_id(
+        &self,
+        parent_id: &str,
+        task_update: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let mut processes = self.processes.lock().await;
+        let task_update = storage::ProcessTrackerUpdateInternal::from(task_update);
+
+        Ok(processes
+            .iter_mut()
+            .filter(|process| {
+                process.status == storage_enums::ProcessTrackerStatus::Waiting
+                    && has_parent(process, parent_id)
+            })
+            .map(|process| {
+                *process = task_update.clone().apply_changeset(process.clone());
+                process.clone()
+            })
+            .collect())
+    }
+
+    async fn update_parent_failed_processes_by_parent_id(
+        &self,
+        parent_id: &str,
+        task_update: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let mut processes = self.processes.lock().await;
+        let task_update = storage::ProcessTrackerUpdateInternal::from(task_update);
+
+        Ok(processes
+            .iter_mut()
+            .filter(|process| {
+                process.status == storage_enums::ProcessTrackerStatus::Finish
+                    && process.business_status == storage::business_status::PARENT_FAILED
+                    && has_parent(process, parent_id)
+            })
+            .map(|process| {
+                *process = task_update.clone().apply_changeset(process.clone());
+                process.clone()
+            })
+            .collect())
+    }
+}
+
+fn has_parent(process: &storage::ProcessTracker, parent_id: &str) -> bool {
+    process
+        .parent_ids
+        .as_ref()
+        .is_some_and(|parent_ids| parent_ids.iter().any(|id| id == parent_id))
 }
@@ -1,11 +1,70 @@
+use std::collections::HashMap;
+
 use common_utils::errors::CustomResult;
 use diesel_models::process_tracker as storage;
-use redis_interface::{errors::RedisError, RedisEntryId, SetnxReply};
+use error_stack::ResultExt;
+use redis_interface::{errors::RedisError, RedisEntryId, RedisKey, SetnxReply};
 use router_env::logger;
 use storage_impl::{mock_db::MockDb, redis::kv_store::RedisConnInterface};
+use time::PrimitiveDateTime;
 
 use crate::{errors::ProcessTrackerError, scheduler::Store};
 
+/// Hash holding the runners whose tasks are not being scheduled by the producer
+const PAUSED_RUNNERS_KEY: &str = "SCHEDULER_PAUSED_RUNNERS";
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct PausedRunner {
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub paused_at: PrimitiveDateTime,
+    pub reason: Option<String>,
+}
+
+/// Reserves slots for as many of the tasks as the limit allows, the slots of tasks which expired
+/// are freed first. Returns the tasks which got a slot.
+///
+/// KEYS: in flight tasks
+/// ARGV: max in flight tasks, slot expiry in seconds, followed by the task ids
+const RESERVE_IN_FLIGHT_SLOTS_SCRIPT: &str = r#"
+local now = tonumber(redis.call('TIME')[1])
+redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', now)
+
+local available = tonumber(ARGV[1]) - redis.call('ZCARD', KEYS[1])
+local reserved = {}
+for index = 3, #ARGV do
+    if available <= 0 then
+        break
+    end
+    if redis.call('ZADD', KEYS[1], now + tonumber(ARGV[2]), ARGV[index]) == 1 then
+        available = available - 1
+    end
+    reserved[#reserved + 1] = ARGV[index]
+end
+redis.call('EXPIRE', KEYS[1], ARGV[2])
+return reserved
+"#;
+
+/// KEYS: in flight tasks
+/// ARGV: task ids
+const RELEASE_IN_FLIGHT_SLOTS_SCRIPT: &str = r#"
+return redis.call('ZREM', KEYS[1], unpack(ARGV))
+"#;
+
+/// The hash is written through scripts, as setting hash fields using the connection pool also
+/// sets an expiry on the hash
+///
+/// KEYS: paused runners
+/// ARGV: runner, paused runner details
+const PAUSE_RUNNER_SCRIPT: &str = r#"
+return redis.call('HSETNX', KEYS[1], ARGV[1], ARGV[2])
+"#;
+
+/// KEYS: paused runners
+/// ARGV: runner
+const RESUME_RUNNER_SCRIPT: &str = r#"
+return redis.call('HDEL', KEYS[1], ARGV[1])
+"#;
+
 #[async_trait::async_trait]
 pub trait QueueInterface {
     async fn fetch_consumer_tasks(
@@ -40,6 +99,35 @@ pub trait QueueInterface {
     ) -> CustomResult<(), RedisError>;
 
     async fn get_key(&self, key: &str) -> CustomResult<Vec<u8>, RedisError>;
+
+    /// Reserves in flight slots for the tasks, up to `max_in_flight` tasks in total, returns the
+    /// ids of the tasks which got a slot
+    async fn reserve_in_flight_slots(
+        &self,
+        key: &str,
+        task_ids: Vec<String>,
+        max_in_flight: u64,
+        expiry: i64,
+    ) -> CustomResult<Vec<String>, RedisError>;
+
+    async fn release_in_flight_slots(
+        &self,
+        key: &str,
+        task_ids: Vec<String>,
+    ) -> CustomResult<(), RedisError>;
+
+    /// Stops the producer from scheduling the tasks of the runner, returns false if the runner is
+    /// paused already
+    async fn pause_runner(
+        &self,
+        runner: &str,
+        paused_runner: PausedRunner,
+    ) -> CustomResult<bool, RedisError>;
+
+    /// Returns false if the runner is not paused
+    async fn resume_runner(&self, runner: &str) -> CustomResult<bool, RedisError>;
+
+    async fn get_paused_runners(&self) -> CustomResult<HashMap<String, PausedRunner>, RedisError>;
 }
 
 #[async_trait::async_trait]
@@ -132,6 +220,40 @@ impl QueueInterface for Store {
     async fn get_key(&self, key: &str) -> CustomResult<Vec<u8>, RedisError> {
         self.get_redis_conn()?.get_key::<Vec<u8>>(&key.into()).await
     }
+
+    async fn reserve_in_flight_slots(
+        &self,
+        key: &str,
+        task_ids: Vec<String>,
+        max_in_flight: u64,
+        expiry: i64,
+    ) -> CustomResult<Vec<String>, RedisError> {
+        reserve_in_flight_slots(self, key, task_ids, max_in_flight, expiry).await
+    }
+
+    async fn release_in_flight_slots(
+        &self,
+        key: &str,
+        task_ids: Vec<String>,
+    ) -> CustomResult<(), RedisError> {
+        release_in_flight_slots(self, key, task_ids).await
+    }
+
+    async fn pause_runner(
+        &self,
+        runner: &str,
+        paused_runner: PausedRunner,
+    ) -> CustomResult<bool, RedisError> {
+        pause_runner(self, runner, paused_runner).await
+    }
+
+    async fn resume_runner(&self, runner: &str) -> CustomResult<bool, RedisError> {
+        resume_runner(self, runner).await
+    }
+
+    async fn get_paused_runners(&self) -> CustomResult<HashMap<String, PausedRunner>, RedisError> {
+        get_paused_runners(self).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -187,4 +309,129 @@ impl QueueInterface for MockDb {
     async fn get_key(&self, _key: &str) -> CustomResult<Vec<u8>, RedisError> {
         Err(RedisError::RedisConnectionError.into())
     }
+
+    async fn reserve_in_flight_slots(
+        &self,
+        key: &str,
+        task_ids: Vec<String>,
+        max_in_flight: u64,
+        expiry: i64,
+    ) -> CustomResult<Vec<String>, RedisError> {
+        reserve_in_flight_slots(self, key, task_ids, max_in_flight, expiry).await
+    }
+
+    async fn release_in_flight_slots(
+        &self,
+        key: &str,
+        task_ids: Vec<String>,
+    ) -> CustomResult<(), RedisError> {
+        release_in_flight_slots(self, key, task_ids).await
+    }
+
+    async fn pause_runner(
+        &self,
+        runner: &str,
+        paused_runner: PausedRunner,
+    ) -> CustomResult<bool, RedisError> {
+        pause_runner(self, runner, paused_runner).await
+    }
+
+    async fn resume_runner(&self, runner: &str) -> CustomResult<bool, RedisError> {
+        resume_runner(self, runner).await
+    }
+
+    async fn get_paused_runners(&self) -> CustomResult<HashMap<String, PausedRunner>, RedisError> {
+        get_paused_runners(self).await
+    }
+}
+
+async fn reserve_in_flight_slots(
+    db: &(dyn RedisConnInterface + Sync),
+    key: &str,
+    task_ids: Vec<String>,
+    max_in_flight: u64,
+    expiry: i64,
+) -> CustomResult<Vec<String>, RedisError> {
+    if task_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let conn = db.get_redis_conn()?;
+    let mut args = vec![max_in_flight.to_string(), expiry.to_string()];
+    args.extend(task_ids);
+
+    conn.evaluate_redis_script::<_, Vec<String>>(
+        RESERVE_IN_FLIGHT_SLOTS_SCRIPT,
+        vec![RedisKey::from(key).tenant_aware_key(&conn)],
+        args,
+    )
+    .await
+}
+
+async fn release_in_flight_slots(
+    db: &(dyn RedisConnInterface + Sync),
+    key: &str,
+    task_ids: Vec<String>,
+) -> CustomResult<(), RedisError> {
+    if task_ids.is_empty() {
+        return Ok(());
+    }
+
+    let conn = db.get_redis_conn()?;
+
+    conn.evaluate_redis_script::<_, u64>(
+        RELEASE_IN_FLIGHT_SLOTS_SCRIPT,
+        vec![RedisKey::from(key).tenant_aware_key(&conn)],
+        task_ids,
+    )
+    .await
+    .map(|_| ())
+}
+
+async fn pause_runner(
+    db: &(dyn RedisConnInterface + Sync),
+    runner: &str,
+    paused_runner: PausedRunner,
+) -> CustomResult<bool, RedisError> {
+    let conn = db.get_redis_conn()?;
+    let paused_runner = serde_json::to_string(&paused_runner)
+        .change_context(RedisError::JsonSerializationFailed)?;
+
+    conn.evaluate_redis_script::<_, u64>(
+        PAUSE_RUNNER_SCRIPT,
+        vec![RedisKey::from(PAUSED_RUNNERS_KEY).tenant_aware_key(&conn)],
+        vec![runner.to_owned(), paused_runner],
+    )
+    .await
+    .map(|fields_set| fields_set > 0)
+}
+
+async fn resume_runner(
+    db: &(dyn RedisConnInterface + Sync),
+    runner: &str,
+) -> CustomResult<bool, RedisError> {
+    let conn = db.get_redis_conn()?;
+
+    conn.evaluate_redis_script::<_, u64>(
+        RESUME_RUNNER_SCRIPT,
+        vec![RedisKey::from(PAUSED_RUNNERS_KEY).tenant_aware_key(&conn)],
+        vec![runner.to_owned()],
+    )
+    .await
+    .map(|fields_deleted| fields_deleted > 0)
+}
+
+async fn get_paused_runners(
+    db: &(dyn RedisConnInterface + Sync),
+) -> CustomResult<HashMap<String, PausedRunner>, RedisError> {
+    db.get_redis_conn()?
+        .get_hash_fields::<HashMap<String, String>>(&PAUSED_RUNNERS_KEY.into())
+        .await?
+        .into_iter()
+        .map(|(runner, paused_runner)| {
+            serde_json::from_str::<PausedRunner>(&paused_runner)
+                .change_context(RedisError::JsonDeserializationFailed)
+                .map(|paused_runner| (runner, paused_runner))
+        })
+        .collect()
 }
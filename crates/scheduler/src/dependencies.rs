@@ -0,0 +1,474 @@
+//! Dependencies between process tracker tasks
+//!
+//! A task can wait for one or more parent tasks, it is created with the `Waiting` status and the
+//! IDs of its parents. Every time a parent succeeds it is removed from the parents of the waiting
+//! tasks, and the tasks left without parents are released for execution, so a task depending on
+//! several parents acts as a barrier. When a parent fails, the tasks waiting for it are finished
+//! without being executed, along with the tasks depending on them in turn. Such tasks are restored
+//! to wait for the parent again when the failed parent is retried.
+//!
+//! The tasks waiting for a parent are resolved right after the workflow of the parent is done with
+//! it. As the parent is finished by its workflow separately, the producer periodically reconciles
+//! the waiting tasks whose parents have finished, in case their resolution did not go through.
+//!
+//! Whether a finished parent failed is only ever decided by its business status, see
+//! [`storage::business_status::is_failure`].
+
+use std::collections::HashSet;
+
+use common_utils::errors::CustomResult;
+use diesel_models::{enums::ProcessTrackerStatus, process_tracker as storage};
+use error_stack::ResultExt;
+use router_env::{instrument, logger, tracing};
+
+use crate::{errors, SchedulerInterface};
+
+/// Creates a task which is executed once all the parent tasks succeed, parents which have finished
+/// already are accounted for right away
+#[instrument(skip_all)]
+pub async fn create_dependent_task<T>(
+    db: &T,
+    process_tracker_entry: storage::ProcessTrackerNew,
+    parent_ids: Vec<String>,
+) -> CustomResult<storage::ProcessTracker, errors::ProcessTrackerError>
+where
+    T: SchedulerInterface + Send + Sync + ?Sized,
+{
+    let task = db
+        .insert_process(process_tracker_entry.with_parent_ids(parent_ids.clone()))
+        .await
+        .change_context(errors::ProcessTrackerError::ProcessUpdateFailed)
+        .attach_printable("Failed to insert dependent task")?;
+
+    // Parents finishing in the meantime are accounted for by both this and their own resolution,
+    // which is harmless as the waiting tasks are only ever updated once
+    let parents = db
+        .find_processes_by_ids(parent_ids.clone())
+        .await
+        .change_context(errors::ProcessTrackerError::ProcessFetchingFailed)?;
+    for parent_id in parent_ids
+        .iter()
+        .filter(|parent_id| !parents.iter().any(|parent| parent.id == **parent_id))
+    {
+        logger::warn!(%parent_id, task_id = %task.id, "Parent of the task does not exist");
+    }
+    for parent in parents {
+        resolve_finished_parent(db, &parent).await?;
+    }
+
+    Ok(task)
+}
+
+/// Releases or fails the tasks waiting for the process, once the workflow of the process is done
+/// with it. Processes which were not finished by their workflow, such as the ones scheduled for a
+/// retry or recurring processes, are left alone.
+#[instrument(skip_all)]
+pub async fn resolve_dependent_tasks<T>(
+    db: &T,
+    process_id: &str,
+) -> CustomResult<(), errors::ProcessTrackerError>
+where
+    T: SchedulerInterface + Send + Sync + ?Sized,
+{
+    match db
+        .find_process_by_id(process_id)
+        .await
+        .change_context(errors::ProcessTrackerError::ProcessFetchingFailed)?
+    {
+        Some(process) => resolve_finished_parent(db, &process).await,
+        None => Ok(()),
+    }
+}
+
+/// Resolves the tasks waiting for parents which have finished already, in case the resolution
+/// which follows the parent finishing did not go through. The waiting tasks are gone through in
+/// batches, and the parents of each batch are fetched at once. Returns the number of finished
+/// parents whose waiting tasks were resolved. Run by the producer under its lock, so that the
+/// producers do not resolve the same tasks concurrently.
+#[instrument(skip_all)]
+pub async fn reconcile_dependent_tasks<T>(
+    db: &T,
+    batch_size: usize,
+) -> CustomResult<usize, errors::ProcessTrackerError>
+where
+    T: SchedulerInterface + Send + Sync + ?Sized,
+{
+    let batch_size = batch_size.max(1);
+    let limit = i64::try_from(batch_size).unwrap_or(i64::MAX);
+    let mut offset = 0;
+    let mut checked_parent_ids = HashSet::new();
+    let mut resolved_parent_count = 0;
+
+    loop {
+        let waiting_tasks = db
+            .filter_processes(
+                None,
+                Some(ProcessTrackerStatus::Waiting),
+                None,
+                limit,
+                offset,
+            )
+            .await
+            .change_context(errors::ProcessTrackerError::ProcessFetchingFailed)?;
+        let is_last_batch = waiting_tasks.len() < batch_size;
+
+        let parent_ids = waiting_tasks
+            .into_iter()
+            .flat_map(|task| task.parent_ids.unwrap_or_default())
+            .filter(|parent_id| checked_parent_ids.insert(parent_id.clone()))
+            .collect::<Vec<_>>();
+        let parents = if parent_ids.is_empty() {
+            Vec::new()
+        } else {
+            db.find_processes_by_ids(parent_ids)
+                .await
+                .change_context(errors::ProcessTrackerError::ProcessFetchingFailed)?
+        };
+
+        for parent in parents
+            .into_iter()
+            .filter(|parent| parent.status == ProcessTrackerStatus::Finish)
+        {
+            logger::warn!(parent_id = %parent.id, "Resolving tasks waiting for a finished parent");
+            resolve_finished_parent(db, &parent).await?;
+            resolved_parent_count += 1;
+        }
+
+        if is_last_batch {
+            break;
+        }
+        // Tasks resolved in the meantime shift the batches, the tasks skipped due to this are
+        // reconciled in the next run
+        offset = offset.saturating_add(limit);
+    }
+
+    Ok(resolved_parent_count)
+}
+
+/// Releases or fails the tasks waiting for the parent using the business status of the parent,
+/// parents which have not finished are left alone
+async fn resolve_finished_parent<T>(
+    db: &T,
+    parent: &storage::ProcessTracker,
+) -> CustomResult<(), errors::ProcessTrackerError>
+where
+    T: SchedulerInterface + Send + Sync + ?Sized,
+{
+    if parent.status != ProcessTrackerStatus::Finish {
+        return Ok(());
+    }
+
+    if is_failed_parent(parent) {
+        fail_dependent_tasks(db, &parent.id).await
+    } else {
+        release_dependent_tasks(db, &parent.id).await
+    }
+}
+
+/// Whether the parent finished with a failure, the tasks waiting for a failed parent are finished
+/// without being executed
+fn is_failed_parent(parent: &storage::ProcessTracker) -> bool {
+    parent.status == ProcessTrackerStatus::Finish
+        && storage::business_status::is_failure(&parent.business_status)
+}
+
+/// Removes the parent from the parents of the tasks waiting for it, and schedules the tasks which
+/// are not waiting for other parents
+#[instrument(skip_all)]
+pub async fn release_dependent_tasks<T>(
+    db: &T,
+    parent_id: &str,
+) -> CustomResult<(), errors::ProcessTrackerError>
+where
+    T: SchedulerInterface + Send + Sync + ?Sized,
+{
+    let tasks = db
+        .remove_parent_from_waiting_processes(parent_id)
+        .await
+        .change_context(errors::ProcessTrackerError::ProcessUpdateFailed)?;
+
+    let now = common_utils::date_time::now();
+    for task in tasks {
+        if task
+            .parent_ids
+            .as_ref()
+            .is_some_and(|parent_ids| !parent_ids.is_empty())
+        {
+            continue;
+        }
+
+        logger::info!(%parent_id, task_id = %task.id, "Releasing task waiting for its parents");
+        // Tasks scheduled before their parents finished are picked up right away
+        let schedule_time = task.schedule_time.unwrap_or(now).max(now);
+        let retry_count = task.retry_count;
+        db.update_process(
+            task,
+            storage::ProcessTrackerUpdate::StatusRetryUpdate {
+                status: ProcessTrackerStatus::New,
+                retry_count,
+                schedule_time,
+            },
+        )
+        .await
+        .change_context(errors::ProcessTrackerError::ProcessUpdateFailed)?;
+    }
+
+    Ok(())
+}
+
+/// Finishes the tasks waiting for the failed parent without executing them, along with the tasks
+/// depending on them in turn
+#[instrument(skip_all)]
+pub async fn fail_dependent_tasks<T>(
+    db: &T,
+    parent_id: &str,
+) -> CustomResult<(), errors::ProcessTrackerError>
+where
+    T: SchedulerInterface + Send + Sync + ?Sized,
+{
+    let mut failed_parent_ids = vec![parent_id.to_owned()];
+
+    while let Some(failed_parent_id) = failed_parent_ids.pop() {
+        let failed_tasks = db
+            .update_waiting_processes_by_parent_id(
+                &failed_parent_id,
+                storage::ProcessTrackerUpdate::StatusUpdate {
+                    status: ProcessTrackerStatus::Finish,
+                    business_status: Some(String::from(storage::business_status::PARENT_FAILED)),
+                },
+            )
+            .await
+            .change_context(errors::ProcessTrackerError::ProcessUpdateFailed)?;
+
+        for task in failed_tasks {
+            logger::info!(
+                parent_id = %failed_parent_id,
+                task_id = %task.id,
+                "Finishing task as its parent failed"
+            );
+            failed_parent_ids.push(task.id);
+        }
+    }
+
+    Ok(())
+}
+
+/// Restores the tasks which were finished without being executed as the parent failed, along with
+/// the tasks depending on them in turn, so that they wait for the parent again. Called before the
+/// failed parent is retried. The restored tasks which are waiting for other parents which failed
+/// as well are finished again.
+#[instrument(skip_all)]
+pub async fn restore_dependent_tasks<T>(
+    db: &T,
+    parent_id: &str,
+) -> CustomResult<(), errors::ProcessTrackerError>
+where
+    T: SchedulerInterface + Send + Sync + ?Sized,
+{
+    let mut restored_parent_ids = vec![parent_id.to_owned()];
+    let mut other_parent_ids = Vec::new();
+
+    while let Some(restored_parent_id) = restored_parent_ids.pop() {
+        let restored_tasks = db
+            .update_parent_failed_processes_by_parent_id(
+                &restored_parent_id,
+                storage::ProcessTrackerUpdate::StatusUpdate {
+                    status: ProcessTrackerStatus::Waiting,
+                    business_status: Some(String::from(storage::business_status::PENDING)),
+                },
+            )
+            .await
+            .change_context(errors::ProcessTrackerError::ProcessUpdateFailed)?;
+
+        for task in restored_tasks {
+            logger::info!(
+                parent_id = %restored_parent_id,
+                task_id = %task.id,
+                "Restoring task as its parent is retried"
+            );
+            other_parent_ids.extend(
+                task.parent_ids
+                    .iter()
+                    .flatten()
+                    .filter(|task_parent_id| **task_parent_id != restored_parent_id)
+                    .cloned(),
+            );
+            restored_parent_ids.push(task.id);
+        }
+    }
+
+    if other_parent_ids.is_empty() {
+        return Ok(());
+    }
+    other_parent_ids.sort();
+    other_parent_ids.dedup();
+    let other_parents = db
+        .find_processes_by_ids(other_parent_ids)
+        .await
+        .change_context(errors::ProcessTrackerError::ProcessFetchingFailed)?;
+    for other_parent in other_parents
+        .iter()
+        .filter(|other_parent| is_failed_parent(other_parent))
+    {
+        fail_dependent_tasks(db, &other_parent.id).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use diesel_models::process_tracker::business_status;
+    use redis_interface::RedisSettings;
+    use storage_impl::mock_db::MockDb;
+
+    use super::*;
+    use crate::db::process_tracker::ProcessTrackerInterface;
+
+    async fn mock_db() -> MockDb {
+        MockDb::new(&RedisSettings::default())
+            .await
+            .expect("Failed to create mock DB")
+    }
+
+    fn new_task(task_id: &str) -> storage::ProcessTrackerNew {
+        storage::ProcessTrackerNew::new(
+            task_id,
+            "TEST_TASK",
+            storage::ProcessTrackerRunner::PaymentsSyncWorkflow,
+            ["TEST"],
+            serde_json::json!({}),
+            None,
+            common_utils::date_time::now(),
+            storage::ApiVersion::V1,
+        )
+        .unwrap()
+    }
+
+    async fn insert_task(db: &MockDb, task_id: &str) {
+        db.insert_process(new_task(task_id)).await.unwrap();
+    }
+
+    async fn create_child(db: &MockDb, task_id: &str, parent_ids: &[&str]) {
+        create_dependent_task(
+            db,
+            new_task(task_id),
+            parent_ids.iter().map(|id| id.to_string()).collect(),
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn get_task(db: &MockDb, task_id: &str) -> storage::ProcessTracker {
+        db.find_process_by_id(task_id).await.unwrap().unwrap()
+    }
+
+    async fn finish_task(db: &MockDb, task_id: &str, task_business_status: &str) {
+        let task = get_task(db, task_id).await;
+        db.update_process(
+            task,
+            storage::ProcessTrackerUpdate::StatusUpdate {
+                status: ProcessTrackerStatus::Finish,
+                business_status: Some(task_business_status.to_string()),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn finish_and_resolve(db: &MockDb, task_id: &str, task_business_status: &str) {
+        finish_task(db, task_id, task_business_status).await;
+        resolve_dependent_tasks(db, task_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_task_waiting_for_parents_is_released_once_all_parents_succeed() {
+        let db = mock_db().await;
+        insert_task(&db, "parent_1").await;
+        insert_task(&db, "parent_2").await;
+        create_child(&db, "child", &["parent_1", "parent_2"]).await;
+
+        let child = get_task(&db, "child").await;
+        assert_eq!(child.status, ProcessTrackerStatus::Waiting);
+
+        finish_and_resolve(&db, "parent_1", business_status::COMPLETED_BY_PT).await;
+        let child = get_task(&db, "child").await;
+        assert_eq!(child.status, ProcessTrackerStatus::Waiting);
+        assert_eq!(child.parent_ids, Some(vec!["parent_2".to_string()]));
+
+        finish_and_resolve(&db, "parent_2", business_status::COMPLETED_BY_PT).await;
+        let child = get_task(&db, "child").await;
+        assert_eq!(child.status, ProcessTrackerStatus::New);
+        assert_eq!(child.parent_ids, Some(vec![]));
+    }
+
+    #[tokio::test]
+    async fn test_failed_parent_fails_dependent_tasks_in_turn() {
+        let db = mock_db().await;
+        insert_task(&db, "parent").await;
+        insert_task(&db, "other_parent").await;
+        create_child(&db, "child", &["parent", "other_parent"]).await;
+        create_child(&db, "grandchild", &["child"]).await;
+
+        finish_and_resolve(&db, "parent", business_status::RETRIES_EXCEEDED).await;
+
+        for task_id in ["child", "grandchild"] {
+            let task = get_task(&db, task_id).await;
+            assert_eq!(task.status, ProcessTrackerStatus::Finish);
+            assert_eq!(task.business_status, business_status::PARENT_FAILED);
+        }
+
+        // The other parent succeeding does not release the failed tasks
+        finish_and_resolve(&db, "other_parent", business_status::COMPLETED_BY_PT).await;
+        let child = get_task(&db, "child").await;
+        assert_eq!(child.status, ProcessTrackerStatus::Finish);
+        assert_eq!(child.business_status, business_status::PARENT_FAILED);
+    }
+
+    #[tokio::test]
+    async fn test_parent_finished_before_dependent_task_is_created() {
+        let db = mock_db().await;
+        insert_task(&db, "succeeded_parent").await;
+        finish_and_resolve(&db, "succeeded_parent", business_status::COMPLETED_BY_PT).await;
+        insert_task(&db, "failed_parent").await;
+        finish_and_resolve(&db, "failed_parent", business_status::FAILURE).await;
+
+        create_child(&db, "released_child", &["succeeded_parent"]).await;
+        let child = get_task(&db, "released_child").await;
+        assert_eq!(child.status, ProcessTrackerStatus::New);
+
+        create_child(&db, "failed_child", &["succeeded_parent", "failed_parent"]).await;
+        let child = get_task(&db, "failed_child").await;
+        assert_eq!(child.status, ProcessTrackerStatus::Finish);
+        assert_eq!(child.business_status, business_status::PARENT_FAILED);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_dependent_tasks_of_finished_parents() {
+        let db = mock_db().await;
+        insert_task(&db, "finished_parent").await;
+        insert_task(&db, "pending_parent").await;
+        create_child(&db, "released_child", &["finished_parent"]).await;
+        create_child(&db, "waiting_child", &["pending_parent"]).await;
+
+        // The parent finishes without its waiting tasks being resolved
+        finish_task(&db, "finished_parent", business_status::COMPLETED_BY_PT).await;
+        assert_eq!(
+            get_task(&db, "released_child").await.status,
+            ProcessTrackerStatus::Waiting
+        );
+
+        let resolved_parent_count = reconcile_dependent_tasks(&db, 1).await.unwrap();
+        assert_eq!(resolved_parent_count, 1);
+        assert_eq!(
+            get_task(&db, "released_child").await.status,
+            ProcessTrackerStatus::New
+        );
+        assert_eq!(
+            get_task(&db, "waiting_child").await.status,
+            ProcessTrackerStatus::Waiting
+        );
+    }
+}
@@ -12,3 +12,5 @@ counter_metric!(TASK_CONSUMED, PT_METER); // Tasks consumed by consumer
 counter_metric!(TASK_PROCESSED, PT_METER); // Tasks completed processing
 counter_metric!(TASK_FINISHED, PT_METER); // Tasks finished
 counter_metric!(TASK_RETRIED, PT_METER); // Tasks added for retries
+counter_metric!(TASKS_THROTTLED_COUNT, PT_METER); // Tasks held back by the in flight limit of their runner
+counter_metric!(TASK_RESCHEDULED, PT_METER); // Recurring tasks rescheduled for their next occurrence
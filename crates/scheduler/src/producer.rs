@@ -15,7 +15,7 @@ use super::{
     metrics,
 };
 use crate::{
-    configs::settings::SchedulerSettings, errors, flow::SchedulerFlow,
+    configs::settings::SchedulerSettings, dependencies, errors, flow::SchedulerFlow,
     scheduler::SchedulerInterface, utils::*, SchedulerAppState, SchedulerSessionState,
 };
 
@@ -63,14 +63,32 @@ where
     let task_handle =
         tokio::spawn(common_utils::signals::signal_handler(signal, tx).in_current_span());
 
+    let dependency_reconciliation_interval = Duration::from_secs(
+        scheduler_settings
+            .producer
+            .dependency_reconciliation_interval,
+    );
+    let mut last_dependency_reconciliation: Option<std::time::Instant> = None;
+
     loop {
         match rx.try_recv() {
             Err(mpsc::error::TryRecvError::Empty) => {
                 interval.tick().await;
+                let should_reconcile_dependencies =
+                    !last_dependency_reconciliation.is_some_and(|last_dependency_reconciliation| {
+                        last_dependency_reconciliation.elapsed()
+                            < dependency_reconciliation_interval
+                    });
                 let tenants = state.get_tenants();
                 for tenant in tenants {
                     let session_state = app_state_to_session_state(state, &tenant)?;
-                    match run_producer_flow(&session_state, &scheduler_settings).await {
+                    match run_producer_flow(
+                        &session_state,
+                        &scheduler_settings,
+                        should_reconcile_dependencies,
+                    )
+                    .await
+                    {
                         Ok(_) => (),
                         Err(error) => {
                             // Intentionally not propagating error to caller.
@@ -80,6 +98,9 @@ where
                         }
                     }
                 }
+                if should_reconcile_dependencies {
+                    last_dependency_reconciliation = Some(std::time::Instant::now());
+                }
             }
             Ok(()) | Err(mpsc::error::TryRecvError::Disconnected) => {
                 logger::debug!("Awaiting shutdown!");
@@ -98,10 +119,14 @@ where
     Ok(())
 }
 
+/// Moves the due tasks to the stream, and reconciles the tasks waiting for finished parents if
+/// `should_reconcile_dependencies` is set. Both are done under the producer lock, and skipped if
+/// another producer holds the lock.
 #[instrument(skip_all)]
 pub async fn run_producer_flow<T>(
     state: &T,
     settings: &SchedulerSettings,
+    should_reconcile_dependencies: bool,
 ) -> CustomResult<(), errors::ProcessTrackerError>
 where
     T: SchedulerSessionState,
@@ -110,6 +135,13 @@ where
         let tasks = fetch_producer_tasks(state.get_db().as_scheduler(), settings).await?;
         debug!("Producer count of tasks {}", tasks.len());
 
+        let mut tasks = skip_paused_runner_tasks(state.get_db().as_scheduler(), tasks).await;
+
+        // Batches are appended to the stream in order, so that the consumers pick up the tasks of
+        // higher priority runners first
+        sort_by_priority(&mut tasks, &settings.consumer);
+        let tasks = limit_in_flight_tasks(state.get_db().as_scheduler(), tasks, settings).await;
+
         // [#268]: Allow task based segregation of tasks
 
         divide_and_append_tasks(
@@ -120,6 +152,20 @@ where
         )
         .await?;
 
+        if should_reconcile_dependencies {
+            if let Err(error) = dependencies::reconcile_dependent_tasks(
+                state.get_db().as_scheduler(),
+                settings.producer.batch_size,
+            )
+            .await
+            {
+                error!(
+                    ?error,
+                    "Failed to reconcile tasks waiting for their parents"
+                );
+            }
+        }
+
         Ok(())
     })
     .await?;
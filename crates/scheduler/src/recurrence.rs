@@ -0,0 +1,529 @@
+//! Recurring schedules of process tracker tasks
+//!
+//! A task with a schedule is not finished once its workflow completes, it is rescheduled for the
+//! next occurrence of its schedule instead. Schedules are either cron expressions or fixed
+//! intervals, evaluated in UTC with a resolution of a minute for cron expressions.
+
+use std::str::FromStr;
+
+use common_utils::errors::{CustomResult, ValidationError};
+pub use diesel_models::process_tracker::{ProcessTrackerRecurrence, ProcessTrackerSchedule};
+use diesel_models::{enums::ApiVersion, process_tracker as storage};
+use error_stack::{report, ResultExt};
+use rand::Rng;
+use router_env::logger;
+use serde::Serialize;
+use storage_impl::errors;
+use time::{Date, Duration, Month, PrimitiveDateTime, Time};
+
+use crate::db::process_tracker::ProcessTrackerInterface;
+
+/// Occurrences of cron expressions are looked up within this many days, expressions which do not
+/// occur within it (such as the 30th of February) are rejected
+const CRON_SEARCH_LIMIT_DAYS: i64 = 5 * 366;
+
+/// Builds a task which is executed at every occurrence of the schedule, starting with the next one
+#[allow(clippy::too_many_arguments)]
+pub fn new_recurring_task<T>(
+    process_tracker_id: impl Into<String>,
+    task: impl Into<String>,
+    runner: storage::ProcessTrackerRunner,
+    tag: impl IntoIterator<Item = impl Into<String>>,
+    tracking_data: T,
+    schedule: ProcessTrackerSchedule,
+    api_version: ApiVersion,
+) -> CustomResult<storage::ProcessTrackerNew, ValidationError>
+where
+    T: Serialize + std::fmt::Debug,
+{
+    let schedule_time = get_next_schedule_time(&schedule, common_utils::date_time::now())?;
+
+    let mut process_tracker_entry = storage::ProcessTrackerNew::new(
+        process_tracker_id,
+        task,
+        runner,
+        tag,
+        tracking_data,
+        None,
+        schedule_time,
+        api_version,
+    )
+    .change_context(ValidationError::InvalidValue {
+        message: "Failed to construct process tracker task".to_string(),
+    })?;
+    process_tracker_entry.schedule = Some(schedule);
+
+    Ok(process_tracker_entry)
+}
+
+/// Registers a recurring task, such as a periodic job of the application. The registration is
+/// idempotent, so that the recurring tasks can be registered every time the scheduler starts: a
+/// task registered already only has its schedule replaced if the schedule changed, and its next
+/// occurrence is left as it is. A registered task which was finished, for instance by an operator
+/// cancelling it, is left finished.
+pub async fn register_recurring_task<T>(
+    db: &T,
+    process_tracker_entry: storage::ProcessTrackerNew,
+) -> CustomResult<(), errors::StorageError>
+where
+    T: ProcessTrackerInterface + ?Sized,
+{
+    let Some(schedule) = process_tracker_entry.schedule.clone() else {
+        return Err(report!(errors::StorageError::ValueNotFound(
+            "Schedule of recurring task".to_string()
+        )));
+    };
+
+    match db.find_process_by_id(&process_tracker_entry.id).await? {
+        None => match db.insert_process(process_tracker_entry).await {
+            Ok(process) => {
+                logger::info!(pt.id = %process.id, "Registered recurring process");
+                Ok(())
+            }
+            // The task was registered by another instance of the scheduler in the meantime
+            Err(error) if error.current_context().is_db_unique_violation() => Ok(()),
+            Err(error) => Err(error),
+        },
+        Some(process) if process.schedule.as_ref() == Some(&schedule) => Ok(()),
+        Some(process) => {
+            logger::info!(pt.id = %process.id, ?schedule, "Updating schedule of recurring process");
+            db.update_process(
+                process,
+                storage::ProcessTrackerUpdate::RecurrenceUpdate { schedule },
+            )
+            .await?;
+            Ok(())
+        }
+    }
+}
+
+/// Update scheduling the next occurrence of a recurring task which finished with the business
+/// status. The business status is reset for the next occurrence, a failed occurrence is recorded
+/// in the retry history of the task so that its failure is not lost.
+pub fn get_next_occurrence_update(
+    process: &storage::ProcessTracker,
+    business_status: &str,
+    schedule_time: PrimitiveDateTime,
+) -> storage::ProcessTrackerUpdate {
+    let retry_history = storage::business_status::is_failure(business_status).then(|| {
+        process.get_retry_history_with_business_status(
+            0,
+            String::from(business_status),
+            schedule_time,
+            false,
+        )
+    });
+
+    storage::ProcessTrackerUpdate::ScheduleUpdate {
+        schedule_time,
+        retry_history,
+    }
+}
+
+/// Time of the next occurrence of the schedule after the given time, including its jitter
+pub fn get_next_schedule_time(
+    schedule: &ProcessTrackerSchedule,
+    after: PrimitiveDateTime,
+) -> CustomResult<PrimitiveDateTime, ValidationError> {
+    let next_occurrence = match &schedule.recurrence {
+        ProcessTrackerRecurrence::Cron { expression } => CronExpression::from_str(expression)?
+            .get_next_occurrence(after)
+            .ok_or_else(|| {
+                report!(ValidationError::InvalidValue {
+                    message: format!(
+                        "Cron expression `{expression}` does not occur within {CRON_SEARCH_LIMIT_DAYS} days"
+                    ),
+                })
+            })?,
+        ProcessTrackerRecurrence::Interval { interval_seconds } => {
+            if *interval_seconds == 0 {
+                return Err(report!(ValidationError::InvalidValue {
+                    message: "Interval of a schedule must be greater than zero".to_string(),
+                }));
+            }
+            add_seconds(after, *interval_seconds)?
+        }
+    };
+
+    let jitter_seconds = if schedule.jitter_seconds > 0 {
+        rand::thread_rng().gen_range(0..=schedule.jitter_seconds)
+    } else {
+        0
+    };
+
+    add_seconds(next_occurrence, jitter_seconds)
+}
+
+fn add_seconds(
+    date_time: PrimitiveDateTime,
+    seconds: u32,
+) -> CustomResult<PrimitiveDateTime, ValidationError> {
+    date_time
+        .checked_add(Duration::seconds(i64::from(seconds)))
+        .ok_or_else(|| {
+            report!(ValidationError::InvalidValue {
+                message: "Next occurrence of the schedule is out of range".to_string(),
+            })
+        })
+}
+
+/// Set of the values matched by a field of a cron expression
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct CronField(u64);
+
+impl CronField {
+    fn contains(self, value: u8) -> bool {
+        self.0 & (1 << value) != 0
+    }
+
+    /// Parses a field consisting of comma separated values, ranges (`a-b`), wildcards (`*`), each
+    /// optionally followed by a step (`/n`)
+    fn parse(field: &str, name: &str, min: u8, max: u8) -> CustomResult<Self, ValidationError> {
+        let invalid_field = || {
+            report!(ValidationError::InvalidValue {
+                message: format!(
+                    "Invalid {name} field `{field}` in cron expression, values must be between {min} and {max}"
+                ),
+            })
+        };
+        let parse_value = |value: &str| {
+            value
+                .parse::<u8>()
+                .ok()
+                .filter(|value| (min..=max).contains(value))
+                .ok_or_else(invalid_field)
+        };
+
+        let mut values = 0;
+        for part in field.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (
+                    range,
+                    Some(
+                        step.parse::<u8>()
+                            .ok()
+                            .filter(|step| *step > 0)
+                            .ok_or_else(invalid_field)?,
+                    ),
+                ),
+                None => (part, None),
+            };
+
+            let (start, end) = match range.split_once('-') {
+                _ if range == "*" => (min, max),
+                Some((start, end)) => (parse_value(start)?, parse_value(end)?),
+                // A single value followed by a step matches the values up to the maximum
+                None if step.is_some() => (parse_value(range)?, max),
+                None => {
+                    let value = parse_value(range)?;
+                    (value, value)
+                }
+            };
+
+            if start > end {
+                return Err(invalid_field());
+            }
+
+            for value in (start..=end).step_by(usize::from(step.unwrap_or(1))) {
+                values |= 1 << value;
+            }
+        }
+
+        Ok(Self(values))
+    }
+}
+
+/// Five field cron expression, the day of month and day of week fields follow the usual cron
+/// semantics, a day matches if either of them matches when both are restricted
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CronExpression {
+    minutes: CronField,
+    hours: CronField,
+    days_of_month: CronField,
+    months: CronField,
+    days_of_week: CronField,
+    is_day_of_month_restricted: bool,
+    is_day_of_week_restricted: bool,
+}
+
+impl FromStr for CronExpression {
+    type Err = error_stack::Report<ValidationError>;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let expression = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            expression => expression,
+        };
+
+        let fields = expression.split_whitespace().collect::<Vec<_>>();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields.as_slice() else {
+            return Err(report!(ValidationError::InvalidValue {
+                message: format!(
+                    "Cron expression `{expression}` must have 5 fields: minute, hour, day of month, month and day of week"
+                ),
+            }));
+        };
+
+        let mut days_of_week_field = CronField::parse(days_of_week, "day of week", 0, 7)?;
+        // Both 0 and 7 stand for Sunday
+        if days_of_week_field.contains(7) {
+            days_of_week_field = CronField(days_of_week_field.0 | 1);
+        }
+
+        Ok(Self {
+            minutes: CronField::parse(minutes, "minute", 0, 59)?,
+            hours: CronField::parse(hours, "hour", 0, 23)?,
+            days_of_month: CronField::parse(days_of_month, "day of month", 1, 31)?,
+            months: CronField::parse(months, "month", 1, 12)?,
+            days_of_week: days_of_week_field,
+            is_day_of_month_restricted: !days_of_month.starts_with('*'),
+            is_day_of_week_restricted: !days_of_week.starts_with('*'),
+        })
+    }
+}
+
+impl CronExpression {
+    /// The first minute matching the expression after the given time
+    pub fn get_next_occurrence(&self, after: PrimitiveDateTime) -> Option<PrimitiveDateTime> {
+        let mut candidate = after
+            .replace_time(Time::from_hms(after.hour(), after.minute(), 0).ok()?)
+            .checked_add(Duration::minutes(1))?;
+        let search_limit = candidate.checked_add(Duration::days(CRON_SEARCH_LIMIT_DAYS))?;
+
+        while candidate <= search_limit {
+            if !self.months.contains(u8::from(candidate.month())) {
+                let (year, month) = match candidate.month() {
+                    Month::December => (candidate.year().checked_add(1)?, Month::January),
+                    month => (candidate.year(), month.next()),
+                };
+                candidate = PrimitiveDateTime::new(
+                    Date::from_calendar_date(year, month, 1).ok()?,
+                    Time::MIDNIGHT,
+                );
+            } else if !self.matches_day(candidate.date()) {
+                candidate = PrimitiveDateTime::new(candidate.date().next_day()?, Time::MIDNIGHT);
+            } else if !self.hours.contains(candidate.hour()) {
+                candidate = candidate
+                    .replace_minute(0)
+                    .ok()?
+                    .checked_add(Duration::hours(1))?;
+            } else if !self.minutes.contains(candidate.minute()) {
+                candidate = candidate.checked_add(Duration::minutes(1))?;
+            } else {
+                return Some(candidate);
+            }
+        }
+
+        None
+    }
+
+    fn matches_day(&self, date: Date) -> bool {
+        let matches_day_of_month = self.days_of_month.contains(date.day());
+        let matches_day_of_week = self
+            .days_of_week
+            .contains(date.weekday().number_days_from_sunday());
+
+        if self.is_day_of_month_restricted && self.is_day_of_week_restricted {
+            matches_day_of_month || matches_day_of_week
+        } else {
+            matches_day_of_month && matches_day_of_week
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use redis_interface::RedisSettings;
+    use storage_impl::mock_db::MockDb;
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn get_interval_schedule(interval_seconds: u32) -> ProcessTrackerSchedule {
+        ProcessTrackerSchedule {
+            recurrence: ProcessTrackerRecurrence::Interval { interval_seconds },
+            jitter_seconds: 0,
+        }
+    }
+
+    fn get_recurring_task(schedule: ProcessTrackerSchedule) -> storage::ProcessTrackerNew {
+        new_recurring_task(
+            "recurring_task",
+            "RECURRING",
+            storage::ProcessTrackerRunner::PaymentsSyncWorkflow,
+            ["TEST"],
+            serde_json::json!({}),
+            schedule,
+            ApiVersion::V1,
+        )
+        .unwrap()
+    }
+
+    fn get_next_occurrence(expression: &str, after: PrimitiveDateTime) -> PrimitiveDateTime {
+        CronExpression::from_str(expression)
+            .unwrap()
+            .get_next_occurrence(after)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_cron_expression_next_occurrence() {
+        let after = datetime!(2025-05-30 10:15:42);
+
+        assert_eq!(
+            get_next_occurrence("* * * * *", after),
+            datetime!(2025-05-30 10:16)
+        );
+        assert_eq!(
+            get_next_occurrence("*/20 * * * *", after),
+            datetime!(2025-05-30 10:20)
+        );
+        assert_eq!(
+            get_next_occurrence("15 10 * * *", after),
+            datetime!(2025-05-31 10:15)
+        );
+        assert_eq!(
+            get_next_occurrence("@monthly", after),
+            datetime!(2025-06-01 00:00)
+        );
+        assert_eq!(
+            get_next_occurrence("0 9-17/4 * * 1-5", after),
+            datetime!(2025-05-30 13:00)
+        );
+        // 2025-06-01 is a Sunday
+        assert_eq!(
+            get_next_occurrence("30 2 * * 7", after),
+            datetime!(2025-06-01 02:30)
+        );
+        // Either the day of month or the day of week has to match, when both are restricted
+        assert_eq!(
+            get_next_occurrence("0 0 15 * 1", after),
+            datetime!(2025-06-02 00:00)
+        );
+        assert_eq!(
+            get_next_occurrence("0 0 29 2 *", after),
+            datetime!(2028-02-29 00:00)
+        );
+    }
+
+    #[test]
+    fn test_invalid_cron_expressions() {
+        for expression in [
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "10-5 * * * *",
+            "a * * * *",
+        ] {
+            assert!(
+                CronExpression::from_str(expression).is_err(),
+                "Cron expression `{expression}` should be invalid"
+            );
+        }
+
+        let schedule = ProcessTrackerSchedule {
+            recurrence: ProcessTrackerRecurrence::Cron {
+                expression: "0 0 30 2 *".to_string(),
+            },
+            jitter_seconds: 0,
+        };
+        assert!(get_next_schedule_time(&schedule, datetime!(2025-05-30 10:15)).is_err());
+    }
+
+    #[test]
+    fn test_interval_next_schedule_time() {
+        let after = datetime!(2025-05-30 10:15:42);
+        let schedule = ProcessTrackerSchedule {
+            recurrence: ProcessTrackerRecurrence::Interval {
+                interval_seconds: 3600,
+            },
+            jitter_seconds: 60,
+        };
+
+        let schedule_time = get_next_schedule_time(&schedule, after).unwrap();
+        assert!(schedule_time >= datetime!(2025-05-30 11:15:42));
+        assert!(schedule_time <= datetime!(2025-05-30 11:16:42));
+    }
+
+    #[tokio::test]
+    async fn test_register_recurring_task_is_idempotent() {
+        let db = MockDb::new(&RedisSettings::default()).await.unwrap();
+
+        register_recurring_task(&db, get_recurring_task(get_interval_schedule(60)))
+            .await
+            .unwrap();
+        let registered_process = db
+            .find_process_by_id("recurring_task")
+            .await
+            .unwrap()
+            .unwrap();
+        register_recurring_task(&db, get_recurring_task(get_interval_schedule(60)))
+            .await
+            .unwrap();
+        register_recurring_task(&db, get_recurring_task(get_interval_schedule(120)))
+            .await
+            .unwrap();
+
+        assert_eq!(db.processes.lock().await.len(), 1);
+        let process = db
+            .find_process_by_id("recurring_task")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(process.schedule, Some(get_interval_schedule(120)));
+        assert_eq!(process.schedule_time, registered_process.schedule_time);
+    }
+
+    #[tokio::test]
+    async fn test_next_occurrence_update_records_failures() {
+        let db = MockDb::new(&RedisSettings::default()).await.unwrap();
+        let process = db
+            .insert_process(get_recurring_task(get_interval_schedule(60)))
+            .await
+            .unwrap();
+        let schedule_time = datetime!(2025-05-30 10:15);
+
+        let process = db
+            .update_process(
+                process.clone(),
+                get_next_occurrence_update(
+                    &process,
+                    storage::business_status::GLOBAL_ERROR,
+                    schedule_time,
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(process.business_status, storage::business_status::PENDING);
+        assert_eq!(process.schedule_time, Some(schedule_time));
+        let retry_history = process.retry_history.clone().unwrap().0;
+        assert_eq!(retry_history.len(), 1);
+        assert_eq!(
+            retry_history.first().unwrap().business_status,
+            storage::business_status::GLOBAL_ERROR
+        );
+
+        // Successful occurrences are not recorded
+        let process = db
+            .update_process(
+                process.clone(),
+                get_next_occurrence_update(
+                    &process,
+                    storage::business_status::COMPLETED_BY_PT,
+                    schedule_time,
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(process.retry_history.unwrap().0.len(), 1);
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync;
+use std::{collections::HashMap, sync};
 
 use common_utils::errors::CustomResult;
 use diesel_models::enums::{self, ProcessTrackerStatus};
@@ -13,8 +13,11 @@ use super::{
     env::logger,
 };
 use crate::{
-    configs::settings::SchedulerSettings, consumer::types::ProcessTrackerBatch, errors,
-    flow::SchedulerFlow, metrics, SchedulerInterface, SchedulerSessionState,
+    configs::settings::{ConsumerSettings, SchedulerSettings},
+    consumer::types::ProcessTrackerBatch,
+    errors,
+    flow::SchedulerFlow,
+    metrics, SchedulerInterface, SchedulerSessionState,
 };
 
 pub async fn divide_and_append_tasks<T>(
@@ -179,6 +182,177 @@ pub fn divide_into_batches(
         })
 }
 
+/// Orders the tasks by the priority class of their runners, tasks of the same priority class retain
+/// their order
+pub fn sort_by_priority(tasks: &mut [storage::ProcessTracker], settings: &ConsumerSettings) {
+    tasks.sort_by_cached_key(|task| {
+        settings
+            .get_runner_settings(task.runner.as_deref())
+            .priority
+    });
+}
+
+pub fn get_in_flight_key(settings: &SchedulerSettings, runner: &str) -> String {
+    format!("{}_IN_FLIGHT_{runner}", settings.stream)
+}
+
+/// Holds back the tasks of runners which have reached their limit of in flight tasks, the tasks
+/// held back are rescheduled to now so that they are picked up by a later run of the producer
+#[instrument(skip_all)]
+pub async fn limit_in_flight_tasks<T>(
+    state: &T,
+    tasks: Vec<storage::ProcessTracker>,
+    settings: &SchedulerSettings,
+) -> Vec<storage::ProcessTracker>
+where
+    T: SchedulerInterface + Send + Sync + ?Sized,
+{
+    let mut limited_task_ids = HashMap::<String, (u64, Vec<String>)>::new();
+    for task in &tasks {
+        let Some(runner) = task.runner.as_ref() else {
+            continue;
+        };
+        if let Some(max_in_flight) = settings
+            .consumer
+            .get_runner_settings(Some(runner))
+            .max_in_flight
+        {
+            limited_task_ids
+                .entry(runner.clone())
+                .or_insert_with(|| (max_in_flight, Vec::new()))
+                .1
+                .push(task.id.clone());
+        }
+    }
+
+    let mut held_back_task_ids = Vec::new();
+    for (runner, (max_in_flight, task_ids)) in limited_task_ids {
+        let reserved_task_ids = state
+            .reserve_in_flight_slots(
+                &get_in_flight_key(settings, &runner),
+                task_ids.clone(),
+                max_in_flight,
+                settings.consumer.in_flight_expiry,
+            )
+            .await
+            .unwrap_or_else(|error| {
+                logger::error!(?error, %runner, "Failed to reserve in flight slots for tasks");
+                Vec::new()
+            });
+
+        let runner_held_back_task_ids = task_ids
+            .into_iter()
+            .filter(|task_id| !reserved_task_ids.contains(task_id))
+            .collect::<Vec<_>>();
+
+        if !runner_held_back_task_ids.is_empty() {
+            logger::info!(
+                %runner,
+                "Holding back {} tasks as the runner reached its in flight limit",
+                runner_held_back_task_ids.len()
+            );
+            metrics::TASKS_THROTTLED_COUNT.add(
+                u64::try_from(runner_held_back_task_ids.len()).unwrap_or(u64::MAX),
+                router_env::metric_attributes!(("runner", runner)),
+            );
+        }
+        held_back_task_ids.extend(runner_held_back_task_ids);
+    }
+
+    if !held_back_task_ids.is_empty() {
+        // The tasks held back would otherwise fall out of the window of schedule times the
+        // producer fetches tasks from, if their runner stays at its limit for long
+        let now = common_utils::date_time::now();
+        let _ = state
+            .update_processes_by_ids_and_statuses(
+                held_back_task_ids.clone(),
+                vec![ProcessTrackerStatus::New, ProcessTrackerStatus::Pending],
+                storage::ProcessTrackerUpdate::Update {
+                    name: None,
+                    retry_count: None,
+                    schedule_time: Some(now),
+                    tracking_data: None,
+                    business_status: None,
+                    status: None,
+                    updated_at: Some(now),
+                },
+            )
+            .await
+            .inspect_err(|error| {
+                logger::error!(?error, "Failed to reschedule the tasks held back");
+            });
+    }
+
+    tasks
+        .into_iter()
+        .filter(|task| !held_back_task_ids.contains(&task.id))
+        .collect()
+}
+
+/// Drops the tasks of paused runners, the tasks are left in their current status and are
+/// rescheduled when their runner is resumed, as they would have left the fetch window by then
+pub async fn skip_paused_runner_tasks<T>(
+    state: &T,
+    tasks: Vec<storage::ProcessTracker>,
+) -> Vec<storage::ProcessTracker>
+where
+    T: SchedulerInterface + Send + Sync + ?Sized,
+{
+    let paused_runners = match state.get_paused_runners().await {
+        Ok(paused_runners) => paused_runners,
+        Err(error) => {
+            logger::error!(?error, "Failed to fetch paused runners");
+            return tasks;
+        }
+    };
+
+    if paused_runners.is_empty() {
+        return tasks;
+    }
+
+    let (skipped_tasks, tasks): (Vec<_>, Vec<_>) = tasks.into_iter().partition(|task| {
+        task.runner
+            .as_ref()
+            .is_some_and(|runner| paused_runners.contains_key(runner))
+    });
+
+    if !skipped_tasks.is_empty() {
+        logger::info!(
+            "Skipping {} tasks as their runners are paused",
+            skipped_tasks.len()
+        );
+    }
+
+    tasks
+}
+
+/// Frees the in flight slot held by the task, if its runner has a limit of in flight tasks
+pub async fn release_in_flight_slot<T>(
+    state: &T,
+    task: &storage::ProcessTracker,
+    settings: &SchedulerSettings,
+) where
+    T: SchedulerInterface + Send + Sync + ?Sized,
+{
+    let Some(runner) = task.runner.as_ref() else {
+        return;
+    };
+
+    if settings
+        .consumer
+        .get_runner_settings(Some(runner))
+        .max_in_flight
+        .is_some()
+    {
+        let _ = state
+            .release_in_flight_slots(&get_in_flight_key(settings, runner), vec![task.id.clone()])
+            .await
+            .inspect_err(|error| {
+                logger::error!(?error, %runner, "Failed to release in flight slot of task");
+            });
+    }
+}
+
 pub async fn get_batches(
     conn: &RedisConnectionPool,
     stream_name: &str,
@@ -425,7 +599,129 @@ where
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use redis_interface::RedisSettings;
+    use storage_impl::mock_db::MockDb;
+
     use super::*;
+    use crate::{
+        configs::settings::{RunnerSettings, TaskPriority},
+        db::process_tracker::ProcessTrackerInterface,
+    };
+
+    fn get_task(id: &str, runner: &str) -> storage::ProcessTracker {
+        let now = common_utils::date_time::now();
+        storage::ProcessTracker {
+            id: id.to_owned(),
+            name: None,
+            tag: Vec::new(),
+            runner: Some(runner.to_owned()),
+            retry_count: 0,
+            schedule_time: Some(now),
+            rule: String::new(),
+            tracking_data: serde_json::Value::Null,
+            business_status: String::from(storage::business_status::PENDING),
+            status: ProcessTrackerStatus::New,
+            event: Vec::new(),
+            created_at: now,
+            updated_at: now,
+            version: storage::ApiVersion::V1,
+            retry_history: None,
+            schedule: None,
+            parent_ids: None,
+        }
+    }
+
+    #[test]
+    fn test_sort_by_priority() {
+        let mut settings = ConsumerSettings::default();
+        settings.runners.insert(
+            "outgoing_webhook_retry_workflow".to_owned(),
+            RunnerSettings {
+                priority: TaskPriority::High,
+                max_in_flight: None,
+            },
+        );
+        settings.runners.insert(
+            "PAYMENTS_SYNC_WORKFLOW".to_owned(),
+            RunnerSettings {
+                priority: TaskPriority::Low,
+                max_in_flight: Some(10),
+            },
+        );
+
+        let mut tasks = vec![
+            get_task("sync_1", "PAYMENTS_SYNC_WORKFLOW"),
+            get_task("refund_1", "REFUND_WORKFLOW_ROUTER"),
+            get_task("sync_2", "PAYMENTS_SYNC_WORKFLOW"),
+            get_task("webhook_1", "OUTGOING_WEBHOOK_RETRY_WORKFLOW"),
+            get_task("refund_2", "REFUND_WORKFLOW_ROUTER"),
+        ];
+
+        sort_by_priority(&mut tasks, &settings);
+
+        let task_ids = tasks
+            .iter()
+            .map(|task| task.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            task_ids,
+            vec!["webhook_1", "refund_1", "refund_2", "sync_1", "sync_2"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_held_back_tasks_are_rescheduled() {
+        let db = MockDb::new(&RedisSettings::default()).await.unwrap();
+        let mut settings = SchedulerSettings {
+            stream: common_utils::generate_id(16, "TEST_STREAM"),
+            ..Default::default()
+        };
+        settings.consumer.runners.insert(
+            "PAYMENTS_SYNC_WORKFLOW".to_owned(),
+            RunnerSettings {
+                priority: TaskPriority::Low,
+                max_in_flight: Some(1),
+            },
+        );
+
+        let schedule_time = common_utils::date_time::now().saturating_sub(time::Duration::hours(1));
+        let mut tasks = Vec::new();
+        for task_id in ["sync_1", "sync_2", "sync_3"] {
+            let task = storage::ProcessTrackerNew::new(
+                task_id,
+                "PSYNC",
+                storage::ProcessTrackerRunner::PaymentsSyncWorkflow,
+                ["TEST"],
+                serde_json::json!({}),
+                None,
+                schedule_time,
+                storage::ApiVersion::V1,
+            )
+            .unwrap();
+            tasks.push(db.insert_process(task).await.unwrap());
+        }
+
+        let start_time = common_utils::date_time::now();
+        let tasks = limit_in_flight_tasks(&db, tasks, &settings).await;
+
+        let task_ids = tasks
+            .iter()
+            .map(|task| task.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(task_ids, vec!["sync_1"]);
+
+        let sync_1 = db.find_process_by_id("sync_1").await.unwrap().unwrap();
+        assert_eq!(sync_1.schedule_time, Some(schedule_time));
+        for task_id in ["sync_2", "sync_3"] {
+            let task = db.find_process_by_id(task_id).await.unwrap().unwrap();
+            assert!(task.schedule_time >= Some(start_time));
+            assert_eq!(task.status, ProcessTrackerStatus::New);
+        }
+
+        release_in_flight_slot(&db, &sync_1, &settings).await;
+    }
 
     #[test]
     fn test_get_delay() {
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE events
+ADD COLUMN IF NOT EXISTS webhook_endpoint_id VARCHAR(64);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS events_business_profile_id_dead_lettered_at_index;
+
+ALTER TABLE events
+DROP COLUMN IF EXISTS dead_lettered_at;
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+ALTER TABLE events
+ADD COLUMN IF NOT EXISTS dead_lettered_at TIMESTAMP;
+
+CREATE INDEX IF NOT EXISTS events_business_profile_id_dead_lettered_at_index
+ON events (business_profile_id, dead_lettered_at)
+WHERE dead_lettered_at IS NOT NULL;
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE process_tracker
+ADD COLUMN IF NOT EXISTS retry_history JSONB;
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS process_tracker_parent_ids_index;
+
+ALTER TABLE process_tracker
+DROP COLUMN IF EXISTS parent_ids;
+
+-- Values cannot be removed from an enum type
+SELECT 1;
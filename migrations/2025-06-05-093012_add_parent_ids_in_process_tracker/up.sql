@@ -0,0 +1,8 @@
+-- Your SQL goes here
+ALTER TYPE "ProcessTrackerStatus"
+ADD VALUE IF NOT EXISTS 'waiting';
+
+ALTER TABLE process_tracker
+ADD COLUMN IF NOT EXISTS parent_ids TEXT[];
+
+CREATE INDEX IF NOT EXISTS process_tracker_parent_ids_index ON process_tracker USING GIN (parent_ids);
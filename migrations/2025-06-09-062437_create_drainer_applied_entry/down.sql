@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS drainer_applied_entry_applied_at_index;
+
+DROP TABLE IF EXISTS drainer_applied_entry;
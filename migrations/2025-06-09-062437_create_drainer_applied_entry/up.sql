@@ -0,0 +1,10 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS drainer_applied_entry (
+    stream_name VARCHAR(255) NOT NULL,
+    entry_id VARCHAR(64) NOT NULL,
+    table_name VARCHAR(64) NOT NULL,
+    applied_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    PRIMARY KEY (stream_name, entry_id)
+);
+
+CREATE INDEX IF NOT EXISTS drainer_applied_entry_applied_at_index ON drainer_applied_entry (applied_at);